@@ -0,0 +1,59 @@
+use std::io;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_SERVER: &str = "http://localhost:3000";
+
+// Isi file config CLI (JSON). Yang disimpan hanya token, bukan password.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CliConfig {
+    pub server: Option<String>,
+    pub email: Option<String>,
+    pub access_token: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl CliConfig {
+    // Lokasi: $CATATAN_CONFIG, atau $XDG_CONFIG_HOME/catatan/config.json,
+    // atau ~/.config/catatan/config.json
+    pub fn path() -> PathBuf {
+        if let Some(path) = std::env::var_os("CATATAN_CONFIG") {
+            return PathBuf::from(path);
+        }
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .unwrap_or_else(|| PathBuf::from("."));
+        base.join("catatan").join("config.json")
+    }
+
+    pub fn load() -> io::Result<Self> {
+        match std::fs::read_to_string(Self::path()) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+
+        // Token = akses ke semua catatan, jadi file hanya boleh dibaca pemiliknya
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+
+    pub fn server(&self) -> &str {
+        self.server.as_deref().unwrap_or(DEFAULT_SERVER)
+    }
+}
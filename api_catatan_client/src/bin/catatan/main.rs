@@ -0,0 +1,233 @@
+// CLI catatan untuk terminal: `catatan login`, `catatan ls`, `catatan new`, dst.
+// Berbicara ke server lewat HTTP memakai `api_catatan_client`.
+mod config;
+mod output;
+
+use std::error::Error;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::Command as Process;
+
+use api_catatan_client::{CatatanClient, ClientError, ContentFormat, CreateNotePayload, Note, PageQuery, UpdateNotePayload};
+use clap::{Parser, Subcommand};
+use futures_util::TryStreamExt;
+
+use config::CliConfig;
+use output::{print_message, print_note, print_notes, OutputFormat};
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+// Ukuran halaman saat mengambil semua catatan (ls tanpa --page, search)
+const FETCH_PAGE_SIZE: u32 = 100;
+
+#[derive(Parser)]
+#[command(name = "catatan", version, about = "Kelola catatan dari terminal")]
+struct Cli {
+    /// Format keluaran
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+    /// URL server, menimpa yang tersimpan di config
+    #[arg(long, global = true, env = "CATATAN_SERVER")]
+    server: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Login dan simpan token di file config
+    Login {
+        #[arg(long)]
+        email: Option<String>,
+        /// Baca password dari stdin (untuk skrip), bukan prompt
+        #[arg(long)]
+        password_stdin: bool,
+    },
+    /// Logout dan hapus token dari file config
+    Logout,
+    /// Daftar catatan, terbaru dulu
+    Ls {
+        /// Hanya tampilkan halaman tertentu
+        #[arg(long)]
+        page: Option<u32>,
+        #[arg(long, default_value_t = 20)]
+        per_page: u32,
+    },
+    /// Tampilkan satu catatan
+    Show { id: u32 },
+    /// Buat catatan baru, isinya dibaca dari stdin
+    New {
+        #[arg(long, short)]
+        title: String,
+        /// Isi catatan ditulis dalam Markdown
+        #[arg(long)]
+        markdown: bool,
+    },
+    /// Ubah catatan dengan $EDITOR (baris pertama = judul)
+    Edit { id: u32 },
+    /// Hapus catatan
+    Rm { id: u32 },
+    /// Cari catatan berdasarkan judul atau isi (tidak peka huruf besar/kecil)
+    Search { query: String },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    if let Err(e) = run(cli).await {
+        eprintln!("Error: {e}");
+        if matches!(e.downcast_ref::<ClientError>(), Some(ClientError::Unauthorized(_) | ClientError::NotLoggedIn)) {
+            eprintln!("Jalankan `catatan login` terlebih dahulu.");
+        }
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> CliResult {
+    let mut config = CliConfig::load()?;
+    if let Some(server) = cli.server {
+        config.server = Some(server);
+    }
+
+    let client = CatatanClient::new(config.server());
+    if let Some(token) = &config.access_token {
+        client.set_access_token(token.clone(), config.expires_at).await;
+    }
+    let format = cli.output;
+
+    match cli.command {
+        Command::Login { email, password_stdin } => {
+            let email = match email {
+                Some(email) => email,
+                None => prompt("Email: ")?,
+            };
+            let password = if password_stdin {
+                read_stdin()?.trim_end_matches(['\r', '\n']).to_string()
+            } else {
+                rpassword::prompt_password("Password: ")?
+            };
+
+            let token = client.login(&email, &password).await?;
+            config.email = Some(email);
+            config.access_token = Some(token.access_token);
+            config.expires_at = Some(token.expires_at);
+            config.save()?;
+            print_message(&format!("Login berhasil, token disimpan di {}.", CliConfig::path().display()), format);
+        }
+        Command::Logout => {
+            // Token di server tetap dicabut jika bisa, file config tetap dibersihkan
+            let result = client.logout().await;
+            config.access_token = None;
+            config.expires_at = None;
+            config.save()?;
+            match result {
+                Ok(_) | Err(ClientError::NotLoggedIn) => print_message("Logout berhasil.", format),
+                Err(e) => eprintln!("Token lokal dihapus, tapi logout di server gagal: {e}"),
+            }
+        }
+        Command::Ls { page, per_page } => {
+            let notes = match page {
+                Some(page) => client.list_notes_page(PageQuery::new(page, per_page)).await?,
+                None => client.notes_pages(FETCH_PAGE_SIZE).collect_all().await?,
+            };
+            print_notes(&notes, format);
+        }
+        Command::Show { id } => print_note(&client.get_note(id).await?, format),
+        Command::New { title, markdown } => {
+            if io::stdin().is_terminal() {
+                eprintln!("Tulis isi catatan, akhiri dengan Ctrl-D:");
+            }
+            let content = read_stdin()?;
+            let payload = CreateNotePayload {
+                title,
+                content: (!content.trim().is_empty()).then_some(content),
+                content_format: markdown.then_some(ContentFormat::Markdown),
+                tags: None,
+            };
+            print_note(&client.create_note(&payload).await?, format);
+        }
+        Command::Edit { id } => {
+            let note = client.get_note(id).await?;
+            let original = to_editor_text(&note);
+            let edited = open_editor(&original)?;
+            if edited == original {
+                print_message("Tidak ada perubahan.", format);
+                return Ok(());
+            }
+
+            let (title, content) = from_editor_text(&edited);
+            if title.is_empty() {
+                return Err("Judul tidak boleh kosong (baris pertama)".into());
+            }
+            let payload = UpdateNotePayload { title: Some(title), content: Some(content), ..Default::default() };
+            print_note(&client.update_note(id, &payload).await?, format);
+        }
+        Command::Rm { id } => {
+            let response = client.delete_note(id).await?;
+            print_message(&response.message, format);
+        }
+        Command::Search { query } => {
+            // Server belum punya endpoint pencarian, jadi disaring di sisi klien
+            let query = query.to_lowercase();
+            let notes: Vec<Note> = client
+                .notes_pages(FETCH_PAGE_SIZE)
+                .items()
+                .try_filter(|note| {
+                    let matched = note.title.to_lowercase().contains(&query)
+                        || note.content.as_deref().is_some_and(|content| content.to_lowercase().contains(&query));
+                    async move { matched }
+                })
+                .try_collect()
+                .await?;
+            print_notes(&notes, format);
+        }
+    }
+
+    Ok(())
+}
+
+fn prompt(label: &str) -> io::Result<String> {
+    eprint!("{label}");
+    io::stderr().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+fn read_stdin() -> io::Result<String> {
+    let mut content = String::new();
+    io::stdin().read_to_string(&mut content)?;
+    Ok(content)
+}
+
+// Format file yang dibuka di editor: judul, baris kosong, lalu isi
+fn to_editor_text(note: &Note) -> String {
+    format!("{}\n\n{}", note.title, note.content.as_deref().unwrap_or_default())
+}
+
+fn from_editor_text(text: &str) -> (String, String) {
+    let (title, content) = text.split_once('\n').unwrap_or((text, ""));
+    (title.trim().to_string(), content.trim_start_matches(['\r', '\n']).to_string())
+}
+
+// Buka $VISUAL/$EDITOR (default vi) pada file sementara, kembalikan isinya setelah editor ditutup
+fn open_editor(initial: &str) -> Result<String, Box<dyn Error>> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let path = std::env::temp_dir().join(format!("catatan-{}.md", std::process::id()));
+    std::fs::write(&path, initial)?;
+
+    // $EDITOR boleh berisi argumen, misal "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or("$EDITOR kosong")?;
+    let status = Process::new(program).args(parts).arg(&path).status();
+
+    let result = match status {
+        Ok(status) if status.success() => Ok(std::fs::read_to_string(&path)?),
+        Ok(status) => Err(format!("Editor keluar dengan status {status}, perubahan dibatalkan").into()),
+        Err(e) => Err(format!("Gagal menjalankan editor '{editor}': {e}").into()),
+    };
+    std::fs::remove_file(&path).ok();
+    result
+}
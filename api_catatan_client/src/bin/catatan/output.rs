@@ -0,0 +1,72 @@
+use api_catatan_client::Note;
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+// Lebar maksimum kolom judul di tabel sebelum dipotong
+const TITLE_WIDTH: usize = 40;
+
+pub fn print_notes(notes: &[Note], format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(notes),
+        OutputFormat::Table => {
+            if notes.is_empty() {
+                println!("Belum ada catatan.");
+                return;
+            }
+            let id_width = notes.iter().map(|note| note.id.to_string().len()).max().unwrap_or(2).max(2);
+            println!("{:<id_width$}  {:<TITLE_WIDTH$}  DIBUAT", "ID", "JUDUL");
+            for note in notes {
+                println!("{:<id_width$}  {:<TITLE_WIDTH$}  {}", note.id, truncate(&note.title, TITLE_WIDTH), created_at(note));
+            }
+        }
+    }
+}
+
+pub fn print_note(note: &Note, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(note),
+        OutputFormat::Table => {
+            println!("ID     : {}", note.id);
+            println!("Judul  : {}", note.title);
+            println!("Dibuat : {}", created_at(note));
+            if let Some(content) = note.content.as_deref().filter(|content| !content.is_empty()) {
+                println!();
+                println!("{content}");
+            }
+        }
+    }
+}
+
+pub fn print_message(message: &str, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(&serde_json::json!({ "status": "success", "message": message })),
+        OutputFormat::Table => println!("{message}"),
+    }
+}
+
+fn print_json<T: serde::Serialize + ?Sized>(value: &T) {
+    println!("{}", serde_json::to_string_pretty(value).expect("respons selalu bisa diserialisasi"));
+}
+
+// Waktu lokal dari server (sesuai pengaturan user) jika ada
+fn created_at(note: &Note) -> String {
+    note.created_at_local
+        .clone()
+        .or_else(|| note.created_at.map(|dt| dt.to_rfc3339()))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    let first_line = text.lines().next().unwrap_or_default();
+    if first_line.chars().count() <= width {
+        return first_line.to_string();
+    }
+    let cut: String = first_line.chars().take(width - 1).collect();
+    format!("{cut}…")
+}
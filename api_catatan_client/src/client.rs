@@ -0,0 +1,413 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use bytes::Bytes;
+use reqwest::multipart::{Form, Part};
+
+use crate::download::Download;
+use crate::error::{ClientError, ClientResult};
+use crate::models::{
+    ApiResponse, Attachment, BulkPayload, BulkResult, CreateNotePayload, ErrorResponse, ExportQuery, ImportJob,
+    ImportSource, LoginPayload, MessageResponse, Note, NoteExportFormat, PageQuery, ReadinessReport, RegisterPayload,
+    SyncPull, SyncPushPayload, SyncPushResult, SyncQuery, TokenResponse, UpdateNotePayload, UpdateSettingsPayload,
+    User, UserProfile, UserSettings, VersionInfo,
+};
+use crate::pagination::Pages;
+
+// Token diperbarui sedikit sebelum benar-benar kedaluwarsa
+const REFRESH_MARGIN_SECS: i64 = 30;
+
+// Server belum punya refresh token, jadi "refresh" = login ulang
+// dengan kredensial yang disimpan saat `login`.
+#[derive(Default)]
+struct Session {
+    access_token: Option<String>,
+    expires_at: Option<DateTime<Utc>>,
+    credentials: Option<(String, String)>,
+}
+
+// Klien murah di-clone; semua clone berbagi koneksi dan sesi login yang sama
+#[derive(Clone)]
+pub struct CatatanClient {
+    http: reqwest::Client,
+    base_url: Arc<str>,
+    session: Arc<RwLock<Session>>,
+}
+
+impl CatatanClient {
+    pub fn new(base_url: impl AsRef<str>) -> Self {
+        Self::with_http_client(base_url, reqwest::Client::new())
+    }
+
+    // Pakai reqwest::Client sendiri (misal untuk timeout atau proxy)
+    pub fn with_http_client(base_url: impl AsRef<str>, http: reqwest::Client) -> Self {
+        Self {
+            http,
+            base_url: base_url.as_ref().trim_end_matches('/').into(),
+            session: Arc::new(RwLock::new(Session::default())),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    // Pakai token yang sudah ada (misal disimpan dari sesi sebelumnya).
+    // Tanpa kredensial, token tidak bisa diperbarui otomatis.
+    pub async fn set_access_token(&self, token: impl Into<String>, expires_at: Option<DateTime<Utc>>) {
+        let mut session = self.session.write().await;
+        session.access_token = Some(token.into());
+        session.expires_at = expires_at;
+    }
+
+    pub async fn access_token(&self) -> Option<String> {
+        self.session.read().await.access_token.clone()
+    }
+
+    // ===== AUTH =====
+
+    pub async fn register(&self, payload: &RegisterPayload) -> ClientResult<User> {
+        self.send_data(self.request(Method::POST, "/auth/register").json(payload)).await
+    }
+
+    // Login dan simpan token + kredensial untuk diperbarui otomatis
+    pub async fn login(&self, email: &str, password: &str) -> ClientResult<TokenResponse> {
+        let token = self.request_token(email, password).await?;
+
+        let mut session = self.session.write().await;
+        session.access_token = Some(token.access_token.clone());
+        session.expires_at = Some(token.expires_at);
+        session.credentials = Some((email.to_string(), password.to_string()));
+        Ok(token)
+    }
+
+    // Cabut token di server lalu lupakan sesi lokal
+    pub async fn logout(&self) -> ClientResult<MessageResponse> {
+        let response = self.send_authed::<MessageResponse, ()>(Method::POST, "/auth/logout", None::<&()>, None).await;
+        *self.session.write().await = Session::default();
+        response
+    }
+
+    pub async fn profile(&self) -> ClientResult<UserProfile> {
+        self.authed_data(Method::GET, "/auth/profile", None::<&()>, None).await
+    }
+
+    pub async fn settings(&self) -> ClientResult<UserSettings> {
+        self.authed_data(Method::GET, "/auth/settings", None::<&()>, None).await
+    }
+
+    pub async fn update_settings(&self, payload: &UpdateSettingsPayload) -> ClientResult<UserSettings> {
+        self.authed_data(Method::PATCH, "/auth/settings", Some(payload), None).await
+    }
+
+    // ===== NOTES =====
+
+    pub async fn create_note(&self, payload: &CreateNotePayload) -> ClientResult<Note> {
+        self.authed_data(Method::POST, "/notes", Some(payload), None).await
+    }
+
+    // Semua catatan sekaligus (tanpa paginasi)
+    pub async fn list_notes(&self) -> ClientResult<Vec<Note>> {
+        self.authed_data(Method::GET, "/notes", None::<&()>, None).await
+    }
+
+    pub async fn list_notes_page(&self, page: PageQuery) -> ClientResult<Vec<Note>> {
+        self.authed_data(Method::GET, "/notes", None::<&()>, Some(page)).await
+    }
+
+    // Iterasi catatan per halaman, terbaru dulu
+    pub fn notes_pages(&self, per_page: u32) -> Pages<Note> {
+        let client = self.clone();
+        Pages::new(per_page, move |page, per_page| {
+            let client = client.clone();
+            async move { client.list_notes_page(PageQuery::new(page, per_page)).await }
+        })
+    }
+
+    pub async fn get_note(&self, id: u32) -> ClientResult<Note> {
+        self.authed_data(Method::GET, &format!("/notes/{id}"), None::<&()>, None).await
+    }
+
+    pub async fn update_note(&self, id: u32, payload: &UpdateNotePayload) -> ClientResult<Note> {
+        self.authed_data(Method::PUT, &format!("/notes/{id}"), Some(payload), None).await
+    }
+
+    pub async fn delete_note(&self, id: u32) -> ClientResult<MessageResponse> {
+        self.send_authed(Method::DELETE, &format!("/notes/{id}"), None::<&()>, None).await
+    }
+
+    // Beberapa operasi (hapus, ubah, arsip, tag) atas banyak catatan dalam satu request
+    pub async fn bulk_notes(&self, payload: &BulkPayload) -> ClientResult<BulkResult> {
+        self.authed_data(Method::POST, "/notes/bulk", Some(payload), None).await
+    }
+
+    // ===== EKSPOR & IMPOR =====
+
+    // Ekspor banyak catatan (ZIP/JSON/HTML/PDF); body dialirkan server, baca lewat `Download::stream`
+    pub async fn export_notes(&self, query: &ExportQuery) -> ClientResult<Download> {
+        let response = self.send_authed_raw(Method::GET, "/notes/export", |request| request.query(query)).await?;
+        Ok(Download::new(response))
+    }
+
+    pub async fn export_note(&self, id: u32, format: NoteExportFormat) -> ClientResult<Download> {
+        let path = format!("/notes/{id}/export");
+        let response = self.send_authed_raw(Method::GET, &path, |request| request.query(&[("format", format)])).await?;
+        Ok(Download::new(response))
+    }
+
+    // Mulai impor di background; pantau dengan `import_status`.
+    // `source` kosong = ditebak server dari nama & isi file.
+    pub async fn import_notes(
+        &self,
+        file_name: &str,
+        data: impl Into<Bytes>,
+        source: Option<ImportSource>,
+    ) -> ClientResult<ImportJob> {
+        let data = data.into();
+        self.authed_data_with(Method::POST, "/notes/import", |request| {
+            let request = request.multipart(file_form(file_name, data.clone()));
+            match source {
+                Some(source) => request.query(&[("source", source)]),
+                None => request,
+            }
+        })
+        .await
+    }
+
+    pub async fn import_status(&self, job_id: &str) -> ClientResult<ImportJob> {
+        self.authed_data(Method::GET, &format!("/notes/import/{job_id}"), None::<&()>, None).await
+    }
+
+    // ===== LAMPIRAN =====
+
+    pub async fn upload_attachment(&self, note_id: u32, file_name: &str, data: impl Into<Bytes>) -> ClientResult<Attachment> {
+        let data = data.into();
+        let path = format!("/notes/{note_id}/attachments");
+        self.authed_data_with(Method::POST, &path, |request| request.multipart(file_form(file_name, data.clone())))
+            .await
+    }
+
+    pub async fn list_attachments(&self, note_id: u32) -> ClientResult<Vec<Attachment>> {
+        self.authed_data(Method::GET, &format!("/notes/{note_id}/attachments"), None::<&()>, None).await
+    }
+
+    pub async fn download_attachment(&self, note_id: u32, attachment_id: u32) -> ClientResult<Download> {
+        let path = format!("/notes/{note_id}/attachments/{attachment_id}");
+        Ok(Download::new(self.send_authed_raw(Method::GET, &path, |request| request).await?))
+    }
+
+    // Thumbnail terkecil yang tidak lebih kecil dari `size` (px); None = ukuran terkecil
+    pub async fn attachment_thumbnail(&self, note_id: u32, attachment_id: u32, size: Option<u32>) -> ClientResult<Download> {
+        let path = format!("/notes/{note_id}/attachments/{attachment_id}/thumbnail");
+        let response = self
+            .send_authed_raw(Method::GET, &path, |request| match size {
+                Some(size) => request.query(&[("size", size)]),
+                None => request,
+            })
+            .await?;
+        Ok(Download::new(response))
+    }
+
+    pub async fn delete_attachment(&self, note_id: u32, attachment_id: u32) -> ClientResult<MessageResponse> {
+        let path = format!("/notes/{note_id}/attachments/{attachment_id}");
+        self.send_authed(Method::DELETE, &path, None::<&()>, None).await
+    }
+
+    // ===== SYNC =====
+
+    // Perubahan sejak `query.since`; ulangi dengan token baru selama `has_more`
+    pub async fn sync_pull(&self, query: &SyncQuery) -> ClientResult<SyncPull> {
+        self.authed_data_with(Method::GET, "/sync", |request| request.query(query)).await
+    }
+
+    pub async fn sync_push(&self, payload: &SyncPushPayload) -> ClientResult<SyncPushResult> {
+        self.authed_data(Method::POST, "/sync", Some(payload), None).await
+    }
+
+    // ===== ADMIN =====
+
+    pub async fn list_users(&self) -> ClientResult<Vec<UserProfile>> {
+        self.authed_data(Method::GET, "/users", None::<&()>, None).await
+    }
+
+    pub async fn list_users_page(&self, page: PageQuery) -> ClientResult<Vec<UserProfile>> {
+        self.authed_data(Method::GET, "/users", None::<&()>, Some(page)).await
+    }
+
+    pub fn users_pages(&self, per_page: u32) -> Pages<UserProfile> {
+        let client = self.clone();
+        Pages::new(per_page, move |page, per_page| {
+            let client = client.clone();
+            async move { client.list_users_page(PageQuery::new(page, per_page)).await }
+        })
+    }
+
+    // ===== HEALTH & INFO =====
+
+    pub async fn health_live(&self) -> ClientResult<Value> {
+        self.send_json(self.request(Method::GET, "/health/live")).await
+    }
+
+    // Laporan tetap dikembalikan walau server menjawab 503 (ada dependensi kritis yang down);
+    // cek `report.is_ready()`.
+    pub async fn health_ready(&self) -> ClientResult<ReadinessReport> {
+        let response = self.request(Method::GET, "/health/ready").send().await?;
+        if response.status() == StatusCode::SERVICE_UNAVAILABLE {
+            return Ok(response.json().await?);
+        }
+        parse_json(response).await
+    }
+
+    pub async fn version(&self) -> ClientResult<VersionInfo> {
+        self.send_json(self.request(Method::GET, "/version")).await
+    }
+
+    // Metrik dalam format teks Prometheus
+    pub async fn metrics(&self) -> ClientResult<String> {
+        let response = check_status(self.request(Method::GET, "/metrics").send().await?).await?;
+        Ok(response.text().await?)
+    }
+
+    // Spesifikasi OpenAPI dari server
+    pub async fn openapi(&self) -> ClientResult<Value> {
+        self.send_json(self.request(Method::GET, "/openapi.json")).await
+    }
+
+    // ===== INTERNAL =====
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http.request(method, format!("{}{}", self.base_url, path))
+    }
+
+    async fn request_token(&self, email: &str, password: &str) -> ClientResult<TokenResponse> {
+        let payload = LoginPayload { email: email.to_string(), password: password.to_string() };
+        self.send_data(self.request(Method::POST, "/auth/login").json(&payload)).await
+    }
+
+    async fn send_json<T: DeserializeOwned>(&self, request: RequestBuilder) -> ClientResult<T> {
+        parse_json(request.send().await?).await
+    }
+
+    async fn send_data<T: DeserializeOwned>(&self, request: RequestBuilder) -> ClientResult<T> {
+        Ok(self.send_json::<ApiResponse<T>>(request).await?.data)
+    }
+
+    async fn authed_data<T, B>(&self, method: Method, path: &str, body: Option<&B>, query: Option<PageQuery>) -> ClientResult<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        Ok(self.send_authed::<ApiResponse<T>, B>(method, path, body, query).await?.data)
+    }
+
+    async fn send_authed<T, B>(&self, method: Method, path: &str, body: Option<&B>, query: Option<PageQuery>) -> ClientResult<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let response = self
+            .send_authed_raw(method, path, |mut request| {
+                if let Some(query) = &query {
+                    request = request.query(query);
+                }
+                if let Some(body) = body {
+                    request = request.json(body);
+                }
+                request
+            })
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    async fn authed_data_with<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        build: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> ClientResult<T> {
+        let response = self.send_authed_raw(method, path, build).await?;
+        Ok(response.json::<ApiResponse<T>>().await?.data)
+    }
+
+    // Kirim request dengan token. Token yang hampir kedaluwarsa diperbarui dulu;
+    // jika server tetap menjawab 401, login ulang sekali lalu kirim ulang
+    // (karena itu `build` bisa dipanggil dua kali).
+    async fn send_authed_raw(
+        &self,
+        method: Method,
+        path: &str,
+        build: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> ClientResult<Response> {
+        let request = |token: &str| build(self.request(method.clone(), path).bearer_auth(token));
+
+        let token = self.valid_token().await?;
+        let response = request(&token).send().await?;
+        if response.status() == StatusCode::UNAUTHORIZED && self.has_credentials().await {
+            let token = self.refresh().await?;
+            return check_status(request(&token).send().await?).await;
+        }
+        check_status(response).await
+    }
+
+    async fn has_credentials(&self) -> bool {
+        self.session.read().await.credentials.is_some()
+    }
+
+    async fn valid_token(&self) -> ClientResult<String> {
+        {
+            let session = self.session.read().await;
+            let expiring = session
+                .expires_at
+                .is_some_and(|exp| exp - Duration::seconds(REFRESH_MARGIN_SECS) <= Utc::now());
+            match &session.access_token {
+                Some(token) if !(expiring && session.credentials.is_some()) => return Ok(token.clone()),
+                None if session.credentials.is_none() => return Err(ClientError::NotLoggedIn),
+                _ => {}
+            }
+        }
+        self.refresh().await
+    }
+
+    // Login ulang dengan kredensial tersimpan
+    async fn refresh(&self) -> ClientResult<String> {
+        let credentials = self.session.read().await.credentials.clone();
+        let (email, password) = credentials.ok_or(ClientError::NotLoggedIn)?;
+        let token = self.login(&email, &password).await?;
+        Ok(token.access_token)
+    }
+}
+
+async fn check_status(response: Response) -> ClientResult<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let request_id = response
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let text = response.text().await?;
+    // Body error standar; jika bukan JSON (misal dari proxy), pakai teks mentahnya
+    let body = serde_json::from_str::<ErrorResponse>(&text).unwrap_or(ErrorResponse {
+        status: "error".to_string(),
+        message: if text.is_empty() { status.to_string() } else { text },
+        request_id,
+    });
+    Err(ClientError::from_status(status.as_u16(), body))
+}
+
+// Body multipart dengan file di field `file` (upload lampiran & impor)
+fn file_form(file_name: &str, data: Bytes) -> Form {
+    Form::new().part("file", Part::stream(data).file_name(file_name.to_string()))
+}
+
+async fn parse_json<T: DeserializeOwned>(response: Response) -> ClientResult<T> {
+    Ok(check_status(response).await?.json().await?)
+}
@@ -0,0 +1,70 @@
+use bytes::Bytes;
+use futures_util::{Stream, TryStreamExt};
+use reqwest::{header, Response};
+
+use crate::error::{ClientError, ClientResult};
+
+// Respons berupa file (ekspor, lampiran, thumbnail). Body belum dibaca:
+// pakai `bytes()` untuk file kecil, atau `stream()` supaya file besar tidak ditampung di memori.
+pub struct Download {
+    response: Response,
+}
+
+impl Download {
+    pub(crate) fn new(response: Response) -> Self {
+        Self { response }
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.response.headers().get(header::CONTENT_TYPE)?.to_str().ok()
+    }
+
+    // Ukuran body jika server mengirim Content-Length (ekspor banyak catatan dialirkan tanpa ini)
+    pub fn content_length(&self) -> Option<u64> {
+        self.response.content_length()
+    }
+
+    // Nama file dari Content-Disposition; `filename*` (UTF-8) diutamakan
+    pub fn file_name(&self) -> Option<String> {
+        let value = self.response.headers().get(header::CONTENT_DISPOSITION)?.to_str().ok()?;
+        let params: Vec<(&str, &str)> = value
+            .split(';')
+            .filter_map(|param| param.trim().split_once('='))
+            .collect();
+        let encoded = params
+            .iter()
+            .find(|(key, _)| *key == "filename*")
+            .and_then(|(_, value)| value.strip_prefix("UTF-8''"))
+            .and_then(percent_decode);
+        encoded.or_else(|| {
+            params
+                .iter()
+                .find(|(key, _)| *key == "filename")
+                .map(|(_, value)| value.trim_matches('"').to_string())
+        })
+    }
+
+    pub async fn bytes(self) -> ClientResult<Bytes> {
+        Ok(self.response.bytes().await?)
+    }
+
+    pub fn stream(self) -> impl Stream<Item = ClientResult<Bytes>> + Send {
+        self.response.bytes_stream().map_err(ClientError::from)
+    }
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+
+use crate::models::ErrorResponse;
+
+pub type ClientResult<T> = Result<T, ClientError>;
+
+// Error dari server dipetakan per status HTTP; body error asli
+// (pesan + request_id) tetap dibawa supaya bisa ditampilkan/dilaporkan.
+#[derive(Debug)]
+pub enum ClientError {
+    // 400 / 422: payload atau query tidak valid
+    BadRequest(ErrorResponse),
+    // 401: token tidak ada, tidak valid, kedaluwarsa, atau email/password salah
+    Unauthorized(ErrorResponse),
+    // 403: bukan admin
+    Forbidden(ErrorResponse),
+    NotFound(ErrorResponse),
+    // 409: email atau username sudah dipakai
+    Conflict(ErrorResponse),
+    // Status error lain (biasanya 5xx)
+    Server { status: u16, body: ErrorResponse },
+    // Method yang butuh login dipanggil sebelum `login`/`set_access_token`
+    NotLoggedIn,
+    // Gagal koneksi, timeout, atau body respons tidak bisa dibaca
+    Http(reqwest::Error),
+}
+
+impl ClientError {
+    pub(crate) fn from_status(status: u16, body: ErrorResponse) -> Self {
+        match status {
+            400 | 422 => ClientError::BadRequest(body),
+            401 => ClientError::Unauthorized(body),
+            403 => ClientError::Forbidden(body),
+            404 => ClientError::NotFound(body),
+            409 => ClientError::Conflict(body),
+            _ => ClientError::Server { status, body },
+        }
+    }
+
+    // Body error dari server, jika error ini berasal dari respons HTTP
+    pub fn body(&self) -> Option<&ErrorResponse> {
+        match self {
+            ClientError::BadRequest(body)
+            | ClientError::Unauthorized(body)
+            | ClientError::Forbidden(body)
+            | ClientError::NotFound(body)
+            | ClientError::Conflict(body)
+            | ClientError::Server { body, .. } => Some(body),
+            ClientError::NotLoggedIn | ClientError::Http(_) => None,
+        }
+    }
+
+    // Id request dari server, berguna saat melapor masalah
+    pub fn request_id(&self) -> Option<&str> {
+        self.body().and_then(|body| body.request_id.as_deref())
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::NotLoggedIn => write!(f, "Belum login"),
+            ClientError::Http(e) => write!(f, "Gagal menghubungi server: {}", e),
+            ClientError::Server { status, body } => write!(f, "Server error {}: {}", status, body.message),
+            other => {
+                let body = other.body().expect("variant dengan body");
+                write!(f, "{}", body.message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Http(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> Self {
+        ClientError::Http(e)
+    }
+}
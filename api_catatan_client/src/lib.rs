@@ -0,0 +1,21 @@
+// Klien async untuk API Catatan.
+//
+//     let client = CatatanClient::new("http://localhost:3000");
+//     client.login("budi@example.com", "rahasia123").await?;
+//     let note = client.create_note(&CreateNotePayload { title: "Belanja".into(), content: None, content_format: None, tags: None }).await?;
+//
+// Tipe request/respons ada di `models` (bentuk JSON-nya sama dengan server), jadi crate ini
+// tidak ikut menarik dependensi server. Stream /events, /events/ws, dan /notes/:id/collab
+// (SSE/WebSocket) belum dibungkus; sambungkan langsung dengan token dari `access_token()`.
+mod client;
+mod download;
+mod error;
+mod models;
+mod pagination;
+
+pub use client::CatatanClient;
+pub use download::Download;
+pub use error::{ClientError, ClientResult};
+pub use pagination::Pages;
+
+pub use models::*;
@@ -0,0 +1,519 @@
+// Tipe request/respons API Catatan versi klien.
+// Bentuk JSON-nya mengikuti `domain::models` di server (diuji lewat tests/ terhadap server sungguhan),
+// tapi tanpa dependensi ke crate server, sqlx, atau utoipa.
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+const REDACTED: &str = "[REDACTED]";
+
+// ===== RESPONS UMUM =====
+
+// Wrapper JSON standar semua respons sukses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiResponse<T> {
+    pub status: String,
+    pub message: String,
+    pub data: T,
+}
+
+// Body JSON semua respons error
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    // Selalu "error"
+    pub status: String,
+    pub message: String,
+    // Id request (header X-Request-Id), sertakan saat melapor masalah
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+// Respons tanpa `data` (logout, hapus catatan/lampiran)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageResponse {
+    pub status: String,
+    pub message: String,
+}
+
+// Query `?page=2&per_page=20` untuk endpoint daftar
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PageQuery {
+    // Nomor halaman, mulai dari 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    // Jumlah item per halaman (default 20, maksimal 100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<u32>,
+}
+
+impl PageQuery {
+    pub fn new(page: u32, per_page: u32) -> Self {
+        Self { page: Some(page), per_page: Some(per_page) }
+    }
+}
+
+// ===== HEALTH & INFO =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyHealth {
+    pub status: String, // "up" atau "down"
+    pub critical: bool,
+    pub latency_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// Respons GET /health/ready
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessReport {
+    // "ok", "degraded", atau "unavailable"
+    pub status: String,
+    pub checks: BTreeMap<String, DependencyHealth>,
+}
+
+impl ReadinessReport {
+    pub fn is_ready(&self) -> bool {
+        self.status != "unavailable"
+    }
+}
+
+// Respons GET /version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionInfo {
+    pub name: String,
+    pub version: String,
+    pub git_commit: String,
+    pub build_time: String,
+}
+
+// ===== USER & AUTH =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: u32,
+    pub email: String,
+    pub full_name: String,
+    pub username: String,
+    pub role: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_local: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserProfile {
+    pub id: u32,
+    pub email: String,
+    pub full_name: String,
+    pub username: String,
+    pub role: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_local: Option<String>,
+}
+
+// Debug payload di bawah ditulis manual agar password/token tersamarkan jika di-log
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RegisterPayload {
+    pub email: String,
+    pub full_name: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl fmt::Debug for RegisterPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisterPayload")
+            .field("email", &self.email)
+            .field("full_name", &self.full_name)
+            .field("username", &self.username)
+            .field("password", &REDACTED)
+            .finish()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LoginPayload {
+    pub email: String,
+    pub password: String,
+}
+
+impl fmt::Debug for LoginPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoginPayload").field("email", &self.email).field("password", &REDACTED).finish()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_local: Option<String>,
+}
+
+impl fmt::Debug for TokenResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenResponse")
+            .field("access_token", &REDACTED)
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserSettings {
+    // Nama zona waktu IANA, misal "Asia/Makassar"
+    pub timezone: String,
+    // None = ikuti header Accept-Language
+    pub locale: Option<String>,
+    // Format strftime untuk bentuk waktu lokal
+    pub date_format: String,
+}
+
+// Payload PATCH /auth/settings, hanya field yang diisi yang diubah
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateSettingsPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+}
+
+// ===== CATATAN =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Note {
+    pub id: u32,
+    pub user_id: u32,
+    pub title: String,
+    pub content: Option<String>,
+    #[serde(default)]
+    pub content_format: ContentFormat,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub archived: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    // Nomor urut perubahan terakhir; dipakai sebagai `base_seq` di POST /sync
+    #[serde(default)]
+    pub change_seq: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_local: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_html: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    #[default]
+    Plain,
+    Markdown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateNotePayload {
+    pub title: String,
+    pub content: Option<String>,
+    // Default `plain`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_format: Option<ContentFormat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateNotePayload {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_format: Option<ContentFormat>,
+    // Mengganti seluruh daftar tag; `[]` = hapus semua tag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+// ===== BULK (POST /notes/bulk) =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum BulkOperation {
+    Delete {
+        ids: Vec<u32>,
+    },
+    // Field yang kosong tidak diubah
+    Update {
+        ids: Vec<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        content: Option<String>,
+    },
+    // `archived: false` = keluarkan dari arsip
+    Archive {
+        ids: Vec<u32>,
+        archived: bool,
+    },
+    // Tag di `remove` dibuang dulu, lalu tag di `add` ditambahkan
+    Tag {
+        ids: Vec<u32>,
+        #[serde(default)]
+        add: Vec<String>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkPayload {
+    pub operations: Vec<BulkOperation>,
+    // true = satu id gagal membatalkan seluruh operasinya
+    pub atomic: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkOperationKind {
+    Delete,
+    Update,
+    Archive,
+    Tag,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkItemStatus {
+    Applied,
+    NotFound,
+    Invalid,
+    // Diubah/dihapus request lain di tengah operasi
+    Conflict,
+    // Tidak diterapkan karena id lain di operasi atomik yang sama gagal
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkItemResult {
+    pub id: u32,
+    pub status: BulkItemStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<Note>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkOperationResult {
+    pub op: BulkOperationKind,
+    pub applied: usize,
+    pub failed: usize,
+    pub items: Vec<BulkItemResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkResult {
+    pub results: Vec<BulkOperationResult>,
+}
+
+// ===== SYNC (GET/POST /sync) =====
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncQuery {
+    // Token dari respons sebelumnya; kosong = sinkronisasi penuh
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tombstone {
+    pub note_id: u32,
+    pub user_id: u32,
+    pub change_seq: i64,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncPull {
+    pub token: String,
+    pub notes: Vec<Note>,
+    pub deleted: Vec<Tombstone>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum SyncChange {
+    Create {
+        client_id: String,
+        title: String,
+        content: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content_format: Option<ContentFormat>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tags: Option<Vec<String>>,
+    },
+    Update {
+        id: u32,
+        base_seq: i64,
+        title: Option<String>,
+        content: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content_format: Option<ContentFormat>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tags: Option<Vec<String>>,
+    },
+    Delete {
+        id: u32,
+        base_seq: Option<i64>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncPushPayload {
+    pub changes: Vec<SyncChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    Applied,
+    Conflict,
+    NotFound,
+    Invalid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncResult {
+    pub status: SyncStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<Note>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncPushResult {
+    pub results: Vec<SyncResult>,
+}
+
+// ===== LAMPIRAN =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: u32,
+    pub note_id: u32,
+    pub user_id: u32,
+    pub file_name: String,
+    pub content_type: String,
+    // Ukuran dalam byte
+    pub size: i64,
+    pub created_at: Option<DateTime<Utc>>,
+    // Khusus gambar, diisi server di background setelah upload
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub exif: Option<BTreeMap<String, String>>,
+    // Ukuran thumbnail yang tersedia; None = belum diproses atau bukan gambar
+    #[serde(default)]
+    pub thumbnails: Option<Vec<u32>>,
+}
+
+// ===== EKSPOR =====
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Zip,
+    Json,
+    Html,
+    Pdf,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteExportFormat {
+    #[default]
+    Md,
+    Json,
+    Html,
+    Pdf,
+}
+
+// Query GET /notes/export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    // Rentang tanggal dibuat (inklusif, zona waktu user)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<NaiveDate>,
+}
+
+// ===== IMPOR =====
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+    Markdown,
+    Enex,
+    Keep,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ImportStatus {
+    // Job tidak akan berubah lagi
+    pub fn is_finished(&self) -> bool {
+        matches!(self, ImportStatus::Completed | ImportStatus::Failed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportItemError {
+    pub item: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportJob {
+    pub id: String,
+    pub source: ImportSource,
+    pub status: ImportStatus,
+    pub total: usize,
+    pub processed: usize,
+    pub imported: usize,
+    pub failed: usize,
+    pub errors: Vec<ImportItemError>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
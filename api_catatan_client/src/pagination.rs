@@ -0,0 +1,68 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use futures_util::stream::{self, Stream, TryStreamExt};
+
+use crate::error::{ClientError, ClientResult};
+
+type PageFuture<T> = Pin<Box<dyn Future<Output = ClientResult<Vec<T>>> + Send>>;
+type FetchPage<T> = Box<dyn Fn(u32, u32) -> PageFuture<T> + Send + Sync>;
+
+// Iterator halaman untuk endpoint daftar (`?page=&per_page=`).
+// Berhenti saat server mengembalikan halaman yang tidak penuh.
+pub struct Pages<T> {
+    fetch: FetchPage<T>,
+    next_page: u32,
+    per_page: u32,
+    done: bool,
+}
+
+impl<T: Send + 'static> Pages<T> {
+    pub(crate) fn new<F, Fut>(per_page: u32, fetch: F) -> Self
+    where
+        F: Fn(u32, u32) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ClientResult<Vec<T>>> + Send + 'static,
+    {
+        Self {
+            fetch: Box::new(move |page, per_page| Box::pin(fetch(page, per_page))),
+            next_page: 1,
+            per_page: per_page.max(1),
+            done: false,
+        }
+    }
+
+    // Ambil halaman berikutnya, None jika sudah habis
+    pub async fn next_page(&mut self) -> ClientResult<Option<Vec<T>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let items = (self.fetch)(self.next_page, self.per_page).await?;
+        self.next_page += 1;
+        if (items.len() as u32) < self.per_page {
+            self.done = true;
+        }
+        if items.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(items))
+    }
+
+    // Semua item dari semua halaman sebagai stream, satu per satu
+    pub fn items(self) -> impl Stream<Item = ClientResult<T>> + Send {
+        stream::try_unfold(self, |mut pages| async move {
+            let items = pages.next_page().await?;
+            Ok::<_, ClientError>(items.map(|items| (stream::iter(items.into_iter().map(Ok::<T, ClientError>)), pages)))
+        })
+        .try_flatten()
+    }
+
+    // Kumpulkan semua item dari semua halaman
+    pub async fn collect_all(mut self) -> ClientResult<Vec<T>> {
+        let mut all = Vec::new();
+        while let Some(items) = self.next_page().await? {
+            all.extend(items);
+        }
+        Ok(all)
+    }
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+use api_catatan_client::RegisterPayload;
+use serde_json::Value;
+
+use common::start_server;
+
+// Satu file config per test supaya tidak saling menimpa
+struct Cli {
+    server: String,
+    config: PathBuf,
+}
+
+impl Cli {
+    fn new(server: &str, name: &str) -> Self {
+        let config = std::env::temp_dir().join(format!("catatan-test-{}-{}.json", name, std::process::id()));
+        std::fs::remove_file(&config).ok();
+        Self { server: server.to_string(), config }
+    }
+
+    // Jalankan binary `catatan` dengan stdin dan env tambahan.
+    // Dipanggil lewat spawn_blocking karena server berjalan di runtime yang sama.
+    async fn run(&self, args: &[&str], stdin: &str, envs: &[(&str, &str)]) -> Output {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_catatan"));
+        command
+            .args(args)
+            .env("CATATAN_CONFIG", &self.config)
+            .env("CATATAN_SERVER", &self.server)
+            .envs(envs.iter().copied())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let stdin = stdin.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut child = command.spawn().unwrap();
+            child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+            child.wait_with_output().unwrap()
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn json(&self, args: &[&str], stdin: &str) -> Value {
+        let mut full = vec!["--output", "json"];
+        full.extend_from_slice(args);
+        let output = self.run(&full, stdin, &[]).await;
+        assert!(output.status.success(), "{:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        serde_json::from_slice(&output.stdout).unwrap()
+    }
+
+    async fn login(&self, email: &str) {
+        let output = self.run(&["login", "--email", email, "--password-stdin"], "rahasia123\n", &[]).await;
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
+
+impl Drop for Cli {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.config).ok();
+    }
+}
+
+async fn setup(name: &str) -> Cli {
+    let (client, _) = start_server().await;
+    client
+        .register(&RegisterPayload {
+            email: format!("{name}@example.com"),
+            full_name: "Test User".to_string(),
+            username: name.to_string(),
+            password: "rahasia123".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let cli = Cli::new(client.base_url(), name);
+    cli.login(&format!("{name}@example.com")).await;
+    cli
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn login_menyimpan_token_di_config() {
+    let cli = setup("budi").await;
+
+    let config: Value = serde_json::from_str(&std::fs::read_to_string(&cli.config).unwrap()).unwrap();
+    assert!(config["access_token"].is_string());
+    assert_eq!(config["email"], "budi@example.com");
+    // Password tidak pernah disimpan
+    assert!(!config.to_string().contains("rahasia123"));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&cli.config).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn new_ls_show_search_rm() {
+    let cli = setup("citra").await;
+
+    let created = cli.json(&["new", "--title", "Belanja"], "telur\nsusu\n").await;
+    assert_eq!(created["title"], "Belanja");
+    assert_eq!(created["content"], "telur\nsusu\n");
+    let id = created["id"].as_u64().unwrap().to_string();
+    cli.json(&["new", "--title", "Rapat"], "").await;
+
+    let notes = cli.json(&["ls"], "").await;
+    assert_eq!(notes.as_array().unwrap().len(), 2);
+
+    let shown = cli.json(&["show", &id], "").await;
+    assert_eq!(shown["title"], "Belanja");
+
+    let found = cli.json(&["search", "SUSU"], "").await;
+    assert_eq!(found.as_array().unwrap().len(), 1);
+    assert_eq!(found[0]["title"], "Belanja");
+
+    // Mode tabel
+    let output = cli.run(&["ls"], "", &[]).await;
+    let table = String::from_utf8(output.stdout).unwrap();
+    assert!(table.starts_with("ID"), "{table}");
+    assert!(table.contains("Rapat"));
+
+    cli.json(&["rm", &id], "").await;
+    let output = cli.run(&["show", &id], "", &[]).await;
+    assert!(!output.status.success());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn edit_memakai_editor() {
+    let cli = setup("dodi").await;
+    let created = cli.json(&["new", "--title", "Draf"], "isi lama\n").await;
+    let id = created["id"].as_u64().unwrap().to_string();
+
+    // "Editor" palsu: skrip yang menulis ulang file
+    let editor = std::env::temp_dir().join(format!("catatan-editor-{}.sh", std::process::id()));
+    std::fs::write(&editor, "#!/bin/sh\nprintf 'Judul baru\\n\\nisi baru\\n' > \"$1\"\n").unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    let editor_path = editor.to_str().unwrap();
+    let output = cli
+        .run(&["--output", "json", "edit", &id], "", &[("EDITOR", editor_path), ("VISUAL", editor_path)])
+        .await;
+    std::fs::remove_file(&editor).ok();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let updated: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(updated["title"], "Judul baru");
+    assert_eq!(updated["content"], "isi baru\n");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn logout_menghapus_token() {
+    let cli = setup("eka").await;
+
+    let output = cli.run(&["logout"], "", &[]).await;
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let config: Value = serde_json::from_str(&std::fs::read_to_string(&cli.config).unwrap()).unwrap();
+    assert!(config["access_token"].is_null());
+
+    let output = cli.run(&["ls"], "", &[]).await;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("catatan login"));
+}
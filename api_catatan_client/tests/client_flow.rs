@@ -0,0 +1,285 @@
+mod common;
+
+use std::time::Duration;
+
+use api_catatan::{
+    application::user_service::UserService,
+    domain::models::user::{RegisterPayload as ServerRegisterPayload, Role},
+};
+use api_catatan_client::{
+    BulkItemStatus, BulkOperation, BulkPayload, CatatanClient, ClientError, CreateNotePayload, ExportFormat,
+    ExportQuery, ImportSource, ImportStatus, Note, NoteExportFormat, PageQuery, RegisterPayload, SyncChange,
+    SyncPushPayload, SyncQuery, SyncStatus, UpdateNotePayload, UpdateSettingsPayload,
+};
+use futures_util::TryStreamExt;
+
+use common::{registered_client, start_server};
+
+fn new_note(title: &str) -> CreateNotePayload {
+    CreateNotePayload { title: title.to_string(), content: Some("isi".to_string()), content_format: None, tags: None }
+}
+
+#[tokio::test]
+async fn crud_catatan_lewat_klien() {
+    let (client, _) = start_server().await;
+    registered_client(&client, "budi").await;
+
+    let note = client.create_note(&new_note("Belanja")).await.unwrap();
+    assert_eq!(note.title, "Belanja");
+
+    let updated = client
+        .update_note(note.id, &UpdateNotePayload { title: Some("Belanja bulanan".to_string()), ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(updated.title, "Belanja bulanan");
+    assert_eq!(updated.content.as_deref(), Some("isi"));
+
+    assert_eq!(client.get_note(note.id).await.unwrap().title, "Belanja bulanan");
+    assert_eq!(client.list_notes().await.unwrap().len(), 1);
+
+    client.delete_note(note.id).await.unwrap();
+    let error = client.get_note(note.id).await.unwrap_err();
+    assert!(matches!(error, ClientError::NotFound(_)), "{error:?}");
+    assert!(error.request_id().is_some());
+}
+
+#[tokio::test]
+async fn profil_dan_pengaturan() {
+    let (client, _) = start_server().await;
+    registered_client(&client, "citra").await;
+
+    assert_eq!(client.profile().await.unwrap().username, "citra");
+
+    let settings = client
+        .update_settings(&UpdateSettingsPayload { timezone: Some("Asia/Makassar".to_string()), ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(settings.timezone, "Asia/Makassar");
+    assert_eq!(client.settings().await.unwrap().timezone, "Asia/Makassar");
+}
+
+#[tokio::test]
+async fn error_dipetakan_ke_enum() {
+    let (client, _) = start_server().await;
+
+    assert!(matches!(client.list_notes().await, Err(ClientError::NotLoggedIn)));
+
+    let error = client.login("tidak-ada@example.com", "salah").await.unwrap_err();
+    assert!(matches!(error, ClientError::Unauthorized(_)), "{error:?}");
+
+    registered_client(&client, "dodi").await;
+    let error = client
+        .register(&RegisterPayload {
+            email: "dodi@example.com".to_string(),
+            full_name: "Dodi".to_string(),
+            username: "dodi2".to_string(),
+            password: "rahasia123".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::Conflict(_)), "{error:?}");
+
+    // Rute admin ditolak untuk user biasa
+    let error = client.list_users().await.unwrap_err();
+    assert!(matches!(error, ClientError::Forbidden(_)), "{error:?}");
+}
+
+#[tokio::test]
+async fn paginasi_mengambil_semua_halaman() {
+    let (client, _) = start_server().await;
+    registered_client(&client, "eka").await;
+
+    for i in 0..5 {
+        client.create_note(&new_note(&format!("Catatan {i}"))).await.unwrap();
+    }
+
+    let first = client.list_notes_page(PageQuery::new(1, 2)).await.unwrap();
+    assert_eq!(first.len(), 2);
+
+    let mut pages = client.notes_pages(2);
+    let mut sizes = Vec::new();
+    while let Some(page) = pages.next_page().await.unwrap() {
+        sizes.push(page.len());
+    }
+    assert_eq!(sizes, vec![2, 2, 1]);
+
+    let titles: Vec<String> = client.notes_pages(2).items().map_ok(|note| note.title).try_collect().await.unwrap();
+    assert_eq!(titles.len(), 5);
+    // Terbaru dulu, tanpa duplikat antar halaman
+    assert_eq!(titles.first().map(String::as_str), Some("Catatan 4"));
+    assert_eq!(client.notes_pages(3).collect_all().await.unwrap().len(), 5);
+}
+
+#[tokio::test]
+async fn token_tidak_valid_diperbarui_dengan_login_ulang() {
+    let (client, _) = start_server().await;
+    registered_client(&client, "fajar").await;
+
+    // Token rusak (misal sudah dicabut): klien login ulang lalu mengulang request
+    client.set_access_token("token-rusak", None).await;
+    assert_eq!(client.profile().await.unwrap().username, "fajar");
+    assert_ne!(client.access_token().await.as_deref(), Some("token-rusak"));
+
+    // Tanpa kredensial tersimpan, 401 diteruskan ke pemanggil
+    let other = CatatanClient::new(client.base_url());
+    other.set_access_token("token-rusak", None).await;
+    assert!(matches!(other.profile().await, Err(ClientError::Unauthorized(_))));
+}
+
+#[tokio::test]
+async fn logout_menghapus_sesi() {
+    let (client, _) = start_server().await;
+    registered_client(&client, "gita").await;
+    let token = client.access_token().await.unwrap();
+
+    client.logout().await.unwrap();
+    assert!(client.access_token().await.is_none());
+
+    // Token lama sudah dicabut di server
+    let other = CatatanClient::new(client.base_url());
+    other.set_access_token(token, None).await;
+    assert!(matches!(other.profile().await, Err(ClientError::Unauthorized(_))));
+}
+
+#[tokio::test]
+async fn rute_admin_dan_info() {
+    let (client, state) = start_server().await;
+    UserService::new(state.user_repo.clone())
+        .create_user(
+            ServerRegisterPayload {
+                email: "admin@example.com".to_string(),
+                full_name: "Admin".to_string(),
+                username: "admin".to_string(),
+                password: "rahasia123".to_string(),
+            },
+            Role::Admin,
+        )
+        .await
+        .unwrap();
+    client.login("admin@example.com", "rahasia123").await.unwrap();
+
+    assert_eq!(client.list_users().await.unwrap().len(), 1);
+    assert_eq!(client.users_pages(10).collect_all().await.unwrap().len(), 1);
+
+    assert_eq!(client.health_live().await.unwrap()["status"], "ok");
+    assert!(client.health_ready().await.unwrap().is_ready());
+    assert_eq!(client.version().await.unwrap().name, "api_catatan");
+    assert!(client.metrics().await.unwrap().contains("api_catatan_http_requests_total"));
+    assert!(client.openapi().await.unwrap()["paths"]["/notes"].is_object());
+}
+
+#[tokio::test]
+async fn bulk_dan_sync() {
+    let (client, _) = start_server().await;
+    registered_client(&client, "hana").await;
+    let a = client.create_note(&new_note("A")).await.unwrap();
+    let b = client.create_note(&new_note("B")).await.unwrap();
+
+    let result = client
+        .bulk_notes(&BulkPayload {
+            operations: vec![
+                BulkOperation::Tag { ids: vec![a.id, b.id], add: vec!["kerja".to_string()], remove: vec![] },
+                BulkOperation::Delete { ids: vec![b.id, 9999] },
+            ],
+            atomic: false,
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.results[0].applied, 2);
+    assert_eq!(result.results[0].items[0].note.as_ref().unwrap().tags, ["kerja"]);
+    let statuses: Vec<BulkItemStatus> = result.results[1].items.iter().map(|item| item.status).collect();
+    assert_eq!(statuses, [BulkItemStatus::Applied, BulkItemStatus::NotFound]);
+
+    let pull = client.sync_pull(&SyncQuery::default()).await.unwrap();
+    assert_eq!(pull.notes.len(), 1);
+    assert_eq!(pull.deleted[0].note_id, b.id);
+
+    let pushed = client
+        .sync_push(&SyncPushPayload {
+            changes: vec![
+                SyncChange::Create {
+                    client_id: "lokal-1".to_string(),
+                    title: "Dari HP".to_string(),
+                    content: None,
+                    content_format: None,
+                    tags: None,
+                },
+                // `a` sudah berubah lewat bulk di atas
+                SyncChange::Update {
+                    id: a.id,
+                    base_seq: a.change_seq,
+                    title: Some("Lama".to_string()),
+                    content: None,
+                    content_format: None,
+                    tags: None,
+                },
+            ],
+        })
+        .await
+        .unwrap();
+    assert_eq!(pushed.results[0].status, SyncStatus::Applied);
+    assert_eq!(pushed.results[0].client_id.as_deref(), Some("lokal-1"));
+    assert_eq!(pushed.results[1].status, SyncStatus::Conflict);
+
+    let next = client.sync_pull(&SyncQuery { since: Some(pull.token), limit: None }).await.unwrap();
+    assert_eq!(next.notes.len(), 1);
+    assert_eq!(next.notes[0].title, "Dari HP");
+}
+
+#[tokio::test]
+async fn lampiran_upload_unduh_hapus() {
+    let (client, _) = start_server().await;
+    registered_client(&client, "indra").await;
+    let note = client.create_note(&new_note("Dengan lampiran")).await.unwrap();
+
+    let attachment = client.upload_attachment(note.id, "catatan rapat.txt", "isi lampiran").await.unwrap();
+    assert_eq!(attachment.file_name, "catatan rapat.txt");
+    assert_eq!(attachment.size, 12);
+    assert_eq!(client.list_attachments(note.id).await.unwrap().len(), 1);
+
+    let download = client.download_attachment(note.id, attachment.id).await.unwrap();
+    assert_eq!(download.file_name().as_deref(), Some("catatan rapat.txt"));
+    assert!(download.content_type().unwrap().starts_with("text/plain"));
+    assert_eq!(&download.bytes().await.unwrap()[..], b"isi lampiran");
+
+    // Bukan gambar, jadi tidak punya thumbnail
+    let error = client.attachment_thumbnail(note.id, attachment.id, Some(256)).await.err().unwrap();
+    assert!(matches!(error, ClientError::NotFound(_)), "{error:?}");
+
+    client.delete_attachment(note.id, attachment.id).await.unwrap();
+    assert!(client.list_attachments(note.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn ekspor_dan_impor() {
+    let (client, _) = start_server().await;
+    registered_client(&client, "joko").await;
+    let note = client.create_note(&new_note("Diekspor")).await.unwrap();
+
+    let export = client.export_notes(&ExportQuery { format: ExportFormat::Json, ..Default::default() }).await.unwrap();
+    assert!(export.file_name().unwrap().ends_with(".json"));
+    let chunks: Vec<_> = export.stream().try_collect().await.unwrap();
+    let export: serde_json::Value = serde_json::from_slice(&chunks.concat()).unwrap();
+    let notes: Vec<Note> = serde_json::from_value(export["notes"].clone()).unwrap();
+    assert_eq!(notes[0].title, "Diekspor");
+
+    let markdown = client.export_note(note.id, NoteExportFormat::Md).await.unwrap();
+    assert!(markdown.file_name().unwrap().ends_with(".md"));
+    let markdown = String::from_utf8(markdown.bytes().await.unwrap().to_vec()).unwrap();
+    assert!(markdown.contains("title: \"Diekspor\""), "{markdown}");
+
+    let enex = r#"<?xml version="1.0" encoding="UTF-8"?>
+<en-export><note><title>Dari Evernote</title><content><![CDATA[<en-note>halo</en-note>]]></content></note></en-export>"#;
+    let mut job = client.import_notes("lama.xml", enex, Some(ImportSource::Enex)).await.unwrap();
+    assert_eq!(job.source, ImportSource::Enex);
+    while !job.status.is_finished() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        job = client.import_status(&job.id).await.unwrap();
+    }
+    assert_eq!(job.status, ImportStatus::Completed, "{job:?}");
+    assert_eq!(job.imported, 1);
+    assert!(client.list_notes().await.unwrap().iter().any(|note| note.title == "Dari Evernote"));
+
+    let error = client.import_status("tidak-ada").await.unwrap_err();
+    assert!(matches!(error, ClientError::NotFound(_)), "{error:?}");
+}
@@ -0,0 +1,86 @@
+#![allow(dead_code)] // Tidak semua file test memakai semua helper
+
+use std::sync::Arc;
+
+use api_catatan::{
+    application::{
+        collab_service::CollabHub, import_service::ImportJobs, note_renderer::NoteRenderer, user_service::SettingsCache,
+    },
+    domain::repositories::event_bus::DynEventBus,
+    infrastructure::{
+        events::in_memory_bus::InMemoryEventBus,
+        repositories::{
+            in_memory::{
+                attachment_repository_impl::InMemoryAttachmentRepository,
+                note_repository_impl::InMemoryNoteRepository, user_repository_impl::InMemoryUserRepository,
+            },
+            with_metrics,
+        },
+        storage::s3_store::S3BlobStore,
+        token_revocation::in_memory_store::InMemoryTokenRevocationStore,
+    },
+    presentation::routes::create_router,
+    utils::{background::BackgroundTasks, config::Config, metrics::Metrics},
+    AppState,
+};
+use api_catatan_client::{CatatanClient, RegisterPayload};
+
+// Jalankan server sungguhan (repository in-memory) di port acak
+pub async fn start_server() -> (CatatanClient, Arc<AppState>) {
+    let config: Config = serde_json::from_value(serde_json::json!({
+        "database_url": "sqlite::memory:",
+        "jwt_secret_key": "test-secret-key-yang-cukup-panjang-123",
+        "jwt_expires_in": "1h",
+    }))
+    .unwrap();
+
+    let metrics = Arc::new(Metrics::new());
+    let (user_repo, note_repo, attachment_repo) = with_metrics(
+        (
+            Arc::new(InMemoryUserRepository::new()),
+            Arc::new(InMemoryNoteRepository::new()),
+            Arc::new(InMemoryAttachmentRepository::new()),
+        ),
+        &metrics,
+    );
+    let events: DynEventBus = Arc::new(InMemoryEventBus::default());
+    let collab = Arc::new(CollabHub::new(note_repo.clone(), events.clone(), config.collab_snapshot_interval));
+    let state = Arc::new(AppState {
+        config: Arc::new(config),
+        user_repo,
+        note_repo,
+        attachment_repo,
+        blobs: Arc::new(S3BlobStore::in_memory()),
+        token_revocation: Arc::new(InMemoryTokenRevocationStore::default()),
+        health_checks: Vec::new(),
+        metrics,
+        background: BackgroundTasks::new(),
+        events,
+        collab,
+        renderer: Arc::new(NoteRenderer::default()),
+        imports: Arc::new(ImportJobs::new()),
+        settings: Arc::new(SettingsCache::new()),
+    });
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = create_router(state.clone());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (CatatanClient::new(format!("http://{addr}/")), state)
+}
+
+// Register + login, sesi disimpan di `client`
+pub async fn registered_client(client: &CatatanClient, username: &str) {
+    let email = format!("{username}@example.com");
+    client
+        .register(&RegisterPayload {
+            email: email.clone(),
+            full_name: "Test User".to_string(),
+            username: username.to_string(),
+            password: "rahasia123".to_string(),
+        })
+        .await
+        .unwrap();
+    client.login(&email, "rahasia123").await.unwrap();
+}
@@ -0,0 +1,24 @@
+use std::process::Command;
+
+fn main() {
+    // Kompilasi ulang jika ada file migrasi baru/berubah,
+    // karena `sqlx::migrate!` meng-embed isi folder `migrations/` saat build.
+    println!("cargo:rerun-if-changed=migrations");
+
+    // Info build untuk GET /version
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+
+    let git_commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|sha| sha.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GIT_COMMIT={}", git_commit);
+
+    let build_time = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
+    println!("cargo:rustc-env=BUILD_TIME={}", build_time);
+}
@@ -0,0 +1,7 @@
+-- Token yang sudah di-revoke (logout), disimpan sebagai hash SHA-256
+CREATE TABLE IF NOT EXISTS revoked_tokens (
+    token_hash CHAR(64) NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (token_hash),
+    INDEX idx_revoked_tokens_expires (expires_at)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
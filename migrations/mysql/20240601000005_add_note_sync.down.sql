@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS note_tombstones;
+DROP TABLE IF EXISTS note_sync_counters;
+ALTER TABLE notes
+    DROP INDEX idx_notes_user_seq,
+    DROP COLUMN updated_at,
+    DROP COLUMN change_seq;
@@ -0,0 +1,30 @@
+-- Delta sync: setiap perubahan catatan mendapat nomor urut per user (change_seq)
+ALTER TABLE notes
+    ADD COLUMN change_seq BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN updated_at TIMESTAMP NULL,
+    ADD INDEX idx_notes_user_seq (user_id, change_seq);
+
+-- Nomor urut terakhir per user. Baris ini dikunci selama transaksi perubahan,
+-- jadi urutan commit selalu sama dengan urutan change_seq.
+CREATE TABLE IF NOT EXISTS note_sync_counters (
+    user_id INT UNSIGNED NOT NULL,
+    last_seq BIGINT NOT NULL,
+    PRIMARY KEY (user_id),
+    CONSTRAINT fk_note_sync_counters_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+-- Jejak catatan yang dihapus, supaya klien offline tahu apa yang harus dihapus
+CREATE TABLE IF NOT EXISTS note_tombstones (
+    note_id INT UNSIGNED NOT NULL,
+    user_id INT UNSIGNED NOT NULL,
+    change_seq BIGINT NOT NULL,
+    deleted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (note_id),
+    INDEX idx_note_tombstones_user_seq (user_id, change_seq),
+    CONSTRAINT fk_note_tombstones_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+-- Catatan lama: id dipakai sebagai change_seq awal (unik per user)
+UPDATE notes SET change_seq = id;
+INSERT INTO note_sync_counters (user_id, last_seq)
+SELECT user_id, MAX(change_seq) FROM notes GROUP BY user_id;
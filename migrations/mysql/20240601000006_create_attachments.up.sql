@@ -0,0 +1,17 @@
+-- Metadata lampiran catatan. Isi file ada di blob store (disk lokal atau S3),
+-- kolom storage_key menunjuk ke objeknya. Ikut terhapus bersama catatannya.
+CREATE TABLE IF NOT EXISTS attachments (
+    id INT UNSIGNED NOT NULL AUTO_INCREMENT,
+    note_id INT UNSIGNED NOT NULL,
+    user_id INT UNSIGNED NOT NULL,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size BIGINT NOT NULL,
+    storage_key VARCHAR(512) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    INDEX idx_attachments_note (note_id),
+    INDEX idx_attachments_user (user_id),
+    CONSTRAINT fk_attachments_note FOREIGN KEY (note_id) REFERENCES notes (id) ON DELETE CASCADE,
+    CONSTRAINT fk_attachments_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
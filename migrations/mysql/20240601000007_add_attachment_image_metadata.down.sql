@@ -0,0 +1,5 @@
+ALTER TABLE attachments
+    DROP COLUMN thumbnails,
+    DROP COLUMN exif,
+    DROP COLUMN height,
+    DROP COLUMN width;
@@ -0,0 +1,7 @@
+-- Metadata lampiran gambar, diisi di background setelah upload.
+-- thumbnails = daftar ukuran thumbnail yang tersedia; NULL = belum diproses atau bukan gambar.
+ALTER TABLE attachments
+    ADD COLUMN width INT UNSIGNED NULL,
+    ADD COLUMN height INT UNSIGNED NULL,
+    ADD COLUMN exif JSON NULL,
+    ADD COLUMN thumbnails JSON NULL;
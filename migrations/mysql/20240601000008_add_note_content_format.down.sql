@@ -0,0 +1,3 @@
+ALTER TABLE notes
+    DROP CHECK chk_notes_content_format,
+    DROP COLUMN content_format;
@@ -0,0 +1,4 @@
+-- Format isi catatan: 'plain' (teks biasa) atau 'markdown'
+ALTER TABLE notes
+    ADD COLUMN content_format VARCHAR(16) NOT NULL DEFAULT 'plain' AFTER content,
+    ADD CONSTRAINT chk_notes_content_format CHECK (content_format IN ('plain', 'markdown'));
@@ -0,0 +1,2 @@
+-- Tag catatan sebagai array JSON string, misal ["kerja","penting"]
+ALTER TABLE notes ADD COLUMN tags JSON NOT NULL DEFAULT (JSON_ARRAY()) AFTER content_format;
@@ -0,0 +1,15 @@
+-- Tabel utama user
+CREATE TABLE IF NOT EXISTS users (
+    id SERIAL PRIMARY KEY,
+    email VARCHAR(255) NOT NULL,
+    full_name VARCHAR(255) NOT NULL,
+    username VARCHAR(100) NOT NULL,
+    role VARCHAR(20) NOT NULL DEFAULT 'user',
+    password_hash VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT uq_users_email UNIQUE (email),
+    CONSTRAINT uq_users_username UNIQUE (username),
+    CONSTRAINT chk_users_role CHECK (role IN ('user', 'admin')),
+    CONSTRAINT chk_users_email CHECK (CHAR_LENGTH(email) > 0),
+    CONSTRAINT chk_users_username CHECK (CHAR_LENGTH(username) > 0)
+);
@@ -0,0 +1,10 @@
+-- Preferensi user (zona waktu IANA, bahasa, format tanggal)
+CREATE TABLE IF NOT EXISTS user_settings (
+    user_id INTEGER PRIMARY KEY,
+    timezone VARCHAR(64) NOT NULL DEFAULT 'Asia/Jakarta',
+    locale VARCHAR(8) NULL,
+    date_format VARCHAR(64) NOT NULL DEFAULT '%Y-%m-%d %H:%M:%S %Z',
+    CONSTRAINT chk_user_settings_locale CHECK (locale IS NULL OR locale IN ('id', 'en')),
+    CONSTRAINT chk_user_settings_date_format CHECK (CHAR_LENGTH(date_format) > 0),
+    CONSTRAINT fk_user_settings_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS note_tombstones;
+DROP TABLE IF EXISTS note_sync_counters;
+DROP INDEX IF EXISTS idx_notes_user_seq;
+ALTER TABLE notes
+    DROP COLUMN IF EXISTS updated_at,
+    DROP COLUMN IF EXISTS change_seq;
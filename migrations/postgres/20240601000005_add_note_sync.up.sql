@@ -0,0 +1,30 @@
+-- Delta sync: setiap perubahan catatan mendapat nomor urut per user (change_seq)
+ALTER TABLE notes
+    ADD COLUMN change_seq BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN updated_at TIMESTAMPTZ NULL;
+
+CREATE INDEX IF NOT EXISTS idx_notes_user_seq ON notes (user_id, change_seq);
+
+-- Nomor urut terakhir per user. Baris ini dikunci selama transaksi perubahan,
+-- jadi urutan commit selalu sama dengan urutan change_seq.
+CREATE TABLE IF NOT EXISTS note_sync_counters (
+    user_id INTEGER PRIMARY KEY,
+    last_seq BIGINT NOT NULL,
+    CONSTRAINT fk_note_sync_counters_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+-- Jejak catatan yang dihapus, supaya klien offline tahu apa yang harus dihapus
+CREATE TABLE IF NOT EXISTS note_tombstones (
+    note_id INTEGER PRIMARY KEY,
+    user_id INTEGER NOT NULL,
+    change_seq BIGINT NOT NULL,
+    deleted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_note_tombstones_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_note_tombstones_user_seq ON note_tombstones (user_id, change_seq);
+
+-- Catatan lama: id dipakai sebagai change_seq awal (unik per user)
+UPDATE notes SET change_seq = id;
+INSERT INTO note_sync_counters (user_id, last_seq)
+SELECT user_id, MAX(change_seq) FROM notes GROUP BY user_id;
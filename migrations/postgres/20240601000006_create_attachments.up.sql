@@ -0,0 +1,17 @@
+-- Metadata lampiran catatan. Isi file ada di blob store (disk lokal atau S3),
+-- kolom storage_key menunjuk ke objeknya. Ikut terhapus bersama catatannya.
+CREATE TABLE IF NOT EXISTS attachments (
+    id SERIAL PRIMARY KEY,
+    note_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size BIGINT NOT NULL,
+    storage_key VARCHAR(512) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_attachments_note FOREIGN KEY (note_id) REFERENCES notes (id) ON DELETE CASCADE,
+    CONSTRAINT fk_attachments_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_note ON attachments (note_id);
+CREATE INDEX IF NOT EXISTS idx_attachments_user ON attachments (user_id);
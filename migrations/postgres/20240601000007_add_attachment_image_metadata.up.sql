@@ -0,0 +1,7 @@
+-- Metadata lampiran gambar, diisi di background setelah upload.
+-- thumbnails = daftar ukuran thumbnail yang tersedia; NULL = belum diproses atau bukan gambar.
+ALTER TABLE attachments
+    ADD COLUMN width INTEGER NULL,
+    ADD COLUMN height INTEGER NULL,
+    ADD COLUMN exif JSONB NULL,
+    ADD COLUMN thumbnails JSONB NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE notes
+    DROP CONSTRAINT IF EXISTS chk_notes_content_format,
+    DROP COLUMN IF EXISTS content_format;
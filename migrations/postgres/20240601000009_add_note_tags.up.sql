@@ -0,0 +1,5 @@
+-- Tag catatan sebagai array JSON string, misal ["kerja","penting"]
+ALTER TABLE notes ADD COLUMN tags JSONB NOT NULL DEFAULT '[]'::jsonb;
+
+-- Untuk filter `tags @> '["kerja"]'`
+CREATE INDEX IF NOT EXISTS idx_notes_tags ON notes USING GIN (tags jsonb_path_ops);
@@ -0,0 +1,2 @@
+-- Catatan yang diarsipkan (lewat POST /notes/bulk)
+ALTER TABLE notes ADD COLUMN archived BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,15 @@
+-- Tabel utama user
+CREATE TABLE IF NOT EXISTS users (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    email TEXT NOT NULL,
+    full_name TEXT NOT NULL,
+    username TEXT NOT NULL,
+    role TEXT NOT NULL DEFAULT 'user',
+    password_hash TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT uq_users_email UNIQUE (email),
+    CONSTRAINT uq_users_username UNIQUE (username),
+    CONSTRAINT chk_users_role CHECK (role IN ('user', 'admin')),
+    CONSTRAINT chk_users_email CHECK (LENGTH(email) > 0),
+    CONSTRAINT chk_users_username CHECK (LENGTH(username) > 0)
+);
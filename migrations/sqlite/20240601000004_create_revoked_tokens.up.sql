@@ -0,0 +1,7 @@
+-- Token yang sudah di-revoke (logout), disimpan sebagai hash SHA-256
+CREATE TABLE IF NOT EXISTS revoked_tokens (
+    token_hash TEXT PRIMARY KEY,
+    expires_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_revoked_tokens_expires ON revoked_tokens (expires_at);
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS note_tombstones;
+DROP TABLE IF EXISTS note_sync_counters;
+DROP INDEX IF EXISTS idx_notes_user_seq;
+ALTER TABLE notes DROP COLUMN updated_at;
+ALTER TABLE notes DROP COLUMN change_seq;
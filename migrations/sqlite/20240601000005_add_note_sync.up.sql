@@ -0,0 +1,28 @@
+-- Delta sync: setiap perubahan catatan mendapat nomor urut per user (change_seq)
+ALTER TABLE notes ADD COLUMN change_seq INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE notes ADD COLUMN updated_at TEXT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_notes_user_seq ON notes (user_id, change_seq);
+
+-- Nomor urut terakhir per user, dinaikkan di transaksi yang sama dengan perubahannya
+CREATE TABLE IF NOT EXISTS note_sync_counters (
+    user_id INTEGER PRIMARY KEY,
+    last_seq INTEGER NOT NULL,
+    CONSTRAINT fk_note_sync_counters_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+-- Jejak catatan yang dihapus, supaya klien offline tahu apa yang harus dihapus
+CREATE TABLE IF NOT EXISTS note_tombstones (
+    note_id INTEGER PRIMARY KEY,
+    user_id INTEGER NOT NULL,
+    change_seq INTEGER NOT NULL,
+    deleted_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_note_tombstones_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_note_tombstones_user_seq ON note_tombstones (user_id, change_seq);
+
+-- Catatan lama: id dipakai sebagai change_seq awal (unik per user)
+UPDATE notes SET change_seq = id;
+INSERT INTO note_sync_counters (user_id, last_seq)
+SELECT user_id, MAX(change_seq) FROM notes GROUP BY user_id;
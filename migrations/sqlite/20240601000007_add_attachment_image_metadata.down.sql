@@ -0,0 +1,4 @@
+ALTER TABLE attachments DROP COLUMN thumbnails;
+ALTER TABLE attachments DROP COLUMN exif;
+ALTER TABLE attachments DROP COLUMN height;
+ALTER TABLE attachments DROP COLUMN width;
@@ -0,0 +1,6 @@
+-- Metadata lampiran gambar, diisi di background setelah upload.
+-- thumbnails = daftar ukuran thumbnail yang tersedia (JSON); NULL = belum diproses atau bukan gambar.
+ALTER TABLE attachments ADD COLUMN width INTEGER NULL;
+ALTER TABLE attachments ADD COLUMN height INTEGER NULL;
+ALTER TABLE attachments ADD COLUMN exif TEXT NULL;
+ALTER TABLE attachments ADD COLUMN thumbnails TEXT NULL;
@@ -0,0 +1,3 @@
+-- Format isi catatan: 'plain' (teks biasa) atau 'markdown'
+ALTER TABLE notes ADD COLUMN content_format TEXT NOT NULL DEFAULT 'plain'
+    CHECK (content_format IN ('plain', 'markdown'));
@@ -0,0 +1,2 @@
+-- Tag catatan sebagai array JSON string, misal ["kerja","penting"]
+ALTER TABLE notes ADD COLUMN tags TEXT NOT NULL DEFAULT '[]' CHECK (json_valid(tags));
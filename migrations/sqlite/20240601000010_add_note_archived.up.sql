@@ -0,0 +1,2 @@
+-- Catatan yang diarsipkan (lewat POST /notes/bulk), 0 = tidak
+ALTER TABLE notes ADD COLUMN archived INTEGER NOT NULL DEFAULT 0 CHECK (archived IN (0, 1));
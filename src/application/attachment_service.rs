@@ -0,0 +1,387 @@
+use bytes::{Bytes, BytesMut};
+use futures_util::{stream, StreamExt};
+use image::ImageFormat;
+use sqlx::types::Json;
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    application::image_processing::process_image,
+    domain::{
+        models::{
+            attachment::{Attachment, ImageMetadata, NewAttachment, SNIFF_BYTES},
+            event::NoteEventKind,
+        },
+        repositories::{
+            attachment_repository::DynAttachmentRepository,
+            blob_store::{BlobStream, DynBlobStore},
+            event_bus::DynEventBus,
+            note_repository::DynNoteRepository,
+        },
+    },
+    utils::{
+        background::BackgroundTasks,
+        config::Config,
+        error::{AppError, AppResult},
+        i18n::Msg,
+    },
+};
+
+// Pengaturan lampiran (ATTACHMENT_MAX_FILE_BYTES, ATTACHMENT_QUOTA_BYTES & THUMBNAIL_SIZES)
+#[derive(Debug, Clone)]
+pub struct AttachmentSettings {
+    pub max_file_bytes: u64,
+    pub quota_bytes: u64,
+    pub thumbnail_sizes: Vec<u32>,
+}
+
+impl From<&Config> for AttachmentSettings {
+    fn from(config: &Config) -> Self {
+        Self {
+            max_file_bytes: config.attachment_max_file_bytes,
+            quota_bytes: config.attachment_quota_bytes,
+            thumbnail_sizes: config.thumbnail_sizes.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AttachmentService {
+    attachment_repo: DynAttachmentRepository,
+    note_repo: DynNoteRepository,
+    blobs: DynBlobStore,
+    settings: Arc<AttachmentSettings>,
+    background: BackgroundTasks,
+}
+
+impl AttachmentService {
+    pub fn new(
+        attachment_repo: DynAttachmentRepository,
+        note_repo: DynNoteRepository,
+        blobs: DynBlobStore,
+        settings: AttachmentSettings,
+        background: BackgroundTasks,
+    ) -> Self {
+        Self { attachment_repo, note_repo, blobs, settings: Arc::new(settings), background }
+    }
+
+    // Catatan harus ada dan milik user; selain itu 404 seperti endpoint /notes/:id
+    async fn ensure_note(&self, note_id: u32, user_id: u32) -> AppResult<()> {
+        match self.note_repo.find_by_id(note_id, user_id).await? {
+            Some(_) => Ok(()),
+            None => Err(AppError::NotFound(Msg::NoteNotFound(note_id))),
+        }
+    }
+
+    // Simpan file dari stream tanpa menampungnya di memori. Yang dibaca di depan
+    // hanya SNIFF_BYTES pertama untuk menentukan content type.
+    #[tracing::instrument(name = "AttachmentService.upload", skip_all, fields(note.id = note_id, user.id = user_id))]
+    pub async fn upload(
+        &self,
+        note_id: u32,
+        user_id: u32,
+        file_name: &str,
+        mut data: BlobStream<'_>,
+    ) -> AppResult<Attachment> {
+        self.ensure_note(note_id, user_id).await?;
+
+        // Batas file ini = yang lebih kecil antara batas per file dan sisa kuota user.
+        // Dua upload bersamaan bisa sedikit melewati kuota; itu masih bisa diterima.
+        let used = self.attachment_repo.total_size(user_id).await?.max(0) as u64;
+        let remaining = self.settings.quota_bytes.saturating_sub(used);
+        let (limit, too_large) = if remaining < self.settings.max_file_bytes {
+            (remaining, Msg::StorageQuotaExceeded(self.settings.quota_bytes))
+        } else {
+            (self.settings.max_file_bytes, Msg::FileTooLarge(self.settings.max_file_bytes))
+        };
+        if limit == 0 {
+            return Err(AppError::PayloadTooLarge(too_large));
+        }
+
+        let mut head = BytesMut::new();
+        while head.len() < SNIFF_BYTES {
+            match data.next().await {
+                Some(Ok(chunk)) => head.extend_from_slice(&chunk),
+                Some(Err(e)) => return Err(AppError::BadRequest(Msg::InvalidMultipart(e.to_string()))),
+                None => break,
+            }
+        }
+        let content_type = sniff_content_type(&head);
+
+        // Error dari stream dicatat di sini supaya bisa dibedakan dari error blob store
+        let failure = Arc::new(Mutex::new(None));
+        let limited = limit_stream(stream::once(async { Ok(head.freeze()) }).chain(data), limit, too_large, failure.clone());
+
+        let storage_key = format!("notes/{}/{}/{}", user_id, note_id, uuid::Uuid::new_v4());
+        let size = match self.blobs.put(&storage_key, Box::pin(limited)).await {
+            Ok(size) => size,
+            Err(e) => return Err(failure.lock().unwrap().take().unwrap_or(e)),
+        };
+
+        let new_attachment = NewAttachment {
+            note_id,
+            user_id,
+            file_name: clean_file_name(file_name),
+            content_type,
+            size: size as i64,
+            storage_key: storage_key.clone(),
+        };
+        match self.attachment_repo.create(&new_attachment).await {
+            Ok(attachment) => {
+                if attachment.is_image() {
+                    self.spawn_image_processing(attachment.clone());
+                }
+                Ok(attachment)
+            }
+            Err(e) => {
+                // Catatan bisa terhapus selama upload (foreign key gagal); jangan sisakan blob
+                if let Err(e) = self.blobs.delete(&storage_key).await {
+                    tracing::warn!("Gagal menghapus blob {}: {}", storage_key, e);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "AttachmentService.list", skip_all, fields(note.id = note_id, user.id = user_id))]
+    pub async fn list(&self, note_id: u32, user_id: u32) -> AppResult<Vec<Attachment>> {
+        self.ensure_note(note_id, user_id).await?;
+        self.attachment_repo.find_by_note(note_id, user_id).await
+    }
+
+    // Metadata + isi file untuk diunduh
+    #[tracing::instrument(name = "AttachmentService.open", skip_all, fields(attachment.id = id, user.id = user_id))]
+    pub async fn open(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<(Attachment, BlobStream<'static>)> {
+        let attachment = self.get(id, note_id, user_id).await?;
+        let data = self
+            .blobs
+            .get(&attachment.storage_key)
+            .await?
+            .ok_or(AppError::NotFound(Msg::AttachmentNotFound(id)))?;
+        Ok((attachment, data))
+    }
+
+    // Thumbnail dengan ukuran terkecil yang tersedia dan >= `size`. Tanpa `size`
+    // dipakai yang terkecil; jika semua lebih kecil dari `size`, yang terbesar.
+    #[tracing::instrument(name = "AttachmentService.thumbnail", skip_all, fields(attachment.id = id, user.id = user_id))]
+    pub async fn thumbnail(
+        &self,
+        id: u32,
+        note_id: u32,
+        user_id: u32,
+        size: Option<u32>,
+    ) -> AppResult<(Attachment, BlobStream<'static>)> {
+        let attachment = self.get(id, note_id, user_id).await?;
+        let mut sizes = attachment.thumbnails.as_ref().map(|t| t.0.clone()).unwrap_or_default();
+        sizes.sort_unstable();
+        let chosen = match size {
+            None => sizes.first().copied(),
+            Some(size) => sizes.iter().copied().find(|s| *s >= size).or(sizes.last().copied()),
+        }
+        .ok_or(AppError::NotFound(Msg::ThumbnailNotAvailable(id)))?;
+
+        let data = self
+            .blobs
+            .get(&attachment.thumbnail_key(chosen))
+            .await?
+            .ok_or(AppError::NotFound(Msg::ThumbnailNotAvailable(id)))?;
+        Ok((attachment, data))
+    }
+
+    pub async fn get(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<Attachment> {
+        self.attachment_repo
+            .find_by_id(id, note_id, user_id)
+            .await?
+            .ok_or(AppError::NotFound(Msg::AttachmentNotFound(id)))
+    }
+
+    #[tracing::instrument(name = "AttachmentService.delete", skip_all, fields(attachment.id = id, user.id = user_id))]
+    pub async fn delete(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<()> {
+        let attachment = self.get(id, note_id, user_id).await?;
+        if self.attachment_repo.delete(id, note_id, user_id).await? == 0 {
+            return Err(AppError::NotFound(Msg::AttachmentNotFound(id)));
+        }
+        // Baris sudah terhapus; blob yang gagal dihapus hanya jadi sampah di storage
+        self.delete_blobs(&attachment).await;
+        Ok(())
+    }
+
+    // File asli beserta semua thumbnail-nya
+    async fn delete_blobs(&self, attachment: &Attachment) {
+        let thumbnails = attachment.thumbnails.as_ref().map(|t| t.0.clone()).unwrap_or_default();
+        let keys = std::iter::once(attachment.storage_key.clone())
+            .chain(thumbnails.into_iter().map(|size| attachment.thumbnail_key(size)));
+        for key in keys {
+            if let Err(e) = self.blobs.delete(&key).await {
+                tracing::warn!("Gagal menghapus blob {}: {}", key, e);
+            }
+        }
+    }
+
+    // Ukuran, EXIF & thumbnail gambar dibuat di background supaya upload tidak
+    // menunggu proses decode/resize
+    fn spawn_image_processing(&self, attachment: Attachment) {
+        let service = self.clone();
+        self.background.spawn(move |cancel| async move {
+            tokio::select! {
+                _ = cancel.cancelled() => {}
+                _ = service.generate_image_metadata(attachment) => {}
+            }
+        });
+    }
+
+    #[tracing::instrument(name = "AttachmentService.generate_image_metadata", skip_all, fields(attachment.id = attachment.id))]
+    async fn generate_image_metadata(&self, attachment: Attachment) {
+        let data = match self.read_blob(&attachment.storage_key).await {
+            Ok(Some(data)) => data,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!("Gagal membaca lampiran {}: {}", attachment.id, e);
+                return;
+            }
+        };
+
+        let sizes = self.settings.thumbnail_sizes.clone();
+        let format = match attachment.thumbnail_content_type() {
+            "image/jpeg" => ImageFormat::Jpeg,
+            _ => ImageFormat::Png,
+        };
+        let processed = tokio::task::spawn_blocking(move || process_image(&data, &sizes, format)).await;
+        let metadata = match processed {
+            Ok(Ok(processed)) => {
+                for (size, thumbnail) in processed.thumbnails {
+                    let key = attachment.thumbnail_key(size);
+                    let data = stream::once(async move { Ok(Bytes::from(thumbnail)) });
+                    if let Err(e) = self.blobs.put(&key, Box::pin(data)).await {
+                        tracing::warn!("Gagal menyimpan thumbnail {}: {}", key, e);
+                        return;
+                    }
+                }
+                processed.metadata
+            }
+            // File rusak atau terlalu besar: tandai sudah diproses tanpa thumbnail
+            Ok(Err(e)) => {
+                tracing::info!("Lampiran {} tidak bisa dibaca sebagai gambar: {}", attachment.id, e);
+                ImageMetadata::default()
+            }
+            Err(e) => {
+                tracing::warn!("Pemrosesan gambar lampiran {} gagal: {}", attachment.id, e);
+                return;
+            }
+        };
+
+        if let Err(e) = self.attachment_repo.set_image_metadata(attachment.id, &metadata).await {
+            tracing::warn!("Gagal menyimpan metadata gambar lampiran {}: {}", attachment.id, e);
+        }
+        // Lampiran bisa terhapus selagi diproses; jangan sisakan thumbnail-nya
+        let exists = self.attachment_repo.find_by_id(attachment.id, attachment.note_id, attachment.user_id).await;
+        if matches!(exists, Ok(None)) {
+            let attachment = Attachment { thumbnails: Some(Json(metadata.thumbnails)), ..attachment };
+            self.delete_blobs(&attachment).await;
+        }
+    }
+
+    async fn read_blob(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        let Some(mut data) = self.blobs.get(key).await? else {
+            return Ok(None);
+        };
+        let mut buffer = Vec::new();
+        while let Some(chunk) = data.next().await {
+            buffer.extend_from_slice(&chunk?);
+        }
+        Ok(Some(buffer))
+    }
+
+    // Hapus semua lampiran catatan yang sudah dihapus (lewat REST, /sync, dll.)
+    async fn remove_note_attachments(&self, note_id: u32, user_id: u32) {
+        if let Err(e) = self.attachment_repo.delete_by_note(note_id, user_id).await {
+            tracing::warn!("Gagal menghapus metadata lampiran catatan {}: {}", note_id, e);
+        }
+        if let Err(e) = self.blobs.delete_prefix(&format!("notes/{}/{}/", user_id, note_id)).await {
+            tracing::warn!("Gagal menghapus file lampiran catatan {}: {}", note_id, e);
+        }
+    }
+
+    // Dengarkan event note.deleted dan bersihkan lampirannya di background
+    pub fn spawn_cleanup(&self, events: &DynEventBus) {
+        let service = self.clone();
+        let mut receiver = events.subscribe();
+        self.background.spawn(move |cancel| async move {
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    event = receiver.recv() => match event {
+                        Ok(event) if event.kind == NoteEventKind::Deleted => {
+                            service.remove_note_attachments(event.note_id, event.user_id).await;
+                        }
+                        Ok(_) => {}
+                        Err(RecvError::Lagged(skipped)) => {
+                            tracing::warn!("{} event terlewat, lampiran catatan yang dihapus bisa tersisa", skipped);
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                }
+            }
+        });
+    }
+}
+
+// Hentikan stream dengan error jika total byte melewati `limit`. Penyebab
+// error (terlalu besar atau body request rusak) disimpan di `failure`.
+fn limit_stream<'a>(
+    data: impl futures_util::Stream<Item = io::Result<Bytes>> + Send + 'a,
+    limit: u64,
+    too_large: Msg,
+    failure: Arc<Mutex<Option<AppError>>>,
+) -> impl futures_util::Stream<Item = io::Result<Bytes>> + Send + 'a {
+    let mut read = 0u64;
+    data.map(move |chunk| {
+        let fail = |error: AppError, message: String| {
+            failure.lock().unwrap().get_or_insert(error);
+            Err(io::Error::other(message))
+        };
+        match chunk {
+            Ok(chunk) => {
+                read += chunk.len() as u64;
+                if read > limit {
+                    return fail(AppError::PayloadTooLarge(too_large.clone()), "lampiran melewati batas ukuran".into());
+                }
+                Ok(chunk)
+            }
+            Err(e) => fail(AppError::BadRequest(Msg::InvalidMultipart(e.to_string())), e.to_string()),
+        }
+    })
+}
+
+// Tebak content type dari isi file, bukan dari header/ekstensi kiriman klien.
+// Teks UTF-8 dianggap text/plain; sisanya application/octet-stream.
+pub fn sniff_content_type(head: &[u8]) -> String {
+    if let Some(kind) = infer::get(head) {
+        return kind.mime_type().to_string();
+    }
+    let is_text = !head.contains(&0)
+        && match std::str::from_utf8(head) {
+            Ok(_) => true,
+            // Karakter multi-byte terpotong di ujung SNIFF_BYTES masih dianggap teks
+            Err(e) => e.error_len().is_none(),
+        };
+    if is_text {
+        "text/plain; charset=utf-8".to_string()
+    } else {
+        "application/octet-stream".to_string()
+    }
+}
+
+// Nama file dari klien hanya untuk ditampilkan: buang path & karakter kontrol
+fn clean_file_name(file_name: &str) -> String {
+    let name = file_name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let name: String = name.chars().filter(|c| !c.is_control()).take(255).collect();
+    if name.trim().is_empty() {
+        "file".to_string()
+    } else {
+        name.trim().to_string()
+    }
+}
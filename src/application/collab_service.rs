@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use automerge::{
+    sync::{self, SyncDoc},
+    transaction::Transactable,
+    AutoCommit, ChangeHash, ObjId, ObjType, ReadDoc, Value, ROOT,
+};
+use tokio::sync::{broadcast, Notify};
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    application::note_service::NoteService,
+    domain::{
+        models::{
+            collab::CollabPeer,
+            event::{NoteEvent, NoteEventKind},
+            note::{Note, UpdateNotePayload},
+        },
+        repositories::{event_bus::DynEventBus, note_repository::DynNoteRepository},
+    },
+    utils::{
+        background::BackgroundTasks,
+        error::{AppError, AppResult},
+        i18n::Msg,
+    },
+};
+
+// Key di root dokumen automerge yang berisi teks `content` catatan
+const CONTENT_KEY: &str = "content";
+// Batas percobaan ulang snapshot saat catatan diubah di tempat lain
+const MAX_SNAPSHOT_ATTEMPTS: usize = 3;
+const ROOM_CHANNEL_CAPACITY: usize = 256;
+
+// Kejadian di satu ruang kolaborasi, diteruskan ke setiap koneksi WebSocket
+#[derive(Debug, Clone)]
+pub enum RoomEvent {
+    // Dokumen berubah; setiap peserta perlu dikirimi pesan sync baru
+    Changed,
+    Joined { peer_id: String, user_id: u32 },
+    Presence { peer_id: String, user_id: u32, state: serde_json::Value },
+    Left { peer_id: String },
+    // Catatan dihapus, semua koneksi ditutup
+    Closed,
+}
+
+// Pengelola ruang kolaborasi (/notes/:id/collab), satu ruang per catatan.
+// Isi `content` disimpan sebagai dokumen automerge di memori; perubahan dari
+// klien digabung tanpa konflik, lalu ditulis berkala ke tabel notes lewat NoteService.
+pub struct CollabHub {
+    notes: NoteService,
+    events: DynEventBus,
+    snapshot_interval: Duration,
+    rooms: Mutex<HashMap<u32, Arc<Room>>>,
+}
+
+impl CollabHub {
+    pub fn new(note_repo: DynNoteRepository, events: DynEventBus, snapshot_interval: Duration) -> Self {
+        Self {
+            notes: NoteService::new(note_repo, events.clone()),
+            events,
+            snapshot_interval,
+            rooms: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Masuk ke ruang milik `note`. Kepemilikan catatan harus sudah dicek pemanggil.
+    // Ruang dibuat dari isi `note` jika belum ada; task snapshot-nya ikut berhenti saat shutdown.
+    pub fn join(self: &Arc<Self>, note: &Note, user_id: u32, background: &BackgroundTasks) -> CollabSession {
+        let mut rooms = lock(&self.rooms);
+        let room = match rooms.get(&note.id) {
+            Some(room) => room.clone(),
+            None => {
+                let room = Arc::new(Room::new(note));
+                rooms.insert(note.id, room.clone());
+
+                // Subscribe di sini supaya perubahan sebelum task mulai tidak terlewat
+                let hub = self.clone();
+                let task_room = room.clone();
+                let note_events = self.events.subscribe();
+                background.spawn(move |token| async move { hub.run_room(task_room, note_events, token).await });
+                room
+            }
+        };
+
+        let peer_id = uuid::Uuid::new_v4().to_string();
+        // Subscribe sebelum peserta didaftarkan supaya tidak ada kejadian yang terlewat
+        let events = room.events.subscribe();
+        lock(&room.state).peers.insert(
+            peer_id.clone(),
+            Peer { user_id, presence: None, sync: sync::State::new() },
+        );
+        let _ = room.events.send(RoomEvent::Joined { peer_id: peer_id.clone(), user_id });
+
+        CollabSession { room, peer_id, user_id, events: Some(events) }
+    }
+
+    // Task per ruang: snapshot berkala, ikuti perubahan dari REST/sync,
+    // lalu snapshot terakhir saat ruang kosong atau server berhenti.
+    async fn run_room(
+        self: Arc<Self>,
+        room: Arc<Room>,
+        mut events: broadcast::Receiver<NoteEvent>,
+        token: CancellationToken,
+    ) {
+        let mut ticker = tokio::time::interval(self.snapshot_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = room.empty.notified() => {
+                    if self.remove_if_empty(&room) {
+                        break;
+                    }
+                }
+                _ = ticker.tick() => {
+                    if !self.snapshot(&room).await {
+                        return;
+                    }
+                }
+                event = events.recv() => match event {
+                    Ok(event) if event.note_id == room.note_id => match event.kind {
+                        NoteEventKind::Updated => {
+                            if let Some(note) = event.note {
+                                room.merge_external(&note);
+                            }
+                        }
+                        NoteEventKind::Deleted => {
+                            self.close(&room);
+                            return;
+                        }
+                        NoteEventKind::Created => {}
+                    },
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            }
+        }
+
+        self.remove(&room);
+        self.snapshot(&room).await;
+    }
+
+    // Tulis isi dokumen ke tabel notes jika ada perubahan sejak snapshot terakhir.
+    // Mengembalikan false jika catatan sudah tidak ada (ruang ditutup).
+    async fn snapshot(&self, room: &Room) -> bool {
+        for _ in 0..MAX_SNAPSHOT_ATTEMPTS {
+            let (heads, content, last_seq) = {
+                let mut state = lock(&room.state);
+                let heads = state.doc.get_heads();
+                if heads == state.saved_heads {
+                    return true;
+                }
+                let Some(content) = read_content(&state.doc) else {
+                    tracing::warn!("Dokumen kolaborasi catatan {} tidak punya teks `content`", room.note_id);
+                    return true;
+                };
+                (heads, content, state.last_seq)
+            };
+
+            let payload = UpdateNotePayload { content: Some(content), ..Default::default() };
+            match self.notes.update_note_if_unchanged(room.note_id, room.owner_id, payload, last_seq).await {
+                Ok(Some(note)) => {
+                    let mut state = lock(&room.state);
+                    state.saved_heads = heads;
+                    state.last_seq = note.change_seq;
+                    return true;
+                }
+                // Catatan diubah di tempat lain: gabungkan versi server lalu coba lagi
+                Ok(None) => match self.notes.get_note_by_id(room.note_id, room.owner_id).await {
+                    Ok(note) => room.merge_external(&note),
+                    Err(AppError::NotFound(_)) => {
+                        self.close(room);
+                        return false;
+                    }
+                    Err(e) => {
+                        tracing::warn!("Gagal mengambil catatan {} untuk snapshot: {}", room.note_id, e);
+                        return true;
+                    }
+                },
+                Err(e) => {
+                    tracing::warn!("Gagal menyimpan snapshot catatan {}: {}", room.note_id, e);
+                    return true;
+                }
+            }
+        }
+        tracing::warn!("Snapshot catatan {} terus bentrok, dicoba lagi nanti", room.note_id);
+        true
+    }
+
+    // Hapus ruang dari daftar jika tidak ada peserta. Dicek di bawah lock daftar
+    // ruang supaya tidak bentrok dengan `join` yang sedang berjalan.
+    fn remove_if_empty(&self, room: &Arc<Room>) -> bool {
+        let mut rooms = lock(&self.rooms);
+        if !lock(&room.state).peers.is_empty() {
+            return false;
+        }
+        if rooms.get(&room.note_id).is_some_and(|current| Arc::ptr_eq(current, room)) {
+            rooms.remove(&room.note_id);
+        }
+        true
+    }
+
+    fn remove(&self, room: &Room) {
+        let mut rooms = lock(&self.rooms);
+        if rooms.get(&room.note_id).is_some_and(|current| std::ptr::eq(current.as_ref(), room)) {
+            rooms.remove(&room.note_id);
+        }
+    }
+
+    fn close(&self, room: &Room) {
+        self.remove(room);
+        let _ = room.events.send(RoomEvent::Closed);
+    }
+}
+
+struct Peer {
+    user_id: u32,
+    presence: Option<serde_json::Value>,
+    // Status sync automerge antara server dan peserta ini
+    sync: sync::State,
+}
+
+struct RoomState {
+    doc: AutoCommit,
+    // Heads dokumen yang isinya sama dengan isi catatan di database
+    saved_heads: Vec<ChangeHash>,
+    // change_seq catatan di database saat `saved_heads`
+    last_seq: i64,
+    peers: HashMap<String, Peer>,
+}
+
+struct Room {
+    note_id: u32,
+    owner_id: u32,
+    state: Mutex<RoomState>,
+    events: broadcast::Sender<RoomEvent>,
+    // Dipicu saat peserta terakhir keluar
+    empty: Notify,
+}
+
+impl Room {
+    fn new(note: &Note) -> Self {
+        // Dokumen baru yang kosong selalu bisa diisi
+        let mut doc = AutoCommit::new();
+        let content = doc.put_object(ROOT, CONTENT_KEY, ObjType::Text).expect("dokumen baru");
+        doc.splice_text(&content, 0, 0, note.content.as_deref().unwrap_or_default())
+            .expect("dokumen baru");
+        let saved_heads = doc.get_heads();
+
+        Self {
+            note_id: note.id,
+            owner_id: note.user_id,
+            state: Mutex::new(RoomState { doc, saved_heads, last_seq: note.change_seq, peers: HashMap::new() }),
+            events: broadcast::channel(ROOM_CHANNEL_CAPACITY).0,
+            empty: Notify::new(),
+        }
+    }
+
+    // Gabungkan versi catatan dari database (diubah lewat REST/sync) ke dokumen.
+    // Selisihnya dihitung dari versi yang terakhir disimpan, jadi edit yang belum
+    // di-snapshot tetap ada.
+    fn merge_external(&self, note: &Note) {
+        let mut state = lock(&self.state);
+        if note.change_seq <= state.last_seq {
+            return;
+        }
+
+        let heads = state.saved_heads.clone();
+        let mut base = state.doc.fork_at(&heads).expect("saved_heads selalu ada di dokumen");
+        if let Some(content) = content_obj(&base) {
+            if let Err(e) = base.update_text(&content, note.content.as_deref().unwrap_or_default()) {
+                tracing::warn!("Gagal menggabungkan isi catatan {}: {}", self.note_id, e);
+                return;
+            }
+        }
+        if let Err(e) = state.doc.merge(&mut base) {
+            tracing::warn!("Gagal menggabungkan isi catatan {}: {}", self.note_id, e);
+            return;
+        }
+        state.saved_heads = base.get_heads();
+        state.last_seq = note.change_seq;
+        drop(state);
+        let _ = self.events.send(RoomEvent::Changed);
+    }
+}
+
+// Satu koneksi di ruang kolaborasi. Peserta keluar saat session di-drop.
+pub struct CollabSession {
+    room: Arc<Room>,
+    pub peer_id: String,
+    pub user_id: u32,
+    events: Option<broadcast::Receiver<RoomEvent>>,
+}
+
+impl CollabSession {
+    // Receiver kejadian ruang; hanya bisa diambil sekali
+    pub fn events(&mut self) -> broadcast::Receiver<RoomEvent> {
+        self.events.take().expect("events hanya diambil sekali")
+    }
+
+    // Peserta lain yang sedang tersambung
+    pub fn peers(&self) -> Vec<CollabPeer> {
+        let state = lock(&self.room.state);
+        state
+            .peers
+            .iter()
+            .filter(|(peer_id, _)| **peer_id != self.peer_id)
+            .map(|(peer_id, peer)| CollabPeer {
+                peer_id: peer_id.clone(),
+                user_id: peer.user_id,
+                presence: peer.presence.clone(),
+            })
+            .collect()
+    }
+
+    // Pesan sync berikutnya untuk peserta ini, None jika tidak ada yang perlu dikirim
+    pub fn sync_message(&self) -> Option<Vec<u8>> {
+        let mut state = lock(&self.room.state);
+        let RoomState { doc, peers, .. } = &mut *state;
+        let peer = peers.get_mut(&self.peer_id)?;
+        let message = doc.sync().generate_sync_message(&mut peer.sync);
+        message.map(|message| message.encode())
+    }
+
+    // Terapkan pesan sync dari peserta ini ke dokumen
+    pub fn receive(&self, data: &[u8]) -> AppResult<()> {
+        let message = sync::Message::decode(data).map_err(|_| AppError::BadRequest(Msg::InvalidCollabMessage))?;
+
+        let mut state = lock(&self.room.state);
+        let RoomState { doc, peers, .. } = &mut *state;
+        let Some(peer) = peers.get_mut(&self.peer_id) else { return Ok(()) };
+        let before = doc.get_heads();
+        doc.sync()
+            .receive_sync_message(&mut peer.sync, message)
+            .map_err(|_| AppError::BadRequest(Msg::InvalidCollabMessage))?;
+        let changed = doc.get_heads() != before;
+        drop(state);
+
+        if changed {
+            let _ = self.room.events.send(RoomEvent::Changed);
+        }
+        Ok(())
+    }
+
+    pub fn set_presence(&self, presence: serde_json::Value) {
+        if let Some(peer) = lock(&self.room.state).peers.get_mut(&self.peer_id) {
+            peer.presence = Some(presence.clone());
+        }
+        let _ = self.room.events.send(RoomEvent::Presence {
+            peer_id: self.peer_id.clone(),
+            user_id: self.user_id,
+            state: presence,
+        });
+    }
+}
+
+impl Drop for CollabSession {
+    fn drop(&mut self) {
+        let mut state = lock(&self.room.state);
+        state.peers.remove(&self.peer_id);
+        let empty = state.peers.is_empty();
+        drop(state);
+
+        let _ = self.room.events.send(RoomEvent::Left { peer_id: self.peer_id.clone() });
+        if empty {
+            self.room.empty.notify_one();
+        }
+    }
+}
+
+// Lock tidak pernah ditahan melewati await, jadi poisoning hanya terjadi
+// jika ada panic; datanya tetap dipakai.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn content_obj(doc: &AutoCommit) -> Option<ObjId> {
+    match doc.get(ROOT, CONTENT_KEY) {
+        Ok(Some((Value::Object(ObjType::Text), id))) => Some(id),
+        _ => None,
+    }
+}
+
+fn read_content(doc: &AutoCommit) -> Option<String> {
+    content_obj(doc).and_then(|content| doc.text(&content).ok())
+}
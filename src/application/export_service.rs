@@ -0,0 +1,358 @@
+use bytes::Bytes;
+use chrono::{Datelike, Timelike, Utc};
+use futures_util::{stream, Stream};
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+use tokio::sync::mpsc;
+use zip::{
+    write::{SimpleFileOptions, StreamWriter},
+    CompressionMethod, ZipWriter,
+};
+
+use crate::{
+    application::note_renderer::{self, NoteRenderer},
+    domain::{
+        models::{
+            export::{ExportFormat, NoteExportFormat},
+            note::{Note, NoteFilter},
+            user_settings::UserSettings,
+        },
+        repositories::note_repository::DynNoteRepository,
+    },
+    utils::{
+        error::{AppError, AppResult},
+        i18n::Msg,
+        pdf::{PdfFont, PdfWriter},
+    },
+};
+
+// Jumlah catatan yang dibaca dari database per query
+const EXPORT_BATCH: u32 = 200;
+// Potongan body dikirim ke klien setiap sudah terkumpul sebanyak ini
+const CHUNK_BYTES: usize = 64 * 1024;
+const MAX_SLUG_LEN: usize = 50;
+
+// Hasil ekspor satu catatan
+pub struct ExportFile {
+    pub content_type: &'static str,
+    pub file_name: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+pub struct ExportService {
+    note_repo: DynNoteRepository,
+    renderer: Arc<NoteRenderer>,
+}
+
+impl ExportService {
+    pub fn new(note_repo: DynNoteRepository, renderer: Arc<NoteRenderer>) -> Self {
+        Self { note_repo, renderer }
+    }
+
+    // Ekspor semua catatan yang cocok dengan `filter` sebagai stream byte.
+    // Catatan dibaca per EXPORT_BATCH dan langsung ditulis ke body, jadi
+    // memori yang dipakai tidak bergantung pada jumlah catatan. Jika terjadi
+    // error di tengah jalan, stream berakhir dengan Err dan koneksi diputus
+    // (status 200 sudah terkirim, file setengah jadi tidak boleh terlihat utuh).
+    pub fn export(
+        &self,
+        user_id: u32,
+        filter: NoteFilter,
+        format: ExportFormat,
+        settings: UserSettings,
+    ) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static {
+        let (tx, rx) = mpsc::channel(4);
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.write_export(user_id, &filter, format, &settings, &tx).await {
+                tracing::error!("Ekspor catatan user {} gagal: {}", user_id, e);
+                let _ = tx.send(Err(io::Error::other(e.to_string()))).await;
+            }
+        });
+        stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|chunk| (chunk, rx)) })
+    }
+
+    #[tracing::instrument(name = "ExportService.export", skip_all, fields(user.id = user_id, export.format = ?format))]
+    async fn write_export(
+        &self,
+        user_id: u32,
+        filter: &NoteFilter,
+        format: ExportFormat,
+        settings: &UserSettings,
+        tx: &mpsc::Sender<io::Result<Bytes>>,
+    ) -> AppResult<()> {
+        let mut writer = ExportWriter::new(format, settings);
+        let mut chunk = writer.take();
+        let mut after_id = 0;
+        loop {
+            let notes = self.note_repo.find_filtered(user_id, filter, after_id, EXPORT_BATCH).await?;
+            for note in &notes {
+                self.write_note(&mut writer, note, settings).await?;
+                chunk.extend(writer.take());
+                // Klien sudah menutup koneksi: hentikan ekspor
+                if chunk.len() >= CHUNK_BYTES && tx.send(Ok(std::mem::take(&mut chunk).into())).await.is_err() {
+                    return Ok(());
+                }
+            }
+            match notes.last() {
+                Some(last) if notes.len() == EXPORT_BATCH as usize => after_id = last.id,
+                _ => break,
+            }
+        }
+        chunk.extend(writer.finish()?);
+        let _ = tx.send(Ok(chunk.into())).await;
+        Ok(())
+    }
+
+    // Ekspor satu catatan; ukurannya kecil jadi langsung dibuat di memori
+    #[tracing::instrument(name = "ExportService.export_note", skip_all, fields(note.id = id, user.id = user_id))]
+    pub async fn export_note(
+        &self,
+        id: u32,
+        user_id: u32,
+        format: NoteExportFormat,
+        settings: &UserSettings,
+    ) -> AppResult<ExportFile> {
+        let mut note = self
+            .note_repo
+            .find_by_id(id, user_id)
+            .await?
+            .ok_or(AppError::NotFound(Msg::NoteNotFound(id)))?;
+        let file_name = |extension: &str| format!("{}-{}.{}", note.id, slug(&note.title), extension);
+
+        let (content_type, file_name, data) = match format {
+            NoteExportFormat::Md => ("text/markdown; charset=utf-8", file_name("md"), markdown_document(&note).into_bytes()),
+            NoteExportFormat::Json => {
+                note.created_at_local = note.created_at.as_ref().map(|dt| settings.format_local(dt));
+                ("application/json", file_name("json"), serde_json::to_vec_pretty(&note).map_err(io::Error::from)?)
+            }
+            NoteExportFormat::Html | NoteExportFormat::Pdf => {
+                let format = if format == NoteExportFormat::Html { ExportFormat::Html } else { ExportFormat::Pdf };
+                let mut writer = ExportWriter::new(format, settings);
+                self.write_note(&mut writer, &note, settings).await?;
+                let mut data = writer.take();
+                data.extend(writer.finish()?);
+                (format.content_type(), file_name(format.extension()), data)
+            }
+        };
+        Ok(ExportFile { content_type, file_name, data })
+    }
+
+    async fn write_note(&self, writer: &mut ExportWriter, note: &Note, settings: &UserSettings) -> io::Result<()> {
+        // Ekspor HTML memakai hasil render yang sama dengan ?render=html (ikut cache)
+        let html = match writer {
+            ExportWriter::Html { .. } => Some(self.renderer.render(note).await),
+            _ => None,
+        };
+        writer.write_note(note, html.as_deref(), settings)
+    }
+}
+
+// Penulis tiap format ekspor. Setiap method menulis ke buffer internal yang
+// diambil lewat `take`, sehingga pemanggil bisa mengirimnya sedikit demi sedikit.
+enum ExportWriter {
+    Json { buf: Vec<u8>, first: bool },
+    Html { buf: Vec<u8> },
+    Zip { zip: ZipWriter<StreamWriter<SharedBuffer>>, buf: SharedBuffer },
+    Pdf(PdfWriter),
+}
+
+impl ExportWriter {
+    fn new(format: ExportFormat, settings: &UserSettings) -> Self {
+        match format {
+            ExportFormat::Json => {
+                let header = format!("{{\"exported_at\":\"{}\",\"notes\":[", Utc::now().to_rfc3339());
+                ExportWriter::Json { buf: header.into_bytes(), first: true }
+            }
+            ExportFormat::Html => ExportWriter::Html { buf: html_header(settings).into_bytes() },
+            ExportFormat::Zip => {
+                let buf = SharedBuffer::default();
+                ExportWriter::Zip { zip: ZipWriter::new_stream(buf.clone()), buf }
+            }
+            ExportFormat::Pdf => ExportWriter::Pdf(PdfWriter::new()),
+        }
+    }
+
+    fn write_note(&mut self, note: &Note, html: Option<&str>, settings: &UserSettings) -> io::Result<()> {
+        match self {
+            ExportWriter::Json { buf, first } => {
+                if !*first {
+                    buf.push(b',');
+                }
+                *first = false;
+                serde_json::to_writer(buf, note)?;
+            }
+            ExportWriter::Html { buf } => buf.extend_from_slice(html_article(note, html.unwrap_or_default(), settings).as_bytes()),
+            ExportWriter::Zip { zip, .. } => {
+                // Waktu file di ZIP tidak punya zona waktu; pakai waktu lokal user
+                let modified = note.updated_at.or(note.created_at).and_then(|dt| {
+                    let dt = dt.with_timezone(&settings.tz());
+                    zip::DateTime::from_date_and_time(
+                        u16::try_from(dt.year()).ok()?,
+                        dt.month() as u8,
+                        dt.day() as u8,
+                        dt.hour() as u8,
+                        dt.minute() as u8,
+                        dt.second() as u8,
+                    )
+                    .ok()
+                });
+                let options = SimpleFileOptions::default()
+                    .compression_method(CompressionMethod::Deflated)
+                    .last_modified_time(modified.unwrap_or_default());
+                zip.start_file(format!("{}-{}.md", note.id, slug(&note.title)), options)?;
+                zip.write_all(markdown_document(note).as_bytes())?;
+            }
+            ExportWriter::Pdf(pdf) => {
+                let meta = note_meta(note, settings);
+                let content = note.content.as_deref().unwrap_or_default();
+                pdf.add_section(&[(PdfFont::Bold, &note.title), (PdfFont::Regular, &meta), (PdfFont::Regular, ""), (PdfFont::Regular, content)]);
+            }
+        }
+        Ok(())
+    }
+
+    fn take(&mut self) -> Vec<u8> {
+        match self {
+            ExportWriter::Json { buf, .. } | ExportWriter::Html { buf } => std::mem::take(buf),
+            ExportWriter::Zip { buf, .. } => buf.take(),
+            ExportWriter::Pdf(pdf) => pdf.take(),
+        }
+    }
+
+    fn finish(self) -> io::Result<Vec<u8>> {
+        Ok(match self {
+            ExportWriter::Json { mut buf, .. } => {
+                buf.extend_from_slice(b"]}");
+                buf
+            }
+            ExportWriter::Html { mut buf } => {
+                buf.extend_from_slice(b"</body>\n</html>\n");
+                buf
+            }
+            // Central directory ZIP ditulis di akhir
+            ExportWriter::Zip { zip, buf } => {
+                zip.finish()?;
+                buf.take()
+            }
+            ExportWriter::Pdf(pdf) => pdf.finish(),
+        })
+    }
+}
+
+// ZipWriter memiliki writer-nya sendiri; buffer ini dibagi supaya isinya
+// bisa diambil setelah setiap file ditulis
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Isi file .md: front-matter YAML lalu isi catatan apa adanya.
+// String ditulis sebagai string JSON (juga YAML valid) supaya aman dari karakter khusus.
+pub fn markdown_document(note: &Note) -> String {
+    let quote = |value: &str| serde_json::to_string(value).unwrap_or_default();
+    let mut doc = format!("---\nid: {}\ntitle: {}\n", note.id, quote(&note.title));
+    if let Some(created_at) = note.created_at {
+        doc.push_str(&format!("created_at: {}\n", quote(&created_at.to_rfc3339())));
+    }
+    if let Some(updated_at) = note.updated_at {
+        doc.push_str(&format!("updated_at: {}\n", quote(&updated_at.to_rfc3339())));
+    }
+    doc.push_str(&format!("content_format: {}\n", note.content_format.as_str()));
+    let tags: Vec<String> = note.tags.iter().map(|tag| quote(tag)).collect();
+    doc.push_str(&format!("tags: [{}]\n---\n\n", tags.join(", ")));
+    if let Some(content) = &note.content {
+        doc.push_str(content);
+        if !content.ends_with('\n') {
+            doc.push('\n');
+        }
+    }
+    doc
+}
+
+// Bagian nama file dari judul: huruf kecil ASCII, angka, dan '-'
+fn slug(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= MAX_SLUG_LEN {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "catatan".to_string() } else { slug.to_string() }
+}
+
+// Baris info di bawah judul: waktu dibuat (zona waktu user) dan tag
+fn note_meta(note: &Note, settings: &UserSettings) -> String {
+    let mut meta = note.created_at.as_ref().map(|dt| settings.format_local(dt)).unwrap_or_default();
+    if !note.tags.is_empty() {
+        let tags: Vec<String> = note.tags.iter().map(|tag| format!("#{}", tag)).collect();
+        if !meta.is_empty() {
+            meta.push_str(" · ");
+        }
+        meta.push_str(&tags.join(" "));
+    }
+    meta
+}
+
+fn html_header(settings: &UserSettings) -> String {
+    let lang = settings.preferred_locale().map(|locale| locale.as_str()).unwrap_or("id");
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>Catatan</title>\n<style>\n\
+         body {{ font-family: sans-serif; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; }}\n\
+         article {{ border-bottom: 1px solid #ddd; padding-bottom: 1.5rem; margin-bottom: 1.5rem; }}\n\
+         .meta {{ color: #666; font-size: 0.9rem; }}\n\
+         pre {{ overflow-x: auto; }}\n{}</style>\n</head>\n<body>\n",
+        lang,
+        note_renderer::highlight_css()
+    )
+}
+
+// `content_html` sudah disanitasi oleh NoteRenderer; judul & meta di-escape di sini
+fn html_article(note: &Note, content_html: &str, settings: &UserSettings) -> String {
+    format!(
+        "<article id=\"note-{}\">\n<h1>{}</h1>\n<p class=\"meta\">{}</p>\n{}</article>\n",
+        note.id,
+        escape_html(&note.title),
+        escape_html(&note_meta(note, settings)),
+        content_html
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use crate::domain::{
+    models::health::{DependencyHealth, ReadinessReport, VersionInfo},
+    repositories::health_check::DynHealthCheck,
+};
+
+pub struct HealthService {
+    checks: Vec<DynHealthCheck>,
+    timeout: Duration,
+}
+
+impl HealthService {
+    pub fn new(checks: Vec<DynHealthCheck>, timeout: Duration) -> Self {
+        Self { checks, timeout }
+    }
+
+    // Ping semua dependensi. Tiap ping dibatasi `timeout` supaya
+    // probe Kubernetes tidak ikut menggantung saat dependensi hang.
+    pub async fn readiness(&self) -> ReadinessReport {
+        let mut checks = BTreeMap::new();
+        let mut critical_down = false;
+        let mut any_down = false;
+
+        for check in &self.checks {
+            let started = Instant::now();
+            let result = match tokio::time::timeout(self.timeout, check.ping()).await {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => Some(e.to_string()),
+                Err(_) => Some(format!("timeout setelah {} ms", self.timeout.as_millis())),
+            };
+            let latency_ms = started.elapsed().as_millis() as u64;
+
+            if result.is_some() {
+                any_down = true;
+                critical_down |= check.is_critical();
+                tracing::warn!("Health check '{}' gagal: {}", check.name(), result.as_deref().unwrap_or_default());
+            }
+
+            checks.insert(
+                check.name().to_string(),
+                DependencyHealth {
+                    status: if result.is_none() { "up" } else { "down" }.to_string(),
+                    critical: check.is_critical(),
+                    latency_ms,
+                    error: result,
+                },
+            );
+        }
+
+        let status = if critical_down {
+            "unavailable"
+        } else if any_down {
+            "degraded"
+        } else {
+            "ok"
+        };
+
+        ReadinessReport { status: status.to_string(), checks }
+    }
+
+    pub fn version() -> VersionInfo {
+        VersionInfo {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("GIT_COMMIT").to_string(),
+            build_time: env!("BUILD_TIME").to_string(),
+        }
+    }
+}
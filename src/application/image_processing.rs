@@ -0,0 +1,84 @@
+use exif::{Context, In, Tag};
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, ImageResult, Limits};
+use std::{collections::BTreeMap, io::Cursor};
+
+use crate::domain::models::attachment::ImageMetadata;
+
+// Gambar dengan sisi lebih panjang dari ini tidak diproses (mencegah decompression bomb)
+const MAX_IMAGE_SIDE: u32 = 16_384;
+// Nilai EXIF yang lebih panjang dari ini (data biner, komentar panjang) tidak disimpan
+const MAX_EXIF_VALUE_LEN: usize = 256;
+
+// Hasil pemrosesan satu gambar: metadata untuk database + isi tiap thumbnail
+pub struct ProcessedImage {
+    pub metadata: ImageMetadata,
+    pub thumbnails: Vec<(u32, Vec<u8>)>,
+}
+
+// Baca ukuran & EXIF lalu buat thumbnail untuk setiap `sizes` (sisi terpanjang).
+// Berat di CPU, jadi panggil lewat spawn_blocking. Thumbnail tidak membawa EXIF
+// sama sekali; orientasi kamera sudah diterapkan ke pikselnya.
+pub fn process_image(data: &[u8], sizes: &[u32], output: ImageFormat) -> ImageResult<ProcessedImage> {
+    let mut reader = ImageReader::new(Cursor::new(data)).with_guessed_format()?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_SIDE);
+    limits.max_image_height = Some(MAX_IMAGE_SIDE);
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+
+    let mut sizes = sizes.to_vec();
+    sizes.sort_unstable();
+    sizes.dedup();
+
+    let mut thumbnails = Vec::with_capacity(sizes.len());
+    for size in sizes {
+        // Gambar yang sudah kecil tidak diperbesar
+        let thumbnail = if image.width().max(image.height()) > size {
+            image.thumbnail(size, size)
+        } else {
+            image.clone()
+        };
+        thumbnails.push((size, encode(thumbnail, output)?));
+    }
+
+    Ok(ProcessedImage {
+        metadata: ImageMetadata {
+            width: Some(image.width()),
+            height: Some(image.height()),
+            exif: read_exif(data),
+            thumbnails: thumbnails.iter().map(|(size, _)| *size).collect(),
+        },
+        thumbnails,
+    })
+}
+
+fn encode(image: DynamicImage, format: ImageFormat) -> ImageResult<Vec<u8>> {
+    // Encoder JPEG tidak menerima alpha maupun 16-bit
+    let image = match format {
+        ImageFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8()),
+        _ => image,
+    };
+    let mut out = Cursor::new(Vec::new());
+    image.write_to(&mut out, format)?;
+    Ok(out.into_inner())
+}
+
+// Tag EXIF gambar utama dalam bentuk teks. Semua tag GPS dibuang supaya
+// lokasi pengambilan foto tidak ikut tersebar.
+fn read_exif(data: &[u8]) -> Option<BTreeMap<String, String>> {
+    let exif = exif::Reader::new().read_from_container(&mut Cursor::new(data)).ok()?;
+    let skipped = [Tag::GPSInfoIFDPointer, Tag::ExifIFDPointer, Tag::InteropIFDPointer, Tag::MakerNote];
+
+    let tags: BTreeMap<String, String> = exif
+        .fields()
+        .filter(|field| field.ifd_num == In::PRIMARY)
+        .filter(|field| field.tag.context() != Context::Gps && !skipped.contains(&field.tag))
+        .map(|field| (field.tag.to_string(), field.display_value().with_unit(&exif).to_string()))
+        .filter(|(_, value)| value.len() <= MAX_EXIF_VALUE_LEN)
+        .collect();
+    (!tags.is_empty()).then_some(tags)
+}
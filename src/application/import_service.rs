@@ -0,0 +1,186 @@
+use chrono::Utc;
+use chrono_tz::Tz;
+use moka::sync::Cache;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    application::note_import,
+    domain::{
+        models::{
+            event::{NoteEvent, NoteEventKind},
+            import::{ImportItemError, ImportJob, ImportSource, ImportStatus},
+            note::NewNote,
+        },
+        repositories::{event_bus::DynEventBus, note_repository::DynNoteRepository},
+    },
+    utils::{
+        background::BackgroundTasks,
+        error::{AppError, AppResult},
+        i18n::{Locale, Msg},
+    },
+};
+
+// Jumlah catatan per `NoteRepository::create_many`
+const IMPORT_BATCH: usize = 100;
+// Status job masih bisa diambil selama ini setelah dibuat
+const JOB_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+// Daftar error per item dipotong supaya arsip yang rusak parah tidak memenuhi memori;
+// jumlah lengkapnya tetap ada di `failed`
+const MAX_REPORTED_ERRORS: usize = 1000;
+
+// Status job impor di memori proses. Cukup untuk satu instance; job yang
+// sedang berjalan ikut hilang saat server restart (seperti background task lain).
+pub struct ImportJobs {
+    jobs: Cache<String, Arc<Mutex<ImportJob>>>,
+}
+
+impl ImportJobs {
+    pub fn new() -> Self {
+        Self { jobs: Cache::builder().time_to_live(JOB_TTL).build() }
+    }
+
+    fn insert(&self, job: ImportJob) -> Arc<Mutex<ImportJob>> {
+        let job = Arc::new(Mutex::new(job));
+        self.jobs.insert(job.lock().unwrap().id.clone(), job.clone());
+        job
+    }
+
+    // Salinan status job; None jika tidak ada atau milik user lain
+    pub fn get(&self, id: &str, user_id: u32) -> Option<ImportJob> {
+        let job = self.jobs.get(id)?;
+        let job = job.lock().unwrap();
+        (job.user_id == user_id).then(|| job.clone())
+    }
+}
+
+impl Default for ImportJobs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct ImportService {
+    note_repo: DynNoteRepository,
+    events: DynEventBus,
+    jobs: Arc<ImportJobs>,
+    background: BackgroundTasks,
+}
+
+impl ImportService {
+    pub fn new(note_repo: DynNoteRepository, events: DynEventBus, jobs: Arc<ImportJobs>, background: BackgroundTasks) -> Self {
+        Self { note_repo, events, jobs, background }
+    }
+
+    // Daftarkan job lalu proses file di background. Format yang tidak dikenali
+    // langsung ditolak; error lain dilaporkan lewat status job.
+    // Pesan error di status job memakai bahasa request ini (`lang`).
+    #[tracing::instrument(name = "ImportService.start", skip_all, fields(user.id = user_id))]
+    pub fn start(
+        &self,
+        user_id: u32,
+        file_name: &str,
+        data: Vec<u8>,
+        source: Option<ImportSource>,
+        tz: Tz,
+        lang: Locale,
+    ) -> AppResult<ImportJob> {
+        let source = source
+            .or_else(|| note_import::detect_source(file_name, &data))
+            .ok_or(AppError::BadRequest(Msg::UnsupportedImportFormat))?;
+        let job = ImportJob::new(user_id, source);
+        let created = job.clone();
+        let job = self.jobs.insert(job);
+
+        let service = self.clone();
+        self.background.spawn(move |cancel| async move {
+            service.run(job, data, tz, lang, cancel).await;
+        });
+        Ok(created)
+    }
+
+    pub fn status(&self, id: &str, user_id: u32) -> AppResult<ImportJob> {
+        self.jobs.get(id, user_id).ok_or_else(|| AppError::NotFound(Msg::ImportJobNotFound(id.to_string())))
+    }
+
+    #[tracing::instrument(name = "ImportService.run", skip_all)]
+    async fn run(&self, job: Arc<Mutex<ImportJob>>, data: Vec<u8>, tz: Tz, lang: Locale, cancel: CancellationToken) {
+        let (user_id, source) = {
+            let mut job = job.lock().unwrap();
+            job.status = ImportStatus::Running;
+            (job.user_id, job.source)
+        };
+
+        let parsed = tokio::task::spawn_blocking(move || note_import::parse(source, &data, user_id, tz)).await;
+        let parsed = match parsed {
+            Ok(Ok(parsed)) => parsed,
+            Ok(Err(msg)) => return finish(&job, Some(msg.text(lang))),
+            Err(e) => {
+                tracing::error!("Task parsing impor gagal: {}", e);
+                return finish(&job, Some(Msg::ServerError.text(lang)));
+            }
+        };
+
+        {
+            let mut job = job.lock().unwrap();
+            job.total = parsed.items.len() + parsed.errors.len();
+            for (item, msg) in parsed.errors {
+                record_error(&mut job, item, msg.text(lang));
+            }
+        }
+
+        let (names, notes): (Vec<String>, Vec<NewNote>) = parsed.items.into_iter().map(|item| (item.name, item.note)).unzip();
+        for (names, notes) in names.chunks(IMPORT_BATCH).zip(notes.chunks(IMPORT_BATCH)) {
+            if cancel.is_cancelled() {
+                return finish(&job, Some(Msg::ImportInterrupted.text(lang)));
+            }
+            self.save_batch(&job, names, notes, lang).await;
+        }
+        finish(&job, None);
+    }
+
+    // Satu batch = satu transaksi: jika gagal, semua catatan di batch ini dilaporkan gagal
+    async fn save_batch(&self, job: &Mutex<ImportJob>, names: &[String], new_notes: &[NewNote], lang: Locale) {
+        match self.note_repo.create_many(new_notes).await {
+            Ok(notes) => {
+                {
+                    let mut job = job.lock().unwrap();
+                    job.processed += notes.len();
+                    job.imported += notes.len();
+                }
+                for note in notes {
+                    let event = NoteEvent::new(NoteEventKind::Created, note.user_id, note.id, Some(note));
+                    if let Err(e) = self.events.publish(event).await {
+                        tracing::warn!("Gagal mengirim event {}: {}", NoteEventKind::Created.as_str(), e);
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!("Gagal menyimpan batch impor: {:?}", e);
+                let mut job = job.lock().unwrap();
+                for name in names {
+                    record_error(&mut job, name.clone(), Msg::ImportSaveFailed.text(lang));
+                }
+            }
+        }
+    }
+}
+
+fn record_error(job: &mut ImportJob, item: String, message: String) {
+    job.processed += 1;
+    job.failed += 1;
+    if job.errors.len() < MAX_REPORTED_ERRORS {
+        job.errors.push(ImportItemError { item, message });
+    }
+}
+
+fn finish(job: &Mutex<ImportJob>, error: Option<String>) {
+    let mut job = job.lock().unwrap();
+    job.status = if error.is_some() { ImportStatus::Failed } else { ImportStatus::Completed };
+    job.error = error;
+    job.finished_at = Some(Utc::now());
+}
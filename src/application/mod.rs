@@ -1,2 +1,10 @@
+pub mod attachment_service;
+pub mod collab_service;
+pub mod export_service;
+pub mod health_service;
+pub mod image_processing;
+pub mod import_service;
+pub mod note_import;
+pub mod note_renderer;
 pub mod note_service;
-pub mod user_service;
\ No newline at end of file
+pub mod user_service;
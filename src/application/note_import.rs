@@ -0,0 +1,668 @@
+// Baca file impor (ZIP Markdown, Evernote .enex, Google Keep Takeout) menjadi `NewNote`.
+// Semua fungsi di sini sinkron dan memakai CPU, jadi dipanggil dari `spawn_blocking`.
+// Error per item dikumpulkan, error yang membuat seluruh file tidak terbaca dikembalikan sebagai Err.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use serde::Deserialize;
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+use crate::{
+    domain::models::{
+        import::ImportSource,
+        note::{ContentFormat, NewNote, MAX_TAGS_PER_NOTE, MAX_TAG_LEN},
+    },
+    utils::i18n::Msg,
+};
+
+// Batas ukuran satu file di dalam arsip setelah diekstrak
+pub const MAX_ITEM_BYTES: u64 = 5 * 1024 * 1024;
+// Batas total isi arsip setelah diekstrak (melindungi dari zip bomb)
+pub const MAX_EXTRACTED_BYTES: u64 = 200 * 1024 * 1024;
+// Sama dengan kolom notes.title VARCHAR(255)
+const MAX_TITLE_LEN: usize = 255;
+const UNTITLED: &str = "Tanpa judul";
+
+// Satu catatan hasil impor beserta nama asalnya (untuk laporan error)
+pub struct ImportItem {
+    pub name: String,
+    pub note: NewNote,
+}
+
+#[derive(Default)]
+pub struct ParsedImport {
+    pub items: Vec<ImportItem>,
+    // (nama item, alasan gagal)
+    pub errors: Vec<(String, Msg)>,
+}
+
+impl ParsedImport {
+    fn push(&mut self, name: String, note: Result<NewNote, Msg>) {
+        match note {
+            Ok(note) => self.items.push(ImportItem { name, note }),
+            Err(msg) => self.errors.push((name, msg)),
+        }
+    }
+}
+
+// Tebak asal file dari nama & isinya. None = format tidak dikenali.
+pub fn detect_source(file_name: &str, data: &[u8]) -> Option<ImportSource> {
+    let head = String::from_utf8_lossy(&data[..data.len().min(1024)]);
+    if file_name.to_lowercase().ends_with(".enex") || head.contains("<en-export") {
+        return Some(ImportSource::Enex);
+    }
+    let archive = ZipArchive::new(Cursor::new(data)).ok()?;
+    let names: Vec<&str> = archive.file_names().filter(|name| !is_hidden(name)).collect();
+    if names.iter().any(|name| is_keep_note(name)) {
+        Some(ImportSource::Keep)
+    } else if names.iter().any(|name| markdown_format(name).is_some()) {
+        Some(ImportSource::Markdown)
+    } else {
+        None
+    }
+}
+
+pub fn parse(source: ImportSource, data: &[u8], user_id: u32, tz: Tz) -> Result<ParsedImport, Msg> {
+    match source {
+        ImportSource::Markdown => parse_markdown_zip(data, user_id, tz),
+        ImportSource::Enex => parse_enex(data, user_id),
+        ImportSource::Keep => parse_keep(data, user_id),
+    }
+}
+
+// --- ZIP ---
+
+// Folder metadata macOS dan file tersembunyi (.DS_Store, .obsidian/...) dilewati
+fn is_hidden(name: &str) -> bool {
+    name.split('/').any(|part| part.starts_with('.') || part == "__MACOSX")
+}
+
+fn is_keep_note(name: &str) -> bool {
+    let mut parts = name.rsplit('/');
+    let file = parts.next().unwrap_or_default();
+    file.to_lowercase().ends_with(".json") && parts.next() == Some("Keep")
+}
+
+fn markdown_format(name: &str) -> Option<ContentFormat> {
+    let ext = name.rsplit_once('.')?.1.to_lowercase();
+    match ext.as_str() {
+        "md" | "markdown" => Some(ContentFormat::Markdown),
+        "txt" => Some(ContentFormat::Plain),
+        _ => None,
+    }
+}
+
+// Panggil `handle` untuk tiap file di arsip yang lolos `wanted`, dengan isi & waktu ubahnya
+fn each_zip_file(
+    data: &[u8],
+    wanted: impl Fn(&str) -> bool,
+    mut handle: impl FnMut(&str, Vec<u8>, Option<zip::DateTime>, &mut ParsedImport),
+) -> Result<ParsedImport, Msg> {
+    let mut archive = ZipArchive::new(Cursor::new(data)).map_err(|e| Msg::InvalidImportFile(e.to_string()))?;
+    let mut parsed = ParsedImport::default();
+    let mut extracted = 0u64;
+
+    for i in 0..archive.len() {
+        let file = archive.by_index(i).map_err(|e| Msg::InvalidImportFile(e.to_string()))?;
+        let name = file.name().to_string();
+        if file.is_dir() || is_hidden(&name) || !wanted(&name) {
+            continue;
+        }
+        if file.size() > MAX_ITEM_BYTES {
+            parsed.errors.push((name, Msg::ImportItemTooLarge(MAX_ITEM_BYTES)));
+            continue;
+        }
+        let modified = file.last_modified();
+        // Ukuran di header bisa bohong; baca paling banyak batas + 1 byte
+        let mut content = Vec::new();
+        file.take(MAX_ITEM_BYTES + 1)
+            .read_to_end(&mut content)
+            .map_err(|e| Msg::InvalidImportFile(e.to_string()))?;
+        if content.len() as u64 > MAX_ITEM_BYTES {
+            parsed.errors.push((name, Msg::ImportItemTooLarge(MAX_ITEM_BYTES)));
+            continue;
+        }
+        extracted += content.len() as u64;
+        if extracted > MAX_EXTRACTED_BYTES {
+            return Err(Msg::ImportArchiveTooLarge(MAX_EXTRACTED_BYTES));
+        }
+        handle(&name, content, modified, &mut parsed);
+    }
+    Ok(parsed)
+}
+
+// Waktu di ZIP tidak punya zona waktu; anggap waktu lokal user
+fn zip_time(modified: Option<zip::DateTime>, tz: Tz) -> Option<DateTime<Utc>> {
+    let modified = modified?;
+    let naive = NaiveDate::from_ymd_opt(modified.year().into(), modified.month().into(), modified.day().into())?
+        .and_hms_opt(modified.hour().into(), modified.minute().into(), modified.second().into())?;
+    Some(tz.from_local_datetime(&naive).earliest()?.with_timezone(&Utc))
+}
+
+// --- Markdown ---
+
+// Front-matter yang dikenali; nama field dari beberapa aplikasi (Obsidian, Jekyll, ekspor kita sendiri)
+#[derive(Default, Deserialize)]
+struct FrontMatter {
+    title: Option<serde_yaml::Value>,
+    #[serde(alias = "created", alias = "date")]
+    created_at: Option<serde_yaml::Value>,
+    #[serde(alias = "updated", alias = "modified")]
+    updated_at: Option<serde_yaml::Value>,
+    content_format: Option<ContentFormat>,
+    #[serde(alias = "tag")]
+    tags: Option<serde_yaml::Value>,
+}
+
+fn parse_markdown_zip(data: &[u8], user_id: u32, tz: Tz) -> Result<ParsedImport, Msg> {
+    each_zip_file(
+        data,
+        |name| markdown_format(name).is_some(),
+        |name, content, modified, parsed| {
+            let note = markdown_note(name, content, zip_time(modified, tz), user_id, tz);
+            parsed.push(name.to_string(), note);
+        },
+    )
+}
+
+fn markdown_note(name: &str, content: Vec<u8>, modified: Option<DateTime<Utc>>, user_id: u32, tz: Tz) -> Result<NewNote, Msg> {
+    let text = String::from_utf8(content).map_err(|_| Msg::InvalidImportItem("UTF-8".to_string()))?;
+    let text = text.strip_prefix('\u{feff}').unwrap_or(&text).replace("\r\n", "\n");
+    let format = markdown_format(name).unwrap_or_default();
+
+    let (front_matter, body) = match split_front_matter(&text) {
+        Some((yaml, body)) => {
+            let front_matter = if yaml.trim().is_empty() {
+                FrontMatter::default()
+            } else {
+                serde_yaml::from_str(yaml).map_err(|e| Msg::InvalidImportItem(e.to_string()))?
+            };
+            (front_matter, body)
+        }
+        None => (FrontMatter::default(), text.as_str()),
+    };
+
+    let stem = name.rsplit('/').next().unwrap_or(name);
+    let stem = stem.rsplit_once('.').map_or(stem, |(stem, _)| stem);
+    let heading = body.lines().find_map(|line| line.strip_prefix("# "));
+    let title = front_matter.title.as_ref().and_then(yaml_string);
+
+    let created_at = front_matter.created_at.as_ref().and_then(|v| yaml_time(v, tz)).or(modified);
+    let updated_at = front_matter.updated_at.as_ref().and_then(|v| yaml_time(v, tz)).or(modified);
+    let tags = match &front_matter.tags {
+        Some(serde_yaml::Value::Sequence(tags)) => tags.iter().filter_map(yaml_string).collect(),
+        // `tags: a, b` atau `tags: "#a #b"`
+        Some(value) => yaml_string(value)
+            .map(|tags| tags.split([',', ' ']).map(|tag| tag.trim_start_matches('#').to_string()).collect())
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    let body = body.trim_start_matches('\n');
+    Ok(NewNote {
+        user_id,
+        title: note_title(title.as_deref().or(heading), stem),
+        content: (!body.trim().is_empty()).then(|| body.to_string()),
+        content_format: front_matter.content_format.unwrap_or(format),
+        tags: lenient_tags(tags),
+        created_at,
+        updated_at: updated_at.filter(|updated| Some(*updated) != created_at),
+    })
+}
+
+// "---\n<yaml>\n---\n<isi>" -> (yaml, isi). Penutup boleh `---` atau `...`.
+fn split_front_matter(text: &str) -> Option<(&str, &str)> {
+    let rest = text.strip_prefix("---\n")?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if matches!(line.trim_end(), "---" | "...") {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn yaml_string(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+// RFC 3339, "YYYY-MM-DD HH:MM[:SS]" / "YYYY-MM-DD" (waktu lokal user), atau unix timestamp (detik)
+fn yaml_time(value: &serde_yaml::Value, tz: Tz) -> Option<DateTime<Utc>> {
+    if let Some(secs) = value.as_i64() {
+        return DateTime::from_timestamp(secs, 0);
+    }
+    let text = value.as_str()?.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(text) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok().map(|date| date.and_time(Default::default())))?;
+    Some(tz.from_local_datetime(&naive).earliest()?.with_timezone(&Utc))
+}
+
+// --- Evernote (.enex) ---
+
+// Field langsung di bawah <note> yang dibaca; <resource>, <note-attributes>, dll. dilewati
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EnexField {
+    Title,
+    Content,
+    Created,
+    Updated,
+    Tag,
+}
+
+#[derive(Default)]
+struct EnexNote {
+    title: String,
+    content: String,
+    created: String,
+    updated: String,
+    tags: Vec<String>,
+}
+
+fn parse_enex(data: &[u8], user_id: u32) -> Result<ParsedImport, Msg> {
+    let text = std::str::from_utf8(data).map_err(|e| Msg::InvalidImportFile(e.to_string()))?;
+    let mut reader = Reader::from_str(text);
+    let mut parsed = ParsedImport::default();
+    let mut depth = 0usize;
+    let mut note: Option<EnexNote> = None;
+    let mut field: Option<(EnexField, String)> = None;
+    let xml_error = |e: quick_xml::Error| Msg::InvalidImportFile(e.to_string());
+
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(start) => {
+                depth += 1;
+                let name = start.local_name();
+                if depth == 2 && name.as_ref() == b"note" {
+                    note = Some(EnexNote::default());
+                } else if depth == 3 && note.is_some() {
+                    let kind = match name.as_ref() {
+                        b"title" => Some(EnexField::Title),
+                        b"content" => Some(EnexField::Content),
+                        b"created" => Some(EnexField::Created),
+                        b"updated" => Some(EnexField::Updated),
+                        b"tag" => Some(EnexField::Tag),
+                        _ => None,
+                    };
+                    field = kind.map(|kind| (kind, String::new()));
+                }
+            }
+            Event::End(_) => {
+                if depth == 3 {
+                    if let (Some(note), Some((kind, value))) = (note.as_mut(), field.take()) {
+                        match kind {
+                            EnexField::Title => note.title = value,
+                            EnexField::Content => note.content = value,
+                            EnexField::Created => note.created = value,
+                            EnexField::Updated => note.updated = value,
+                            EnexField::Tag => note.tags.push(value),
+                        }
+                    }
+                } else if depth == 2 {
+                    if let Some(note) = note.take() {
+                        let name = if note.title.trim().is_empty() { UNTITLED.to_string() } else { note.title.clone() };
+                        parsed.push(name, enex_note(note, user_id));
+                    }
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Event::Text(text) => {
+                if let Some((_, value)) = field.as_mut() {
+                    value.push_str(&text.xml_content().map_err(|e| Msg::InvalidImportFile(e.to_string()))?);
+                }
+            }
+            Event::CData(data) => {
+                if let Some((_, value)) = field.as_mut() {
+                    value.push_str(&data.decode().map_err(|e| Msg::InvalidImportFile(e.to_string()))?);
+                }
+            }
+            Event::GeneralRef(entity) => {
+                if let Some((_, value)) = field.as_mut() {
+                    value.push_str(&resolve_entity(&entity));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(parsed)
+}
+
+fn enex_note(note: EnexNote, user_id: u32) -> Result<NewNote, Msg> {
+    let content = enml_to_markdown(&note.content)?;
+    let created_at = enex_time(&note.created);
+    Ok(NewNote {
+        user_id,
+        title: note_title(Some(&note.title), UNTITLED),
+        content: (!content.is_empty()).then_some(content),
+        content_format: ContentFormat::Markdown,
+        tags: lenient_tags(note.tags),
+        created_at,
+        updated_at: enex_time(&note.updated).filter(|updated| Some(*updated) != created_at),
+    })
+}
+
+// Format waktu Evernote: 20240131T083000Z
+fn enex_time(text: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(text.trim(), "%Y%m%dT%H%M%SZ").ok().map(|dt| dt.and_utc())
+}
+
+// `&amp;`, `&#233;`, `&nbsp;` -> teksnya. Entity yang tidak dikenal ditulis apa adanya.
+fn resolve_entity(entity: &quick_xml::events::BytesRef) -> String {
+    if let Ok(Some(c)) = entity.resolve_char_ref() {
+        return c.to_string();
+    }
+    let name = entity.decode().unwrap_or_default();
+    quick_xml::escape::resolve_predefined_entity(&name)
+        .or_else(|| quick_xml::escape::resolve_html5_entity(&name))
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("&{};", name))
+}
+
+// Isi catatan Evernote (ENML, mirip XHTML) -> Markdown sederhana.
+// Gambar/lampiran (<en-media>) dan teks terenkripsi (<en-crypt>) tidak ikut diimpor.
+fn enml_to_markdown(enml: &str) -> Result<String, Msg> {
+    let mut reader = Reader::from_str(enml);
+    let mut md = MarkdownWriter::default();
+    // Urutan list yang sedang terbuka: Some(nomor berikutnya) untuk <ol>, None untuk <ul>
+    let mut lists: Vec<Option<u32>> = Vec::new();
+    let mut links: Vec<Option<String>> = Vec::new();
+    let mut skip = 0usize;
+    let mut pre = 0usize;
+    let item_error = |e: quick_xml::Error| Msg::InvalidImportItem(e.to_string());
+
+    loop {
+        let event = reader.read_event().map_err(item_error)?;
+        if skip > 0 {
+            match event {
+                Event::Start(_) => skip += 1,
+                Event::End(_) => skip -= 1,
+                Event::Eof => break,
+                _ => {}
+            }
+            continue;
+        }
+        match event {
+            Event::Start(start) => match start.local_name().as_ref() {
+                b"en-crypt" | b"en-media" | b"style" | b"script" => skip = 1,
+                b"div" | b"p" | b"table" | b"tr" | b"blockquote" => md.block(),
+                b"br" => md.newline(),
+                b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6" => {
+                    md.block();
+                    let level = (start.local_name().as_ref()[1] - b'0') as usize;
+                    md.push_raw(&format!("{} ", "#".repeat(level)));
+                }
+                b"b" | b"strong" => md.push_raw("**"),
+                b"i" | b"em" => md.push_raw("*"),
+                b"s" | b"strike" | b"del" => md.push_raw("~~"),
+                b"code" if pre == 0 => md.push_raw("`"),
+                b"ul" => {
+                    md.block();
+                    lists.push(None);
+                }
+                b"ol" => {
+                    md.block();
+                    lists.push(Some(1));
+                }
+                b"li" => {
+                    md.block();
+                    md.push_raw(&"  ".repeat(lists.len().saturating_sub(1)));
+                    match lists.last_mut() {
+                        Some(Some(n)) => {
+                            md.push_raw(&format!("{}. ", n));
+                            *n += 1;
+                        }
+                        _ => md.push_raw("- "),
+                    }
+                }
+                b"a" => {
+                    let href = attribute(&start, b"href");
+                    if href.is_some() {
+                        md.push_raw("[");
+                    }
+                    links.push(href);
+                }
+                b"pre" => {
+                    md.block();
+                    md.push_raw("```\n");
+                    pre += 1;
+                }
+                b"en-todo" => md.push_raw(todo_marker(&start)),
+                b"hr" => {
+                    md.block();
+                    md.push_raw("---\n");
+                }
+                _ => {}
+            },
+            Event::Empty(empty) => match empty.local_name().as_ref() {
+                b"br" => md.newline(),
+                b"en-todo" => md.push_raw(todo_marker(&empty)),
+                b"hr" => {
+                    md.block();
+                    md.push_raw("---\n");
+                }
+                _ => {}
+            },
+            Event::End(end) => match end.local_name().as_ref() {
+                b"div" | b"p" | b"table" | b"tr" | b"blockquote" | b"li" => md.block(),
+                b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6" => md.block(),
+                b"b" | b"strong" => md.push_raw("**"),
+                b"i" | b"em" => md.push_raw("*"),
+                b"s" | b"strike" | b"del" => md.push_raw("~~"),
+                b"code" if pre == 0 => md.push_raw("`"),
+                b"td" | b"th" => md.push_raw(" "),
+                b"ul" | b"ol" => {
+                    lists.pop();
+                    md.block();
+                }
+                b"a" => {
+                    if let Some(Some(href)) = links.pop() {
+                        md.push_raw(&format!("]({})", href));
+                    }
+                }
+                b"pre" => {
+                    pre = pre.saturating_sub(1);
+                    md.newline();
+                    md.push_raw("```\n");
+                }
+                _ => {}
+            },
+            Event::Text(text) => md.push_text(&text.xml_content().map_err(|e| Msg::InvalidImportItem(e.to_string()))?, pre > 0),
+            Event::CData(data) => md.push_text(&data.decode().map_err(|e| Msg::InvalidImportItem(e.to_string()))?, pre > 0),
+            Event::GeneralRef(entity) => md.push_text(&resolve_entity(&entity), true),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(md.finish())
+}
+
+fn attribute(start: &BytesStart, name: &[u8]) -> Option<String> {
+    let attr = start.try_get_attribute(name).ok()??;
+    attr.unescape_value().ok().map(|value| value.into_owned())
+}
+
+fn todo_marker(start: &BytesStart) -> &'static str {
+    if attribute(start, b"checked").as_deref() == Some("true") { "- [x] " } else { "- [ ] " }
+}
+
+// Penyusun teks Markdown: spasi di luar <pre> dirapatkan seperti di HTML,
+// baris kosong berlebih dibuang.
+#[derive(Default)]
+struct MarkdownWriter {
+    out: String,
+}
+
+impl MarkdownWriter {
+    // Tidak perlu spasi pemisah sebelum kata berikutnya
+    fn at_word_boundary(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n') || self.out.ends_with(' ')
+    }
+
+    fn push_raw(&mut self, text: &str) {
+        self.out.push_str(text);
+    }
+
+    fn push_text(&mut self, text: &str, verbatim: bool) {
+        if verbatim {
+            self.out.push_str(text);
+            return;
+        }
+        for (i, word) in text.split(char::is_whitespace).enumerate() {
+            if i > 0 && !self.at_word_boundary() {
+                self.out.push(' ');
+            }
+            self.out.push_str(word);
+        }
+    }
+
+    fn newline(&mut self) {
+        self.trim_spaces();
+        self.out.push('\n');
+    }
+
+    // Mulai blok baru di baris sendiri
+    fn block(&mut self) {
+        self.trim_spaces();
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    fn trim_spaces(&mut self) {
+        let len = self.out.trim_end_matches(' ').len();
+        self.out.truncate(len);
+    }
+
+    fn finish(self) -> String {
+        let mut result = String::with_capacity(self.out.len());
+        let mut blank = 0;
+        for line in self.out.trim().lines() {
+            let line = line.trim_end();
+            blank = if line.is_empty() { blank + 1 } else { 0 };
+            if blank <= 1 {
+                result.push_str(line);
+                result.push('\n');
+            }
+        }
+        result.trim_end().to_string()
+    }
+}
+
+// --- Google Keep (Takeout) ---
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeepNote {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    text_content: String,
+    #[serde(default)]
+    list_content: Vec<KeepListItem>,
+    #[serde(default)]
+    labels: Vec<KeepLabel>,
+    #[serde(default)]
+    is_trashed: bool,
+    created_timestamp_usec: Option<i64>,
+    user_edited_timestamp_usec: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeepListItem {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    is_checked: bool,
+}
+
+#[derive(Deserialize)]
+struct KeepLabel {
+    name: String,
+}
+
+fn parse_keep(data: &[u8], user_id: u32) -> Result<ParsedImport, Msg> {
+    each_zip_file(data, is_keep_note, |name, content, _, parsed| {
+        match serde_json::from_slice::<KeepNote>(&content) {
+            // Catatan di tempat sampah Keep tidak ikut diimpor
+            Ok(note) if note.is_trashed => {}
+            Ok(note) => parsed.push(name.to_string(), Ok(keep_note(note, user_id))),
+            Err(e) => parsed.errors.push((name.to_string(), Msg::InvalidImportItem(e.to_string()))),
+        }
+    })
+}
+
+fn keep_note(note: KeepNote, user_id: u32) -> NewNote {
+    // Tanpa judul: pakai baris pertama teks atau item checklist pertama
+    let first_line = note
+        .list_content
+        .first()
+        .map(|item| item.text.clone())
+        .or_else(|| note.text_content.lines().find(|line| !line.trim().is_empty()).map(str::to_string));
+    // Checklist Keep -> task list Markdown
+    let (content, content_format) = if note.list_content.is_empty() {
+        (note.text_content, ContentFormat::Plain)
+    } else {
+        let items: Vec<String> = note
+            .list_content
+            .iter()
+            .map(|item| format!("- [{}] {}", if item.is_checked { "x" } else { " " }, item.text.replace('\n', " ")))
+            .collect();
+        (items.join("\n"), ContentFormat::Markdown)
+    };
+    let usec = |usec: Option<i64>| usec.filter(|usec| *usec > 0).and_then(DateTime::from_timestamp_micros);
+    let created_at = usec(note.created_timestamp_usec);
+
+    NewNote {
+        user_id,
+        title: note_title(Some(&note.title).filter(|title| !title.trim().is_empty()).map(String::as_str).or(first_line.as_deref()), UNTITLED),
+        content: (!content.trim().is_empty()).then_some(content),
+        content_format,
+        tags: lenient_tags(note.labels.into_iter().map(|label| label.name).collect()),
+        created_at,
+        updated_at: usec(note.user_edited_timestamp_usec).filter(|updated| Some(*updated) != created_at),
+    }
+}
+
+// --- Umum ---
+
+// Baris pertama judul, maksimal 255 karakter; fallback jika kosong
+fn note_title(title: Option<&str>, fallback: &str) -> String {
+    let title = title.and_then(|title| title.lines().map(str::trim).find(|line| !line.is_empty()));
+    let title = title.unwrap_or(fallback).trim();
+    let title = if title.is_empty() { UNTITLED } else { title };
+    title.chars().take(MAX_TITLE_LEN).collect()
+}
+
+// Tag dari aplikasi lain tidak ditolak satu catatan penuh seperti di API:
+// tag yang tidak valid dilewati, sisanya dipotong ke batas jumlah tag.
+fn lenient_tags(tags: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || tag.chars().count() > MAX_TAG_LEN || result.contains(&tag) {
+            continue;
+        }
+        result.push(tag);
+        if result.len() == MAX_TAGS_PER_NOTE {
+            break;
+        }
+    }
+    result
+}
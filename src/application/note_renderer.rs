@@ -0,0 +1,175 @@
+use ammonia::Builder;
+use moka::sync::Cache;
+use pulldown_cmark::{html, CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use std::{
+    borrow::Cow,
+    sync::{Arc, LazyLock},
+};
+use syntect::{
+    highlighting::ThemeSet,
+    html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+
+use crate::domain::models::note::{ContentFormat, Note};
+
+// Prefix class hasil highlight kode, misal `<span class="hl-keyword hl-rust">`
+pub const HIGHLIGHT_CLASS_PREFIX: &str = "hl-";
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: HIGHLIGHT_CLASS_PREFIX };
+// Tema untuk CSS di GET /render/highlight.css
+const HIGHLIGHT_THEME: &str = "InspiredGitHub";
+
+// Definisi syntax & sanitizer dibuat sekali saat pertama dipakai (lumayan berat)
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static SANITIZER: LazyLock<Builder<'static>> = LazyLock::new(sanitizer);
+static HIGHLIGHT_CSS: LazyLock<String> = LazyLock::new(|| {
+    let themes = ThemeSet::load_defaults();
+    css_for_theme_with_class_style(&themes.themes[HIGHLIGHT_THEME], CLASS_STYLE).expect("tema bawaan syntect selalu valid")
+});
+
+// Render isi catatan ke HTML yang aman ditampilkan langsung oleh klien.
+// Hasilnya di-cache per versi catatan (id + change_seq); setiap perubahan
+// menaikkan change_seq, jadi entri lama tidak pernah terpakai lagi.
+pub struct NoteRenderer {
+    cache: Cache<(u32, i64), Arc<str>>,
+}
+
+impl NoteRenderer {
+    pub fn new(max_capacity: u64) -> Self {
+        Self { cache: Cache::new(max_capacity) }
+    }
+
+    pub async fn render(&self, note: &Note) -> Arc<str> {
+        let key = (note.id, note.change_seq);
+        if let Some(html) = self.cache.get(&key) {
+            return html;
+        }
+
+        // Parsing + highlight bisa berat untuk catatan panjang, jadi jangan di thread async
+        let content = note.content.clone().unwrap_or_default();
+        let format = note.content_format;
+        let html: Arc<str> = match tokio::task::spawn_blocking(move || render_html(&content, format)).await {
+            Ok(html) => html.into(),
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        };
+        self.cache.insert(key, html.clone());
+        html
+    }
+}
+
+impl Default for NoteRenderer {
+    fn default() -> Self {
+        Self::new(1_000)
+    }
+}
+
+// CSS untuk class `hl-*` pada blok kode
+pub fn highlight_css() -> &'static str {
+    &HIGHLIGHT_CSS
+}
+
+pub fn render_html(content: &str, format: ContentFormat) -> String {
+    let mut output = String::new();
+    match format {
+        ContentFormat::Markdown => {
+            let options = Options::ENABLE_TABLES
+                | Options::ENABLE_TASKLISTS
+                | Options::ENABLE_STRIKETHROUGH
+                | Options::ENABLE_FOOTNOTES
+                | Options::ENABLE_GFM;
+            html::push_html(&mut output, highlight_code_blocks(Parser::new_ext(content, options)).into_iter());
+        }
+        // Teks biasa: paragraf dipisah baris kosong, baris baru jadi <br>
+        ContentFormat::Plain => {
+            let mut events = Vec::new();
+            for paragraph in content.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+                events.push(Event::Start(Tag::Paragraph));
+                for (i, line) in paragraph.lines().enumerate() {
+                    if i > 0 {
+                        events.push(Event::HardBreak);
+                    }
+                    events.push(Event::Text(line.into()));
+                }
+                events.push(Event::End(TagEnd::Paragraph));
+            }
+            html::push_html(&mut output, events.into_iter());
+        }
+    }
+    // HTML mentah di Markdown (<script>, onclick, javascript:, ...) dibuang di sini
+    SANITIZER.clean(&output).to_string()
+}
+
+// Ganti blok kode berbahasa yang dikenal syntect dengan HTML ber-highlight
+fn highlight_code_blocks<'a>(parser: Parser<'a>) -> Vec<Event<'a>> {
+    let mut events = Vec::new();
+    let mut block: Option<(String, String)> = None;
+    for event in parser {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(ref info))) => {
+                let lang = info.split_whitespace().next().unwrap_or_default();
+                if SYNTAXES.find_syntax_by_token(lang).is_some() {
+                    block = Some((lang.to_string(), String::new()));
+                } else {
+                    events.push(event);
+                }
+            }
+            Event::Text(text) if block.is_some() => {
+                if let Some((_, code)) = block.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) if block.is_some() => {
+                if let Some((lang, code)) = block.take() {
+                    events.push(Event::Html(highlight(&lang, &code).into()));
+                }
+            }
+            event => events.push(event),
+        }
+    }
+    events
+}
+
+fn highlight(lang: &str, code: &str) -> String {
+    let syntax = SYNTAXES.find_syntax_by_token(lang).unwrap_or_else(|| SYNTAXES.find_syntax_plain_text());
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAXES, CLASS_STYLE);
+    for line in LinesWithEndings::from(code) {
+        if generator.parse_html_for_line_which_includes_newline(line).is_err() {
+            // Highlight gagal: tampilkan apa adanya (tetap di-escape)
+            let mut escaped = String::new();
+            html::push_html(&mut escaped, std::iter::once(Event::Text(code.into())));
+            return format!("<pre><code>{}</code></pre>\n", escaped);
+        }
+    }
+    let lang: String = lang.chars().filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '+')).collect();
+    format!("<pre><code class=\"language-{}\">{}</code></pre>\n", lang, generator.finalize())
+}
+
+// Whitelist ammonia + yang dibutuhkan fitur GFM: checkbox task list (apa pun
+// isinya selalu jadi checkbox disabled), rata kolom tabel, dan class
+// highlight/bahasa pada blok kode
+fn sanitizer() -> Builder<'static> {
+    let mut builder = Builder::default();
+    builder
+        .add_tags(["input"])
+        .add_tag_attributes("input", ["checked"])
+        .set_tag_attribute_value("input", "type", "checkbox")
+        .set_tag_attribute_value("input", "disabled", "")
+        .add_tag_attributes("span", ["class"])
+        .add_tag_attributes("code", ["class"])
+        .add_tag_attributes("th", ["style"])
+        .add_tag_attributes("td", ["style"])
+        .attribute_filter(|_element, attribute, value| match attribute {
+            "class" => {
+                let classes: Vec<&str> = value
+                    .split_whitespace()
+                    .filter(|class| class.starts_with(HIGHLIGHT_CLASS_PREFIX) || class.starts_with("language-"))
+                    .collect();
+                (!classes.is_empty()).then(|| Cow::Owned(classes.join(" ")))
+            }
+            "style" => matches!(value, "text-align: left" | "text-align: center" | "text-align: right")
+                .then_some(Cow::Borrowed(value)),
+            _ => Some(Cow::Borrowed(value)),
+        });
+    builder
+}
@@ -1,70 +1,331 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::{
     domain::{
-        models::note::{CreateNotePayload, NewNote, Note, UpdateNotePayload},
-        repositories::note_repository::DynNoteRepository,
+        models::{
+            bulk::{BulkItemResult, BulkItemStatus, BulkOperation, BulkOperationResult, BulkPayload, BulkResult, MAX_BULK_IDS},
+            event::{NoteEvent, NoteEventKind},
+            note::{normalize_tags, CreateNotePayload, NewNote, Note, NoteUpdate, UpdateNotePayload, MAX_TAGS_PER_NOTE},
+            pagination::PageQuery,
+            sync::{SyncChange, SyncPull, SyncQuery, SyncResult, SyncStatus, SyncToken, MAX_SYNC_PUSH},
+        },
+        repositories::{event_bus::DynEventBus, note_repository::DynNoteRepository},
+    },
+    utils::{
+        error::{AppError, AppResult},
+        i18n::Msg,
     },
-    utils::error::{AppError, AppResult},
 };
 
+#[derive(Clone)]
 pub struct NoteService {
     note_repo: DynNoteRepository,
+    events: DynEventBus,
 }
 
 impl NoteService {
-    pub fn new(note_repo: DynNoteRepository) -> Self {
-        Self { note_repo }
+    pub fn new(note_repo: DynNoteRepository, events: DynEventBus) -> Self {
+        Self { note_repo, events }
     }
 
+    // Kirim event ke /events. Perubahan sudah tersimpan, jadi gagal publish
+    // hanya dicatat; klien tetap bisa sinkron ulang lewat GET /notes.
+    async fn publish(&self, kind: NoteEventKind, user_id: u32, note_id: u32, note: Option<&Note>) {
+        let event = NoteEvent::new(kind, user_id, note_id, note.cloned());
+        if let Err(e) = self.events.publish(event).await {
+            tracing::warn!("Gagal mengirim event {}: {}", kind.as_str(), e);
+        }
+    }
+
+    #[tracing::instrument(name = "NoteService.create_note", skip_all, fields(user.id = user_id))]
     pub async fn create_note(&self, payload: CreateNotePayload, user_id: u32) -> AppResult<Note> {
         let new_note = NewNote {
             user_id,
             title: payload.title,
             content: payload.content,
+            content_format: payload.content_format.unwrap_or_default(),
+            tags: valid_tags(payload.tags)?.unwrap_or_default(),
+            created_at: None,
+            updated_at: None,
         };
-        self.note_repo.create(&new_note).await
+        let note = self.note_repo.create(&new_note).await?;
+        self.publish(NoteEventKind::Created, user_id, note.id, Some(&note)).await;
+        Ok(note)
     }
 
-    pub async fn get_all_notes(&self, user_id: u32) -> AppResult<Vec<Note>> {
-        self.note_repo.find_all(user_id).await
+    #[tracing::instrument(name = "NoteService.get_all_notes", skip_all, fields(user.id = user_id))]
+    pub async fn get_all_notes(&self, user_id: u32, page: PageQuery) -> AppResult<Vec<Note>> {
+        match page.limit_offset() {
+            Some((limit, offset)) => self.note_repo.find_page(user_id, limit, offset).await,
+            None => self.note_repo.find_all(user_id).await,
+        }
     }
 
+    #[tracing::instrument(name = "NoteService.get_note_by_id", skip_all, fields(note.id = id, user.id = user_id))]
     pub async fn get_note_by_id(&self, id: u32, user_id: u32) -> AppResult<Note> {
         self.note_repo
             .find_by_id(id, user_id)
             .await?
-            .ok_or_else(|| {
-                AppError::NotFound(format!(
-                    "Catatan dengan id {} tidak ditemukan atau bukan milik anda",
-                    id
-                ))
-            })
+            .ok_or(AppError::NotFound(Msg::NoteNotFound(id)))
     }
 
+    #[tracing::instrument(name = "NoteService.update_note", skip_all, fields(note.id = id, user.id = user_id))]
     pub async fn update_note(
         &self,
         id: u32,
         user_id: u32,
-        payload: UpdateNotePayload,
+        mut payload: UpdateNotePayload,
     ) -> AppResult<Note> {
-        self.note_repo
-            .update(id, user_id, &payload)
+        payload.tags = valid_tags(payload.tags)?;
+        let note = self
+            .note_repo
+            .update(id, user_id, &payload, None)
             .await?
-            .ok_or_else(|| {
-                AppError::NotFound(format!(
-                    "Catatan dengan id {} tidak ditemukan atau bukan milik anda",
-                    id
-                ))
-            })
+            .ok_or(AppError::NotFound(Msg::NoteNotFound(id)))?;
+        self.publish(NoteEventKind::Updated, user_id, id, Some(&note)).await;
+        Ok(note)
+    }
+
+    // Update hanya jika change_seq catatan masih `expected_seq`.
+    // None = catatan tidak ada atau sudah diubah di tempat lain.
+    #[tracing::instrument(name = "NoteService.update_note_if_unchanged", skip_all, fields(note.id = id, user.id = user_id))]
+    pub async fn update_note_if_unchanged(
+        &self,
+        id: u32,
+        user_id: u32,
+        mut payload: UpdateNotePayload,
+        expected_seq: i64,
+    ) -> AppResult<Option<Note>> {
+        payload.tags = valid_tags(payload.tags)?;
+        let note = self.note_repo.update(id, user_id, &payload, Some(expected_seq)).await?;
+        if let Some(note) = &note {
+            self.publish(NoteEventKind::Updated, user_id, id, Some(note)).await;
+        }
+        Ok(note)
     }
 
+    #[tracing::instrument(name = "NoteService.delete_note", skip_all, fields(note.id = id, user.id = user_id))]
     pub async fn delete_note(&self, id: u32, user_id: u32) -> AppResult<()> {
-        let rows_affected = self.note_repo.delete(id, user_id).await?;
+        let rows_affected = self.note_repo.delete(id, user_id, None).await?;
         if rows_affected == 0 {
-            return Err(AppError::NotFound(format!(
-                "Catatan dengan id {} tidak ditemukan atau bukan milik anda",
-                id
-            )));
+            return Err(AppError::NotFound(Msg::NoteNotFound(id)));
         }
+        self.publish(NoteEventKind::Deleted, user_id, id, None).await;
         Ok(())
     }
-}
\ No newline at end of file
+
+    // Perubahan sejak token `since` (catatan baru/berubah + tombstone).
+    // Tanpa `since` = sinkronisasi penuh dari awal.
+    #[tracing::instrument(name = "NoteService.pull_changes", skip_all, fields(user.id = user_id))]
+    pub async fn pull_changes(&self, user_id: u32, query: SyncQuery) -> AppResult<SyncPull> {
+        let since = match query.since.as_deref().filter(|since| !since.is_empty()) {
+            Some(since) => SyncToken::parse(since).ok_or(AppError::BadRequest(Msg::InvalidSyncToken))?,
+            None => SyncToken(0),
+        };
+
+        let changes = self.note_repo.changes_since(user_id, since.0, query.limit()).await?;
+        let token = changes.last_seq().map(SyncToken).unwrap_or(since);
+        Ok(SyncPull {
+            token: token.to_string(),
+            has_more: changes.has_more,
+            notes: changes.notes,
+            deleted: changes.tombstones,
+        })
+    }
+
+    // Terapkan perubahan dari klien satu per satu. Setiap perubahan berdiri sendiri:
+    // yang konflik atau gagal tidak membatalkan yang lain.
+    #[tracing::instrument(name = "NoteService.push_changes", skip_all, fields(user.id = user_id, sync.changes = changes.len()))]
+    pub async fn push_changes(&self, user_id: u32, changes: Vec<SyncChange>) -> AppResult<Vec<SyncResult>> {
+        if changes.len() > MAX_SYNC_PUSH {
+            return Err(AppError::BadRequest(Msg::TooManySyncChanges(MAX_SYNC_PUSH)));
+        }
+
+        let mut results = Vec::with_capacity(changes.len());
+        for change in changes {
+            results.push(self.apply_change(user_id, change).await?);
+        }
+        Ok(results)
+    }
+
+    async fn apply_change(&self, user_id: u32, change: SyncChange) -> AppResult<SyncResult> {
+        let result = |status, id, note| SyncResult { status, client_id: None, id, note, message: None };
+
+        match change {
+            SyncChange::Create { client_id, title, content, content_format, tags } => {
+                if title.trim().is_empty() || valid_tags(tags.clone()).is_err() {
+                    return Ok(SyncResult { client_id: Some(client_id), ..result(SyncStatus::Invalid, None, None) });
+                }
+                let payload = CreateNotePayload { title, content, content_format, tags };
+                let note = self.create_note(payload, user_id).await?;
+                Ok(SyncResult { client_id: Some(client_id), ..result(SyncStatus::Applied, Some(note.id), Some(note)) })
+            }
+            SyncChange::Update { id, base_seq, title, content, content_format, tags } => {
+                if title.as_deref().is_some_and(|title| title.trim().is_empty()) || valid_tags(tags.clone()).is_err() {
+                    return Ok(result(SyncStatus::Invalid, Some(id), None));
+                }
+                let payload = UpdateNotePayload { title, content, content_format, tags };
+                match self.update_note_if_unchanged(id, user_id, payload, base_seq).await? {
+                    Some(note) => Ok(result(SyncStatus::Applied, Some(id), Some(note))),
+                    None => self.rejected(id, user_id).await,
+                }
+            }
+            SyncChange::Delete { id, base_seq } => {
+                if self.note_repo.delete(id, user_id, base_seq).await? == 0 {
+                    return self.rejected(id, user_id).await;
+                }
+                self.publish(NoteEventKind::Deleted, user_id, id, None).await;
+                Ok(result(SyncStatus::Applied, Some(id), None))
+            }
+        }
+    }
+
+    // Operasi dijalankan berurutan dan masing-masing punya transaksi sendiri,
+    // jadi operasi yang gagal tidak membatalkan operasi sebelumnya.
+    #[tracing::instrument(name = "NoteService.bulk", skip_all, fields(user.id = user_id, bulk.operations = payload.operations.len()))]
+    pub async fn bulk(&self, user_id: u32, payload: BulkPayload) -> AppResult<BulkResult> {
+        let total: usize = payload.operations.iter().map(|operation| operation.ids().len()).sum();
+        if total > MAX_BULK_IDS {
+            return Err(AppError::BadRequest(Msg::TooManyBulkItems(MAX_BULK_IDS)));
+        }
+
+        let mut results = Vec::with_capacity(payload.operations.len());
+        for operation in payload.operations {
+            results.push(self.apply_bulk(user_id, operation, payload.atomic).await?);
+        }
+        Ok(BulkResult { results })
+    }
+
+    async fn apply_bulk(&self, user_id: u32, operation: BulkOperation, atomic: bool) -> AppResult<BulkOperationResult> {
+        let mut ids = operation.ids().to_vec();
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+
+        // Hitung isi baru tiap catatan dari versi yang dibaca sekarang; yang gagal langsung dicatat
+        let current: HashMap<u32, Note> =
+            self.note_repo.find_by_ids(&ids, user_id).await?.into_iter().map(|note| (note.id, note)).collect();
+        let mut failed: HashMap<u32, BulkItemStatus> = HashMap::new();
+        let mut planned: Vec<NoteUpdate> = Vec::with_capacity(ids.len());
+        for id in &ids {
+            match current.get(id) {
+                None => {
+                    failed.insert(*id, BulkItemStatus::NotFound);
+                }
+                Some(note) => match planned_update(&operation, note) {
+                    Some(update) => planned.push(update),
+                    None => {
+                        failed.insert(*id, BulkItemStatus::Invalid);
+                    }
+                },
+            }
+        }
+
+        // Operasi atomik dengan id yang gagal tidak diterapkan sama sekali
+        let mut applied: HashMap<u32, Option<Note>> = HashMap::new();
+        let mut latest: HashMap<u32, Note> = HashMap::new();
+        if !planned.is_empty() && (!atomic || failed.is_empty()) {
+            if let BulkOperation::Delete { .. } = operation {
+                // Sama seperti update: catatan yang diubah sejak dibaca tidak ikut terhapus (konflik)
+                let expected: Vec<(u32, i64)> = planned.iter().map(|update| (update.id, update.expected_seq)).collect();
+                for id in self.note_repo.delete_many(user_id, &expected, atomic).await? {
+                    self.publish(NoteEventKind::Deleted, user_id, id, None).await;
+                    applied.insert(id, None);
+                }
+            } else {
+                for note in self.note_repo.update_many(user_id, &planned, atomic).await? {
+                    self.publish(NoteEventKind::Updated, user_id, note.id, Some(&note)).await;
+                    applied.insert(note.id, Some(note));
+                }
+            }
+
+            // Catatan yang tidak ikut berubah dibaca ulang: dihapus/diubah request lain sejak dibaca
+            // (konflik), atau ikut di-rollback karena catatan lain di operasi atomik ini
+            let missed: Vec<u32> = planned.iter().map(|update| update.id).filter(|id| !applied.contains_key(id)).collect();
+            if !missed.is_empty() {
+                latest = self.note_repo.find_by_ids(&missed, user_id).await?.into_iter().map(|note| (note.id, note)).collect();
+                for update in planned.iter().filter(|update| missed.contains(&update.id)) {
+                    let status = match latest.get(&update.id) {
+                        // Tadi masih ada, jadi hilangnya karena request lain
+                        None => BulkItemStatus::Conflict,
+                        Some(note) if note.change_seq != update.expected_seq => BulkItemStatus::Conflict,
+                        Some(_) => BulkItemStatus::Skipped,
+                    };
+                    failed.insert(update.id, status);
+                }
+            }
+        }
+        Ok(bulk_result(&operation, ids, failed, applied, latest))
+    }
+
+    // Update/delete tidak mengubah apa pun: catatan sudah berubah (konflik) atau tidak ada
+    async fn rejected(&self, id: u32, user_id: u32) -> AppResult<SyncResult> {
+        let current = self.note_repo.find_by_id(id, user_id).await?;
+        let status = if current.is_some() { SyncStatus::Conflict } else { SyncStatus::NotFound };
+        Ok(SyncResult { status, client_id: None, id: Some(id), note: current, message: None })
+    }
+}
+
+// Isi baru catatan untuk operasi bulk; None jika hasilnya tidak valid.
+// Untuk delete isinya tidak dipakai, hanya id & change_seq.
+fn planned_update(operation: &BulkOperation, note: &Note) -> Option<NoteUpdate> {
+    let mut update = NoteUpdate::from_note(note);
+    match operation {
+        BulkOperation::Delete { .. } => {}
+        BulkOperation::Update { title, content, .. } => {
+            if let Some(title) = title {
+                if title.trim().is_empty() {
+                    return None;
+                }
+                update.title = title.clone();
+            }
+            if let Some(content) = content {
+                update.content = Some(content.clone());
+            }
+        }
+        BulkOperation::Archive { archived, .. } => update.archived = *archived,
+        BulkOperation::Tag { add, remove, .. } => {
+            let add = normalize_tags(add.clone())?;
+            let remove = normalize_tags(remove.clone())?;
+            update.tags.retain(|tag| !remove.contains(tag));
+            for tag in add {
+                if !update.tags.contains(&tag) {
+                    update.tags.push(tag);
+                }
+            }
+            if update.tags.len() > MAX_TAGS_PER_NOTE {
+                return None;
+            }
+        }
+    }
+    Some(update)
+}
+
+// Hasil per id sesuai urutan `ids`. Id yang tidak ada di `failed` maupun `applied`
+// dilewati karena operasi atomik ini dibatalkan sebelum menyentuh database.
+fn bulk_result(
+    operation: &BulkOperation,
+    ids: Vec<u32>,
+    failed: HashMap<u32, BulkItemStatus>,
+    mut applied: HashMap<u32, Option<Note>>,
+    mut latest: HashMap<u32, Note>,
+) -> BulkOperationResult {
+    let items: Vec<BulkItemResult> = ids
+        .into_iter()
+        .map(|id| {
+            let (status, note) = match (applied.remove(&id), failed.get(&id)) {
+                (Some(note), _) => (BulkItemStatus::Applied, note),
+                (None, Some(BulkItemStatus::Conflict)) => (BulkItemStatus::Conflict, latest.remove(&id)),
+                (None, Some(status)) => (*status, None),
+                (None, None) => (BulkItemStatus::Skipped, None),
+            };
+            BulkItemResult { id, status, note, message: None }
+        })
+        .collect();
+    let applied = items.iter().filter(|item| item.status == BulkItemStatus::Applied).count();
+    BulkOperationResult { op: operation.kind(), applied, failed: items.len() - applied, items }
+}
+
+// Tag dari klien dirapikan dulu; daftar yang tidak valid ditolak seluruhnya
+fn valid_tags(tags: Option<Vec<String>>) -> AppResult<Option<Vec<String>>> {
+    tags.map(|tags| normalize_tags(tags).ok_or(AppError::BadRequest(Msg::InvalidTags))).transpose()
+}
@@ -1,11 +1,19 @@
 use bcrypt;
+use moka::sync::Cache;
+use std::time::Duration;
 
 use crate::{
     domain::{
-        models::user::{LoginPayload, RegisterPayload, User},
+        models::{
+            user::{LoginPayload, RegisterPayload, Role, User},
+            user_settings::{is_valid_date_format, is_valid_timezone, UpdateSettingsPayload, UserSettings},
+        },
         repositories::user_repository::DynUserRepository,
     },
-    utils::error::{AppError, AppResult},
+    utils::{
+        error::{AppError, AppResult},
+        i18n::{Locale, Msg},
+    },
 };
 
 pub struct UserService {
@@ -17,7 +25,15 @@ impl UserService {
         Self { user_repo }
     }
 
+    #[tracing::instrument(name = "UserService.register_user", skip_all)]
     pub async fn register_user(&self, payload: RegisterPayload) -> AppResult<User> {
+        // Registrasi publik selalu membuat user biasa
+        self.create_user(payload, Role::User).await
+    }
+
+    // Buat user dengan role tertentu (admin hanya lewat CLI admin)
+    #[tracing::instrument(name = "UserService.create_user", skip_all, fields(role = role.as_ref()))]
+    pub async fn create_user(&self, payload: RegisterPayload, role: Role) -> AppResult<User> {
         // Cek apakah email sudah ada
         if self.user_repo.find_by_email(&payload.email).await?.is_some() {
             return Err(AppError::UserAlreadyExists);
@@ -37,15 +53,17 @@ impl UserService {
             email: payload.email,
             full_name: payload.full_name,
             username: payload.username,
-            role: "user".to_string(), // Default role
+            role: role.as_ref().to_string(),
             password_hash,
             created_at: chrono::Utc::now(), // Akan di-override oleh DB, tapi baik untuk ada
+            created_at_local: None,
         };
 
         // Simpan ke repositori
         self.user_repo.create(&new_user_data).await
     }
 
+    #[tracing::instrument(name = "UserService.login_user", skip_all)]
     pub async fn login_user(&self, payload: LoginPayload) -> AppResult<User> {
         // Cari user berdasarkan email
         let user = self.user_repo
@@ -60,6 +78,109 @@ impl UserService {
 
         Ok(user)
     }
+
+    #[tracing::instrument(name = "UserService.set_role", skip_all, fields(user.id = user_id, role = role.as_ref()))]
+    pub async fn set_role(&self, user_id: u32, role: Role) -> AppResult<()> {
+        let rows_affected = self.user_repo.update_role(user_id, role.as_ref()).await?;
+        if rows_affected == 0 && self.user_repo.find_by_id(user_id).await?.is_none() {
+            return Err(AppError::NotFound(Msg::UserProfileNotFound(user_id)));
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "UserService.reset_password", skip_all, fields(user.id = user_id))]
+    pub async fn reset_password(&self, user_id: u32, new_password: String) -> AppResult<()> {
+        let password_hash = hash_password(new_password).await?;
+        let rows_affected = self.user_repo.update_password(user_id, &password_hash).await?;
+        if rows_affected == 0 && self.user_repo.find_by_id(user_id).await?.is_none() {
+            return Err(AppError::NotFound(Msg::UserProfileNotFound(user_id)));
+        }
+        Ok(())
+    }
+
+    // Ambil pengaturan user, atau default jika belum pernah disimpan
+    #[tracing::instrument(name = "UserService.get_settings", skip_all, fields(user.id = user_id))]
+    pub async fn get_settings(&self, user_id: u32) -> AppResult<UserSettings> {
+        Ok(self.user_repo
+            .find_settings(user_id).await?
+            .unwrap_or_else(|| UserSettings::default_for(user_id)))
+    }
+
+    #[tracing::instrument(name = "UserService.update_settings", skip_all, fields(user.id = user_id))]
+    pub async fn update_settings(
+        &self,
+        user_id: u32,
+        payload: UpdateSettingsPayload
+    ) -> AppResult<UserSettings> {
+        let mut settings = self.get_settings(user_id).await?;
+
+        if let Some(timezone) = payload.timezone {
+            if !is_valid_timezone(&timezone) {
+                return Err(AppError::BadRequest(Msg::InvalidTimezone(timezone)));
+            }
+            settings.timezone = timezone;
+        }
+
+        if let Some(locale) = payload.locale {
+            // String kosong = kembali mengikuti Accept-Language
+            if locale.trim().is_empty() {
+                settings.locale = None;
+            } else {
+                let parsed = Locale::from_tag(&locale).ok_or(AppError::BadRequest(Msg::InvalidLocale(locale)))?;
+                settings.locale = Some(parsed.as_str().to_string());
+            }
+        }
+
+        if let Some(date_format) = payload.date_format {
+            if !is_valid_date_format(&date_format) {
+                return Err(AppError::BadRequest(Msg::InvalidDateFormat));
+            }
+            settings.date_format = date_format;
+        }
+
+        self.user_repo.upsert_settings(&settings).await
+    }
+}
+
+// Pengaturan dibaca auth_middleware di setiap request, jadi disimpan sebentar di memori.
+// PATCH /auth/settings langsung mengganti isinya; perubahan lewat instance lain
+// paling lama terlambat selama SETTINGS_CACHE_TTL.
+const SETTINGS_CACHE_TTL: Duration = Duration::from_secs(30);
+const SETTINGS_CACHE_CAPACITY: u64 = 10_000;
+
+pub struct SettingsCache {
+    settings: Cache<u32, UserSettings>,
+}
+
+impl SettingsCache {
+    pub fn new() -> Self {
+        Self {
+            settings: Cache::builder()
+                .max_capacity(SETTINGS_CACHE_CAPACITY)
+                .time_to_live(SETTINGS_CACHE_TTL)
+                .build(),
+        }
+    }
+
+    pub async fn get_or_load(&self, user_service: &UserService, user_id: u32) -> AppResult<UserSettings> {
+        if let Some(settings) = self.settings.get(&user_id) {
+            return Ok(settings);
+        }
+        let settings = user_service.get_settings(user_id).await?;
+        self.settings.insert(user_id, settings.clone());
+        Ok(settings)
+    }
+
+    // Dipanggil setelah pengaturan disimpan
+    pub fn set(&self, user_id: u32, settings: UserSettings) {
+        self.settings.insert(user_id, settings);
+    }
+}
+
+impl Default for SettingsCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // Helper ini bisa dipindah ke modul terpisah, misal `application/utils/security.rs`
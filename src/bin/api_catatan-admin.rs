@@ -0,0 +1,279 @@
+// CLI untuk tugas operasional (bootstrap admin, reset password, migrasi, dll).
+// Memakai config, pool, dan repository yang sama dengan server.
+use std::error::Error;
+use std::path::PathBuf;
+
+use api_catatan::{
+    application::user_service::UserService,
+    cli::{run_migrate, ConfigArgs, MigrateAction},
+    domain::{
+        models::{
+            note::{ContentFormat, NewNote},
+            user::{RegisterPayload, Role, User},
+        },
+        repositories::{note_repository::DynNoteRepository, user_repository::DynUserRepository},
+    },
+    infrastructure::{
+        repositories::create_repositories,
+        token_revocation::create_token_revocation_store,
+    },
+    utils::{
+        config::Config,
+        db::{self, DbPool},
+        telemetry,
+    },
+};
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(name = "api_catatan-admin", version, about = "Alat administrasi API Catatan")]
+struct Cli {
+    #[command(flatten)]
+    config: ConfigArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Kelola migrasi database
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    #[command(flatten)]
+    Db(DbCommand),
+}
+
+// Perintah yang butuh pool & repository
+#[derive(Subcommand)]
+enum DbCommand {
+    /// Buat user baru dengan role admin
+    CreateAdmin {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        username: String,
+        #[arg(long)]
+        full_name: String,
+        /// Jika kosong, password ditanyakan secara interaktif
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Jadikan user sebagai admin (USER = id, email, atau username)
+    Promote { user: String },
+    /// Turunkan admin menjadi user biasa
+    Demote { user: String },
+    /// Ganti password user
+    ResetPassword {
+        user: String,
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Hapus semua token yang sudah di-revoke (logout)
+    #[command(alias = "purge-blacklist")]
+    PurgeRevokedTokens,
+    /// Ekspor semua catatan milik user ke JSON
+    DumpNotes {
+        user: String,
+        /// File tujuan, default ke stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Impor catatan dari hasil `dump-notes` ke user tertentu
+    RestoreNotes {
+        user: String,
+        /// File sumber, default dari stdin
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+    },
+}
+
+// Format file dump catatan
+#[derive(Serialize, Deserialize)]
+struct NotesDump {
+    user_email: String,
+    exported_at: DateTime<Utc>,
+    notes: Vec<DumpedNote>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DumpedNote {
+    title: String,
+    content: Option<String>,
+    // Dump lama (sebelum ada content_format) dipulihkan sebagai teks biasa
+    #[serde(default)]
+    content_format: ContentFormat,
+    #[serde(default)]
+    tags: Vec<String>,
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    updated_at: Option<DateTime<Utc>>,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt().with_env_filter(telemetry::env_filter("warn")).init();
+
+    let cli = Cli::parse();
+    let config = cli.config.load_or_exit();
+
+    if let Err(e) = run(cli.command, config).await {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(command: Command, config: Config) -> CliResult {
+    match command {
+        // Migrasi tidak butuh repository
+        Command::Migrate { action } => {
+            run_migrate(&config, action).await;
+            Ok(())
+        }
+        Command::Db(command) => {
+            let pool = db::connect(&config).await?;
+            // Pool selalu ditutup, termasuk saat perintahnya gagal
+            let result = run_db_command(command, &config, &pool).await;
+            pool.close().await;
+            result
+        }
+    }
+}
+
+async fn run_db_command(command: DbCommand, config: &Config, pool: &DbPool) -> CliResult {
+    let (user_repo, note_repo, _) = create_repositories(pool);
+    let user_service = UserService::new(user_repo.clone());
+
+    match command {
+        DbCommand::CreateAdmin { email, username, full_name, password } => {
+            let password = password_or_prompt(password)?;
+            let payload = RegisterPayload { email, full_name, username, password };
+            let admin = user_service.create_user(payload, Role::Admin).await?;
+            println!("Admin '{}' dibuat dengan id {}.", admin.username, admin.id);
+            Ok(())
+        }
+        DbCommand::Promote { user } => {
+            let user = resolve_user(&user_repo, &user).await?;
+            user_service.set_role(user.id, Role::Admin).await?;
+            println!("User '{}' sekarang admin.", user.username);
+            Ok(())
+        }
+        DbCommand::Demote { user } => {
+            let user = resolve_user(&user_repo, &user).await?;
+            user_service.set_role(user.id, Role::User).await?;
+            println!("User '{}' sekarang user biasa.", user.username);
+            Ok(())
+        }
+        DbCommand::ResetPassword { user, password } => {
+            let user = resolve_user(&user_repo, &user).await?;
+            let password = password_or_prompt(password)?;
+            user_service.reset_password(user.id, password).await?;
+            println!("Password user '{}' berhasil diganti.", user.username);
+            Ok(())
+        }
+        DbCommand::DumpNotes { user, output } => {
+            let user = resolve_user(&user_repo, &user).await?;
+            dump_notes(&note_repo, &user, output).await
+        }
+        DbCommand::RestoreNotes { user, input } => {
+            let user = resolve_user(&user_repo, &user).await?;
+            restore_notes(&note_repo, &user, input).await
+        }
+        DbCommand::PurgeRevokedTokens => {
+            let store = create_token_revocation_store(config, pool)?;
+            let removed = store.purge().await?;
+            println!("{} token revoked dihapus.", removed);
+            Ok(())
+        }
+    }
+}
+
+// Cari user berdasarkan id (angka), email (mengandung '@'), atau username
+async fn resolve_user(user_repo: &DynUserRepository, ident: &str) -> Result<User, Box<dyn Error>> {
+    let user = if let Ok(id) = ident.parse::<u32>() {
+        user_repo.find_by_id(id).await?
+    } else if ident.contains('@') {
+        user_repo.find_by_email(ident).await?
+    } else {
+        user_repo.find_by_username(ident).await?
+    };
+
+    user.ok_or_else(|| format!("User '{}' tidak ditemukan", ident).into())
+}
+
+fn password_or_prompt(password: Option<String>) -> Result<String, Box<dyn Error>> {
+    if let Some(password) = password {
+        return Ok(password);
+    }
+
+    let password = rpassword::prompt_password("Password: ")?;
+    let confirm = rpassword::prompt_password("Ulangi password: ")?;
+    if password != confirm {
+        return Err("Password tidak sama".into());
+    }
+    if password.is_empty() {
+        return Err("Password tidak boleh kosong".into());
+    }
+    Ok(password)
+}
+
+async fn dump_notes(note_repo: &DynNoteRepository, user: &User, output: Option<PathBuf>) -> CliResult {
+    let notes = note_repo.find_all(user.id).await?;
+    let dump = NotesDump {
+        user_email: user.email.clone(),
+        exported_at: Utc::now(),
+        notes: notes
+            .into_iter()
+            .map(|note| DumpedNote {
+                title: note.title,
+                content: note.content,
+                content_format: note.content_format,
+                tags: note.tags,
+                created_at: note.created_at,
+                updated_at: note.updated_at,
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&dump)?;
+    match output {
+        Some(path) => {
+            std::fs::write(&path, json)?;
+            eprintln!("{} catatan diekspor ke {}.", dump.notes.len(), path.display());
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+async fn restore_notes(note_repo: &DynNoteRepository, user: &User, input: Option<PathBuf>) -> CliResult {
+    let json = match input {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => std::io::read_to_string(std::io::stdin())?,
+    };
+    let dump: NotesDump = serde_json::from_str(&json)?;
+
+    let new_notes: Vec<NewNote> = dump
+        .notes
+        .into_iter()
+        .map(|note| NewNote {
+            user_id: user.id,
+            title: note.title,
+            content: note.content,
+            content_format: note.content_format,
+            tags: note.tags,
+            created_at: note.created_at,
+            updated_at: note.updated_at,
+        })
+        .collect();
+    // Satu transaksi: kalau gagal di tengah, tidak ada catatan yang setengah dipulihkan
+    let restored = note_repo.create_many(&new_notes).await?;
+
+    println!("{} catatan dipulihkan ke user '{}'.", restored.len(), user.username);
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use clap::Subcommand;
+use std::path::PathBuf;
+
+use crate::utils::{
+    config::{load_config, Config, ConfigError},
+    db, migrate,
+};
+
+// Subcommand migrasi, dipakai bersama oleh binary server dan admin
+#[derive(Subcommand)]
+pub enum MigrateAction {
+    /// Terapkan semua migrasi yang tertunda
+    Up,
+    /// Batalkan migrasi terakhir
+    Down {
+        /// Jumlah migrasi yang dibatalkan
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+    },
+    /// Tampilkan status setiap migrasi
+    Status,
+}
+
+pub async fn run_migrate(config: &Config, action: MigrateAction) {
+    let pool = match db::connect(config).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("Gagal terhubung ke database: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let result = match action {
+        MigrateAction::Up => migrate::up(&pool).await.map(|_| {
+            println!("Semua migrasi sudah diterapkan.");
+        }),
+        MigrateAction::Down { steps } => migrate::down(&pool, steps).await.map(|reverted| {
+            if reverted.is_empty() {
+                println!("Tidak ada migrasi yang dibatalkan.");
+            }
+            for version in reverted {
+                println!("Migrasi {} dibatalkan.", version);
+            }
+        }),
+        MigrateAction::Status => migrate::status(&pool).await.map(|list| {
+            for item in list {
+                let state = if item.applied { "applied" } else { "pending" };
+                println!("{:<16} {:<8} {}", item.version, state, item.description);
+            }
+        }),
+    };
+
+    pool.close().await;
+
+    if let Err(e) = result {
+        eprintln!("Migrasi gagal: {e}");
+        std::process::exit(1);
+    }
+}
+
+// Opsi config yang dipakai bersama oleh binary server dan admin
+#[derive(clap::Args)]
+pub struct ConfigArgs {
+    /// File konfigurasi TOML (default: config.toml, atau env CONFIG_FILE)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Tampilkan config efektif (secret disamarkan) lalu keluar
+    #[arg(long, global = true)]
+    pub print_config: bool,
+}
+
+impl ConfigArgs {
+    // Muat config, atau keluar dengan kode 1 sambil menampilkan semua error-nya.
+    // Dengan --print-config, config dicetak lalu proses langsung selesai.
+    pub fn load_or_exit(&self) -> Config {
+        if self.print_config {
+            let config = Config::from_sources(self.config.as_deref()).unwrap_or_else(|e| exit_with(&e));
+            print!("{}", config.to_redacted_toml());
+            if let Err(e) = config.validate() {
+                exit_with(&e);
+            }
+            std::process::exit(0);
+        }
+
+        load_config(self.config.as_deref()).unwrap_or_else(|e| exit_with(&e))
+    }
+}
+
+fn exit_with(error: &ConfigError) -> ! {
+    eprint!("{error}");
+    std::process::exit(1);
+}
@@ -1,4 +1,5 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 // Buat fungsi helper kecil ini di atas struct
 // Fungsi ini akan memeriksa apakah tipe 'T' adalah tipe unit '()'
@@ -8,7 +9,7 @@ fn is_unit<T>(_: &T) -> bool {
 
 // Struct generik ini akan menjadi wrapper JSON standar
 // 'T' adalah tipe data generik (bisa Note, Vec<Note>, atau apapun)
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub status: String,
     pub message: String,
@@ -18,3 +19,14 @@ pub struct ApiResponse<T> {
     #[serde(skip_serializing_if = "is_unit")]
     pub data: T,
 }
+
+// Body JSON untuk semua respons error (lihat `IntoResponse for AppError`)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    // Selalu "error"
+    pub status: String,
+    pub message: String,
+    // Id request (header X-Request-Id) untuk dilaporkan ke kami
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use std::collections::BTreeMap;
+use utoipa::{IntoParams, ToSchema};
+
+// Jumlah byte awal file yang dibaca untuk menebak content type
+pub const SNIFF_BYTES: usize = 8 * 1024;
+
+// Metadata lampiran (tabel `attachments`). Isi filenya ada di blob store.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct Attachment {
+    pub id: u32,
+    pub note_id: u32,
+    pub user_id: u32,
+    pub file_name: String,
+    // Hasil sniffing isi file, bukan header dari klien
+    pub content_type: String,
+    // Ukuran dalam byte
+    pub size: i64,
+    // Lokasi objek di blob store, tidak dikirim ke klien
+    #[serde(skip_serializing, default)]
+    #[schema(ignore)]
+    pub storage_key: String,
+    pub created_at: Option<DateTime<Utc>>,
+    // --- Khusus gambar, diisi di background setelah upload ---
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+    // Tag EXIF (nama tag -> nilai yang bisa dibaca), tanpa lokasi GPS
+    #[serde(default)]
+    #[schema(value_type = Option<HashMap<String, String>>)]
+    pub exif: Option<Json<BTreeMap<String, String>>>,
+    // Ukuran thumbnail yang tersedia (sisi terpanjang, px).
+    // null = belum diproses atau bukan gambar; [] = gambar gagal dibaca.
+    #[serde(default)]
+    #[schema(value_type = Option<Vec<u32>>)]
+    pub thumbnails: Option<Json<Vec<u32>>>,
+}
+
+impl Attachment {
+    // Format yang bisa dibuatkan thumbnail
+    pub fn is_image(&self) -> bool {
+        matches!(self.content_type.as_str(), "image/png" | "image/jpeg" | "image/gif" | "image/webp")
+    }
+
+    // Key blob thumbnail, satu folder dengan file aslinya
+    pub fn thumbnail_key(&self, size: u32) -> String {
+        format!("{}.thumb-{}", self.storage_key, size)
+    }
+
+    // Thumbnail PNG untuk gambar yang bisa transparan, JPEG untuk foto
+    pub fn thumbnail_content_type(&self) -> &'static str {
+        if self.content_type == "image/jpeg" {
+            "image/jpeg"
+        } else {
+            "image/png"
+        }
+    }
+}
+
+// Hasil pemrosesan gambar yang disimpan ke baris lampiran
+#[derive(Debug, Clone, Default)]
+pub struct ImageMetadata {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub exif: Option<BTreeMap<String, String>>,
+    pub thumbnails: Vec<u32>,
+}
+
+// Query `GET /notes/:id/attachments/:aid/thumbnail?size=256`
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ThumbnailQuery {
+    /// Sisi terpanjang yang diinginkan (px). Dipakai ukuran terkecil yang
+    /// tersedia dan tidak lebih kecil dari ini; kosong = ukuran terkecil.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct NewAttachment {
+    pub note_id: u32,
+    pub user_id: u32,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub storage_key: String,
+}
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::note::Note;
+
+// Total id di semua operasi dalam satu POST /notes/bulk
+pub const MAX_BULK_IDS: usize = 500;
+
+// Satu operasi di POST /notes/bulk, berlaku untuk semua `ids`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum BulkOperation {
+    Delete {
+        ids: Vec<u32>,
+    },
+    // Field yang kosong tidak diubah
+    Update {
+        ids: Vec<u32>,
+        title: Option<String>,
+        content: Option<String>,
+    },
+    // `archived: false` = keluarkan dari arsip
+    Archive {
+        ids: Vec<u32>,
+        #[serde(default = "default_true")]
+        archived: bool,
+    },
+    // Tag di `remove` dibuang dulu, lalu tag di `add` ditambahkan
+    Tag {
+        ids: Vec<u32>,
+        #[serde(default)]
+        add: Vec<String>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+}
+
+impl BulkOperation {
+    pub fn ids(&self) -> &[u32] {
+        match self {
+            BulkOperation::Delete { ids }
+            | BulkOperation::Update { ids, .. }
+            | BulkOperation::Archive { ids, .. }
+            | BulkOperation::Tag { ids, .. } => ids,
+        }
+    }
+
+    pub fn kind(&self) -> BulkOperationKind {
+        match self {
+            BulkOperation::Delete { .. } => BulkOperationKind::Delete,
+            BulkOperation::Update { .. } => BulkOperationKind::Update,
+            BulkOperation::Archive { .. } => BulkOperationKind::Archive,
+            BulkOperation::Tag { .. } => BulkOperationKind::Tag,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkPayload {
+    pub operations: Vec<BulkOperation>,
+    // true = tiap operasi satu transaksi: jika satu id gagal, operasi itu tidak diterapkan sama sekali.
+    // false = id yang valid tetap diterapkan walau id lain gagal.
+    #[serde(default = "default_true")]
+    pub atomic: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkOperationKind {
+    Delete,
+    Update,
+    Archive,
+    Tag,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkItemStatus {
+    Applied,
+    // Catatan tidak ada atau bukan milik user
+    NotFound,
+    // Hasil perubahan tidak valid (judul kosong, tag tidak valid/terlalu banyak)
+    Invalid,
+    // Catatan diubah/dihapus request lain di tengah operasi; versi server disertakan jika masih ada
+    Conflict,
+    // Valid, tapi tidak diterapkan karena id lain di operasi atomik yang sama gagal
+    Skipped,
+}
+
+// Hasil untuk satu id
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkItemResult {
+    pub id: u32,
+    pub status: BulkItemStatus,
+    // Versi catatan setelah diterapkan (bukan untuk delete), atau versi server saat konflik
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<Note>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+// Hasil satu operasi; urutan `items` sama dengan `ids` (tanpa duplikat)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkOperationResult {
+    pub op: BulkOperationKind,
+    pub applied: usize,
+    pub failed: usize,
+    pub items: Vec<BulkItemResult>,
+}
+
+// Respons POST /notes/bulk, urutannya sama dengan `operations`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkResult {
+    pub results: Vec<BulkOperationResult>,
+}
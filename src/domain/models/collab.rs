@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+// Batas ukuran satu pesan presence (posisi kursor, nama, warna, ...)
+pub const MAX_PRESENCE_BYTES: usize = 4 * 1024;
+// Batas ukuran satu frame WebSocket di /notes/:id/collab
+pub const MAX_COLLAB_MESSAGE_BYTES: usize = 1024 * 1024;
+
+// Peserta lain di ruang kolaborasi
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CollabPeer {
+    pub peer_id: String,
+    pub user_id: u32,
+    // Presence terakhir yang dikirim peserta ini, null jika belum ada
+    #[schema(value_type = Option<Object>)]
+    pub presence: Option<Value>,
+}
+
+// Pesan teks JSON dari server. Isi dokumen dikirim sebagai frame biner
+// (pesan sync automerge), bukan lewat pesan ini.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollabServerMessage {
+    // Pesan pertama setelah tersambung
+    Welcome { peer_id: String, peers: Vec<CollabPeer> },
+    Joined { peer_id: String, user_id: u32 },
+    Presence {
+        peer_id: String,
+        user_id: u32,
+        #[schema(value_type = Object)]
+        state: Value,
+    },
+    Left { peer_id: String },
+}
+
+// Pesan teks JSON dari klien
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollabClientMessage {
+    // Bebas bentuknya, diteruskan apa adanya ke peserta lain
+    Presence {
+        #[schema(value_type = Object)]
+        state: Value,
+    },
+}
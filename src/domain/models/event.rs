@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::models::note::Note;
+
+// Jenis perubahan catatan yang dikirim ke klien lewat /events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum NoteEventKind {
+    #[serde(rename = "note.created")]
+    Created,
+    #[serde(rename = "note.updated")]
+    Updated,
+    #[serde(rename = "note.deleted")]
+    Deleted,
+}
+
+impl NoteEventKind {
+    // Dipakai sebagai nama event SSE (`event: note.created`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NoteEventKind::Created => "note.created",
+            NoteEventKind::Updated => "note.updated",
+            NoteEventKind::Deleted => "note.deleted",
+        }
+    }
+}
+
+// Satu event perubahan catatan milik satu user
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NoteEvent {
+    // Diisi oleh event bus saat publish, naik terus; dipakai sebagai Last-Event-ID
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub kind: NoteEventKind,
+    pub user_id: u32,
+    pub note_id: u32,
+    // Isi catatan setelah berubah (kosong untuk note.deleted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<Note>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl NoteEvent {
+    pub fn new(kind: NoteEventKind, user_id: u32, note_id: u32, note: Option<Note>) -> Self {
+        Self {
+            id: 0,
+            kind,
+            user_id,
+            note_id,
+            note,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+// Query untuk GET /events dan /events/ws
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventsQuery {
+    /// Lanjutkan setelah event ini (sama dengan header `Last-Event-ID`;
+    /// header diutamakan jika keduanya dikirim)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_event_id: Option<u64>,
+}
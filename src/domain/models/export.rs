@@ -0,0 +1,103 @@
+use chrono::{DateTime, Days, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::models::{note::NoteFilter, user_settings::UserSettings};
+
+// Format ekspor banyak catatan sekaligus (`GET /notes/export`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    // Satu file .md (dengan front-matter YAML) per catatan
+    #[default]
+    Zip,
+    Json,
+    Html,
+    Pdf,
+}
+
+// Format ekspor satu catatan (`GET /notes/:id/export`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteExportFormat {
+    #[default]
+    Md,
+    Json,
+    Html,
+    Pdf,
+}
+
+// Query `GET /notes/export`
+#[derive(Debug, Clone, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// `zip` (default), `json`, `html`, atau `pdf`
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Hanya catatan dengan tag ini
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Dibuat pada/sesudah tanggal ini (YYYY-MM-DD, zona waktu user)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<NaiveDate>,
+    /// Dibuat pada/sebelum tanggal ini (YYYY-MM-DD, zona waktu user)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<NaiveDate>,
+}
+
+impl ExportQuery {
+    // Ubah tanggal (inklusif, di zona waktu user) ke rentang UTC untuk repository.
+    // None jika `from` setelah `to`.
+    pub fn filter(&self, settings: &UserSettings) -> Option<NoteFilter> {
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from > to {
+                return None;
+            }
+        }
+        let tz = settings.tz();
+        Some(NoteFilter {
+            tag: self.tag.as_deref().map(|tag| tag.trim().to_lowercase()).filter(|tag| !tag.is_empty()),
+            from: self.from.map(|date| start_of_day(date, tz)),
+            until: self.to.and_then(|date| date.checked_add_days(Days::new(1))).map(|date| start_of_day(date, tz)),
+        })
+    }
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Zip => "application/zip",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Html => "text/html; charset=utf-8",
+            ExportFormat::Pdf => "application/pdf",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Zip => "zip",
+            ExportFormat::Json => "json",
+            ExportFormat::Html => "html",
+            ExportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+// Query `GET /notes/:id/export`
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NoteExportQuery {
+    /// `md` (default), `json`, `html`, atau `pdf`
+    #[serde(default)]
+    pub format: NoteExportFormat,
+}
+
+// Pukul 00:00 waktu lokal. Jika jam itu tidak ada (pergantian DST), pakai tengah malam UTC.
+fn start_of_day(date: NaiveDate, tz: Tz) -> DateTime<Utc> {
+    let midnight = date.and_time(Default::default());
+    tz.from_local_datetime(&midnight)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&midnight))
+}
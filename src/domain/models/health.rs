@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Hasil pengecekan satu dependensi
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DependencyHealth {
+    pub status: String, // "up" atau "down"
+    pub critical: bool,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// Respons GET /health/ready
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReadinessReport {
+    // "ok" = semua up, "degraded" = hanya dependensi non-kritis yang down,
+    // "unavailable" = ada dependensi kritis yang down
+    pub status: String,
+    pub checks: std::collections::BTreeMap<String, DependencyHealth>,
+}
+
+impl ReadinessReport {
+    pub fn is_ready(&self) -> bool {
+        self.status != "unavailable"
+    }
+}
+
+// Respons GET /version
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct VersionInfo {
+    pub name: String,
+    pub version: String,
+    pub git_commit: String,
+    pub build_time: String,
+}
@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+// Asal file impor (`POST /notes/import`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+    // ZIP berisi file .md/.markdown/.txt, boleh dengan front-matter YAML
+    Markdown,
+    // Ekspor Evernote (.enex)
+    Enex,
+    // Arsip Google Takeout untuk Keep (ZIP berisi Keep/*.json)
+    Keep,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStatus {
+    // Menunggu giliran di background
+    Pending,
+    Running,
+    // Selesai; sebagian item bisa saja gagal (lihat `errors`)
+    Completed,
+    // File tidak bisa dibaca sama sekali atau server berhenti di tengah jalan
+    Failed,
+}
+
+// Satu item (file/catatan) yang gagal diimpor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ImportItemError {
+    // Nama file di dalam arsip, atau judul catatan untuk .enex
+    pub item: String,
+    pub message: String,
+}
+
+// Status job impor, disimpan di memori selama 24 jam
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportJob {
+    pub id: String,
+    #[serde(skip)]
+    pub user_id: u32,
+    pub source: ImportSource,
+    pub status: ImportStatus,
+    // Jumlah item yang ditemukan di file (0 sampai file selesai dibaca)
+    pub total: usize,
+    // Item yang sudah diproses (berhasil + gagal)
+    pub processed: usize,
+    pub imported: usize,
+    pub failed: usize,
+    pub errors: Vec<ImportItemError>,
+    // Alasan job gagal total (status `failed`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ImportJob {
+    pub fn new(user_id: u32, source: ImportSource) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id,
+            source,
+            status: ImportStatus::Pending,
+            total: 0,
+            processed: 0,
+            imported: 0,
+            failed: 0,
+            errors: Vec::new(),
+            error: None,
+            created_at: Utc::now(),
+            finished_at: None,
+        }
+    }
+}
+
+// Query `POST /notes/import`
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// `markdown`, `enex`, atau `keep`; jika kosong ditebak dari nama & isi file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ImportSource>,
+}
@@ -1,3 +1,13 @@
 pub mod api_response;
+pub mod attachment;
+pub mod bulk;
+pub mod collab;
+pub mod event;
+pub mod export;
+pub mod health;
+pub mod import;
 pub mod note;
+pub mod pagination;
+pub mod sync;
 pub mod user;
+pub mod user_settings;
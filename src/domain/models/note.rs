@@ -3,8 +3,11 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 // Struct ini untuk payload 'Create Note' (data dari user)
 // `Deserialize` -> untuk mengubah JSON (request) ke struct
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate; // <-- Import
 
+use crate::domain::models::user_settings::{Localize, UserSettings};
+
 // Ini adalah Entitas Domain.
 // Atribut `FromRow` secara teknis adalah pelanggaran kecil terhadap Clean Architecture
 // karena bergantung pada `sqlx`, namun ini adalah kompromi pragmatis yang umum di Rust.
@@ -12,13 +15,91 @@ use validator::Validate; // <-- Import
 // Struct ini mewakili data di tabel `notes`
 // `FromRow` -> untuk memetakan hasil query DB ke struct
 // `Serialize` -> untuk mengubah struct ke JSON (respons)
-#[derive(Debug, FromRow, Serialize)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
 pub struct Note {
     pub id: u32,
     pub user_id: u32,
     pub title: String,
     pub content: Option<String>,
+    // Cara membaca `content`: teks biasa atau Markdown
+    #[sqlx(try_from = "String")]
+    #[serde(default)]
+    pub content_format: ContentFormat,
+    // Label catatan (huruf kecil, unik), disimpan sebagai array JSON
+    #[sqlx(json)]
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Diarsipkan lewat POST /notes/bulk
+    #[serde(default)]
+    pub archived: bool,
     pub created_at: Option<DateTime<Utc>>,
+    // Waktu perubahan terakhir (kosong jika belum pernah diubah)
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    // Nomor urut perubahan terakhir milik user; dipakai /sync untuk delta & deteksi konflik
+    #[serde(default)]
+    pub change_seq: i64,
+    // Bentuk lokal dari 'created_at' sesuai preferensi user (bukan kolom DB)
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_local: Option<String>,
+    // `content` dalam bentuk HTML yang sudah disanitasi, hanya untuk ?render=html (bukan kolom DB)
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_html: Option<String>,
+}
+
+impl Localize for Note {
+    fn localize(&mut self, settings: &UserSettings) {
+        self.created_at_local = self.created_at.as_ref().map(|dt| settings.format_local(dt));
+    }
+}
+
+// Format isi catatan (kolom `content_format`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    #[default]
+    Plain,
+    // CommonMark + ekstensi GFM (tabel, task list, strikethrough, ...)
+    Markdown,
+}
+
+impl ContentFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentFormat::Plain => "plain",
+            ContentFormat::Markdown => "markdown",
+        }
+    }
+}
+
+impl TryFrom<String> for ContentFormat {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "plain" => Ok(ContentFormat::Plain),
+            "markdown" => Ok(ContentFormat::Markdown),
+            _ => Err(format!("content_format tidak dikenal: {}", value)),
+        }
+    }
+}
+
+// Bentuk render isi catatan di `GET /notes/:id?render=html`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderMode {
+    Html,
+}
+
+// Query `GET /notes/:id?render=html`
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NoteQuery {
+    /// `html` = sertakan `content_html` (Markdown dirender & disanitasi di server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub render: Option<RenderMode>,
 }
 
 // Struct ini untuk data note baru yang akan disimpan ke DB
@@ -28,19 +109,103 @@ pub struct NewNote {
     pub user_id: u32,
     pub title: String,
     pub content: Option<String>,
+    #[serde(default)]
+    pub content_format: ContentFormat,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Diisi saat restore/impor agar waktu asli tetap terjaga.
+    // None = pakai waktu sekarang dari database.
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    // Waktu perubahan terakhir di aplikasi asal (impor); None = belum pernah diubah
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Deserialize, Validate)] // <-- Tambahkan Validate
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)] // <-- Tambahkan Validate
 pub struct CreateNotePayload {
     pub title: String,
     pub content: Option<String>,
+    // Default `plain`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_format: Option<ContentFormat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
 }
 
 // Struct ini untuk payload 'Update Note'
 // gunakan Option<> karena user mungkin hanya ingin
 // update judulnya saja atau kontennya saja.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
 pub struct UpdateNotePayload {
     pub title: Option<String>,
     pub content: Option<String>,
-}
\ No newline at end of file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_format: Option<ContentFormat>,
+    // Mengganti seluruh daftar tag; `[]` = hapus semua tag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+// Isi baru satu catatan untuk `NoteRepository::update_many`, dihitung dari versi
+// yang dibaca sebelumnya. Hanya disimpan jika change_seq masih `expected_seq`.
+#[derive(Debug, Clone)]
+pub struct NoteUpdate {
+    pub id: u32,
+    pub expected_seq: i64,
+    pub title: String,
+    pub content: Option<String>,
+    pub tags: Vec<String>,
+    pub archived: bool,
+}
+
+impl NoteUpdate {
+    pub fn from_note(note: &Note) -> Self {
+        Self {
+            id: note.id,
+            expected_seq: note.change_seq,
+            title: note.title.clone(),
+            content: note.content.clone(),
+            tags: note.tags.clone(),
+            archived: note.archived,
+        }
+    }
+}
+
+pub const MAX_TAGS_PER_NOTE: usize = 20;
+pub const MAX_TAG_LEN: usize = 50;
+
+// Rapikan tag dari klien: trim, huruf kecil, buang duplikat (urutan pertama dipertahankan).
+// None jika ada tag kosong/terlalu panjang atau jumlahnya lebih dari MAX_TAGS_PER_NOTE.
+pub fn normalize_tags(tags: Vec<String>) -> Option<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || tag.chars().count() > MAX_TAG_LEN {
+            return None;
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    (normalized.len() <= MAX_TAGS_PER_NOTE).then_some(normalized)
+}
+
+// Filter untuk `NoteRepository::find_filtered` (dipakai ekspor)
+#[derive(Debug, Clone, Default)]
+pub struct NoteFilter {
+    // Hanya catatan yang punya tag ini (sudah dinormalisasi)
+    pub tag: Option<String>,
+    // created_at >= from
+    pub from: Option<DateTime<Utc>>,
+    // created_at < until
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl NoteFilter {
+    pub fn matches(&self, note: &Note) -> bool {
+        self.tag.as_ref().is_none_or(|tag| note.tags.contains(tag))
+            && self.from.is_none_or(|from| note.created_at.is_some_and(|at| at >= from))
+            && self.until.is_none_or(|until| note.created_at.is_some_and(|at| at < until))
+    }
+}
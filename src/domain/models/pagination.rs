@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+pub const DEFAULT_PER_PAGE: u32 = 20;
+pub const MAX_PER_PAGE: u32 = 100;
+
+// Query `?page=2&per_page=20` untuk endpoint daftar.
+// Tanpa keduanya semua data dikembalikan sekaligus (perilaku lama).
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    /// Nomor halaman, mulai dari 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    /// Jumlah item per halaman (default 20, maksimal 100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<u32>,
+}
+
+impl PageQuery {
+    pub fn new(page: u32, per_page: u32) -> Self {
+        Self { page: Some(page), per_page: Some(per_page) }
+    }
+
+    // (limit, offset) untuk query SQL, None = ambil semua
+    pub fn limit_offset(&self) -> Option<(u32, u32)> {
+        if self.page.is_none() && self.per_page.is_none() {
+            return None;
+        }
+        let per_page = self.per_page.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE);
+        let page = self.page.unwrap_or(1).max(1);
+        Some((per_page, (page - 1).saturating_mul(per_page)))
+    }
+}
@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::models::note::{ContentFormat, Note};
+
+pub const DEFAULT_SYNC_LIMIT: u32 = 500;
+pub const MAX_SYNC_LIMIT: u32 = 1000;
+// Batas jumlah perubahan dalam satu POST /sync
+pub const MAX_SYNC_PUSH: usize = 500;
+
+// Query `GET /sync?since=<token>&limit=500`
+#[derive(Debug, Clone, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SyncQuery {
+    /// Token dari respons /sync sebelumnya; kosong = sinkronisasi penuh
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// Jumlah perubahan maksimum per respons (default 500, maksimal 1000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl SyncQuery {
+    pub fn limit(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_SYNC_LIMIT).clamp(1, MAX_SYNC_LIMIT)
+    }
+}
+
+// Token perubahan yang dikirim ke klien. Isinya change_seq terakhir yang sudah
+// dikirim, tapi bagi klien token ini harus dianggap opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncToken(pub i64);
+
+impl SyncToken {
+    pub fn parse(token: &str) -> Option<Self> {
+        token.parse().ok().filter(|seq: &i64| *seq >= 0).map(SyncToken)
+    }
+}
+
+impl std::fmt::Display for SyncToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// Jejak catatan yang sudah dihapus (tabel `note_tombstones`)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct Tombstone {
+    pub note_id: u32,
+    pub user_id: u32,
+    pub change_seq: i64,
+    pub deleted_at: DateTime<Utc>,
+}
+
+// Hasil `NoteRepository::changes_since`, urut naik per change_seq
+#[derive(Debug, Default)]
+pub struct NoteChanges {
+    pub notes: Vec<Note>,
+    pub tombstones: Vec<Tombstone>,
+    // Masih ada perubahan setelah batas `limit`
+    pub has_more: bool,
+}
+
+impl NoteChanges {
+    // Gabungkan hasil dua query (masing-masing urut naik, maksimal limit + 1 baris)
+    // dan ambil `limit` perubahan dengan change_seq terkecil
+    pub fn merge(mut notes: Vec<Note>, mut tombstones: Vec<Tombstone>, limit: u32) -> Self {
+        let limit = limit as usize;
+        let has_more = notes.len() + tombstones.len() > limit;
+        if has_more {
+            let mut seqs: Vec<i64> = notes
+                .iter()
+                .map(|note| note.change_seq)
+                .chain(tombstones.iter().map(|tombstone| tombstone.change_seq))
+                .collect();
+            seqs.sort_unstable();
+            let last_seq = seqs[limit - 1];
+            notes.retain(|note| note.change_seq <= last_seq);
+            tombstones.retain(|tombstone| tombstone.change_seq <= last_seq);
+        }
+        Self { notes, tombstones, has_more }
+    }
+
+    // change_seq terbesar yang ada di hasil ini
+    pub fn last_seq(&self) -> Option<i64> {
+        let notes = self.notes.iter().map(|note| note.change_seq);
+        notes.chain(self.tombstones.iter().map(|tombstone| tombstone.change_seq)).max()
+    }
+}
+
+// Respons GET /sync
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncPull {
+    // Kirim sebagai `since` pada sinkronisasi berikutnya
+    pub token: String,
+    // Catatan yang dibuat atau diubah (versi terbaru)
+    pub notes: Vec<Note>,
+    // Catatan yang dihapus
+    pub deleted: Vec<Tombstone>,
+    // true = panggil lagi dengan token baru untuk sisa perubahan
+    pub has_more: bool,
+}
+
+// Satu perubahan dari klien di POST /sync
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum SyncChange {
+    // `client_id` adalah id sementara dari klien, dikembalikan di hasil
+    // supaya klien bisa memetakan ke id dari server
+    Create {
+        client_id: String,
+        title: String,
+        content: Option<String>,
+        #[serde(default)]
+        content_format: Option<ContentFormat>,
+        #[serde(default)]
+        tags: Option<Vec<String>>,
+    },
+    // `base_seq` = change_seq catatan saat terakhir disinkronkan klien
+    Update {
+        id: u32,
+        base_seq: i64,
+        title: Option<String>,
+        content: Option<String>,
+        #[serde(default)]
+        content_format: Option<ContentFormat>,
+        #[serde(default)]
+        tags: Option<Vec<String>>,
+    },
+    // Tanpa `base_seq` catatan dihapus walau sudah diubah di tempat lain
+    Delete {
+        id: u32,
+        base_seq: Option<i64>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncPushPayload {
+    pub changes: Vec<SyncChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    Applied,
+    // Catatan sudah diubah di tempat lain sejak `base_seq`; versi server disertakan
+    Conflict,
+    // Catatan tidak ada (mungkin sudah dihapus, lihat `deleted` di GET /sync)
+    NotFound,
+    Invalid,
+}
+
+// Hasil per perubahan, urutannya sama dengan `changes`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncResult {
+    pub status: SyncStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    // Versi catatan di server setelah diterapkan, atau versi server saat konflik
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<Note>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+// Respons POST /sync
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncPushResult {
+    pub results: Vec<SyncResult>,
+}
@@ -1,11 +1,15 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::fmt;
+use utoipa::ToSchema;
+
+use crate::domain::models::user_settings::{Localize, UserSettings};
+use crate::utils::redact::REDACTED;
 
 // Enum untuk Role, agar lebih aman dan terstruktur
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
-#[allow(dead_code)]
 pub enum Role {
     User,
     Admin,
@@ -23,20 +27,45 @@ impl AsRef<str> for Role {
 
 // Entitas Domain 'User'
 // Mewakili tabel 'users' di database
-#[derive(FromRow, Debug, Serialize)]
+#[derive(FromRow, Serialize, Deserialize, ToSchema)]
 pub struct User {
     pub id: u32,
     pub email: String,
     pub full_name: String,
     pub username: String,
     pub role: String,
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing, default)]
     pub password_hash: String,
     pub created_at: DateTime<Utc>,
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_local: Option<String>,
+}
+
+// Debug manual supaya hash password tidak pernah ikut tercetak di log
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("User")
+            .field("id", &self.id)
+            .field("email", &self.email)
+            .field("full_name", &self.full_name)
+            .field("username", &self.username)
+            .field("role", &self.role)
+            .field("password_hash", &REDACTED)
+            .field("created_at", &self.created_at)
+            .finish()
+    }
+}
+
+impl Localize for User {
+    fn localize(&mut self, settings: &UserSettings) {
+        self.created_at_local = Some(settings.format_local(&self.created_at));
+    }
 }
 
-// Payload untuk registrasi dan login
-#[derive(Deserialize, Debug)]
+// Payload untuk registrasi dan login.
+// Debug ditulis manual agar password tersamarkan jika payload di-log.
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct RegisterPayload {
     pub email: String,
     pub full_name: String,
@@ -44,16 +73,36 @@ pub struct RegisterPayload {
     pub password: String,
 }
 
-#[derive(Deserialize, Debug)]
+impl fmt::Debug for RegisterPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisterPayload")
+            .field("email", &self.email)
+            .field("full_name", &self.full_name)
+            .field("username", &self.username)
+            .field("password", &REDACTED)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct LoginPayload {
     pub email: String,
     pub password: String,
 }
 
+impl fmt::Debug for LoginPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoginPayload")
+            .field("email", &self.email)
+            .field("password", &REDACTED)
+            .finish()
+    }
+}
+
 // DTO (Data Transfer Object) untuk profil, bukan entitas murni
 // Struct untuk data profil yang akan dikirim ke klien
 // Perhatikan tidak ada password_hash di sini.
-#[derive(FromRow, Debug, Serialize)]
+#[derive(FromRow, Debug, Serialize, Deserialize, ToSchema)]
 pub struct UserProfile {
     pub id: u32,
     pub email: String,
@@ -61,6 +110,15 @@ pub struct UserProfile {
     pub username: String,
     pub role: String,
     pub created_at: DateTime<Utc>,
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_local: Option<String>,
+}
+
+impl Localize for UserProfile {
+    fn localize(&mut self, settings: &UserSettings) {
+        self.created_at_local = Some(settings.format_local(&self.created_at));
+    }
 }
 
 // Struct untuk data di dalam token JWT
@@ -71,9 +129,32 @@ pub struct TokenClaims {
     pub exp: i64,
 }
 
+// Hash SHA-256 dari access token yang sedang dipakai.
+// Disimpan di request extensions oleh auth_middleware untuk keperluan logout.
+#[derive(Debug, Clone)]
+pub struct TokenHash(pub String);
+
 // Struct untuk response token JWT
-#[derive(Debug, Serialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct TokenResponse {
     pub access_token: String,
-    pub expires_at: String,
+    // Selalu RFC 3339 UTC
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_local: Option<String>,
+}
+
+impl fmt::Debug for TokenResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenResponse")
+            .field("access_token", &REDACTED)
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+impl Localize for TokenResponse {
+    fn localize(&mut self, settings: &UserSettings) {
+        self.expires_at_local = Some(settings.format_local(&self.expires_at));
+    }
 }
\ No newline at end of file
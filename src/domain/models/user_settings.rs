@@ -0,0 +1,90 @@
+use chrono::{format::{Item, StrftimeItems}, DateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+use crate::utils::i18n::Locale;
+
+pub const DEFAULT_TIMEZONE: &str = "Asia/Jakarta";
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %Z";
+
+// Entitas Domain 'UserSettings'
+// Mewakili tabel 'user_settings' (satu baris per user)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct UserSettings {
+    #[serde(skip_serializing, default)]
+    #[schema(ignore)]
+    pub user_id: u32,
+    // Nama zona waktu IANA, misal "Asia/Makassar"
+    pub timezone: String,
+    // None = ikuti header Accept-Language
+    pub locale: Option<String>,
+    // Format strftime untuk bentuk waktu lokal
+    pub date_format: String,
+}
+
+impl UserSettings {
+    // Pengaturan default untuk user yang belum pernah menyimpan preferensi
+    pub fn default_for(user_id: u32) -> Self {
+        Self {
+            user_id,
+            timezone: DEFAULT_TIMEZONE.to_string(),
+            locale: None,
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+        }
+    }
+
+    pub fn preferred_locale(&self) -> Option<Locale> {
+        self.locale.as_deref().and_then(Locale::from_tag)
+    }
+
+    // Zona waktu user; data yang tidak valid di DB tidak boleh membuat panic, jadi pakai default
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(chrono_tz::Asia::Jakarta)
+    }
+
+    // Render waktu UTC ke zona waktu & format milik user.
+    // Data yang tidak valid di DB tidak boleh membuat panic, jadi pakai default.
+    pub fn format_local(&self, dt: &DateTime<Utc>) -> String {
+        let tz = self.tz();
+        let fmt = if is_valid_date_format(&self.date_format) {
+            self.date_format.as_str()
+        } else {
+            DEFAULT_DATE_FORMAT
+        };
+        dt.with_timezone(&tz).format(fmt).to_string()
+    }
+}
+
+// Payload untuk 'PATCH /auth/settings'
+// Semua field opsional, hanya field yang dikirim yang diubah.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct UpdateSettingsPayload {
+    pub timezone: Option<String>,
+    pub locale: Option<String>,
+    pub date_format: Option<String>,
+}
+
+pub fn is_valid_timezone(timezone: &str) -> bool {
+    timezone.parse::<Tz>().is_ok()
+}
+
+// Format strftime yang tidak valid akan panic saat di-Display oleh chrono,
+// jadi harus dicek dulu sebelum disimpan.
+pub fn is_valid_date_format(format: &str) -> bool {
+    !format.trim().is_empty() && StrftimeItems::new(format).all(|item| item != Item::Error)
+}
+
+// Trait untuk mengisi field waktu lokal (`*_local`) pada DTO respons
+pub trait Localize {
+    fn localize(&mut self, settings: &UserSettings);
+}
+
+impl<T: Localize> Localize for Vec<T> {
+    fn localize(&mut self, settings: &UserSettings) {
+        for item in self.iter_mut() {
+            item.localize(settings);
+        }
+    }
+}
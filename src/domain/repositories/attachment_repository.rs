@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::{
+    domain::models::attachment::{Attachment, ImageMetadata, NewAttachment},
+    utils::error::AppResult,
+};
+
+// Kontrak untuk metadata lampiran. Semua query dibatasi `user_id` pemiliknya;
+// isi file diurus terpisah oleh BlobStore.
+#[async_trait]
+pub trait AttachmentRepository: Send + Sync {
+    async fn create(&self, new_attachment: &NewAttachment) -> AppResult<Attachment>;
+    // Lampiran satu catatan, urut dari yang paling lama
+    async fn find_by_note(&self, note_id: u32, user_id: u32) -> AppResult<Vec<Attachment>>;
+    async fn find_by_id(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<Option<Attachment>>;
+    async fn delete(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<u64>;
+    // Hapus semua lampiran catatan (dipanggil setelah catatannya dihapus).
+    // Di database baris ini sudah ikut terhapus lewat ON DELETE CASCADE.
+    async fn delete_by_note(&self, note_id: u32, user_id: u32) -> AppResult<u64>;
+    // Simpan hasil pemrosesan gambar (ukuran, EXIF, thumbnail yang tersedia)
+    async fn set_image_metadata(&self, id: u32, metadata: &ImageMetadata) -> AppResult<()>;
+    // Total ukuran semua lampiran milik user, untuk kuota
+    async fn total_size(&self, user_id: u32) -> AppResult<i64>;
+}
+
+pub type DynAttachmentRepository = Arc<dyn AttachmentRepository>;
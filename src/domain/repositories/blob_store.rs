@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::Stream;
+use std::{io, pin::Pin, sync::Arc};
+
+use crate::utils::error::AppResult;
+
+// Isi file yang dialirkan potong per potong, tidak pernah dimuat utuh ke memori.
+// Lifetime dipakai saat upload: stream field multipart meminjam body request.
+pub type BlobStream<'a> = Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send + 'a>>;
+
+// Port untuk menyimpan isi file lampiran. Key berbentuk path relatif
+// dengan pemisah `/` (misal `notes/1/2/<uuid>`).
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    // Simpan seluruh stream ke `key`, mengembalikan jumlah byte yang ditulis.
+    // Jika stream error di tengah jalan, tidak ada objek yang tersisa di `key`.
+    async fn put(&self, key: &str, data: BlobStream<'_>) -> AppResult<u64>;
+    // None jika objeknya tidak ada
+    async fn get(&self, key: &str) -> AppResult<Option<BlobStream<'static>>>;
+    // Objek yang tidak ada dianggap sudah terhapus
+    async fn delete(&self, key: &str) -> AppResult<()>;
+    // Hapus semua objek yang key-nya diawali `prefix` (diakhiri `/`)
+    async fn delete_prefix(&self, prefix: &str) -> AppResult<()>;
+}
+
+pub type DynBlobStore = Arc<dyn BlobStore>;
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::{domain::models::event::NoteEvent, utils::error::AppResult};
+
+// Port untuk menyebarkan event perubahan catatan ke semua koneksi /events.
+// Backend Redis meneruskan event ke semua instance server lewat pub/sub,
+// jadi klien tetap menerima event walau perubahannya terjadi di instance lain.
+#[async_trait]
+pub trait EventBus: Send + Sync {
+    // Kirim event ke semua subscriber; id event diisi di sini dan dikembalikan
+    async fn publish(&self, event: NoteEvent) -> AppResult<u64>;
+    // Event baru dari semua user (disaring per user oleh pemanggil)
+    fn subscribe(&self) -> broadcast::Receiver<NoteEvent>;
+    // Event milik user dengan id > last_id, urut naik. Untuk resume via Last-Event-ID;
+    // hanya sebagian riwayat terakhir yang disimpan.
+    async fn replay(&self, user_id: u32, last_id: u64) -> AppResult<Vec<NoteEvent>>;
+    // Tutup koneksi ke backend saat server berhenti (default: tidak ada yang ditutup)
+    async fn close(&self) {}
+}
+
+pub type DynEventBus = Arc<dyn EventBus>;
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::utils::error::AppResult;
+
+// Dependensi eksternal yang bisa di-ping untuk readiness probe
+// (database, Redis, dll). Implementasinya ada di infrastructure/health.
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    // Nama dependensi di respons /health/ready, misal "database"
+    fn name(&self) -> &'static str;
+
+    // Jika dependensi kritis mati, service dianggap tidak siap (503)
+    fn is_critical(&self) -> bool {
+        true
+    }
+
+    async fn ping(&self) -> AppResult<()>;
+}
+
+pub type DynHealthCheck = Arc<dyn HealthCheck>;
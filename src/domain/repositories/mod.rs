@@ -1,2 +1,7 @@
+pub mod attachment_repository;
+pub mod blob_store;
+pub mod event_bus;
+pub mod health_check;
 pub mod note_repository;
-pub mod user_repository;
\ No newline at end of file
+pub mod token_revocation_store;
+pub mod user_repository;
@@ -2,18 +2,51 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 use crate::{
-    domain::models::note::{NewNote, Note, UpdateNotePayload},
+    domain::models::{
+        note::{NewNote, Note, NoteFilter, NoteUpdate, UpdateNotePayload},
+        sync::NoteChanges,
+    },
     utils::error::AppResult,
 };
 
 // Trait ini mendefinisikan kontrak untuk operasi data Note.
+// Setiap create/update/delete menaikkan change_seq milik user, dan delete
+// meninggalkan tombstone, supaya perubahan bisa diambil lewat `changes_since`.
 #[async_trait]
 pub trait NoteRepository: Send + Sync {
     async fn create(&self, new_note: &NewNote) -> AppResult<Note>;
+    // Simpan banyak catatan dengan satu INSERT multi-baris dalam satu transaksi
+    // (semua tersimpan atau tidak sama sekali). Hasil urut sesuai `new_notes`.
+    async fn create_many(&self, new_notes: &[NewNote]) -> AppResult<Vec<Note>>;
     async fn find_all(&self, user_id: u32) -> AppResult<Vec<Note>>;
+    // Satu halaman dari `find_all` (urutan sama)
+    async fn find_page(&self, user_id: u32, limit: u32, offset: u32) -> AppResult<Vec<Note>>;
+    // Catatan yang cocok dengan `filter` dan id > after_id, urut id naik.
+    // Untuk membaca banyak catatan per halaman tanpa OFFSET (ekspor).
+    async fn find_filtered(&self, user_id: u32, filter: &NoteFilter, after_id: u32, limit: u32)
+        -> AppResult<Vec<Note>>;
     async fn find_by_id(&self, id: u32, user_id: u32) -> AppResult<Option<Note>>;
-    async fn update(&self, id: u32, user_id: u32, payload: &UpdateNotePayload) -> AppResult<Option<Note>>;
-    async fn delete(&self, id: u32, user_id: u32) -> AppResult<u64>;
+    // Catatan milik user dengan id di `ids`, urut id naik; id yang tidak ada dilewati
+    async fn find_by_ids(&self, ids: &[u32], user_id: u32) -> AppResult<Vec<Note>>;
+    // `expected_seq` = hanya ubah/hapus jika change_seq catatan masih sama (untuk /sync).
+    // None jika catatan tidak ada atau change_seq-nya berbeda.
+    async fn update(
+        &self,
+        id: u32,
+        user_id: u32,
+        payload: &UpdateNotePayload,
+        expected_seq: Option<i64>,
+    ) -> AppResult<Option<Note>>;
+    async fn delete(&self, id: u32, user_id: u32, expected_seq: Option<i64>) -> AppResult<u64>;
+    // Versi banyak baris dari `update`/`delete` (UPDATE/DELETE multi-baris dalam satu transaksi).
+    // Hanya baris yang change_seq-nya masih sama yang diubah/dihapus (`expected` = pasangan
+    // id & change_seq). Hasilnya hanya catatan/id yang benar-benar berubah, urut id naik.
+    // Dengan `all_or_nothing`, jika ada satu saja yang tidak cocok (tidak ada / change_seq
+    // berbeda) semuanya di-rollback dan hasilnya kosong.
+    async fn update_many(&self, user_id: u32, updates: &[NoteUpdate], all_or_nothing: bool) -> AppResult<Vec<Note>>;
+    async fn delete_many(&self, user_id: u32, expected: &[(u32, i64)], all_or_nothing: bool) -> AppResult<Vec<u32>>;
+    // Catatan & tombstone dengan change_seq > since, maksimal `limit` item, urut naik
+    async fn changes_since(&self, user_id: u32, since: i64, limit: u32) -> AppResult<NoteChanges>;
 }
 
 // Tipe alias untuk Arc<dyn NoteRepository> agar lebih mudah digunakan.
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use crate::utils::error::AppResult;
+
+// Port untuk menyimpan token yang sudah di-revoke (logout).
+// Middleware auth dan handler logout hanya bergantung pada trait ini.
+// Semua method menerima hash token (lihat `hash_token`), bukan JWT mentah,
+// supaya token asli tidak pernah tersimpan di backend.
+#[async_trait]
+pub trait TokenRevocationStore: Send + Sync {
+    // Tandai token sebagai revoked sampai token itu kedaluwarsa (ttl_secs)
+    async fn revoke(&self, token_hash: &str, ttl_secs: u64) -> AppResult<()>;
+    async fn is_revoked(&self, token_hash: &str) -> AppResult<bool>;
+    // Hapus semua entri, mengembalikan jumlah yang dihapus
+    async fn purge(&self) -> AppResult<usize>;
+    // Tutup koneksi ke backend saat server berhenti (default: tidak ada yang ditutup)
+    async fn close(&self) {}
+}
+
+pub type DynTokenRevocationStore = Arc<dyn TokenRevocationStore>;
+
+// Id token yang disimpan: SHA-256 (hex) dari JWT
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
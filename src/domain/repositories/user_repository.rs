@@ -1,7 +1,10 @@
 use async_trait::async_trait;
 use std::sync::Arc;
 
-use crate::domain::models::user::{User, UserProfile};
+use crate::domain::models::{
+    user::{User, UserProfile},
+    user_settings::UserSettings,
+};
 use crate::utils::error::AppResult;
 
 // Trait ini adalah "port" dalam arsitektur heksagonal.
@@ -13,7 +16,12 @@ pub trait UserRepository: Send + Sync {
     async fn find_by_id(&self, id: u32) -> AppResult<Option<User>>;
     async fn find_profile_by_id(&self, id: u32) -> AppResult<Option<UserProfile>>;
     async fn get_all_profiles(&self) -> AppResult<Vec<UserProfile>>;
+    async fn get_profiles_page(&self, limit: u32, offset: u32) -> AppResult<Vec<UserProfile>>;
     async fn create(&self, payload: &User) -> AppResult<User>;
+    async fn update_role(&self, id: u32, role: &str) -> AppResult<u64>;
+    async fn update_password(&self, id: u32, password_hash: &str) -> AppResult<u64>;
+    async fn find_settings(&self, user_id: u32) -> AppResult<Option<UserSettings>>;
+    async fn upsert_settings(&self, settings: &UserSettings) -> AppResult<UserSettings>;
 }
 
 pub type DynUserRepository = Arc<dyn UserRepository>;
\ No newline at end of file
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Mutex,
+};
+use tokio::sync::broadcast;
+
+use crate::{
+    domain::{models::event::NoteEvent, repositories::event_bus::EventBus},
+    utils::error::AppResult,
+};
+
+// Kapasitas channel broadcast; subscriber yang tertinggal lebih jauh dari ini
+// menerima `Lagged` dan mengambil ulang event yang terlewat lewat `replay`.
+pub const BROADCAST_CAPACITY: usize = 1024;
+
+// Event bus di memori proses: cocok untuk satu instance / development / test.
+// Riwayat per user dibatasi `history_capacity` event terakhir.
+pub struct InMemoryEventBus {
+    sender: broadcast::Sender<NoteEvent>,
+    history: Mutex<HashMap<u32, VecDeque<NoteEvent>>>,
+    history_capacity: usize,
+    // Dimulai dari waktu start (ms) supaya id tetap naik setelah server restart,
+    // jadi Last-Event-ID lama dari klien tidak menutupi event baru.
+    next_id: AtomicU64,
+}
+
+impl InMemoryEventBus {
+    pub fn new(history_capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Self {
+            sender,
+            history: Mutex::new(HashMap::new()),
+            history_capacity,
+            next_id: AtomicU64::new(Utc::now().timestamp_millis().max(0) as u64),
+        }
+    }
+}
+
+impl Default for InMemoryEventBus {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+#[async_trait]
+impl EventBus for InMemoryEventBus {
+    async fn publish(&self, mut event: NoteEvent) -> AppResult<u64> {
+        // Id diambil, disimpan, dan dikirim di dalam lock supaya riwayat maupun
+        // urutan yang diterima subscriber tetap urut naik
+        let mut history = self.history.lock().unwrap();
+        event.id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let events = history.entry(event.user_id).or_default();
+        events.push_back(event.clone());
+        while events.len() > self.history_capacity {
+            events.pop_front();
+        }
+        let id = event.id;
+        // Error hanya berarti belum ada subscriber
+        let _ = self.sender.send(event);
+        Ok(id)
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<NoteEvent> {
+        self.sender.subscribe()
+    }
+
+    async fn replay(&self, user_id: u32, last_id: u64) -> AppResult<Vec<NoteEvent>> {
+        let history = self.history.lock().unwrap();
+        Ok(history
+            .get(&user_id)
+            .map(|events| events.iter().filter(|e| e.id > last_id).cloned().collect())
+            .unwrap_or_default())
+    }
+}
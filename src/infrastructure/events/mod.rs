@@ -0,0 +1,34 @@
+pub mod in_memory_bus;
+pub mod redis_bus;
+
+use std::sync::Arc;
+
+use crate::{
+    domain::repositories::event_bus::DynEventBus,
+    utils::{
+        background::BackgroundTasks,
+        config::{Config, EventBusBackend},
+        db,
+    },
+};
+
+use in_memory_bus::InMemoryEventBus;
+use redis_bus::RedisEventBus;
+
+// Pilih event bus sesuai EVENT_BUS_BACKEND. Listener Redis ikut berhenti
+// bersama background task lain saat shutdown.
+pub fn create_event_bus(config: &Config, background: &BackgroundTasks) -> redis::RedisResult<DynEventBus> {
+    let bus: DynEventBus = match config.event_bus_backend {
+        EventBusBackend::Redis => {
+            let bus = RedisEventBus::new(
+                db::create_redis_client(config)?,
+                config.event_history_capacity,
+                config.event_history_ttl,
+            );
+            bus.start_listener(background);
+            Arc::new(bus)
+        }
+        EventBusBackend::Memory => Arc::new(InMemoryEventBus::new(config.event_history_capacity)),
+    };
+    Ok(bus)
+}
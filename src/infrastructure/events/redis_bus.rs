@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use redis::{aio::ConnectionManager, AsyncCommands, Client as RedisClient, Script};
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    domain::{models::event::NoteEvent, repositories::event_bus::EventBus},
+    infrastructure::events::in_memory_bus::BROADCAST_CAPACITY,
+    utils::{background::BackgroundTasks, db, error::AppResult},
+};
+
+// Channel pub/sub yang didengarkan semua instance server
+pub const CHANNEL: &str = "events:notes";
+// Counter id event, dipakai bersama semua instance supaya Last-Event-ID
+// berlaku di instance mana pun klien tersambung ulang
+const SEQUENCE_KEY: &str = "events:seq";
+// Riwayat event per user (LIST, terbaru di depan)
+const HISTORY_PREFIX: &str = "events:user:";
+
+// Jeda sebelum mencoba subscribe ulang setelah koneksi pub/sub putus
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+// Ambil id, simpan ke riwayat, dan PUBLISH dalam satu script. Redis menjalankan
+// script satu per satu, jadi urutan PUBLISH selalu sama dengan urutan id; jika
+// INCR dan PUBLISH terpisah, event 6 bisa terkirim sebelum 5 dan resume lewat
+// Last-Event-ID melewatkan event 5.
+// KEYS: counter id, riwayat user. ARGV: JSON event tanpa awalan `{"id":0`,
+// kapasitas riwayat, TTL riwayat (detik), channel.
+const PUBLISH_SCRIPT: &str = r#"
+local id = redis.call('INCR', KEYS[1])
+local payload = '{"id":' .. string.format('%d', id) .. ARGV[1]
+redis.call('LPUSH', KEYS[2], payload)
+redis.call('LTRIM', KEYS[2], 0, tonumber(ARGV[2]) - 1)
+redis.call('EXPIRE', KEYS[2], ARGV[3])
+redis.call('PUBLISH', ARGV[4], payload)
+return id
+"#;
+
+fn history_key(user_id: u32) -> String {
+    format!("{}{}", HISTORY_PREFIX, user_id)
+}
+
+// Event bus lewat Redis pub/sub: setiap instance mem-PUBLISH event ke CHANNEL,
+// dan listener di tiap instance meneruskan pesan dari CHANNEL ke subscriber lokal.
+// Event dari instance sendiri juga datang lewat Redis, jadi urutan di semua
+// instance sama, dan id-nya selalu naik (lihat PUBLISH_SCRIPT).
+pub struct RedisEventBus {
+    redis_client: RedisClient,
+    publish_script: Script,
+    // Lihat RedisTokenRevocationStore: dibuat saat pertama dipakai, dilepas di `close()`
+    manager: Mutex<Option<ConnectionManager>>,
+    sender: broadcast::Sender<NoteEvent>,
+    history_capacity: usize,
+    history_ttl: Duration,
+}
+
+impl RedisEventBus {
+    pub fn new(redis_client: RedisClient, history_capacity: usize, history_ttl: Duration) -> Self {
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Self {
+            redis_client,
+            publish_script: Script::new(PUBLISH_SCRIPT),
+            manager: Mutex::new(None),
+            sender,
+            history_capacity,
+            history_ttl,
+        }
+    }
+
+    // Jalankan listener pub/sub sebagai background task; berhenti saat shutdown
+    pub fn start_listener(&self, background: &BackgroundTasks) {
+        let client = self.redis_client.clone();
+        let sender = self.sender.clone();
+        background.spawn(move |token| listen(client, sender, token));
+    }
+
+    async fn connection(&self) -> AppResult<ConnectionManager> {
+        if let Some(manager) = self.manager.lock().await.as_ref() {
+            return Ok(manager.clone());
+        }
+        // Sama seperti RedisTokenRevocationStore: sambung di luar lock dengan batas waktu
+        let connected = db::connect_redis(&self.redis_client).await?;
+        Ok(self.manager.lock().await.get_or_insert(connected).clone())
+    }
+}
+
+#[async_trait]
+impl EventBus for RedisEventBus {
+    #[tracing::instrument(name = "redis.EVALSHA", skip_all, fields(otel.kind = "client", db.system = "redis"))]
+    async fn publish(&self, mut event: NoteEvent) -> AppResult<u64> {
+        let mut conn = self.connection().await?;
+
+        // `id` field pertama di JSON; script menggantinya dengan id dari INCR
+        event.id = 0;
+        let payload = serde_json::to_string(&event).expect("event selalu bisa diserialisasi");
+        let rest = payload.strip_prefix(r#"{"id":0"#).expect("id selalu field pertama");
+        let id: u64 = self
+            .publish_script
+            .key(SEQUENCE_KEY)
+            .key(history_key(event.user_id))
+            .arg(rest)
+            .arg(self.history_capacity)
+            .arg(self.history_ttl.as_secs().max(1))
+            .arg(CHANNEL)
+            .invoke_async(&mut conn)
+            .await?;
+        Ok(id)
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<NoteEvent> {
+        self.sender.subscribe()
+    }
+
+    #[tracing::instrument(name = "redis.LRANGE", skip_all, fields(otel.kind = "client", db.system = "redis"))]
+    async fn replay(&self, user_id: u32, last_id: u64) -> AppResult<Vec<NoteEvent>> {
+        let mut conn = self.connection().await?;
+        let payloads: Vec<String> = conn.lrange(history_key(user_id), 0, -1).await?;
+
+        let mut events: Vec<NoteEvent> = payloads
+            .iter()
+            .filter_map(|payload| serde_json::from_str::<NoteEvent>(payload).ok())
+            .filter(|event| event.id > last_id)
+            .collect();
+        events.sort_by_key(|event| event.id);
+        Ok(events)
+    }
+
+    async fn close(&self) {
+        self.manager.lock().await.take();
+    }
+}
+
+// Dengarkan CHANNEL dan teruskan ke subscriber lokal. Jika koneksi putus,
+// subscribe ulang dengan jeda yang makin lama (maks. MAX_RECONNECT_DELAY).
+// Event yang terbit selama putus bisa diambil klien lewat Last-Event-ID.
+async fn listen(client: RedisClient, sender: broadcast::Sender<NoteEvent>, token: CancellationToken) {
+    let mut delay = RECONNECT_DELAY;
+    loop {
+        match client.get_async_pubsub().await {
+            Ok(mut pubsub) => match pubsub.subscribe(CHANNEL).await {
+                Ok(()) => {
+                    tracing::info!("Event bus Redis mendengarkan channel {}", CHANNEL);
+                    delay = RECONNECT_DELAY;
+                    let mut messages = pubsub.on_message();
+                    loop {
+                        tokio::select! {
+                            _ = token.cancelled() => return,
+                            message = messages.next() => match message {
+                                Some(message) => {
+                                    let event = message
+                                        .get_payload::<String>()
+                                        .ok()
+                                        .and_then(|payload| serde_json::from_str::<NoteEvent>(&payload).ok());
+                                    match event {
+                                        // Error hanya berarti belum ada subscriber
+                                        Some(event) => { let _ = sender.send(event); }
+                                        None => tracing::warn!("Pesan event bus tidak valid, diabaikan"),
+                                    }
+                                }
+                                None => break,
+                            },
+                        }
+                    }
+                    tracing::warn!("Koneksi pub/sub Redis terputus, mencoba lagi");
+                }
+                Err(e) => tracing::warn!("Gagal subscribe ke channel event Redis: {}", e),
+            },
+            Err(e) => tracing::warn!("Gagal terhubung ke Redis untuk event bus: {}", e),
+        }
+
+        tokio::select! {
+            _ = token.cancelled() => return,
+            _ = tokio::time::sleep(delay) => {}
+        }
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+}
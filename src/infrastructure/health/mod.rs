@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use redis::Client as RedisClient;
+
+use crate::{
+    domain::repositories::health_check::HealthCheck,
+    utils::{db::DbPool, error::AppResult},
+};
+
+// Ping database utama lewat pool yang sama dengan repository
+pub struct DatabaseHealthCheck {
+    db_pool: DbPool,
+}
+
+impl DatabaseHealthCheck {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for DatabaseHealthCheck {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    #[tracing::instrument(name = "db.ping", skip_all, fields(otel.kind = "client", db.system = %self.db_pool.backend_name()))]
+    async fn ping(&self) -> AppResult<()> {
+        self.db_pool.ping().await?;
+        Ok(())
+    }
+}
+
+// Ping Redis dengan perintah PING.
+// Kritis hanya jika Redis dipakai sebagai penyimpanan revokasi token.
+pub struct RedisHealthCheck {
+    redis_client: RedisClient,
+    critical: bool,
+}
+
+impl RedisHealthCheck {
+    pub fn new(redis_client: RedisClient, critical: bool) -> Self {
+        Self { redis_client, critical }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for RedisHealthCheck {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    fn is_critical(&self) -> bool {
+        self.critical
+    }
+
+    #[tracing::instrument(name = "redis.PING", skip_all, fields(otel.kind = "client", db.system = "redis"))]
+    async fn ping(&self) -> AppResult<()> {
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let _: String = redis::cmd("PING").query_async(&mut conn).await?;
+        Ok(())
+    }
+}
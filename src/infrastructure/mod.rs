@@ -1 +1,5 @@
-pub mod repositories;
\ No newline at end of file
+pub mod events;
+pub mod health;
+pub mod repositories;
+pub mod storage;
+pub mod token_revocation;
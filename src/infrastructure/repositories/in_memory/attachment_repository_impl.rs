@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::types::Json;
+use std::sync::RwLock;
+
+use crate::{
+    domain::{
+        models::attachment::{Attachment, ImageMetadata, NewAttachment},
+        repositories::attachment_repository::AttachmentRepository,
+    },
+    utils::error::AppResult,
+};
+
+// Implementasi AttachmentRepository di memori untuk test tanpa database.
+// Tidak ada foreign key di sini, jadi lampiran catatan yang dihapus baru
+// hilang saat `delete_by_note` dipanggil.
+#[derive(Default)]
+pub struct InMemoryAttachmentRepository {
+    state: RwLock<AttachmentStore>,
+}
+
+#[derive(Default)]
+struct AttachmentStore {
+    next_id: u32,
+    attachments: Vec<Attachment>,
+}
+
+impl InMemoryAttachmentRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for InMemoryAttachmentRepository {
+    async fn create(&self, new_attachment: &NewAttachment) -> AppResult<Attachment> {
+        let mut state = self.state.write().unwrap();
+        state.next_id += 1;
+
+        let attachment = Attachment {
+            id: state.next_id,
+            note_id: new_attachment.note_id,
+            user_id: new_attachment.user_id,
+            file_name: new_attachment.file_name.clone(),
+            content_type: new_attachment.content_type.clone(),
+            size: new_attachment.size,
+            storage_key: new_attachment.storage_key.clone(),
+            created_at: Some(Utc::now()),
+            width: None,
+            height: None,
+            exif: None,
+            thumbnails: None,
+        };
+        state.attachments.push(attachment.clone());
+        Ok(attachment)
+    }
+
+    async fn find_by_note(&self, note_id: u32, user_id: u32) -> AppResult<Vec<Attachment>> {
+        let state = self.state.read().unwrap();
+        Ok(state
+            .attachments
+            .iter()
+            .filter(|a| a.note_id == note_id && a.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_id(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<Option<Attachment>> {
+        let state = self.state.read().unwrap();
+        Ok(state
+            .attachments
+            .iter()
+            .find(|a| a.id == id && a.note_id == note_id && a.user_id == user_id)
+            .cloned())
+    }
+
+    async fn delete(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<u64> {
+        let mut state = self.state.write().unwrap();
+        let before = state.attachments.len();
+        state.attachments.retain(|a| !(a.id == id && a.note_id == note_id && a.user_id == user_id));
+        Ok((before - state.attachments.len()) as u64)
+    }
+
+    async fn delete_by_note(&self, note_id: u32, user_id: u32) -> AppResult<u64> {
+        let mut state = self.state.write().unwrap();
+        let before = state.attachments.len();
+        state.attachments.retain(|a| !(a.note_id == note_id && a.user_id == user_id));
+        Ok((before - state.attachments.len()) as u64)
+    }
+
+    async fn set_image_metadata(&self, id: u32, metadata: &ImageMetadata) -> AppResult<()> {
+        let mut state = self.state.write().unwrap();
+        if let Some(attachment) = state.attachments.iter_mut().find(|a| a.id == id) {
+            attachment.width = metadata.width;
+            attachment.height = metadata.height;
+            attachment.exif = metadata.exif.clone().map(Json);
+            attachment.thumbnails = Some(Json(metadata.thumbnails.clone()));
+        }
+        Ok(())
+    }
+
+    async fn total_size(&self, user_id: u32) -> AppResult<i64> {
+        let state = self.state.read().unwrap();
+        Ok(state.attachments.iter().filter(|a| a.user_id == user_id).map(|a| a.size).sum())
+    }
+}
@@ -0,0 +1,273 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::{
+    domain::{
+        models::{
+            note::{NewNote, Note, NoteFilter, NoteUpdate, UpdateNotePayload},
+            sync::{NoteChanges, Tombstone},
+        },
+        repositories::note_repository::NoteRepository,
+    },
+    utils::error::AppResult,
+};
+
+// Implementasi NoteRepository di memori, dipakai untuk test tanpa database.
+// Perilakunya meniru implementasi SQL (urutan, kepemilikan, rows_affected).
+#[derive(Default)]
+pub struct InMemoryNoteRepository {
+    state: RwLock<NoteStore>,
+}
+
+#[derive(Default)]
+struct NoteStore {
+    next_id: u32,
+    notes: Vec<Note>,
+    tombstones: Vec<Tombstone>,
+    // Nomor urut perubahan terakhir per user (tabel note_sync_counters)
+    last_seq: HashMap<u32, i64>,
+}
+
+impl NoteStore {
+    fn next_seq(&mut self, user_id: u32) -> i64 {
+        let seq = self.last_seq.entry(user_id).or_default();
+        *seq += 1;
+        *seq
+    }
+
+    fn insert(&mut self, new_note: &NewNote) -> Note {
+        self.next_id += 1;
+        let note = Note {
+            id: self.next_id,
+            user_id: new_note.user_id,
+            title: new_note.title.clone(),
+            content: new_note.content.clone(),
+            content_format: new_note.content_format,
+            tags: new_note.tags.clone(),
+            archived: false,
+            created_at: Some(new_note.created_at.unwrap_or_else(Utc::now)),
+            updated_at: new_note.updated_at,
+            change_seq: self.next_seq(new_note.user_id),
+            created_at_local: None,
+            content_html: None,
+        };
+        let created = copy_note(&note);
+        self.notes.push(note);
+        created
+    }
+}
+
+impl InMemoryNoteRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// Salinan seperti baris dari database (tanpa field hasil lokalisasi)
+fn copy_note(note: &Note) -> Note {
+    Note {
+        created_at_local: None,
+        content_html: None,
+        ..note.clone()
+    }
+}
+
+// WHERE id = ? AND user_id = ? AND (? IS NULL OR change_seq = ?)
+fn matches(note: &Note, id: u32, user_id: u32, expected_seq: Option<i64>) -> bool {
+    note.id == id && note.user_id == user_id && expected_seq.is_none_or(|seq| note.change_seq == seq)
+}
+
+#[async_trait]
+impl NoteRepository for InMemoryNoteRepository {
+    async fn create(&self, new_note: &NewNote) -> AppResult<Note> {
+        let mut state = self.state.write().unwrap();
+        Ok(state.insert(new_note))
+    }
+
+    async fn create_many(&self, new_notes: &[NewNote]) -> AppResult<Vec<Note>> {
+        // Satu kunci tulis untuk semua catatan, setara satu transaksi
+        let mut state = self.state.write().unwrap();
+        Ok(new_notes.iter().map(|new_note| state.insert(new_note)).collect())
+    }
+
+    async fn find_all(&self, user_id: u32) -> AppResult<Vec<Note>> {
+        let state = self.state.read().unwrap();
+        let mut notes: Vec<Note> = state
+            .notes
+            .iter()
+            .filter(|note| note.user_id == user_id)
+            .map(copy_note)
+            .collect();
+        // ORDER BY created_at DESC (id sebagai penentu jika waktunya sama)
+        notes.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        Ok(notes)
+    }
+
+    async fn find_page(&self, user_id: u32, limit: u32, offset: u32) -> AppResult<Vec<Note>> {
+        let notes = self.find_all(user_id).await?;
+        Ok(notes.into_iter().skip(offset as usize).take(limit as usize).collect())
+    }
+
+    async fn find_filtered(
+        &self,
+        user_id: u32,
+        filter: &NoteFilter,
+        after_id: u32,
+        limit: u32,
+    ) -> AppResult<Vec<Note>> {
+        let state = self.state.read().unwrap();
+        let mut notes: Vec<Note> = state
+            .notes
+            .iter()
+            .filter(|note| note.user_id == user_id && note.id > after_id && filter.matches(note))
+            .map(copy_note)
+            .collect();
+        notes.sort_by_key(|note| note.id);
+        notes.truncate(limit as usize);
+        Ok(notes)
+    }
+
+    async fn find_by_id(&self, id: u32, user_id: u32) -> AppResult<Option<Note>> {
+        let state = self.state.read().unwrap();
+        Ok(state
+            .notes
+            .iter()
+            .find(|note| note.id == id && note.user_id == user_id)
+            .map(copy_note))
+    }
+
+    async fn find_by_ids(&self, ids: &[u32], user_id: u32) -> AppResult<Vec<Note>> {
+        let state = self.state.read().unwrap();
+        let mut notes: Vec<Note> = state
+            .notes
+            .iter()
+            .filter(|note| note.user_id == user_id && ids.contains(&note.id))
+            .map(copy_note)
+            .collect();
+        notes.sort_by_key(|note| note.id);
+        Ok(notes)
+    }
+
+    async fn update(
+        &self,
+        id: u32,
+        user_id: u32,
+        payload: &UpdateNotePayload,
+        expected_seq: Option<i64>,
+    ) -> AppResult<Option<Note>> {
+        let mut state = self.state.write().unwrap();
+        let Some(index) = state.notes.iter().position(|note| matches(note, id, user_id, expected_seq)) else {
+            return Ok(None);
+        };
+
+        let seq = state.next_seq(user_id);
+        let note = &mut state.notes[index];
+        if let Some(title) = &payload.title {
+            note.title = title.clone();
+        }
+        if let Some(content) = &payload.content {
+            note.content = Some(content.clone());
+        }
+        if let Some(content_format) = payload.content_format {
+            note.content_format = content_format;
+        }
+        if let Some(tags) = &payload.tags {
+            note.tags = tags.clone();
+        }
+        note.change_seq = seq;
+        note.updated_at = Some(Utc::now());
+        Ok(Some(copy_note(note)))
+    }
+
+    async fn delete(&self, id: u32, user_id: u32, expected_seq: Option<i64>) -> AppResult<u64> {
+        let mut state = self.state.write().unwrap();
+        let Some(index) = state.notes.iter().position(|note| matches(note, id, user_id, expected_seq)) else {
+            return Ok(0);
+        };
+
+        state.notes.remove(index);
+        let seq = state.next_seq(user_id);
+        state.tombstones.retain(|tombstone| tombstone.note_id != id);
+        state.tombstones.push(Tombstone { note_id: id, user_id, change_seq: seq, deleted_at: Utc::now() });
+        Ok(1)
+    }
+
+    async fn update_many(&self, user_id: u32, updates: &[NoteUpdate], all_or_nothing: bool) -> AppResult<Vec<Note>> {
+        let mut state = self.state.write().unwrap();
+        let indexes: Vec<(usize, &NoteUpdate)> = updates
+            .iter()
+            .filter_map(|update| {
+                let index = state
+                    .notes
+                    .iter()
+                    .position(|note| matches(note, update.id, user_id, Some(update.expected_seq)))?;
+                Some((index, update))
+            })
+            .collect();
+        if all_or_nothing && indexes.len() < updates.len() {
+            return Ok(Vec::new());
+        }
+
+        let mut updated = Vec::with_capacity(indexes.len());
+        for (index, update) in indexes {
+            let seq = state.next_seq(user_id);
+            let note = &mut state.notes[index];
+            note.title = update.title.clone();
+            note.content = update.content.clone();
+            note.tags = update.tags.clone();
+            note.archived = update.archived;
+            note.change_seq = seq;
+            note.updated_at = Some(Utc::now());
+            updated.push(copy_note(note));
+        }
+        updated.sort_by_key(|note| note.id);
+        Ok(updated)
+    }
+
+    async fn delete_many(&self, user_id: u32, expected: &[(u32, i64)], all_or_nothing: bool) -> AppResult<Vec<u32>> {
+        let mut state = self.state.write().unwrap();
+        let mut deleted: Vec<u32> = state
+            .notes
+            .iter()
+            .filter(|note| expected.iter().any(|&(id, seq)| matches(note, id, user_id, Some(seq))))
+            .map(|note| note.id)
+            .collect();
+        if all_or_nothing && deleted.len() < expected.len() {
+            return Ok(Vec::new());
+        }
+
+        deleted.sort_unstable();
+        state.notes.retain(|note| !(note.user_id == user_id && deleted.contains(&note.id)));
+        state.tombstones.retain(|tombstone| !deleted.contains(&tombstone.note_id));
+        for &id in &deleted {
+            let seq = state.next_seq(user_id);
+            state.tombstones.push(Tombstone { note_id: id, user_id, change_seq: seq, deleted_at: Utc::now() });
+        }
+        Ok(deleted)
+    }
+
+    async fn changes_since(&self, user_id: u32, since: i64, limit: u32) -> AppResult<NoteChanges> {
+        let state = self.state.read().unwrap();
+        let mut notes: Vec<Note> = state
+            .notes
+            .iter()
+            .filter(|note| note.user_id == user_id && note.change_seq > since)
+            .map(copy_note)
+            .collect();
+        notes.sort_by_key(|note| note.change_seq);
+        notes.truncate(limit as usize + 1);
+
+        let mut tombstones: Vec<Tombstone> = state
+            .tombstones
+            .iter()
+            .filter(|tombstone| tombstone.user_id == user_id && tombstone.change_seq > since)
+            .cloned()
+            .collect();
+        tombstones.sort_by_key(|tombstone| tombstone.change_seq);
+        tombstones.truncate(limit as usize + 1);
+
+        Ok(NoteChanges::merge(notes, tombstones, limit))
+    }
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::{collections::HashMap, sync::RwLock};
+
+use crate::domain::{
+    models::{
+        user::{User, UserProfile},
+        user_settings::UserSettings,
+    },
+    repositories::user_repository::UserRepository,
+};
+use crate::utils::error::AppResult;
+
+// Implementasi UserRepository di memori, dipakai untuk test tanpa database.
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    state: RwLock<UserStore>,
+}
+
+#[derive(Default)]
+struct UserStore {
+    next_id: u32,
+    users: Vec<User>,
+    settings: HashMap<u32, UserSettings>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn copy_user(user: &User) -> User {
+    User {
+        id: user.id,
+        email: user.email.clone(),
+        full_name: user.full_name.clone(),
+        username: user.username.clone(),
+        role: user.role.clone(),
+        password_hash: user.password_hash.clone(),
+        created_at: user.created_at,
+        created_at_local: None,
+    }
+}
+
+fn to_profile(user: &User) -> UserProfile {
+    UserProfile {
+        id: user.id,
+        email: user.email.clone(),
+        full_name: user.full_name.clone(),
+        username: user.username.clone(),
+        role: user.role.clone(),
+        created_at: user.created_at,
+        created_at_local: None,
+    }
+}
+
+impl UserStore {
+    fn find(&self, predicate: impl Fn(&User) -> bool) -> Option<User> {
+        self.users.iter().find(|user| predicate(user)).map(copy_user)
+    }
+
+    fn update(&mut self, id: u32, apply: impl FnOnce(&mut User)) -> u64 {
+        match self.users.iter_mut().find(|user| user.id == id) {
+            Some(user) => {
+                apply(user);
+                1
+            }
+            None => 0,
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_by_email(&self, email: &str) -> AppResult<Option<User>> {
+        Ok(self.state.read().unwrap().find(|user| user.email == email))
+    }
+
+    async fn find_by_username(&self, username: &str) -> AppResult<Option<User>> {
+        Ok(self.state.read().unwrap().find(|user| user.username == username))
+    }
+
+    async fn find_by_id(&self, id: u32) -> AppResult<Option<User>> {
+        Ok(self.state.read().unwrap().find(|user| user.id == id))
+    }
+
+    async fn find_profile_by_id(&self, id: u32) -> AppResult<Option<UserProfile>> {
+        let state = self.state.read().unwrap();
+        Ok(state.users.iter().find(|user| user.id == id).map(to_profile))
+    }
+
+    async fn get_all_profiles(&self) -> AppResult<Vec<UserProfile>> {
+        let state = self.state.read().unwrap();
+        let mut profiles: Vec<UserProfile> = state.users.iter().map(to_profile).collect();
+        profiles.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        Ok(profiles)
+    }
+
+    async fn get_profiles_page(&self, limit: u32, offset: u32) -> AppResult<Vec<UserProfile>> {
+        let profiles = self.get_all_profiles().await?;
+        Ok(profiles.into_iter().skip(offset as usize).take(limit as usize).collect())
+    }
+
+    async fn create(&self, user_data: &User) -> AppResult<User> {
+        let mut state = self.state.write().unwrap();
+        state.next_id += 1;
+
+        let user = User {
+            id: state.next_id,
+            created_at: Utc::now(),
+            ..copy_user(user_data)
+        };
+        let created = copy_user(&user);
+        state.users.push(user);
+        Ok(created)
+    }
+
+    async fn update_role(&self, id: u32, role: &str) -> AppResult<u64> {
+        let mut state = self.state.write().unwrap();
+        Ok(state.update(id, |user| user.role = role.to_string()))
+    }
+
+    async fn update_password(&self, id: u32, password_hash: &str) -> AppResult<u64> {
+        let mut state = self.state.write().unwrap();
+        Ok(state.update(id, |user| user.password_hash = password_hash.to_string()))
+    }
+
+    async fn find_settings(&self, user_id: u32) -> AppResult<Option<UserSettings>> {
+        Ok(self.state.read().unwrap().settings.get(&user_id).cloned())
+    }
+
+    async fn upsert_settings(&self, settings: &UserSettings) -> AppResult<UserSettings> {
+        let mut state = self.state.write().unwrap();
+        state.settings.insert(settings.user_id, settings.clone());
+        Ok(settings.clone())
+    }
+}
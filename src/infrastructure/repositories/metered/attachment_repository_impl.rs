@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use super::observe;
+use crate::{
+    domain::{
+        models::attachment::{Attachment, ImageMetadata, NewAttachment},
+        repositories::attachment_repository::{AttachmentRepository, DynAttachmentRepository},
+    },
+    utils::{error::AppResult, metrics::Metrics},
+};
+
+const REPOSITORY: &str = "attachment";
+
+// Decorator metrik untuk AttachmentRepository (latensi query + span tracing)
+pub struct MeteredAttachmentRepository {
+    inner: DynAttachmentRepository,
+    metrics: Arc<Metrics>,
+}
+
+impl MeteredAttachmentRepository {
+    pub fn new(inner: DynAttachmentRepository, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for MeteredAttachmentRepository {
+    async fn create(&self, new_attachment: &NewAttachment) -> AppResult<Attachment> {
+        observe(&self.metrics, REPOSITORY, "create", self.inner.create(new_attachment)).await
+    }
+
+    async fn find_by_note(&self, note_id: u32, user_id: u32) -> AppResult<Vec<Attachment>> {
+        observe(&self.metrics, REPOSITORY, "find_by_note", self.inner.find_by_note(note_id, user_id)).await
+    }
+
+    async fn find_by_id(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<Option<Attachment>> {
+        observe(&self.metrics, REPOSITORY, "find_by_id", self.inner.find_by_id(id, note_id, user_id)).await
+    }
+
+    async fn delete(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<u64> {
+        observe(&self.metrics, REPOSITORY, "delete", self.inner.delete(id, note_id, user_id)).await
+    }
+
+    async fn delete_by_note(&self, note_id: u32, user_id: u32) -> AppResult<u64> {
+        observe(&self.metrics, REPOSITORY, "delete_by_note", self.inner.delete_by_note(note_id, user_id)).await
+    }
+
+    async fn set_image_metadata(&self, id: u32, metadata: &ImageMetadata) -> AppResult<()> {
+        observe(&self.metrics, REPOSITORY, "set_image_metadata", self.inner.set_image_metadata(id, metadata)).await
+    }
+
+    async fn total_size(&self, user_id: u32) -> AppResult<i64> {
+        observe(&self.metrics, REPOSITORY, "total_size", self.inner.total_size(user_id)).await
+    }
+}
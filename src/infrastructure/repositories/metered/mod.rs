@@ -0,0 +1,36 @@
+pub mod attachment_repository_impl;
+pub mod note_repository_impl;
+pub mod user_repository_impl;
+
+use std::future::Future;
+use std::time::Instant;
+
+use tracing::Instrument;
+
+use crate::utils::{error::AppResult, metrics::Metrics};
+
+// Catat durasi satu pemanggilan repository beserta hasilnya (ok/error),
+// sekaligus membuat span tracing untuk query-nya
+async fn observe<T>(
+    metrics: &Metrics,
+    repository: &str,
+    method: &str,
+    fut: impl Future<Output = AppResult<T>>,
+) -> AppResult<T> {
+    let span = tracing::info_span!(
+        "db.query",
+        otel.name = %format!("{}.{}", repository, method),
+        otel.kind = "client",
+        db.collection.name = repository,
+        db.operation.name = method,
+    );
+
+    let started = Instant::now();
+    let result = fut.instrument(span).await;
+    let outcome = if result.is_ok() { "ok" } else { "error" };
+    metrics
+        .repository_query_duration_seconds
+        .with_label_values(&[repository, method, outcome])
+        .observe(started.elapsed().as_secs_f64());
+    result
+}
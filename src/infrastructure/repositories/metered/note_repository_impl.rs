@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use super::observe;
+use crate::{
+    domain::{
+        models::{
+            note::{NewNote, Note, NoteFilter, NoteUpdate, UpdateNotePayload},
+            sync::NoteChanges,
+        },
+        repositories::note_repository::{DynNoteRepository, NoteRepository},
+    },
+    utils::{error::AppResult, metrics::Metrics},
+};
+
+const REPOSITORY: &str = "note";
+
+// Decorator yang membungkus NoteRepository mana pun (MySQL, Postgres, SQLite,
+// in-memory) dan mencatat latensi query, span tracing, serta jumlah catatan yang berubah.
+pub struct MeteredNoteRepository {
+    inner: DynNoteRepository,
+    metrics: Arc<Metrics>,
+}
+
+impl MeteredNoteRepository {
+    pub fn new(inner: DynNoteRepository, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    fn count_change(&self, operation: &str) {
+        self.metrics.notes_changes_total.with_label_values(&[operation]).inc();
+    }
+}
+
+#[async_trait]
+impl NoteRepository for MeteredNoteRepository {
+    async fn create(&self, new_note: &NewNote) -> AppResult<Note> {
+        let note = observe(&self.metrics, REPOSITORY, "create", self.inner.create(new_note)).await?;
+        self.count_change("created");
+        Ok(note)
+    }
+
+    async fn create_many(&self, new_notes: &[NewNote]) -> AppResult<Vec<Note>> {
+        let notes = observe(&self.metrics, REPOSITORY, "create_many", self.inner.create_many(new_notes)).await?;
+        self.metrics.notes_changes_total.with_label_values(&["created"]).inc_by(notes.len() as u64);
+        Ok(notes)
+    }
+
+    async fn find_all(&self, user_id: u32) -> AppResult<Vec<Note>> {
+        observe(&self.metrics, REPOSITORY, "find_all", self.inner.find_all(user_id)).await
+    }
+
+    async fn find_page(&self, user_id: u32, limit: u32, offset: u32) -> AppResult<Vec<Note>> {
+        observe(&self.metrics, REPOSITORY, "find_page", self.inner.find_page(user_id, limit, offset)).await
+    }
+
+    async fn find_filtered(
+        &self,
+        user_id: u32,
+        filter: &NoteFilter,
+        after_id: u32,
+        limit: u32,
+    ) -> AppResult<Vec<Note>> {
+        let find = self.inner.find_filtered(user_id, filter, after_id, limit);
+        observe(&self.metrics, REPOSITORY, "find_filtered", find).await
+    }
+
+    async fn find_by_id(&self, id: u32, user_id: u32) -> AppResult<Option<Note>> {
+        observe(&self.metrics, REPOSITORY, "find_by_id", self.inner.find_by_id(id, user_id)).await
+    }
+
+    async fn find_by_ids(&self, ids: &[u32], user_id: u32) -> AppResult<Vec<Note>> {
+        observe(&self.metrics, REPOSITORY, "find_by_ids", self.inner.find_by_ids(ids, user_id)).await
+    }
+
+    async fn update(
+        &self,
+        id: u32,
+        user_id: u32,
+        payload: &UpdateNotePayload,
+        expected_seq: Option<i64>,
+    ) -> AppResult<Option<Note>> {
+        let update = self.inner.update(id, user_id, payload, expected_seq);
+        let note = observe(&self.metrics, REPOSITORY, "update", update).await?;
+        if note.is_some() {
+            self.count_change("updated");
+        }
+        Ok(note)
+    }
+
+    async fn delete(&self, id: u32, user_id: u32, expected_seq: Option<i64>) -> AppResult<u64> {
+        let delete = self.inner.delete(id, user_id, expected_seq);
+        let rows_affected = observe(&self.metrics, REPOSITORY, "delete", delete).await?;
+        if rows_affected > 0 {
+            self.metrics.notes_changes_total.with_label_values(&["deleted"]).inc_by(rows_affected);
+        }
+        Ok(rows_affected)
+    }
+
+    async fn update_many(&self, user_id: u32, updates: &[NoteUpdate], all_or_nothing: bool) -> AppResult<Vec<Note>> {
+        let update = self.inner.update_many(user_id, updates, all_or_nothing);
+        let notes = observe(&self.metrics, REPOSITORY, "update_many", update).await?;
+        self.metrics.notes_changes_total.with_label_values(&["updated"]).inc_by(notes.len() as u64);
+        Ok(notes)
+    }
+
+    async fn delete_many(&self, user_id: u32, expected: &[(u32, i64)], all_or_nothing: bool) -> AppResult<Vec<u32>> {
+        let delete = self.inner.delete_many(user_id, expected, all_or_nothing);
+        let deleted = observe(&self.metrics, REPOSITORY, "delete_many", delete).await?;
+        self.metrics.notes_changes_total.with_label_values(&["deleted"]).inc_by(deleted.len() as u64);
+        Ok(deleted)
+    }
+
+    async fn changes_since(&self, user_id: u32, since: i64, limit: u32) -> AppResult<NoteChanges> {
+        observe(&self.metrics, REPOSITORY, "changes_since", self.inner.changes_since(user_id, since, limit)).await
+    }
+}
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use super::observe;
+use crate::{
+    domain::{
+        models::{
+            user::{User, UserProfile},
+            user_settings::UserSettings,
+        },
+        repositories::user_repository::{DynUserRepository, UserRepository},
+    },
+    utils::{error::AppResult, metrics::Metrics},
+};
+
+const REPOSITORY: &str = "user";
+
+// Decorator pencatat latensi & span tracing untuk UserRepository
+pub struct MeteredUserRepository {
+    inner: DynUserRepository,
+    metrics: Arc<Metrics>,
+}
+
+impl MeteredUserRepository {
+    pub fn new(inner: DynUserRepository, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl UserRepository for MeteredUserRepository {
+    async fn find_by_email(&self, email: &str) -> AppResult<Option<User>> {
+        observe(&self.metrics, REPOSITORY, "find_by_email", self.inner.find_by_email(email)).await
+    }
+
+    async fn find_by_username(&self, username: &str) -> AppResult<Option<User>> {
+        observe(&self.metrics, REPOSITORY, "find_by_username", self.inner.find_by_username(username)).await
+    }
+
+    async fn find_by_id(&self, id: u32) -> AppResult<Option<User>> {
+        observe(&self.metrics, REPOSITORY, "find_by_id", self.inner.find_by_id(id)).await
+    }
+
+    async fn find_profile_by_id(&self, id: u32) -> AppResult<Option<UserProfile>> {
+        observe(&self.metrics, REPOSITORY, "find_profile_by_id", self.inner.find_profile_by_id(id)).await
+    }
+
+    async fn get_all_profiles(&self) -> AppResult<Vec<UserProfile>> {
+        observe(&self.metrics, REPOSITORY, "get_all_profiles", self.inner.get_all_profiles()).await
+    }
+
+    async fn get_profiles_page(&self, limit: u32, offset: u32) -> AppResult<Vec<UserProfile>> {
+        observe(&self.metrics, REPOSITORY, "get_profiles_page", self.inner.get_profiles_page(limit, offset)).await
+    }
+
+    async fn create(&self, payload: &User) -> AppResult<User> {
+        observe(&self.metrics, REPOSITORY, "create", self.inner.create(payload)).await
+    }
+
+    async fn update_role(&self, id: u32, role: &str) -> AppResult<u64> {
+        observe(&self.metrics, REPOSITORY, "update_role", self.inner.update_role(id, role)).await
+    }
+
+    async fn update_password(&self, id: u32, password_hash: &str) -> AppResult<u64> {
+        observe(&self.metrics, REPOSITORY, "update_password", self.inner.update_password(id, password_hash)).await
+    }
+
+    async fn find_settings(&self, user_id: u32) -> AppResult<Option<UserSettings>> {
+        observe(&self.metrics, REPOSITORY, "find_settings", self.inner.find_settings(user_id)).await
+    }
+
+    async fn upsert_settings(&self, settings: &UserSettings) -> AppResult<UserSettings> {
+        observe(&self.metrics, REPOSITORY, "upsert_settings", self.inner.upsert_settings(settings)).await
+    }
+}
@@ -1,2 +1,79 @@
-pub mod note_repository_impl;
-pub mod user_repository_impl;
\ No newline at end of file
+pub mod in_memory;
+pub mod metered;
+#[cfg(feature = "mysql")]
+pub mod mysql;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    domain::{
+        models::note::NewNote,
+        repositories::{
+            attachment_repository::DynAttachmentRepository, note_repository::DynNoteRepository,
+            user_repository::DynUserRepository,
+        },
+    },
+    utils::{db::DbPool, metrics::Metrics},
+};
+
+pub type Repositories = (DynUserRepository, DynNoteRepository, DynAttachmentRepository);
+
+// Pilih implementasi repository sesuai backend database yang aktif.
+// Lapisan di atasnya hanya melihat trait (DynUserRepository, DynNoteRepository, ...).
+pub fn create_repositories(pool: &DbPool) -> Repositories {
+    match pool {
+        #[cfg(feature = "mysql")]
+        DbPool::MySql(pool) => (
+            Arc::new(mysql::user_repository_impl::MySqlUserRepositoryImpl::new(pool.clone())),
+            Arc::new(mysql::note_repository_impl::MySqlNoteRepositoryImpl::new(pool.clone())),
+            Arc::new(mysql::attachment_repository_impl::MySqlAttachmentRepositoryImpl::new(pool.clone())),
+        ),
+        #[cfg(feature = "postgres")]
+        DbPool::Postgres(pool) => (
+            Arc::new(postgres::user_repository_impl::PgUserRepositoryImpl::new(pool.clone())),
+            Arc::new(postgres::note_repository_impl::PgNoteRepositoryImpl::new(pool.clone())),
+            Arc::new(postgres::attachment_repository_impl::PgAttachmentRepositoryImpl::new(pool.clone())),
+        ),
+        #[cfg(feature = "sqlite")]
+        DbPool::Sqlite(pool) => (
+            Arc::new(sqlite::user_repository_impl::SqliteUserRepositoryImpl::new(pool.clone())),
+            Arc::new(sqlite::note_repository_impl::SqliteNoteRepositoryImpl::new(pool.clone())),
+            Arc::new(sqlite::attachment_repository_impl::SqliteAttachmentRepositoryImpl::new(pool.clone())),
+        ),
+    }
+}
+
+// Bungkus repository dengan decorator metrik (latensi query, jumlah perubahan catatan)
+pub fn with_metrics((user_repo, note_repo, attachment_repo): Repositories, metrics: &Arc<Metrics>) -> Repositories {
+    (
+        Arc::new(metered::user_repository_impl::MeteredUserRepository::new(user_repo, metrics.clone())),
+        Arc::new(metered::note_repository_impl::MeteredNoteRepository::new(note_repo, metrics.clone())),
+        Arc::new(metered::attachment_repository_impl::MeteredAttachmentRepository::new(attachment_repo, metrics.clone())),
+    )
+}
+
+// Jumlah catatan baru per user (untuk memesan rentang nomor urut perubahan sekaligus)
+pub(crate) fn count_per_user(new_notes: &[NewNote]) -> HashMap<u32, i64> {
+    let mut counts = HashMap::new();
+    for new_note in new_notes {
+        *counts.entry(new_note.user_id).or_default() += 1;
+    }
+    counts
+}
+
+// Bagikan nomor urut ke tiap catatan sesuai urutan input.
+// `next` berisi nomor pertama dari rentang yang sudah dipesan per user.
+pub(crate) fn assign_seqs(new_notes: &[NewNote], mut next: HashMap<u32, i64>) -> Vec<i64> {
+    new_notes
+        .iter()
+        .map(|new_note| {
+            let seq = next.get_mut(&new_note.user_id).expect("nomor urut user sudah dipesan");
+            *seq += 1;
+            *seq - 1
+        })
+        .collect()
+}
@@ -0,0 +1,438 @@
+use async_trait::async_trait;
+use sqlx::{types::Json, Encode, MySql, MySqlPool, QueryBuilder, Transaction, Type};
+use std::collections::HashMap;
+
+use crate::{
+    domain::{
+        models::{
+            note::{NewNote, Note, NoteFilter, NoteUpdate, UpdateNotePayload},
+            sync::{NoteChanges, Tombstone},
+        },
+        repositories::note_repository::NoteRepository,
+    },
+    infrastructure::repositories::{assign_seqs, count_per_user},
+    utils::error::AppResult,
+};
+
+pub struct MySqlNoteRepositoryImpl {
+    db_pool: MySqlPool,
+}
+
+impl MySqlNoteRepositoryImpl {
+    pub fn new(db_pool: MySqlPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+// Naikkan nomor urut perubahan milik user. Baris counter terkunci sampai
+// transaksi selesai, jadi perubahan milik user yang sama di-commit berurutan.
+async fn next_seq(tx: &mut Transaction<'_, MySql>, user_id: u32) -> AppResult<i64> {
+    reserve_seqs(tx, user_id, 1).await
+}
+
+// Pesan `count` nomor urut sekaligus; hasilnya nomor terakhir dari rentang itu
+async fn reserve_seqs(tx: &mut Transaction<'_, MySql>, user_id: u32, count: i64) -> AppResult<i64> {
+    sqlx::query(
+        "INSERT INTO note_sync_counters (user_id, last_seq) VALUES (?, ?) \
+         ON DUPLICATE KEY UPDATE last_seq = last_seq + VALUES(last_seq)",
+    )
+    .bind(user_id)
+    .bind(count)
+    .execute(&mut **tx)
+    .await?;
+
+    let seq = sqlx::query_scalar::<_, i64>("SELECT last_seq FROM note_sync_counters WHERE user_id = ?")
+        .bind(user_id)
+        .fetch_one(&mut **tx)
+        .await?;
+    Ok(seq)
+}
+
+// Baris per INSERT/UPDATE multi-baris (8-12 parameter per baris, batas MySQL 65535)
+const INSERT_BATCH: usize = 100;
+
+// `column = CASE id WHEN ? THEN ? ... END` untuk UPDATE multi-baris
+fn push_case<'a, T>(
+    query: &mut QueryBuilder<'a, MySql>,
+    column: &str,
+    batch: &'a [(&NoteUpdate, i64)],
+    value: impl Fn(&'a NoteUpdate, i64) -> T,
+) where
+    T: 'a + Encode<'a, MySql> + Type<MySql> + Send,
+{
+    query.push(format!("{column} = CASE id"));
+    for (update, seq) in batch {
+        query.push(" WHEN ").push_bind(update.id).push(" THEN ").push_bind(value(update, *seq));
+    }
+    query.push(" END");
+}
+
+// `column IN (?, ?, ...)`
+fn push_ids<'a>(query: &mut QueryBuilder<'a, MySql>, column: &str, ids: &'a [u32]) {
+    query.push(format!("{column} IN ("));
+    let mut separated = query.separated(", ");
+    for id in ids {
+        separated.push_bind(*id);
+    }
+    query.push(")");
+}
+
+// `((id = ? AND change_seq = ?) OR ...)`
+fn push_versions<'a>(query: &mut QueryBuilder<'a, MySql>, versions: impl IntoIterator<Item = (u32, i64)>) {
+    query.push("(");
+    for (i, (id, seq)) in versions.into_iter().enumerate() {
+        if i > 0 {
+            query.push(" OR ");
+        }
+        query.push("(id = ").push_bind(id).push(" AND change_seq = ").push_bind(seq).push(")");
+    }
+    query.push(")");
+}
+
+#[async_trait]
+impl NoteRepository for MySqlNoteRepositoryImpl {
+    async fn create(&self, new_note: &NewNote) -> AppResult<Note> {
+        let mut tx = self.db_pool.begin().await?;
+        let seq = next_seq(&mut tx, new_note.user_id).await?;
+        let insert_result = sqlx::query(
+            "INSERT INTO notes (title, content, content_format, tags, user_id, created_at, updated_at, change_seq) \
+             VALUES (?, ?, ?, ?, ?, COALESCE(?, CURRENT_TIMESTAMP), ?, ?)",
+        )
+        .bind(&new_note.title)
+        .bind(&new_note.content)
+        .bind(new_note.content_format.as_str())
+        .bind(Json(&new_note.tags))
+        .bind(new_note.user_id)
+        .bind(new_note.created_at)
+        .bind(new_note.updated_at)
+        .bind(seq)
+        .execute(&mut *tx)
+        .await?;
+
+        let new_id = insert_result.last_insert_id() as u32;
+
+        // InnoDB bisa memakai ulang id setelah restart jika baris terakhir dihapus;
+        // tombstone lama dengan id yang sama tidak berlaku lagi
+        sqlx::query("DELETE FROM note_tombstones WHERE note_id = ?")
+            .bind(new_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let created_note = sqlx::query_as::<_, Note>("SELECT * FROM notes WHERE id = ?")
+            .bind(new_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(created_note)
+    }
+
+    async fn create_many(&self, new_notes: &[NewNote]) -> AppResult<Vec<Note>> {
+        let mut tx = self.db_pool.begin().await?;
+        let mut next = HashMap::new();
+        let mut ranges = HashMap::new();
+        for (user_id, count) in count_per_user(new_notes) {
+            let last = reserve_seqs(&mut tx, user_id, count).await?;
+            next.insert(user_id, last - count + 1);
+            ranges.insert(user_id, (last - count + 1, last));
+        }
+        let seqs = assign_seqs(new_notes, next);
+
+        for batch in new_notes.iter().zip(seqs.iter()).collect::<Vec<_>>().chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<MySql>::new(
+                "INSERT INTO notes (title, content, content_format, tags, user_id, created_at, updated_at, change_seq) ",
+            );
+            query.push_values(batch, |mut row, (new_note, seq)| {
+                row.push_bind(&new_note.title)
+                    .push_bind(&new_note.content)
+                    .push_bind(new_note.content_format.as_str())
+                    .push_bind(Json(&new_note.tags))
+                    .push_bind(new_note.user_id)
+                    .push("COALESCE(")
+                    .push_bind_unseparated(new_note.created_at)
+                    .push_unseparated(", CURRENT_TIMESTAMP)")
+                    .push_bind(new_note.updated_at)
+                    .push_bind(**seq);
+            });
+            query.build().execute(&mut *tx).await?;
+        }
+
+        // Id dari INSERT multi-baris tidak dijamin berurutan (innodb_autoinc_lock_mode=2,
+        // auto_increment_increment > 1), jadi baris dibaca ulang lewat change_seq yang
+        // sudah dipesan: rentang itu hanya dimiliki catatan dari transaksi ini
+        let mut inserted = HashMap::with_capacity(new_notes.len());
+        for (user_id, (first, last)) in ranges {
+            let notes = sqlx::query_as::<_, Note>(
+                "SELECT * FROM notes WHERE user_id = ? AND change_seq BETWEEN ? AND ?",
+            )
+            .bind(user_id)
+            .bind(first)
+            .bind(last)
+            .fetch_all(&mut *tx)
+            .await?;
+
+            // InnoDB bisa memakai ulang id setelah restart; tombstone lama untuk id baru ini tidak berlaku lagi
+            let ids: Vec<u32> = notes.iter().map(|note| note.id).collect();
+            for batch in ids.chunks(INSERT_BATCH) {
+                let mut query = QueryBuilder::<MySql>::new("DELETE FROM note_tombstones WHERE user_id = ");
+                query.push_bind(user_id).push(" AND ");
+                push_ids(&mut query, "note_id", batch);
+                query.build().execute(&mut *tx).await?;
+            }
+            inserted.extend(notes.into_iter().map(|note| ((note.user_id, note.change_seq), note)));
+        }
+        tx.commit().await?;
+
+        Ok(new_notes
+            .iter()
+            .zip(seqs)
+            .map(|(new_note, seq)| inserted.remove(&(new_note.user_id, seq)).expect("baris yang baru disimpan"))
+            .collect())
+    }
+
+    async fn find_all(&self, user_id: u32) -> AppResult<Vec<Note>> {
+        let notes = sqlx::query_as::<_, Note>(
+            "SELECT * FROM notes WHERE user_id = ? ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(notes)
+    }
+
+    async fn find_page(&self, user_id: u32, limit: u32, offset: u32) -> AppResult<Vec<Note>> {
+        let notes = sqlx::query_as::<_, Note>(
+            "SELECT * FROM notes WHERE user_id = ? ORDER BY created_at DESC, id DESC LIMIT ? OFFSET ?",
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(notes)
+    }
+
+    async fn find_filtered(
+        &self,
+        user_id: u32,
+        filter: &NoteFilter,
+        after_id: u32,
+        limit: u32,
+    ) -> AppResult<Vec<Note>> {
+        let notes = sqlx::query_as::<_, Note>(
+            "SELECT * FROM notes WHERE user_id = ? AND id > ? \
+             AND (? IS NULL OR JSON_CONTAINS(tags, JSON_QUOTE(?))) \
+             AND (? IS NULL OR created_at >= ?) AND (? IS NULL OR created_at < ?) \
+             ORDER BY id LIMIT ?",
+        )
+        .bind(user_id)
+        .bind(after_id)
+        .bind(&filter.tag)
+        .bind(&filter.tag)
+        .bind(filter.from)
+        .bind(filter.from)
+        .bind(filter.until)
+        .bind(filter.until)
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(notes)
+    }
+
+    async fn find_by_id(&self, id: u32, user_id: u32) -> AppResult<Option<Note>> {
+        let note = sqlx::query_as::<_, Note>("SELECT * FROM notes WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(&self.db_pool)
+            .await?;
+        Ok(note)
+    }
+
+    async fn find_by_ids(&self, ids: &[u32], user_id: u32) -> AppResult<Vec<Note>> {
+        let mut found = Vec::with_capacity(ids.len());
+        for batch in ids.chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<MySql>::new("SELECT * FROM notes WHERE user_id = ");
+            query.push_bind(user_id).push(" AND ");
+            push_ids(&mut query, "id", batch);
+            found.extend(query.build_query_as::<Note>().fetch_all(&self.db_pool).await?);
+        }
+        found.sort_by_key(|note| note.id);
+        Ok(found)
+    }
+
+    async fn update(
+        &self,
+        id: u32,
+        user_id: u32,
+        payload: &UpdateNotePayload,
+        expected_seq: Option<i64>,
+    ) -> AppResult<Option<Note>> {
+        // Jika tidak ada baris yang berubah, transaksi di-rollback (nomor urut tidak terpakai)
+        let mut tx = self.db_pool.begin().await?;
+        let seq = next_seq(&mut tx, user_id).await?;
+
+        // COALESCE mempertahankan nilai lama untuk field yang tidak dikirim.
+        // change_seq selalu berubah, jadi rows_affected = 1 jika barisnya cocok.
+        let result = sqlx::query(
+            "UPDATE notes SET title = COALESCE(?, title), content = COALESCE(?, content), \
+             content_format = COALESCE(?, content_format), tags = COALESCE(?, tags), \
+             change_seq = ?, updated_at = CURRENT_TIMESTAMP \
+             WHERE id = ? AND user_id = ? AND (? IS NULL OR change_seq = ?)",
+        )
+        .bind(&payload.title)
+        .bind(&payload.content)
+        .bind(payload.content_format.map(|format| format.as_str()))
+        .bind(payload.tags.as_ref().map(Json))
+        .bind(seq)
+        .bind(id)
+        .bind(user_id)
+        .bind(expected_seq)
+        .bind(expected_seq)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let note = sqlx::query_as::<_, Note>("SELECT * FROM notes WHERE id = ?")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(Some(note))
+    }
+
+    async fn delete(&self, id: u32, user_id: u32, expected_seq: Option<i64>) -> AppResult<u64> {
+        let mut tx = self.db_pool.begin().await?;
+        let seq = next_seq(&mut tx, user_id).await?;
+        let result = sqlx::query("DELETE FROM notes WHERE id = ? AND user_id = ? AND (? IS NULL OR change_seq = ?)")
+            .bind(id)
+            .bind(user_id)
+            .bind(expected_seq)
+            .bind(expected_seq)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Ok(0);
+        }
+
+        sqlx::query(
+            "INSERT INTO note_tombstones (note_id, user_id, change_seq) VALUES (?, ?, ?) \
+             ON DUPLICATE KEY UPDATE user_id = VALUES(user_id), \
+             change_seq = VALUES(change_seq), deleted_at = CURRENT_TIMESTAMP",
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(seq)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn update_many(&self, user_id: u32, updates: &[NoteUpdate], all_or_nothing: bool) -> AppResult<Vec<Note>> {
+        // Tiap catatan dapat nomor urutnya sendiri; nomor milik baris yang tidak cocok dibiarkan bolong
+        let mut tx = self.db_pool.begin().await?;
+        let count = updates.len() as i64;
+        let first = reserve_seqs(&mut tx, user_id, count).await? - count + 1;
+        let rows: Vec<(&NoteUpdate, i64)> = updates.iter().zip(first..).collect();
+
+        // change_seq selalu berubah, jadi rows_affected = jumlah baris yang cocok
+        let mut rows_affected = 0;
+        for batch in rows.chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<MySql>::new("UPDATE notes SET ");
+            push_case(&mut query, "title", batch, |update, _| &update.title);
+            query.push(", ");
+            push_case(&mut query, "content", batch, |update, _| &update.content);
+            query.push(", ");
+            push_case(&mut query, "tags", batch, |update, _| Json(&update.tags));
+            query.push(", ");
+            push_case(&mut query, "archived", batch, |update, _| update.archived);
+            query.push(", ");
+            push_case(&mut query, "change_seq", batch, |_, seq| seq);
+            query.push(", updated_at = CURRENT_TIMESTAMP WHERE user_id = ").push_bind(user_id).push(" AND ");
+            push_versions(&mut query, batch.iter().map(|(update, _)| (update.id, update.expected_seq)));
+            rows_affected += query.build().execute(&mut *tx).await?.rows_affected();
+        }
+
+        // Tanpa commit transaksi di-rollback saat `tx` di-drop
+        if rows_affected == 0 || (all_or_nothing && rows_affected < updates.len() as u64) {
+            return Ok(Vec::new());
+        }
+
+        // Nomor urut dari rentang yang baru dipesan hanya dimiliki baris yang ikut berubah
+        let ids: Vec<u32> = updates.iter().map(|update| update.id).collect();
+        let mut updated = Vec::with_capacity(ids.len());
+        for batch in ids.chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<MySql>::new("SELECT * FROM notes WHERE user_id = ");
+            query.push_bind(user_id).push(" AND change_seq >= ").push_bind(first).push(" AND ");
+            push_ids(&mut query, "id", batch);
+            updated.extend(query.build_query_as::<Note>().fetch_all(&mut *tx).await?);
+        }
+        tx.commit().await?;
+        updated.sort_by_key(|note| note.id);
+        Ok(updated)
+    }
+
+    async fn delete_many(&self, user_id: u32, expected: &[(u32, i64)], all_or_nothing: bool) -> AppResult<Vec<u32>> {
+        // MySQL tidak punya DELETE ... RETURNING; baris yang change_seq-nya masih sama
+        // dikunci dulu supaya tidak berubah sebelum DELETE di bawah
+        let mut tx = self.db_pool.begin().await?;
+        let mut deleted = Vec::with_capacity(expected.len());
+        for batch in expected.chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<MySql>::new("SELECT id FROM notes WHERE user_id = ");
+            query.push_bind(user_id).push(" AND ");
+            push_versions(&mut query, batch.iter().copied());
+            query.push(" FOR UPDATE");
+            deleted.extend(query.build_query_scalar::<u32>().fetch_all(&mut *tx).await?);
+        }
+        if deleted.is_empty() || (all_or_nothing && deleted.len() < expected.len()) {
+            return Ok(Vec::new());
+        }
+        deleted.sort_unstable();
+
+        let count = deleted.len() as i64;
+        let first = reserve_seqs(&mut tx, user_id, count).await? - count + 1;
+        for batch in deleted.chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<MySql>::new("DELETE FROM notes WHERE user_id = ");
+            query.push_bind(user_id).push(" AND ");
+            push_ids(&mut query, "id", batch);
+            query.build().execute(&mut *tx).await?;
+        }
+        for batch in deleted.iter().zip(first..).collect::<Vec<_>>().chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<MySql>::new("INSERT INTO note_tombstones (note_id, user_id, change_seq) ");
+            query.push_values(batch, |mut row, (id, seq)| {
+                row.push_bind(**id).push_bind(user_id).push_bind(*seq);
+            });
+            query.push(
+                " ON DUPLICATE KEY UPDATE user_id = VALUES(user_id), \
+                 change_seq = VALUES(change_seq), deleted_at = CURRENT_TIMESTAMP",
+            );
+            query.build().execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    async fn changes_since(&self, user_id: u32, since: i64, limit: u32) -> AppResult<NoteChanges> {
+        // Ambil satu baris lebih dari limit untuk tahu apakah masih ada sisa
+        let notes = sqlx::query_as::<_, Note>(
+            "SELECT * FROM notes WHERE user_id = ? AND change_seq > ? ORDER BY change_seq LIMIT ?",
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(limit + 1)
+        .fetch_all(&self.db_pool)
+        .await?;
+        let tombstones = sqlx::query_as::<_, Tombstone>(
+            "SELECT * FROM note_tombstones WHERE user_id = ? AND change_seq > ? ORDER BY change_seq LIMIT ?",
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(limit + 1)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(NoteChanges::merge(notes, tombstones, limit))
+    }
+}
\ No newline at end of file
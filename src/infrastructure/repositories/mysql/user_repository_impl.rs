@@ -2,24 +2,28 @@ use async_trait::async_trait;
 use sqlx::MySqlPool;
 
 use crate::domain::{
-    models::user::{User, UserProfile},
+    models::{
+        user::{User, UserProfile},
+        user_settings::UserSettings,
+    },
     repositories::user_repository::UserRepository,
 };
 use crate::utils::error::{AppError, AppResult};
+use crate::utils::i18n::Msg;
 
 // Ini adalah "adapter" yang mengimplementasikan port UserRepository.
-pub struct UserRepositoryImpl {
+pub struct MySqlUserRepositoryImpl {
     db_pool: MySqlPool,
 }
 
-impl UserRepositoryImpl {
+impl MySqlUserRepositoryImpl {
     pub fn new(db_pool: MySqlPool) -> Self {
         Self { db_pool }
     }
 }
 
 #[async_trait]
-impl UserRepository for UserRepositoryImpl {
+impl UserRepository for MySqlUserRepositoryImpl {
     async fn find_by_email(&self, email: &str) -> AppResult<Option<User>> {
         let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE email = ?")
             .bind(email)
@@ -63,6 +67,18 @@ impl UserRepository for UserRepositoryImpl {
         Ok(users)
     }
 
+    async fn get_profiles_page(&self, limit: u32, offset: u32) -> AppResult<Vec<UserProfile>> {
+        let users = sqlx::query_as::<_, UserProfile>(
+            "SELECT id, email, full_name, username, role, created_at FROM users \
+             ORDER BY created_at DESC, id DESC LIMIT ? OFFSET ?",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(users)
+    }
+
     async fn create(&self, user_data: &User) -> AppResult<User> {
         let insert_result = sqlx::query(
             "INSERT INTO users (email, full_name, username, role, password_hash) VALUES (?, ?, ?, ?, ?)",
@@ -77,7 +93,50 @@ impl UserRepository for UserRepositoryImpl {
 
         let new_user_id = insert_result.last_insert_id() as u32;
 
-        let new_user = self.find_by_id(new_user_id).await?.ok_or(AppError::NotFound("Gagal mengambil user setelah dibuat".to_string()))?;
+        let new_user = self.find_by_id(new_user_id).await?.ok_or(AppError::NotFound(Msg::UserNotFoundAfterCreate))?;
         Ok(new_user)
     }
-}
\ No newline at end of file
+
+    async fn update_role(&self, id: u32, role: &str) -> AppResult<u64> {
+        let result = sqlx::query("UPDATE users SET role = ? WHERE id = ?")
+            .bind(role)
+            .bind(id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn update_password(&self, id: u32, password_hash: &str) -> AppResult<u64> {
+        let result = sqlx::query("UPDATE users SET password_hash = ? WHERE id = ?")
+            .bind(password_hash)
+            .bind(id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn find_settings(&self, user_id: u32) -> AppResult<Option<UserSettings>> {
+        let settings = sqlx::query_as::<_, UserSettings>(
+            "SELECT user_id, timezone, locale, date_format FROM user_settings WHERE user_id = ?",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(settings)
+    }
+
+    async fn upsert_settings(&self, settings: &UserSettings) -> AppResult<UserSettings> {
+        sqlx::query(
+            "INSERT INTO user_settings (user_id, timezone, locale, date_format) VALUES (?, ?, ?, ?) \
+             ON DUPLICATE KEY UPDATE timezone = VALUES(timezone), locale = VALUES(locale), date_format = VALUES(date_format)",
+        )
+        .bind(settings.user_id)
+        .bind(&settings.timezone)
+        .bind(&settings.locale)
+        .bind(&settings.date_format)
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(settings.clone())
+    }
+}
@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, FromRow, PgPool};
+use std::collections::BTreeMap;
+
+use crate::{
+    domain::{
+        models::attachment::{Attachment, ImageMetadata, NewAttachment},
+        repositories::attachment_repository::AttachmentRepository,
+    },
+    utils::error::AppResult,
+};
+
+// Kolom id di Postgres berupa INTEGER (i32), lihat NoteRow
+#[derive(FromRow)]
+struct AttachmentRow {
+    id: i32,
+    note_id: i32,
+    user_id: i32,
+    file_name: String,
+    content_type: String,
+    size: i64,
+    storage_key: String,
+    created_at: Option<DateTime<Utc>>,
+    width: Option<i32>,
+    height: Option<i32>,
+    exif: Option<Json<BTreeMap<String, String>>>,
+    thumbnails: Option<Json<Vec<u32>>>,
+}
+
+impl From<AttachmentRow> for Attachment {
+    fn from(row: AttachmentRow) -> Self {
+        Attachment {
+            id: row.id as u32,
+            note_id: row.note_id as u32,
+            user_id: row.user_id as u32,
+            file_name: row.file_name,
+            content_type: row.content_type,
+            size: row.size,
+            storage_key: row.storage_key,
+            created_at: row.created_at,
+            width: row.width.map(|width| width as u32),
+            height: row.height.map(|height| height as u32),
+            exif: row.exif,
+            thumbnails: row.thumbnails,
+        }
+    }
+}
+
+pub struct PgAttachmentRepositoryImpl {
+    db_pool: PgPool,
+}
+
+impl PgAttachmentRepositoryImpl {
+    pub fn new(db_pool: PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for PgAttachmentRepositoryImpl {
+    async fn create(&self, new_attachment: &NewAttachment) -> AppResult<Attachment> {
+        let row = sqlx::query_as::<_, AttachmentRow>(
+            "INSERT INTO attachments (note_id, user_id, file_name, content_type, size, storage_key) \
+             VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+        )
+        .bind(new_attachment.note_id as i32)
+        .bind(new_attachment.user_id as i32)
+        .bind(&new_attachment.file_name)
+        .bind(&new_attachment.content_type)
+        .bind(new_attachment.size)
+        .bind(&new_attachment.storage_key)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(row.into())
+    }
+
+    async fn find_by_note(&self, note_id: u32, user_id: u32) -> AppResult<Vec<Attachment>> {
+        let rows = sqlx::query_as::<_, AttachmentRow>(
+            "SELECT * FROM attachments WHERE note_id = $1 AND user_id = $2 ORDER BY id",
+        )
+        .bind(note_id as i32)
+        .bind(user_id as i32)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(rows.into_iter().map(Attachment::from).collect())
+    }
+
+    async fn find_by_id(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<Option<Attachment>> {
+        let row = sqlx::query_as::<_, AttachmentRow>(
+            "SELECT * FROM attachments WHERE id = $1 AND note_id = $2 AND user_id = $3",
+        )
+        .bind(id as i32)
+        .bind(note_id as i32)
+        .bind(user_id as i32)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(row.map(Attachment::from))
+    }
+
+    async fn delete(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<u64> {
+        let result = sqlx::query("DELETE FROM attachments WHERE id = $1 AND note_id = $2 AND user_id = $3")
+            .bind(id as i32)
+            .bind(note_id as i32)
+            .bind(user_id as i32)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_by_note(&self, note_id: u32, user_id: u32) -> AppResult<u64> {
+        let result = sqlx::query("DELETE FROM attachments WHERE note_id = $1 AND user_id = $2")
+            .bind(note_id as i32)
+            .bind(user_id as i32)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn set_image_metadata(&self, id: u32, metadata: &ImageMetadata) -> AppResult<()> {
+        sqlx::query("UPDATE attachments SET width = $1, height = $2, exif = $3, thumbnails = $4 WHERE id = $5")
+            .bind(metadata.width.map(|width| width as i32))
+            .bind(metadata.height.map(|height| height as i32))
+            .bind(metadata.exif.as_ref().map(Json))
+            .bind(Json(&metadata.thumbnails))
+            .bind(id as i32)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn total_size(&self, user_id: u32) -> AppResult<i64> {
+        // SUM(BIGINT) di Postgres menghasilkan NUMERIC
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COALESCE(SUM(size), 0)::BIGINT FROM attachments WHERE user_id = $1",
+        )
+        .bind(user_id as i32)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(total)
+    }
+}
@@ -0,0 +1,413 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, Encode, FromRow, PgPool, Postgres, QueryBuilder, Transaction, Type};
+use std::collections::HashMap;
+
+use crate::{
+    domain::{
+        models::{
+            note::{ContentFormat, NewNote, Note, NoteFilter, NoteUpdate, UpdateNotePayload},
+            sync::{NoteChanges, Tombstone},
+        },
+        repositories::note_repository::NoteRepository,
+    },
+    infrastructure::repositories::{assign_seqs, count_per_user},
+    utils::error::AppResult,
+};
+
+// Postgres tidak punya tipe unsigned, jadi kolom id berupa INTEGER (i32).
+// Row ini dipetakan dulu lalu dikonversi ke entitas domain `Note`.
+#[derive(FromRow)]
+struct NoteRow {
+    id: i32,
+    user_id: i32,
+    title: String,
+    content: Option<String>,
+    content_format: String,
+    tags: Json<Vec<String>>,
+    archived: bool,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    change_seq: i64,
+}
+
+impl From<NoteRow> for Note {
+    fn from(row: NoteRow) -> Self {
+        Note {
+            id: row.id as u32,
+            user_id: row.user_id as u32,
+            title: row.title,
+            content: row.content,
+            // Nilai selain plain/markdown ditolak CHECK constraint
+            content_format: ContentFormat::try_from(row.content_format).unwrap_or_default(),
+            tags: row.tags.0,
+            archived: row.archived,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            change_seq: row.change_seq,
+            created_at_local: None,
+            content_html: None,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct TombstoneRow {
+    note_id: i32,
+    user_id: i32,
+    change_seq: i64,
+    deleted_at: DateTime<Utc>,
+}
+
+impl From<TombstoneRow> for Tombstone {
+    fn from(row: TombstoneRow) -> Self {
+        Tombstone {
+            note_id: row.note_id as u32,
+            user_id: row.user_id as u32,
+            change_seq: row.change_seq,
+            deleted_at: row.deleted_at,
+        }
+    }
+}
+
+// Naikkan nomor urut perubahan milik user. Baris counter terkunci sampai
+// transaksi selesai, jadi perubahan milik user yang sama di-commit berurutan.
+async fn next_seq(tx: &mut Transaction<'_, Postgres>, user_id: u32) -> AppResult<i64> {
+    reserve_seqs(tx, user_id, 1).await
+}
+
+// Pesan `count` nomor urut sekaligus; hasilnya nomor terakhir dari rentang itu
+async fn reserve_seqs(tx: &mut Transaction<'_, Postgres>, user_id: u32, count: i64) -> AppResult<i64> {
+    let seq = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO note_sync_counters (user_id, last_seq) VALUES ($1, $2) \
+         ON CONFLICT (user_id) DO UPDATE SET last_seq = note_sync_counters.last_seq + EXCLUDED.last_seq \
+         RETURNING last_seq",
+    )
+    .bind(user_id as i32)
+    .bind(count)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(seq)
+}
+
+// Baris per INSERT/UPDATE multi-baris (8-12 parameter per baris, batas Postgres 65535)
+const INSERT_BATCH: usize = 100;
+
+// `column = CASE id WHEN $n THEN $m ... END` untuk UPDATE multi-baris
+fn push_case<'a, T>(
+    query: &mut QueryBuilder<'a, Postgres>,
+    column: &str,
+    batch: &'a [(&NoteUpdate, i64)],
+    value: impl Fn(&'a NoteUpdate, i64) -> T,
+) where
+    T: 'a + Encode<'a, Postgres> + Type<Postgres> + Send,
+{
+    query.push(format!("{column} = CASE id"));
+    for (update, seq) in batch {
+        query.push(" WHEN ").push_bind(update.id as i32).push(" THEN ").push_bind(value(update, *seq));
+    }
+    query.push(" END");
+}
+
+pub struct PgNoteRepositoryImpl {
+    db_pool: PgPool,
+}
+
+impl PgNoteRepositoryImpl {
+    pub fn new(db_pool: PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+// `((id = $n AND change_seq = $m) OR ...)`
+fn push_versions<'a>(query: &mut QueryBuilder<'a, Postgres>, versions: impl IntoIterator<Item = (u32, i64)>) {
+    query.push("(");
+    for (i, (id, seq)) in versions.into_iter().enumerate() {
+        if i > 0 {
+            query.push(" OR ");
+        }
+        query.push("(id = ").push_bind(id as i32).push(" AND change_seq = ").push_bind(seq).push(")");
+    }
+    query.push(")");
+}
+
+#[async_trait]
+impl NoteRepository for PgNoteRepositoryImpl {
+    async fn create(&self, new_note: &NewNote) -> AppResult<Note> {
+        let mut tx = self.db_pool.begin().await?;
+        let seq = next_seq(&mut tx, new_note.user_id).await?;
+        let created_note = sqlx::query_as::<_, NoteRow>(
+            "INSERT INTO notes (title, content, content_format, tags, user_id, created_at, updated_at, change_seq) \
+             VALUES ($1, $2, $3, $4, $5, COALESCE($6, NOW()), $7, $8) RETURNING *",
+        )
+        .bind(&new_note.title)
+        .bind(&new_note.content)
+        .bind(new_note.content_format.as_str())
+        .bind(Json(&new_note.tags))
+        .bind(new_note.user_id as i32)
+        .bind(new_note.created_at)
+        .bind(new_note.updated_at)
+        .bind(seq)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(created_note.into())
+    }
+
+    async fn create_many(&self, new_notes: &[NewNote]) -> AppResult<Vec<Note>> {
+        let mut tx = self.db_pool.begin().await?;
+        let mut next = HashMap::new();
+        for (user_id, count) in count_per_user(new_notes) {
+            next.insert(user_id, reserve_seqs(&mut tx, user_id, count).await? - count + 1);
+        }
+        let seqs = assign_seqs(new_notes, next);
+
+        let mut created = Vec::with_capacity(new_notes.len());
+        for batch in new_notes.iter().zip(seqs).collect::<Vec<_>>().chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<Postgres>::new(
+                "INSERT INTO notes (title, content, content_format, tags, user_id, created_at, updated_at, change_seq) ",
+            );
+            query.push_values(batch, |mut row, (new_note, seq)| {
+                row.push_bind(&new_note.title)
+                    .push_bind(&new_note.content)
+                    .push_bind(new_note.content_format.as_str())
+                    .push_bind(Json(&new_note.tags))
+                    .push_bind(new_note.user_id as i32)
+                    .push("COALESCE(")
+                    .push_bind_unseparated(new_note.created_at)
+                    .push_unseparated(", NOW())")
+                    .push_bind(new_note.updated_at)
+                    .push_bind(*seq);
+            });
+            query.push(" RETURNING *");
+            let mut notes = query.build_query_as::<NoteRow>().fetch_all(&mut *tx).await?;
+            // Urutan RETURNING tidak dijamin; id SERIAL naik sesuai urutan baris VALUES
+            notes.sort_by_key(|note| note.id);
+            created.extend(notes.into_iter().map(Note::from));
+        }
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn find_all(&self, user_id: u32) -> AppResult<Vec<Note>> {
+        let notes = sqlx::query_as::<_, NoteRow>(
+            "SELECT * FROM notes WHERE user_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(user_id as i32)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(notes.into_iter().map(Note::from).collect())
+    }
+
+    async fn find_page(&self, user_id: u32, limit: u32, offset: u32) -> AppResult<Vec<Note>> {
+        let notes = sqlx::query_as::<_, NoteRow>(
+            "SELECT * FROM notes WHERE user_id = $1 ORDER BY created_at DESC, id DESC LIMIT $2 OFFSET $3",
+        )
+        .bind(user_id as i32)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(notes.into_iter().map(Note::from).collect())
+    }
+
+    async fn find_filtered(
+        &self,
+        user_id: u32,
+        filter: &NoteFilter,
+        after_id: u32,
+        limit: u32,
+    ) -> AppResult<Vec<Note>> {
+        let notes = sqlx::query_as::<_, NoteRow>(
+            "SELECT * FROM notes WHERE user_id = $1 AND id > $2 \
+             AND ($3::TEXT IS NULL OR tags @> jsonb_build_array($3::TEXT)) \
+             AND ($4::TIMESTAMPTZ IS NULL OR created_at >= $4) AND ($5::TIMESTAMPTZ IS NULL OR created_at < $5) \
+             ORDER BY id LIMIT $6",
+        )
+        .bind(user_id as i32)
+        .bind(after_id as i64)
+        .bind(&filter.tag)
+        .bind(filter.from)
+        .bind(filter.until)
+        .bind(limit as i64)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(notes.into_iter().map(Note::from).collect())
+    }
+
+    async fn find_by_id(&self, id: u32, user_id: u32) -> AppResult<Option<Note>> {
+        let note = sqlx::query_as::<_, NoteRow>("SELECT * FROM notes WHERE id = $1 AND user_id = $2")
+            .bind(id as i32)
+            .bind(user_id as i32)
+            .fetch_optional(&self.db_pool)
+            .await?;
+        Ok(note.map(Note::from))
+    }
+
+    async fn find_by_ids(&self, ids: &[u32], user_id: u32) -> AppResult<Vec<Note>> {
+        let ids: Vec<i32> = ids.iter().map(|id| *id as i32).collect();
+        let notes = sqlx::query_as::<_, NoteRow>("SELECT * FROM notes WHERE user_id = $1 AND id = ANY($2) ORDER BY id")
+            .bind(user_id as i32)
+            .bind(ids)
+            .fetch_all(&self.db_pool)
+            .await?;
+        Ok(notes.into_iter().map(Note::from).collect())
+    }
+
+    async fn update(
+        &self,
+        id: u32,
+        user_id: u32,
+        payload: &UpdateNotePayload,
+        expected_seq: Option<i64>,
+    ) -> AppResult<Option<Note>> {
+        // Jika tidak ada baris yang berubah, transaksi di-rollback (nomor urut tidak terpakai)
+        let mut tx = self.db_pool.begin().await?;
+        let seq = next_seq(&mut tx, user_id).await?;
+        // COALESCE mempertahankan nilai lama untuk field yang tidak dikirim
+        let note = sqlx::query_as::<_, NoteRow>(
+            "UPDATE notes SET title = COALESCE($1, title), content = COALESCE($2, content), \
+             content_format = COALESCE($3, content_format), tags = COALESCE($4, tags), change_seq = $5, \
+             updated_at = NOW() WHERE id = $6 AND user_id = $7 AND ($8::BIGINT IS NULL OR change_seq = $8) RETURNING *",
+        )
+        .bind(&payload.title)
+        .bind(&payload.content)
+        .bind(payload.content_format.map(|format| format.as_str()))
+        .bind(payload.tags.as_ref().map(Json))
+        .bind(seq)
+        .bind(id as i32)
+        .bind(user_id as i32)
+        .bind(expected_seq)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if note.is_some() {
+            tx.commit().await?;
+        }
+        Ok(note.map(Note::from))
+    }
+
+    async fn delete(&self, id: u32, user_id: u32, expected_seq: Option<i64>) -> AppResult<u64> {
+        let mut tx = self.db_pool.begin().await?;
+        let seq = next_seq(&mut tx, user_id).await?;
+        let result = sqlx::query(
+            "DELETE FROM notes WHERE id = $1 AND user_id = $2 AND ($3::BIGINT IS NULL OR change_seq = $3)",
+        )
+        .bind(id as i32)
+        .bind(user_id as i32)
+        .bind(expected_seq)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(0);
+        }
+
+        sqlx::query(
+            "INSERT INTO note_tombstones (note_id, user_id, change_seq) VALUES ($1, $2, $3) \
+             ON CONFLICT (note_id) DO UPDATE SET user_id = EXCLUDED.user_id, \
+             change_seq = EXCLUDED.change_seq, deleted_at = NOW()",
+        )
+        .bind(id as i32)
+        .bind(user_id as i32)
+        .bind(seq)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn update_many(&self, user_id: u32, updates: &[NoteUpdate], all_or_nothing: bool) -> AppResult<Vec<Note>> {
+        // Tiap catatan dapat nomor urutnya sendiri; nomor milik baris yang tidak cocok dibiarkan bolong
+        let mut tx = self.db_pool.begin().await?;
+        let count = updates.len() as i64;
+        let first = reserve_seqs(&mut tx, user_id, count).await? - count + 1;
+        let rows: Vec<(&NoteUpdate, i64)> = updates.iter().zip(first..).collect();
+
+        let mut updated = Vec::with_capacity(updates.len());
+        for batch in rows.chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<Postgres>::new("UPDATE notes SET ");
+            push_case(&mut query, "title", batch, |update, _| &update.title);
+            query.push(", ");
+            push_case(&mut query, "content", batch, |update, _| &update.content);
+            query.push(", ");
+            push_case(&mut query, "tags", batch, |update, _| Json(&update.tags));
+            query.push(", ");
+            push_case(&mut query, "archived", batch, |update, _| update.archived);
+            query.push(", ");
+            push_case(&mut query, "change_seq", batch, |_, seq| seq);
+            query.push(", updated_at = NOW() WHERE user_id = ").push_bind(user_id as i32).push(" AND ");
+            push_versions(&mut query, batch.iter().map(|(update, _)| (update.id, update.expected_seq)));
+            query.push(" RETURNING *");
+            let rows = query.build_query_as::<NoteRow>().fetch_all(&mut *tx).await?;
+            updated.extend(rows.into_iter().map(Note::from));
+        }
+
+        // Tanpa commit transaksi di-rollback saat `tx` di-drop
+        if updated.is_empty() || (all_or_nothing && updated.len() < updates.len()) {
+            return Ok(Vec::new());
+        }
+        tx.commit().await?;
+        updated.sort_by_key(|note| note.id);
+        Ok(updated)
+    }
+
+    async fn delete_many(&self, user_id: u32, expected: &[(u32, i64)], all_or_nothing: bool) -> AppResult<Vec<u32>> {
+        let mut tx = self.db_pool.begin().await?;
+        let mut deleted = Vec::with_capacity(expected.len());
+        for batch in expected.chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<Postgres>::new("DELETE FROM notes WHERE user_id = ");
+            query.push_bind(user_id as i32).push(" AND ");
+            push_versions(&mut query, batch.iter().copied());
+            query.push(" RETURNING id");
+            deleted.extend(query.build_query_scalar::<i32>().fetch_all(&mut *tx).await?);
+        }
+        if deleted.is_empty() || (all_or_nothing && deleted.len() < expected.len()) {
+            return Ok(Vec::new());
+        }
+        let mut deleted: Vec<u32> = deleted.into_iter().map(|id| id as u32).collect();
+        deleted.sort_unstable();
+
+        let count = deleted.len() as i64;
+        let first = reserve_seqs(&mut tx, user_id, count).await? - count + 1;
+        for batch in deleted.iter().zip(first..).collect::<Vec<_>>().chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<Postgres>::new("INSERT INTO note_tombstones (note_id, user_id, change_seq) ");
+            query.push_values(batch, |mut row, (id, seq)| {
+                row.push_bind(**id as i32).push_bind(user_id as i32).push_bind(*seq);
+            });
+            query.push(
+                " ON CONFLICT (note_id) DO UPDATE SET user_id = EXCLUDED.user_id, \
+                 change_seq = EXCLUDED.change_seq, deleted_at = NOW()",
+            );
+            query.build().execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    async fn changes_since(&self, user_id: u32, since: i64, limit: u32) -> AppResult<NoteChanges> {
+        // Ambil satu baris lebih dari limit untuk tahu apakah masih ada sisa
+        let notes = sqlx::query_as::<_, NoteRow>(
+            "SELECT * FROM notes WHERE user_id = $1 AND change_seq > $2 ORDER BY change_seq LIMIT $3",
+        )
+        .bind(user_id as i32)
+        .bind(since)
+        .bind(limit as i64 + 1)
+        .fetch_all(&self.db_pool)
+        .await?;
+        let tombstones = sqlx::query_as::<_, TombstoneRow>(
+            "SELECT * FROM note_tombstones WHERE user_id = $1 AND change_seq > $2 ORDER BY change_seq LIMIT $3",
+        )
+        .bind(user_id as i32)
+        .bind(since)
+        .bind(limit as i64 + 1)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(NoteChanges::merge(
+            notes.into_iter().map(Note::from).collect(),
+            tombstones.into_iter().map(Tombstone::from).collect(),
+            limit,
+        ))
+    }
+}
@@ -0,0 +1,209 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+
+use crate::domain::{
+    models::{
+        user::{User, UserProfile},
+        user_settings::UserSettings,
+    },
+    repositories::user_repository::UserRepository,
+};
+use crate::utils::error::AppResult;
+
+// Row mentah Postgres (id INTEGER), dikonversi ke entitas domain
+#[derive(FromRow)]
+struct UserRow {
+    id: i32,
+    email: String,
+    full_name: String,
+    username: String,
+    role: String,
+    password_hash: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<UserRow> for User {
+    fn from(row: UserRow) -> Self {
+        User {
+            id: row.id as u32,
+            email: row.email,
+            full_name: row.full_name,
+            username: row.username,
+            role: row.role,
+            password_hash: row.password_hash,
+            created_at: row.created_at,
+            created_at_local: None,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct UserProfileRow {
+    id: i32,
+    email: String,
+    full_name: String,
+    username: String,
+    role: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<UserProfileRow> for UserProfile {
+    fn from(row: UserProfileRow) -> Self {
+        UserProfile {
+            id: row.id as u32,
+            email: row.email,
+            full_name: row.full_name,
+            username: row.username,
+            role: row.role,
+            created_at: row.created_at,
+            created_at_local: None,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct UserSettingsRow {
+    user_id: i32,
+    timezone: String,
+    locale: Option<String>,
+    date_format: String,
+}
+
+impl From<UserSettingsRow> for UserSettings {
+    fn from(row: UserSettingsRow) -> Self {
+        UserSettings {
+            user_id: row.user_id as u32,
+            timezone: row.timezone,
+            locale: row.locale,
+            date_format: row.date_format,
+        }
+    }
+}
+
+// Adapter UserRepository untuk PostgreSQL
+pub struct PgUserRepositoryImpl {
+    db_pool: PgPool,
+}
+
+impl PgUserRepositoryImpl {
+    pub fn new(db_pool: PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl UserRepository for PgUserRepositoryImpl {
+    async fn find_by_email(&self, email: &str) -> AppResult<Option<User>> {
+        let user = sqlx::query_as::<_, UserRow>("SELECT * FROM users WHERE email = $1")
+            .bind(email)
+            .fetch_optional(&self.db_pool)
+            .await?;
+        Ok(user.map(User::from))
+    }
+
+    async fn find_by_username(&self, username: &str) -> AppResult<Option<User>> {
+        let user = sqlx::query_as::<_, UserRow>("SELECT * FROM users WHERE username = $1")
+            .bind(username)
+            .fetch_optional(&self.db_pool)
+            .await?;
+        Ok(user.map(User::from))
+    }
+
+    async fn find_by_id(&self, id: u32) -> AppResult<Option<User>> {
+        let user = sqlx::query_as::<_, UserRow>("SELECT * FROM users WHERE id = $1")
+            .bind(id as i32)
+            .fetch_optional(&self.db_pool)
+            .await?;
+        Ok(user.map(User::from))
+    }
+
+    async fn find_profile_by_id(&self, id: u32) -> AppResult<Option<UserProfile>> {
+        let profile = sqlx::query_as::<_, UserProfileRow>(
+            "SELECT id, email, full_name, username, role, created_at FROM users WHERE id = $1",
+        )
+        .bind(id as i32)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(profile.map(UserProfile::from))
+    }
+
+    async fn get_all_profiles(&self) -> AppResult<Vec<UserProfile>> {
+        let users = sqlx::query_as::<_, UserProfileRow>(
+            "SELECT id, email, full_name, username, role, created_at FROM users ORDER BY created_at DESC",
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(users.into_iter().map(UserProfile::from).collect())
+    }
+
+    async fn get_profiles_page(&self, limit: u32, offset: u32) -> AppResult<Vec<UserProfile>> {
+        let users = sqlx::query_as::<_, UserProfileRow>(
+            "SELECT id, email, full_name, username, role, created_at FROM users \
+             ORDER BY created_at DESC, id DESC LIMIT $1 OFFSET $2",
+        )
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(users.into_iter().map(UserProfile::from).collect())
+    }
+
+    async fn create(&self, user_data: &User) -> AppResult<User> {
+        let new_user = sqlx::query_as::<_, UserRow>(
+            "INSERT INTO users (email, full_name, username, role, password_hash) VALUES ($1, $2, $3, $4, $5) RETURNING *",
+        )
+        .bind(&user_data.email)
+        .bind(&user_data.full_name)
+        .bind(&user_data.username)
+        .bind(&user_data.role)
+        .bind(&user_data.password_hash)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(new_user.into())
+    }
+
+    async fn update_role(&self, id: u32, role: &str) -> AppResult<u64> {
+        let result = sqlx::query("UPDATE users SET role = $1 WHERE id = $2")
+            .bind(role)
+            .bind(id as i32)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn update_password(&self, id: u32, password_hash: &str) -> AppResult<u64> {
+        let result = sqlx::query("UPDATE users SET password_hash = $1 WHERE id = $2")
+            .bind(password_hash)
+            .bind(id as i32)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn find_settings(&self, user_id: u32) -> AppResult<Option<UserSettings>> {
+        let settings = sqlx::query_as::<_, UserSettingsRow>(
+            "SELECT user_id, timezone, locale, date_format FROM user_settings WHERE user_id = $1",
+        )
+        .bind(user_id as i32)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(settings.map(UserSettings::from))
+    }
+
+    async fn upsert_settings(&self, settings: &UserSettings) -> AppResult<UserSettings> {
+        let saved = sqlx::query_as::<_, UserSettingsRow>(
+            "INSERT INTO user_settings (user_id, timezone, locale, date_format) VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (user_id) DO UPDATE SET timezone = EXCLUDED.timezone, locale = EXCLUDED.locale, \
+             date_format = EXCLUDED.date_format \
+             RETURNING user_id, timezone, locale, date_format",
+        )
+        .bind(settings.user_id as i32)
+        .bind(&settings.timezone)
+        .bind(&settings.locale)
+        .bind(&settings.date_format)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(saved.into())
+    }
+}
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use sqlx::{types::Json, SqlitePool};
+
+use crate::{
+    domain::{
+        models::attachment::{Attachment, ImageMetadata, NewAttachment},
+        repositories::attachment_repository::AttachmentRepository,
+    },
+    utils::error::AppResult,
+};
+
+pub struct SqliteAttachmentRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl SqliteAttachmentRepositoryImpl {
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for SqliteAttachmentRepositoryImpl {
+    async fn create(&self, new_attachment: &NewAttachment) -> AppResult<Attachment> {
+        let attachment = sqlx::query_as::<_, Attachment>(
+            "INSERT INTO attachments (note_id, user_id, file_name, content_type, size, storage_key) \
+             VALUES (?, ?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(new_attachment.note_id)
+        .bind(new_attachment.user_id)
+        .bind(&new_attachment.file_name)
+        .bind(&new_attachment.content_type)
+        .bind(new_attachment.size)
+        .bind(&new_attachment.storage_key)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(attachment)
+    }
+
+    async fn find_by_note(&self, note_id: u32, user_id: u32) -> AppResult<Vec<Attachment>> {
+        let attachments = sqlx::query_as::<_, Attachment>(
+            "SELECT * FROM attachments WHERE note_id = ? AND user_id = ? ORDER BY id",
+        )
+        .bind(note_id)
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(attachments)
+    }
+
+    async fn find_by_id(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<Option<Attachment>> {
+        let attachment = sqlx::query_as::<_, Attachment>(
+            "SELECT * FROM attachments WHERE id = ? AND note_id = ? AND user_id = ?",
+        )
+        .bind(id)
+        .bind(note_id)
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(attachment)
+    }
+
+    async fn delete(&self, id: u32, note_id: u32, user_id: u32) -> AppResult<u64> {
+        let result = sqlx::query("DELETE FROM attachments WHERE id = ? AND note_id = ? AND user_id = ?")
+            .bind(id)
+            .bind(note_id)
+            .bind(user_id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_by_note(&self, note_id: u32, user_id: u32) -> AppResult<u64> {
+        let result = sqlx::query("DELETE FROM attachments WHERE note_id = ? AND user_id = ?")
+            .bind(note_id)
+            .bind(user_id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn set_image_metadata(&self, id: u32, metadata: &ImageMetadata) -> AppResult<()> {
+        sqlx::query("UPDATE attachments SET width = ?, height = ?, exif = ?, thumbnails = ? WHERE id = ?")
+            .bind(metadata.width)
+            .bind(metadata.height)
+            .bind(metadata.exif.as_ref().map(Json))
+            .bind(Json(&metadata.thumbnails))
+            .bind(id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn total_size(&self, user_id: u32) -> AppResult<i64> {
+        let total = sqlx::query_scalar::<_, i64>("SELECT COALESCE(SUM(size), 0) FROM attachments WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_one(&self.db_pool)
+            .await?;
+        Ok(total)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod attachment_repository_impl;
+pub mod note_repository_impl;
+pub mod user_repository_impl;
@@ -0,0 +1,361 @@
+use async_trait::async_trait;
+use sqlx::{types::Json, Encode, QueryBuilder, Sqlite, SqlitePool, Transaction, Type};
+use std::collections::HashMap;
+
+use crate::{
+    domain::{
+        models::{
+            note::{NewNote, Note, NoteFilter, NoteUpdate, UpdateNotePayload},
+            sync::{NoteChanges, Tombstone},
+        },
+        repositories::note_repository::NoteRepository,
+    },
+    infrastructure::repositories::{assign_seqs, count_per_user},
+    utils::error::AppResult,
+};
+
+// Baris per INSERT/UPDATE/DELETE multi-baris; 100 x 12 parameter masih jauh di bawah batas SQLite
+const INSERT_BATCH: usize = 100;
+
+pub struct SqliteNoteRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl SqliteNoteRepositoryImpl {
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+// Naikkan nomor urut perubahan milik user di dalam transaksi `tx`
+async fn next_seq(tx: &mut Transaction<'_, Sqlite>, user_id: u32) -> AppResult<i64> {
+    reserve_seqs(tx, user_id, 1).await
+}
+
+// Pesan `count` nomor urut sekaligus; hasilnya nomor terakhir dari rentang itu
+async fn reserve_seqs(tx: &mut Transaction<'_, Sqlite>, user_id: u32, count: i64) -> AppResult<i64> {
+    let seq = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO note_sync_counters (user_id, last_seq) VALUES (?, ?) \
+         ON CONFLICT (user_id) DO UPDATE SET last_seq = last_seq + excluded.last_seq RETURNING last_seq",
+    )
+    .bind(user_id)
+    .bind(count)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(seq)
+}
+
+// `column = CASE id WHEN ? THEN ? ... END` untuk UPDATE multi-baris
+fn push_case<'a, T>(
+    query: &mut QueryBuilder<'a, Sqlite>,
+    column: &str,
+    batch: &'a [(&NoteUpdate, i64)],
+    value: impl Fn(&'a NoteUpdate, i64) -> T,
+) where
+    T: 'a + Encode<'a, Sqlite> + Type<Sqlite> + Send,
+{
+    query.push(format!("{column} = CASE id"));
+    for (update, seq) in batch {
+        query.push(" WHEN ").push_bind(update.id).push(" THEN ").push_bind(value(update, *seq));
+    }
+    query.push(" END");
+}
+
+// `((id = ? AND change_seq = ?) OR ...)`
+fn push_versions<'a>(query: &mut QueryBuilder<'a, Sqlite>, versions: impl IntoIterator<Item = (u32, i64)>) {
+    query.push("(");
+    for (i, (id, seq)) in versions.into_iter().enumerate() {
+        if i > 0 {
+            query.push(" OR ");
+        }
+        query.push("(id = ").push_bind(id).push(" AND change_seq = ").push_bind(seq).push(")");
+    }
+    query.push(")");
+}
+
+#[async_trait]
+impl NoteRepository for SqliteNoteRepositoryImpl {
+    async fn create(&self, new_note: &NewNote) -> AppResult<Note> {
+        // Waktu disimpan sebagai teks "YYYY-MM-DD HH:MM:SS" (sama dengan CURRENT_TIMESTAMP)
+        // supaya urutan ORDER BY created_at tetap benar.
+        let mut tx = self.db_pool.begin().await?;
+        let seq = next_seq(&mut tx, new_note.user_id).await?;
+        let created_note = sqlx::query_as::<_, Note>(
+            "INSERT INTO notes (title, content, content_format, tags, user_id, created_at, updated_at, change_seq) \
+             VALUES (?, ?, ?, ?, ?, COALESCE(?, CURRENT_TIMESTAMP), ?, ?) RETURNING *",
+        )
+        .bind(&new_note.title)
+        .bind(&new_note.content)
+        .bind(new_note.content_format.as_str())
+        .bind(Json(&new_note.tags))
+        .bind(new_note.user_id)
+        .bind(new_note.created_at.map(|dt| dt.naive_utc()))
+        .bind(new_note.updated_at.map(|dt| dt.naive_utc()))
+        .bind(seq)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(created_note)
+    }
+
+    async fn create_many(&self, new_notes: &[NewNote]) -> AppResult<Vec<Note>> {
+        let mut tx = self.db_pool.begin().await?;
+        let mut next = HashMap::new();
+        for (user_id, count) in count_per_user(new_notes) {
+            next.insert(user_id, reserve_seqs(&mut tx, user_id, count).await? - count + 1);
+        }
+        let seqs = assign_seqs(new_notes, next);
+
+        let mut created = Vec::with_capacity(new_notes.len());
+        for batch in new_notes.iter().zip(seqs).collect::<Vec<_>>().chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<Sqlite>::new(
+                "INSERT INTO notes (title, content, content_format, tags, user_id, created_at, updated_at, change_seq) ",
+            );
+            query.push_values(batch, |mut row, (new_note, seq)| {
+                row.push_bind(&new_note.title)
+                    .push_bind(&new_note.content)
+                    .push_bind(new_note.content_format.as_str())
+                    .push_bind(Json(&new_note.tags))
+                    .push_bind(new_note.user_id)
+                    .push("COALESCE(")
+                    .push_bind_unseparated(new_note.created_at.map(|dt| dt.naive_utc()))
+                    .push_unseparated(", CURRENT_TIMESTAMP)")
+                    .push_bind(new_note.updated_at.map(|dt| dt.naive_utc()))
+                    .push_bind(*seq);
+            });
+            query.push(" RETURNING *");
+            let mut notes = query.build_query_as::<Note>().fetch_all(&mut *tx).await?;
+            // Urutan RETURNING tidak dijamin; id naik sesuai urutan baris VALUES
+            notes.sort_by_key(|note| note.id);
+            created.extend(notes);
+        }
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn find_all(&self, user_id: u32) -> AppResult<Vec<Note>> {
+        let notes = sqlx::query_as::<_, Note>(
+            "SELECT * FROM notes WHERE user_id = ? ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(notes)
+    }
+
+    async fn find_page(&self, user_id: u32, limit: u32, offset: u32) -> AppResult<Vec<Note>> {
+        let notes = sqlx::query_as::<_, Note>(
+            "SELECT * FROM notes WHERE user_id = ? ORDER BY created_at DESC, id DESC LIMIT ? OFFSET ?",
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(notes)
+    }
+
+    async fn find_filtered(
+        &self,
+        user_id: u32,
+        filter: &NoteFilter,
+        after_id: u32,
+        limit: u32,
+    ) -> AppResult<Vec<Note>> {
+        let notes = sqlx::query_as::<_, Note>(
+            "SELECT * FROM notes WHERE user_id = ? AND id > ? \
+             AND (? IS NULL OR EXISTS (SELECT 1 FROM json_each(notes.tags) WHERE value = ?)) \
+             AND (? IS NULL OR created_at >= ?) AND (? IS NULL OR created_at < ?) \
+             ORDER BY id LIMIT ?",
+        )
+        .bind(user_id)
+        .bind(after_id)
+        .bind(&filter.tag)
+        .bind(&filter.tag)
+        .bind(filter.from.map(|dt| dt.naive_utc()))
+        .bind(filter.from.map(|dt| dt.naive_utc()))
+        .bind(filter.until.map(|dt| dt.naive_utc()))
+        .bind(filter.until.map(|dt| dt.naive_utc()))
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(notes)
+    }
+
+    async fn find_by_id(&self, id: u32, user_id: u32) -> AppResult<Option<Note>> {
+        let note = sqlx::query_as::<_, Note>("SELECT * FROM notes WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(&self.db_pool)
+            .await?;
+        Ok(note)
+    }
+
+    async fn find_by_ids(&self, ids: &[u32], user_id: u32) -> AppResult<Vec<Note>> {
+        let mut found = Vec::with_capacity(ids.len());
+        for batch in ids.chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<Sqlite>::new("SELECT * FROM notes WHERE user_id = ");
+            query.push_bind(user_id).push(" AND id IN (");
+            let mut separated = query.separated(", ");
+            for id in batch {
+                separated.push_bind(*id);
+            }
+            query.push(")");
+            found.extend(query.build_query_as::<Note>().fetch_all(&self.db_pool).await?);
+        }
+        found.sort_by_key(|note| note.id);
+        Ok(found)
+    }
+
+    async fn update(
+        &self,
+        id: u32,
+        user_id: u32,
+        payload: &UpdateNotePayload,
+        expected_seq: Option<i64>,
+    ) -> AppResult<Option<Note>> {
+        // Jika tidak ada baris yang berubah, transaksi di-rollback (nomor urut tidak terpakai)
+        let mut tx = self.db_pool.begin().await?;
+        let seq = next_seq(&mut tx, user_id).await?;
+        // COALESCE mempertahankan nilai lama untuk field yang tidak dikirim
+        let note = sqlx::query_as::<_, Note>(
+            "UPDATE notes SET title = COALESCE(?, title), content = COALESCE(?, content), \
+             content_format = COALESCE(?, content_format), tags = COALESCE(?, tags), \
+             change_seq = ?, updated_at = CURRENT_TIMESTAMP \
+             WHERE id = ? AND user_id = ? AND (? IS NULL OR change_seq = ?) RETURNING *",
+        )
+        .bind(&payload.title)
+        .bind(&payload.content)
+        .bind(payload.content_format.map(|format| format.as_str()))
+        .bind(payload.tags.as_ref().map(Json))
+        .bind(seq)
+        .bind(id)
+        .bind(user_id)
+        .bind(expected_seq)
+        .bind(expected_seq)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if note.is_some() {
+            tx.commit().await?;
+        }
+        Ok(note)
+    }
+
+    async fn delete(&self, id: u32, user_id: u32, expected_seq: Option<i64>) -> AppResult<u64> {
+        let mut tx = self.db_pool.begin().await?;
+        let seq = next_seq(&mut tx, user_id).await?;
+        let result = sqlx::query("DELETE FROM notes WHERE id = ? AND user_id = ? AND (? IS NULL OR change_seq = ?)")
+            .bind(id)
+            .bind(user_id)
+            .bind(expected_seq)
+            .bind(expected_seq)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Ok(0);
+        }
+
+        sqlx::query(
+            "INSERT INTO note_tombstones (note_id, user_id, change_seq) VALUES (?, ?, ?) \
+             ON CONFLICT (note_id) DO UPDATE SET user_id = excluded.user_id, \
+             change_seq = excluded.change_seq, deleted_at = CURRENT_TIMESTAMP",
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(seq)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn update_many(&self, user_id: u32, updates: &[NoteUpdate], all_or_nothing: bool) -> AppResult<Vec<Note>> {
+        // Tiap catatan dapat nomor urutnya sendiri; nomor milik baris yang tidak cocok dibiarkan bolong
+        let mut tx = self.db_pool.begin().await?;
+        let count = updates.len() as i64;
+        let first = reserve_seqs(&mut tx, user_id, count).await? - count + 1;
+        let rows: Vec<(&NoteUpdate, i64)> = updates.iter().zip(first..).collect();
+
+        let mut updated = Vec::with_capacity(updates.len());
+        for batch in rows.chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<Sqlite>::new("UPDATE notes SET ");
+            push_case(&mut query, "title", batch, |update, _| &update.title);
+            query.push(", ");
+            push_case(&mut query, "content", batch, |update, _| &update.content);
+            query.push(", ");
+            push_case(&mut query, "tags", batch, |update, _| Json(&update.tags));
+            query.push(", ");
+            push_case(&mut query, "archived", batch, |update, _| update.archived);
+            query.push(", ");
+            push_case(&mut query, "change_seq", batch, |_, seq| seq);
+            query.push(", updated_at = CURRENT_TIMESTAMP WHERE user_id = ").push_bind(user_id).push(" AND ");
+            push_versions(&mut query, batch.iter().map(|(update, _)| (update.id, update.expected_seq)));
+            query.push(" RETURNING *");
+            updated.extend(query.build_query_as::<Note>().fetch_all(&mut *tx).await?);
+        }
+
+        // Tanpa commit transaksi di-rollback saat `tx` di-drop
+        if updated.is_empty() || (all_or_nothing && updated.len() < updates.len()) {
+            return Ok(Vec::new());
+        }
+        tx.commit().await?;
+        updated.sort_by_key(|note| note.id);
+        Ok(updated)
+    }
+
+    async fn delete_many(&self, user_id: u32, expected: &[(u32, i64)], all_or_nothing: bool) -> AppResult<Vec<u32>> {
+        let mut tx = self.db_pool.begin().await?;
+        let mut deleted = Vec::with_capacity(expected.len());
+        for batch in expected.chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<Sqlite>::new("DELETE FROM notes WHERE user_id = ");
+            query.push_bind(user_id).push(" AND ");
+            push_versions(&mut query, batch.iter().copied());
+            query.push(" RETURNING id");
+            deleted.extend(query.build_query_scalar::<u32>().fetch_all(&mut *tx).await?);
+        }
+        if deleted.is_empty() || (all_or_nothing && deleted.len() < expected.len()) {
+            return Ok(Vec::new());
+        }
+        deleted.sort_unstable();
+
+        let count = deleted.len() as i64;
+        let first = reserve_seqs(&mut tx, user_id, count).await? - count + 1;
+        for batch in deleted.iter().zip(first..).collect::<Vec<_>>().chunks(INSERT_BATCH) {
+            let mut query = QueryBuilder::<Sqlite>::new("INSERT INTO note_tombstones (note_id, user_id, change_seq) ");
+            query.push_values(batch, |mut row, (id, seq)| {
+                row.push_bind(**id).push_bind(user_id).push_bind(*seq);
+            });
+            query.push(
+                " ON CONFLICT (note_id) DO UPDATE SET user_id = excluded.user_id, \
+                 change_seq = excluded.change_seq, deleted_at = CURRENT_TIMESTAMP",
+            );
+            query.build().execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    async fn changes_since(&self, user_id: u32, since: i64, limit: u32) -> AppResult<NoteChanges> {
+        // Ambil satu baris lebih dari limit untuk tahu apakah masih ada sisa
+        let notes = sqlx::query_as::<_, Note>(
+            "SELECT * FROM notes WHERE user_id = ? AND change_seq > ? ORDER BY change_seq LIMIT ?",
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(limit + 1)
+        .fetch_all(&self.db_pool)
+        .await?;
+        let tombstones = sqlx::query_as::<_, Tombstone>(
+            "SELECT * FROM note_tombstones WHERE user_id = ? AND change_seq > ? ORDER BY change_seq LIMIT ?",
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(limit + 1)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(NoteChanges::merge(notes, tombstones, limit))
+    }
+}
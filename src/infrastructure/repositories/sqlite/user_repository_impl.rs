@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+
+use crate::domain::{
+    models::{
+        user::{User, UserProfile},
+        user_settings::UserSettings,
+    },
+    repositories::user_repository::UserRepository,
+};
+use crate::utils::error::AppResult;
+
+// Adapter UserRepository untuk SQLite (cocok untuk development lokal)
+pub struct SqliteUserRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl SqliteUserRepositoryImpl {
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl UserRepository for SqliteUserRepositoryImpl {
+    async fn find_by_email(&self, email: &str) -> AppResult<Option<User>> {
+        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE email = ?")
+            .bind(email)
+            .fetch_optional(&self.db_pool)
+            .await?;
+        Ok(user)
+    }
+
+    async fn find_by_username(&self, username: &str) -> AppResult<Option<User>> {
+        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = ?")
+            .bind(username)
+            .fetch_optional(&self.db_pool)
+            .await?;
+        Ok(user)
+    }
+
+    async fn find_by_id(&self, id: u32) -> AppResult<Option<User>> {
+        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.db_pool)
+            .await?;
+        Ok(user)
+    }
+
+    async fn find_profile_by_id(&self, id: u32) -> AppResult<Option<UserProfile>> {
+        let profile = sqlx::query_as::<_, UserProfile>(
+            "SELECT id, email, full_name, username, role, created_at FROM users WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(profile)
+    }
+
+    async fn get_all_profiles(&self) -> AppResult<Vec<UserProfile>> {
+        let users = sqlx::query_as::<_, UserProfile>(
+            "SELECT id, email, full_name, username, role, created_at FROM users ORDER BY created_at DESC",
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(users)
+    }
+
+    async fn get_profiles_page(&self, limit: u32, offset: u32) -> AppResult<Vec<UserProfile>> {
+        let users = sqlx::query_as::<_, UserProfile>(
+            "SELECT id, email, full_name, username, role, created_at FROM users \
+             ORDER BY created_at DESC, id DESC LIMIT ? OFFSET ?",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(users)
+    }
+
+    async fn create(&self, user_data: &User) -> AppResult<User> {
+        let new_user = sqlx::query_as::<_, User>(
+            "INSERT INTO users (email, full_name, username, role, password_hash) VALUES (?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(&user_data.email)
+        .bind(&user_data.full_name)
+        .bind(&user_data.username)
+        .bind(&user_data.role)
+        .bind(&user_data.password_hash)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(new_user)
+    }
+
+    async fn update_role(&self, id: u32, role: &str) -> AppResult<u64> {
+        let result = sqlx::query("UPDATE users SET role = ? WHERE id = ?")
+            .bind(role)
+            .bind(id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn update_password(&self, id: u32, password_hash: &str) -> AppResult<u64> {
+        let result = sqlx::query("UPDATE users SET password_hash = ? WHERE id = ?")
+            .bind(password_hash)
+            .bind(id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn find_settings(&self, user_id: u32) -> AppResult<Option<UserSettings>> {
+        let settings = sqlx::query_as::<_, UserSettings>(
+            "SELECT user_id, timezone, locale, date_format FROM user_settings WHERE user_id = ?",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(settings)
+    }
+
+    async fn upsert_settings(&self, settings: &UserSettings) -> AppResult<UserSettings> {
+        let saved = sqlx::query_as::<_, UserSettings>(
+            "INSERT INTO user_settings (user_id, timezone, locale, date_format) VALUES (?, ?, ?, ?) \
+             ON CONFLICT (user_id) DO UPDATE SET timezone = excluded.timezone, locale = excluded.locale, \
+             date_format = excluded.date_format \
+             RETURNING user_id, timezone, locale, date_format",
+        )
+        .bind(settings.user_id)
+        .bind(&settings.timezone)
+        .bind(&settings.locale)
+        .bind(&settings.date_format)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(saved)
+    }
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use std::{
+    io,
+    path::{Component, Path, PathBuf},
+};
+use tokio::{fs, io::AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+
+use crate::{
+    domain::repositories::blob_store::{BlobStore, BlobStream},
+    utils::error::AppResult,
+};
+
+// Blob store di folder lokal: key `notes/1/2/abc` menjadi file `<root>/notes/1/2/abc`.
+// Cocok untuk satu instance; jika server lebih dari satu, pakai S3.
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    // Key hanya boleh berisi segmen biasa, supaya tidak bisa keluar dari `root`
+    fn path(&self, key: &str) -> io::Result<PathBuf> {
+        let relative = Path::new(key.trim_end_matches('/'));
+        let valid = relative.components().next().is_some()
+            && relative.components().all(|component| matches!(component, Component::Normal(_)));
+        if !valid {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("key blob tidak valid: {key}")));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, mut data: BlobStream<'_>) -> AppResult<u64> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        // Tulis ke file sementara lalu rename, supaya pembaca tidak pernah
+        // melihat file setengah jadi
+        let temp = path.with_file_name(format!(".{}.partial", uuid::Uuid::new_v4()));
+        let written = async {
+            let mut file = fs::File::create(&temp).await?;
+            let mut written = 0u64;
+            while let Some(chunk) = data.next().await {
+                let chunk = chunk?;
+                file.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+            }
+            file.sync_all().await?;
+            Ok::<_, io::Error>(written)
+        }
+        .await;
+
+        match written {
+            Ok(written) => {
+                fs::rename(&temp, &path).await?;
+                Ok(written)
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&temp).await;
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Option<BlobStream<'static>>> {
+        match fs::File::open(self.path(key)?).await {
+            Ok(file) => Ok(Some(Box::pin(ReaderStream::new(file)))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> AppResult<()> {
+        match fs::remove_dir_all(self.path(prefix)?).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
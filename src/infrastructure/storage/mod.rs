@@ -0,0 +1,24 @@
+pub mod local_store;
+pub mod s3_store;
+
+use std::sync::Arc;
+
+use crate::{
+    domain::repositories::blob_store::DynBlobStore,
+    utils::{
+        config::{BlobStoreBackend, Config},
+        error::AppResult,
+    },
+};
+
+use local_store::LocalBlobStore;
+use s3_store::S3BlobStore;
+
+// Pilih penyimpanan isi lampiran sesuai BLOB_STORE_BACKEND
+pub fn create_blob_store(config: &Config) -> AppResult<DynBlobStore> {
+    let store: DynBlobStore = match config.blob_store_backend {
+        BlobStoreBackend::Local => Arc::new(LocalBlobStore::new(&config.attachment_dir)),
+        BlobStoreBackend::S3 => Arc::new(S3BlobStore::from_config(config)?),
+    };
+    Ok(store)
+}
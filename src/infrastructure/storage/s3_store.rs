@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use futures_util::{StreamExt, TryStreamExt};
+use object_store::{aws::AmazonS3Builder, buffered::BufWriter, memory::InMemory, path::Path, ObjectStore};
+use std::{io, sync::Arc};
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    domain::repositories::blob_store::{BlobStore, BlobStream},
+    utils::{config::Config, error::{AppError, AppResult}},
+};
+
+// Blob store di atas `object_store`: bucket S3, MinIO, atau layanan
+// S3-compatible lain. Test memakai `object_store::memory::InMemory` sebagai S3 palsu.
+pub struct S3BlobStore {
+    store: Arc<dyn ObjectStore>,
+}
+
+impl S3BlobStore {
+    pub fn new(store: Arc<dyn ObjectStore>) -> Self {
+        Self { store }
+    }
+
+    // S3 palsu di memori, untuk test tanpa MinIO
+    pub fn in_memory() -> Self {
+        Self::new(Arc::new(InMemory::new()))
+    }
+
+    pub fn from_config(config: &Config) -> AppResult<Self> {
+        let mut builder = AmazonS3Builder::new()
+            .with_bucket_name(&config.s3_bucket)
+            .with_region(&config.s3_region)
+            .with_access_key_id(&config.s3_access_key_id)
+            .with_secret_access_key(&config.s3_secret_access_key);
+        if let Some(endpoint) = config.s3_endpoint.as_deref().filter(|endpoint| !endpoint.is_empty()) {
+            // MinIO lokal biasanya tanpa TLS dan memakai path-style (`/bucket/key`)
+            builder = builder
+                .with_endpoint(endpoint)
+                .with_allow_http(endpoint.starts_with("http://"))
+                .with_virtual_hosted_style_request(false);
+        }
+        Ok(Self::new(Arc::new(builder.build()?)))
+    }
+}
+
+fn location(key: &str) -> AppResult<Path> {
+    Path::parse(key).map_err(|e| AppError::StorageError(Box::new(e)))
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, mut data: BlobStream<'_>) -> AppResult<u64> {
+        // File kecil dikirim sekali PUT, file besar otomatis jadi multipart upload
+        let mut writer = BufWriter::new(self.store.clone(), location(key)?);
+        let written = async {
+            let mut written = 0u64;
+            while let Some(chunk) = data.next().await {
+                let chunk = chunk?;
+                written += chunk.len() as u64;
+                writer.put(chunk).await?;
+            }
+            writer.shutdown().await?;
+            Ok::<_, io::Error>(written)
+        }
+        .await;
+
+        match written {
+            Ok(written) => Ok(written),
+            Err(e) => {
+                // Batalkan multipart upload yang belum selesai
+                let _ = writer.abort().await;
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Option<BlobStream<'static>>> {
+        match self.store.get(&location(key)?).await {
+            Ok(result) => Ok(Some(Box::pin(result.into_stream().map_err(io::Error::from)))),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        match self.store.delete(&location(key)?).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> AppResult<()> {
+        let prefix = location(prefix.trim_end_matches('/'))?;
+        let locations = self.store.list(Some(&prefix)).map_ok(|meta| meta.location).boxed();
+        self.store.delete_stream(locations).try_collect::<Vec<_>>().await?;
+        Ok(())
+    }
+}
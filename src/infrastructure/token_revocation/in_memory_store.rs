@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use moka::{sync::Cache, Expiry};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    domain::repositories::token_revocation_store::TokenRevocationStore,
+    utils::error::{AppError, AppResult},
+};
+
+// Nilai cache = TTL entri, dipakai oleh `PerEntryTtl` di bawah
+struct PerEntryTtl;
+
+impl Expiry<String, Duration> for PerEntryTtl {
+    fn expire_after_create(&self, _key: &String, ttl: &Duration, _created_at: Instant) -> Option<Duration> {
+        Some(*ttl)
+    }
+}
+
+// Penyimpanan revokasi di memori proses (moka, TTL per entri).
+// Cocok untuk satu instance / development / test tanpa Redis.
+// Sengaja tanpa `max_capacity` moka: entri yang dibuang karena cache penuh berarti
+// token yang sudah logout berlaku lagi. Jika penuh, `revoke` gagal (logout ditolak)
+// sampai ada entri yang kedaluwarsa.
+pub struct InMemoryTokenRevocationStore {
+    cache: Cache<String, Duration>,
+    max_capacity: u64,
+    // Cek kapasitas & insert dalam satu lock supaya revoke bersamaan tidak melewati kapasitas
+    revoke_lock: Mutex<()>,
+}
+
+impl InMemoryTokenRevocationStore {
+    pub fn new(max_capacity: u64) -> Self {
+        Self {
+            cache: Cache::builder().expire_after(PerEntryTtl).build(),
+            max_capacity,
+            revoke_lock: Mutex::new(()),
+        }
+    }
+}
+
+impl Default for InMemoryTokenRevocationStore {
+    fn default() -> Self {
+        Self::new(100_000)
+    }
+}
+
+#[async_trait]
+impl TokenRevocationStore for InMemoryTokenRevocationStore {
+    async fn revoke(&self, token_hash: &str, ttl_secs: u64) -> AppResult<()> {
+        let _guard = self.revoke_lock.lock().unwrap();
+        // entry_count baru akurat setelah tugas tertunda (termasuk buang entri kedaluwarsa) dijalankan
+        self.cache.run_pending_tasks();
+        if self.cache.entry_count() >= self.max_capacity && !self.cache.contains_key(token_hash) {
+            return Err(AppError::StorageError(
+                format!("penyimpanan revokasi token penuh ({} entri)", self.max_capacity).into(),
+            ));
+        }
+        self.cache.insert(token_hash.to_string(), Duration::from_secs(ttl_secs));
+        Ok(())
+    }
+
+    async fn is_revoked(&self, token_hash: &str) -> AppResult<bool> {
+        Ok(self.cache.contains_key(token_hash))
+    }
+
+    async fn purge(&self) -> AppResult<usize> {
+        self.cache.run_pending_tasks();
+        let count = self.cache.entry_count() as usize;
+        self.cache.invalidate_all();
+        self.cache.run_pending_tasks();
+        Ok(count)
+    }
+}
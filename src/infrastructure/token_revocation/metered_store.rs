@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::{
+    domain::repositories::token_revocation_store::{DynTokenRevocationStore, TokenRevocationStore},
+    utils::{error::AppResult, metrics::Metrics},
+};
+
+// Decorator pencatat latensi penyimpanan revokasi.
+// Dengan backend Redis, ini adalah latensi perintah Redis per operasi.
+pub struct MeteredTokenRevocationStore {
+    inner: DynTokenRevocationStore,
+    backend: &'static str,
+    metrics: Arc<Metrics>,
+}
+
+impl MeteredTokenRevocationStore {
+    pub fn new(inner: DynTokenRevocationStore, backend: &'static str, metrics: Arc<Metrics>) -> Self {
+        Self { inner, backend, metrics }
+    }
+
+    async fn observe<T>(&self, operation: &str, fut: impl Future<Output = AppResult<T>>) -> AppResult<T> {
+        let started = Instant::now();
+        let result = fut.await;
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+        self.metrics
+            .token_revocation_duration_seconds
+            .with_label_values(&[self.backend, operation, outcome])
+            .observe(started.elapsed().as_secs_f64());
+        result
+    }
+}
+
+#[async_trait]
+impl TokenRevocationStore for MeteredTokenRevocationStore {
+    async fn revoke(&self, token_hash: &str, ttl_secs: u64) -> AppResult<()> {
+        self.observe("revoke", self.inner.revoke(token_hash, ttl_secs)).await
+    }
+
+    async fn is_revoked(&self, token_hash: &str) -> AppResult<bool> {
+        self.observe("is_revoked", self.inner.is_revoked(token_hash)).await
+    }
+
+    async fn purge(&self) -> AppResult<usize> {
+        self.observe("purge", self.inner.purge()).await
+    }
+
+    async fn close(&self) {
+        self.inner.close().await
+    }
+}
@@ -0,0 +1,46 @@
+pub mod in_memory_store;
+pub mod metered_store;
+pub mod redis_store;
+pub mod sql_store;
+
+use std::sync::Arc;
+
+use crate::{
+    domain::repositories::token_revocation_store::DynTokenRevocationStore,
+    utils::{
+        config::{Config, TokenRevocationBackend},
+        db::{self, DbPool},
+        metrics::Metrics,
+    },
+};
+
+use in_memory_store::InMemoryTokenRevocationStore;
+use metered_store::MeteredTokenRevocationStore;
+use redis_store::RedisTokenRevocationStore;
+use sql_store::SqlTokenRevocationStore;
+
+// Pilih penyimpanan revokasi token sesuai TOKEN_REVOCATION_BACKEND
+pub fn create_token_revocation_store(
+    config: &Config,
+    pool: &DbPool,
+) -> redis::RedisResult<DynTokenRevocationStore> {
+    let store: DynTokenRevocationStore = match config.token_revocation_backend {
+        TokenRevocationBackend::Redis => {
+            Arc::new(RedisTokenRevocationStore::new(db::create_redis_client(config)?))
+        }
+        TokenRevocationBackend::Memory => {
+            Arc::new(InMemoryTokenRevocationStore::new(config.token_revocation_cache_capacity))
+        }
+        TokenRevocationBackend::Database => Arc::new(SqlTokenRevocationStore::new(pool.clone())),
+    };
+    Ok(store)
+}
+
+// Bungkus penyimpanan revokasi dengan decorator metrik latensi
+pub fn with_metrics(
+    store: DynTokenRevocationStore,
+    backend: TokenRevocationBackend,
+    metrics: &Arc<Metrics>,
+) -> DynTokenRevocationStore {
+    Arc::new(MeteredTokenRevocationStore::new(store, backend.as_str(), metrics.clone()))
+}
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use redis::{aio::ConnectionManager, AsyncCommands, Client as RedisClient};
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::repositories::token_revocation_store::TokenRevocationStore,
+    utils::{db, error::AppResult},
+};
+
+// Semua token yang di-revoke disimpan dengan prefix ini,
+// supaya bisa dibedakan dari key lain di database Redis yang sama.
+pub const KEY_PREFIX: &str = "revoked:";
+
+fn revocation_key(token_hash: &str) -> String {
+    format!("{}{}", KEY_PREFIX, token_hash)
+}
+
+pub struct RedisTokenRevocationStore {
+    redis_client: RedisClient,
+    // Satu ConnectionManager dipakai bersama semua request (otomatis reconnect).
+    // Dibuat saat pertama dipakai supaya server tetap bisa start walau Redis mati,
+    // dan dilepas di `close()` saat server berhenti.
+    manager: Mutex<Option<ConnectionManager>>,
+}
+
+impl RedisTokenRevocationStore {
+    pub fn new(redis_client: RedisClient) -> Self {
+        Self {
+            redis_client,
+            manager: Mutex::new(None),
+        }
+    }
+
+    async fn connection(&self) -> AppResult<ConnectionManager> {
+        // Clone ConnectionManager murah: semua clone berbagi koneksi yang sama
+        if let Some(manager) = self.manager.lock().await.as_ref() {
+            return Ok(manager.clone());
+        }
+        // Koneksi dibuka di luar lock (dengan batas waktu), supaya saat Redis mati
+        // request lain tidak mengantre di belakang satu percobaan reconnect
+        let connected = db::connect_redis(&self.redis_client).await?;
+        // Request lain mungkin sudah lebih dulu tersambung; pakai yang itu
+        Ok(self.manager.lock().await.get_or_insert(connected).clone())
+    }
+}
+
+#[async_trait]
+impl TokenRevocationStore for RedisTokenRevocationStore {
+    #[tracing::instrument(name = "redis.SETEX", skip_all, fields(otel.kind = "client", db.system = "redis"))]
+    async fn revoke(&self, token_hash: &str, ttl_secs: u64) -> AppResult<()> {
+        let mut conn = self.connection().await?;
+        let _: () = conn.set_ex(revocation_key(token_hash), 1, ttl_secs).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "redis.EXISTS", skip_all, fields(otel.kind = "client", db.system = "redis"))]
+    async fn is_revoked(&self, token_hash: &str) -> AppResult<bool> {
+        let mut conn = self.connection().await?;
+        let exists: bool = conn.exists(revocation_key(token_hash)).await?;
+        Ok(exists)
+    }
+
+    // Memakai SCAN (bukan KEYS) agar Redis tidak terblokir saat datanya besar.
+    #[tracing::instrument(name = "redis.SCAN+DEL", skip_all, fields(otel.kind = "client", db.system = "redis"))]
+    async fn purge(&self) -> AppResult<usize> {
+        let mut conn = self.connection().await?;
+
+        let keys: Vec<String> = {
+            let mut scan_conn = conn.clone();
+            let mut iter = scan_conn.scan_match::<_, String>(format!("{}*", KEY_PREFIX)).await?;
+            let mut keys = Vec::new();
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            keys
+        };
+
+        for chunk in keys.chunks(500) {
+            let _: () = conn.del(chunk).await?;
+        }
+
+        Ok(keys.len())
+    }
+
+    async fn close(&self) {
+        // Koneksi tertutup setelah clone terakhir (yang sedang dipakai request) di-drop
+        self.manager.lock().await.take();
+    }
+}
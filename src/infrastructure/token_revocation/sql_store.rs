@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::{
+    domain::repositories::token_revocation_store::TokenRevocationStore,
+    utils::{db::DbPool, error::AppResult},
+};
+
+// Penyimpanan revokasi di tabel `revoked_tokens` pada database utama.
+// Berguna jika Redis tidak tersedia tapi ada beberapa instance server.
+pub struct SqlTokenRevocationStore {
+    db_pool: DbPool,
+}
+
+impl SqlTokenRevocationStore {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl TokenRevocationStore for SqlTokenRevocationStore {
+    #[tracing::instrument(name = "revoked_tokens.revoke", skip_all, fields(otel.kind = "client", db.system = %self.db_pool.backend_name()))]
+    async fn revoke(&self, token_hash: &str, ttl_secs: u64) -> AppResult<()> {
+        let now = Utc::now();
+        let expires_at = now + Duration::seconds(ttl_secs as i64);
+
+        // Sekalian bersihkan entri yang sudah kedaluwarsa
+        match &self.db_pool {
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => {
+                sqlx::query("DELETE FROM revoked_tokens WHERE expires_at <= ?")
+                    .bind(now)
+                    .execute(pool)
+                    .await?;
+                sqlx::query(
+                    "INSERT INTO revoked_tokens (token_hash, expires_at) VALUES (?, ?) \
+                     ON DUPLICATE KEY UPDATE expires_at = VALUES(expires_at)",
+                )
+                .bind(token_hash)
+                .bind(expires_at)
+                .execute(pool)
+                .await?;
+            }
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => {
+                sqlx::query("DELETE FROM revoked_tokens WHERE expires_at <= $1")
+                    .bind(now)
+                    .execute(pool)
+                    .await?;
+                sqlx::query(
+                    "INSERT INTO revoked_tokens (token_hash, expires_at) VALUES ($1, $2) \
+                     ON CONFLICT (token_hash) DO UPDATE SET expires_at = EXCLUDED.expires_at",
+                )
+                .bind(token_hash)
+                .bind(expires_at)
+                .execute(pool)
+                .await?;
+            }
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => {
+                sqlx::query("DELETE FROM revoked_tokens WHERE expires_at <= ?")
+                    .bind(now.naive_utc())
+                    .execute(pool)
+                    .await?;
+                sqlx::query(
+                    "INSERT INTO revoked_tokens (token_hash, expires_at) VALUES (?, ?) \
+                     ON CONFLICT (token_hash) DO UPDATE SET expires_at = excluded.expires_at",
+                )
+                .bind(token_hash)
+                .bind(expires_at.naive_utc())
+                .execute(pool)
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "revoked_tokens.is_revoked", skip_all, fields(otel.kind = "client", db.system = %self.db_pool.backend_name()))]
+    async fn is_revoked(&self, token_hash: &str) -> AppResult<bool> {
+        let now = Utc::now();
+        let found: Option<i32> = match &self.db_pool {
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => {
+                sqlx::query_scalar("SELECT 1 FROM revoked_tokens WHERE token_hash = ? AND expires_at > ?")
+                    .bind(token_hash)
+                    .bind(now)
+                    .fetch_optional(pool)
+                    .await?
+            }
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => {
+                sqlx::query_scalar("SELECT 1 FROM revoked_tokens WHERE token_hash = $1 AND expires_at > $2")
+                    .bind(token_hash)
+                    .bind(now)
+                    .fetch_optional(pool)
+                    .await?
+            }
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => {
+                sqlx::query_scalar("SELECT 1 FROM revoked_tokens WHERE token_hash = ? AND expires_at > ?")
+                    .bind(token_hash)
+                    .bind(now.naive_utc())
+                    .fetch_optional(pool)
+                    .await?
+            }
+        };
+        Ok(found.is_some())
+    }
+
+    #[tracing::instrument(name = "revoked_tokens.purge", skip_all, fields(otel.kind = "client", db.system = %self.db_pool.backend_name()))]
+    async fn purge(&self) -> AppResult<usize> {
+        let rows_affected = match &self.db_pool {
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => sqlx::query("DELETE FROM revoked_tokens").execute(pool).await?.rows_affected(),
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => sqlx::query("DELETE FROM revoked_tokens").execute(pool).await?.rows_affected(),
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => sqlx::query("DELETE FROM revoked_tokens").execute(pool).await?.rows_affected(),
+        };
+        Ok(rows_affected as usize)
+    }
+}
@@ -0,0 +1,44 @@
+// Layering
+pub mod domain;
+pub mod application;
+pub mod infrastructure;
+pub mod presentation;
+pub mod utils; // <-- DEKLARASIKAN MODUL UTILS
+pub mod cli;
+pub mod server;
+
+// Impor dependensi yang dibutuhkan
+use crate::domain::repositories::{
+    attachment_repository::DynAttachmentRepository,
+    blob_store::DynBlobStore,
+    event_bus::DynEventBus,
+    health_check::DynHealthCheck,
+    note_repository::DynNoteRepository,
+    token_revocation_store::DynTokenRevocationStore,
+    user_repository::DynUserRepository,
+};
+use crate::application::{
+    collab_service::CollabHub, import_service::ImportJobs, note_renderer::NoteRenderer, user_service::SettingsCache,
+};
+use crate::utils::{background::BackgroundTasks, config::Config, metrics::Metrics};
+use std::sync::Arc;
+
+// State bersama untuk semua handler.
+// Tidak ada pool database di sini: handler hanya mengenal trait repository.
+#[derive(Clone)]
+pub struct AppState {
+    pub config: Arc<Config>, // Simpan juga config di AppState (opsional, tapi bagus)
+    pub user_repo: DynUserRepository,
+    pub note_repo: DynNoteRepository,
+    pub attachment_repo: DynAttachmentRepository,
+    pub blobs: DynBlobStore, // Isi file lampiran (folder lokal atau S3)
+    pub token_revocation: DynTokenRevocationStore, // Redis/DB di produksi, in-memory untuk test
+    pub health_checks: Vec<DynHealthCheck>, // Dependensi yang di-ping oleh /health/ready
+    pub metrics: Arc<Metrics>, // Metrik Prometheus untuk /metrics
+    pub background: BackgroundTasks, // Background task yang ikut berhenti saat shutdown
+    pub events: DynEventBus, // Event perubahan catatan untuk /events (memori atau Redis pub/sub)
+    pub collab: Arc<CollabHub>, // Ruang edit kolaboratif /notes/:id/collab
+    pub renderer: Arc<NoteRenderer>, // Render HTML catatan (?render=html) + cache per versi
+    pub imports: Arc<ImportJobs>, // Status job POST /notes/import (memori, 24 jam)
+    pub settings: Arc<SettingsCache>, // Pengaturan user yang dibaca auth_middleware (memori, 30 detik)
+}
@@ -1,73 +1,185 @@
-// Layering
-mod domain;
-mod application;
-mod infrastructure;
-mod presentation;
-mod utils; // <-- DEKLARASIKAN MODUL UTILS
-
 // Impor dependensi yang dibutuhkan
-use crate::domain::repositories::{note_repository::DynNoteRepository, user_repository::DynUserRepository};
-use crate::infrastructure::repositories::{note_repository_impl::NoteRepositoryImpl, user_repository_impl::UserRepositoryImpl};
-use crate::presentation::routes::create_router;
-use crate::utils::{config::{Config, load_config}, db};
-use sqlx::MySqlPool;
+use api_catatan::application::{
+    attachment_service::{AttachmentSettings, AttachmentService},
+    collab_service::CollabHub,
+    import_service::ImportJobs,
+    note_renderer::NoteRenderer,
+    user_service::SettingsCache,
+};
+use api_catatan::cli::{run_migrate, ConfigArgs, MigrateAction};
+use api_catatan::infrastructure::{
+    events::create_event_bus,
+    health::{DatabaseHealthCheck, RedisHealthCheck},
+    repositories::{self, create_repositories},
+    storage::create_blob_store,
+    token_revocation::{self, create_token_revocation_store},
+};
+use api_catatan::domain::repositories::health_check::DynHealthCheck;
+use api_catatan::presentation::routes::create_router;
+use api_catatan::utils::{config::{Config, EventBusBackend, TokenRevocationBackend}, db, metrics::Metrics, telemetry};
+use api_catatan::utils::background::BackgroundTasks;
+use api_catatan::{server, AppState};
+use clap::{Parser, Subcommand};
+use std::error::Error;
 use std::sync::Arc;
-use redis::Client as RedisClient;
-
-#[derive(Clone)]
-pub struct AppState {
-    pub db_pool: MySqlPool,
-    pub config: Arc<Config>, // Simpan juga config di AppState (opsional, tapi bagus)
-    pub redis_client: RedisClient, // Simpan client Redis di AppState
-    pub user_repo: DynUserRepository,
-    pub note_repo: DynNoteRepository,
+
+#[derive(Parser)]
+#[command(name = "api_catatan", version, about = "REST API Catatan")]
+struct Cli {
+    #[command(flatten)]
+    config: ConfigArgs,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Jalankan server HTTP (default jika tanpa subcommand)
+    Serve,
+    /// Kelola migrasi database
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
 }
 
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).init();
+    let cli = Cli::parse();
+
+    // Muat config (default -> config.toml -> env), keluar jika tidak valid
+    let config = cli.config.load_or_exit();
 
-    // Muat semua config dari .env ke struct
-    let config = load_config();
+    // Logging + tracing OpenTelemetry (jika OTEL_EXPORTER_OTLP_ENDPOINT diisi)
+    let tracer_provider = telemetry::init_tracing(&config);
 
+    let result = match cli.command {
+        Some(Command::Migrate { action }) => {
+            run_migrate(&config, action).await;
+            Ok(())
+        }
+        Some(Command::Serve) | None => serve(config).await,
+    };
+
+    // Kirim sisa span yang masih di-buffer sebelum proses selesai
+    if let Some(provider) = tracer_provider {
+        let _ = tokio::task::spawn_blocking(move || provider.shutdown()).await;
+    }
+
+    // Gagal start (database mati, port dipakai, dll): tampilkan pesan yang jelas
+    if let Err(e) = result {
+        tracing::error!("{e}");
+        std::process::exit(1);
+    }
+}
+
+async fn serve(config: Config) -> Result<(), Box<dyn Error>> {
     // Kirim config ke fungsi 'create_pool'
-    let pool = db::create_pool(&config).await;
-
-    // Bangun Redis connection string
-    // Format: redis://[username:password@]host[:port][/database]
-    let redis_url = format!(
-        "redis://:{}@{}:{}/{}",
-        config.redis_password, // Asumsi password ada, walau kosong
-        config.redis_host,
-        config.redis_port,
-        config.redis_db
+    let pool = db::create_pool(&config)
+        .await
+        .map_err(|e| format!("Gagal terhubung ke database: {e}"))?;
+
+    // Metrik Prometheus; statistik pool dibaca langsung saat /metrics di-scrape
+    let metrics = Arc::new(Metrics::new());
+    metrics.register_db_pool(pool.clone());
+
+    // Penyimpanan token yang sudah logout (Redis, memori, atau tabel database)
+    let token_revocation = token_revocation::with_metrics(
+        create_token_revocation_store(&config, &pool).map_err(|e| format!("Konfigurasi Redis tidak valid: {e}"))?,
+        config.token_revocation_backend,
+        &metrics,
     );
 
-    // Buat client Redis menggunakan URL yang baru dibuat
-    let redis_client = RedisClient::open(redis_url) // Gunakan 'redis_url'
-        .expect("Gagal terhubung ke Redis");
+    // Inisialisasi Repositories sesuai backend database, dibungkus decorator metrik
+    let (user_repo, note_repo, attachment_repo) = repositories::with_metrics(create_repositories(&pool), &metrics);
+    tracing::info!("Memakai backend database: {}", pool.backend_name());
+
+    // Dependensi yang dicek oleh /health/ready.
+    // Redis hanya kritis jika dipakai untuk revokasi token atau event bus.
+    let redis_critical = config.token_revocation_backend == TokenRevocationBackend::Redis
+        || config.event_bus_backend == EventBusBackend::Redis;
+    let redis_client = db::create_redis_client(&config).map_err(|e| format!("Konfigurasi Redis tidak valid: {e}"))?;
+    let health_checks: Vec<DynHealthCheck> = vec![
+        Arc::new(DatabaseHealthCheck::new(pool.clone())),
+        Arc::new(RedisHealthCheck::new(redis_client, redis_critical)),
+    ];
+
+    let addr = format!("{}:{}", config.server_host, config.server_port);
+    let drain_timeout = config.shutdown_timeout;
+    let background = BackgroundTasks::new();
+
+    // Event perubahan catatan untuk /events; dengan Redis, listener pub/sub
+    // berjalan sebagai background task
+    let events = create_event_bus(&config, &background).map_err(|e| format!("Konfigurasi Redis tidak valid: {e}"))?;
+
+    // Ruang edit kolaboratif; isinya disimpan berkala ke tabel notes
+    let collab = Arc::new(CollabHub::new(note_repo.clone(), events.clone(), config.collab_snapshot_interval));
 
-    // Inisialisasi Repositories
-    let user_repo = Arc::new(UserRepositoryImpl::new(pool.clone())) as DynUserRepository;
-    let note_repo = Arc::new(NoteRepositoryImpl::new(pool.clone())) as DynNoteRepository;
+    // Isi file lampiran; lampiran catatan yang dihapus dibersihkan di background
+    let blobs = create_blob_store(&config).map_err(|e| format!("Konfigurasi blob store tidak valid: {e}"))?;
+    AttachmentService::new(
+        attachment_repo.clone(),
+        note_repo.clone(),
+        blobs.clone(),
+        AttachmentSettings::from(&config),
+        background.clone(),
+    )
+    .spawn_cleanup(&events);
+
+    // Hasil render HTML catatan, di-cache per versi catatan
+    let renderer = Arc::new(NoteRenderer::new(config.render_cache_capacity));
 
     // Buat AppState
     let app_state = Arc::new(AppState {
-        db_pool: pool,
         config: Arc::new(config),
-        redis_client: redis_client,
         user_repo,
         note_repo,
+        attachment_repo,
+        blobs,
+        token_revocation: token_revocation.clone(),
+        health_checks,
+        metrics,
+        background: background.clone(),
+        events: events.clone(),
+        collab,
+        renderer,
+        imports: Arc::new(ImportJobs::new()),
+        settings: Arc::new(SettingsCache::new()),
     });
 
     // Buat router dengan state
     let app = create_router(app_state);
 
-    // Jalankan server di port 3000
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .map_err(|e| format!("Gagal membuka port {addr}: {e}"))?;
+
+    tracing::info!("Server berjalan di http://{}", addr);
+
+    // SIGTERM/SIGINT membatalkan token ini: server berhenti menerima koneksi
+    // baru dan background task diberi tahu untuk selesai.
+    let shutdown = background.cancellation_token();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            server::shutdown_signal().await;
+            shutdown.cancel();
+        }
+    });
+
+    let drained = server::serve(listener, app, shutdown, drain_timeout).await?;
+    if drained {
+        tracing::info!("Semua request selesai diproses");
+    }
 
-    tracing::info!("Server berjalan di http://0.0.0.0:3000");
+    // Tunggu background task, lalu tutup koneksi Redis dan pool database
+    if !background.shutdown(drain_timeout).await {
+        tracing::warn!("Sebagian background task belum selesai saat shutdown");
+    }
+    token_revocation.close().await;
+    events.close().await;
+    pool.close().await;
+    tracing::info!("Server berhenti");
 
-    // Jalankan server dengan listener yang sudah dibuat
-    axum::serve(listener, app).await.unwrap();
+    Ok(())
 }
@@ -9,14 +9,34 @@ use axum::http::header;
 use std::sync::Arc;
 use chrono::Utc;
 use jsonwebtoken::{ decode, Validation, DecodingKey };
-use redis::AsyncCommands; // <-- Penting untuk .exists() dan .set_ex()
 
 use crate::{
-    domain::models::user::TokenClaims,
-    utils::error::AppError,
+    application::user_service::UserService,
+    domain::{
+        models::user::{TokenClaims, TokenHash},
+        repositories::token_revocation_store::hash_token,
+    },
+    utils::{error::AppError, i18n::{set_current_locale, Locale}},
     AppState,
 };
 
+// EventSource & WebSocket di browser tidak bisa mengirim header Authorization,
+// jadi khusus /events dan /notes/:id/collab token boleh dikirim lewat `?access_token=`.
+// Log & trace hanya mencatat path, jadi query ini tidak ikut tercatat.
+fn stream_query_token(req: &Request<Body>) -> Option<String> {
+    let path = req.uri().path();
+    let is_collab = path.starts_with("/notes/") && path.ends_with("/collab");
+    if !path.starts_with("/events") && !is_collab {
+        return None;
+    }
+    req.uri()
+        .query()?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == "access_token")
+        .map(|(_, value)| value.to_string())
+}
+
 // Ini adalah middleware auth
 pub async fn auth_middleware(
     State(state): State<Arc<AppState>>,
@@ -28,9 +48,9 @@ pub async fn auth_middleware(
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|auth_header| auth_header.to_str().ok())
-        .and_then(|auth_value| {
-            if auth_value.starts_with("Bearer ") { Some(auth_value[7..].to_string()) } else { None }
-        });
+        .and_then(|auth_value| auth_value.strip_prefix("Bearer "))
+        .map(|token| token.to_string())
+        .or_else(|| stream_query_token(&req));
 
     let token_str = match token {
         Some(token) => token,
@@ -39,31 +59,8 @@ pub async fn auth_middleware(
         }
     };
 
-    // 2. Cek apakah token ada di blacklist (di Redis)
-    let mut redis_conn = match state.redis_client.get_multiplexed_async_connection().await {
-        Ok(conn) => conn,
-        Err(e) => {
-            tracing::error!("Gagal konek Redis: {}", e);
-            //     return auth_error(AppError::InvalidToken); // Anggap invalid jika Redis down
-            return AppError::InvalidToken.into_response();
-        }
-    };
-
-    let is_blacklisted: bool = match redis_conn.exists(&token_str).await {
-        Ok(exists) => exists,
-        Err(e) => {
-            tracing::error!("Gagal cek Redis: {}", e);
-            //     return auth_error(AppError::InvalidToken);
-            return AppError::InvalidToken.into_response();
-        }
-    };
-
-    if is_blacklisted {
-        // return auth_error(AppError::InvalidToken); // Token sudah di-logout
-        return AppError::InvalidToken.into_response();
-    }
-
-    // 3. Validasi token (signature & expiry)
+    // 2. Validasi token (signature & expiry) lebih dulu, supaya token sembarangan
+    //    tidak sampai memicu query ke penyimpanan revokasi
     let validation = Validation::new(jsonwebtoken::Algorithm::HS256);
     let claims = match
         decode::<TokenClaims>(
@@ -84,17 +81,56 @@ pub async fn auth_middleware(
         }
     };
 
-    // 4. Cek expiry sekali lagi (double check)
+    // 3. Cek expiry sekali lagi (double check)
     let now = Utc::now().timestamp();
     if claims.exp < now {
         // return auth_error(AppError::TokenExpired);
         return AppError::TokenExpired.into_response();
     }
 
-    // 5. Simpan data token di request 'extensions' agar bisa
+    // 4. Cek apakah token (yang sudah terverifikasi) sudah di-revoke (logout).
+    //    Yang disimpan hanya hash SHA-256, bukan token aslinya.
+    let token_hash = hash_token(&token_str);
+    let is_revoked = match state.token_revocation.is_revoked(&token_hash).await {
+        Ok(revoked) => revoked,
+        Err(e) if state.config.token_revocation_fail_open => {
+            tracing::warn!("Gagal cek revokasi token, request tetap diizinkan (fail-open): {}", e);
+            false
+        }
+        Err(e) => {
+            tracing::error!("Gagal cek revokasi token: {}", e);
+            // Anggap invalid jika penyimpanan revokasi tidak bisa diakses
+            return AppError::InvalidToken.into_response();
+        }
+    };
+
+    if is_revoked {
+        return AppError::InvalidToken.into_response();
+    }
+
+    // Tandai span request dengan id user (lihat trace_middleware)
+    tracing::Span::current().record("user.id", claims.sub);
+
+    // 5. Muat preferensi user (zona waktu, bahasa, format tanggal), lewat cache singkat
+    //    supaya tidak query database di setiap request.
+    //    Pengaturan bukan data kritis, jadi kalau gagal cukup pakai default.
+    let user_service = UserService::new(state.user_repo.clone());
+    match state.settings.get_or_load(&user_service, claims.sub).await {
+        Ok(settings) => {
+            // Bahasa pilihan user lebih diutamakan daripada Accept-Language
+            if let Some(locale) = settings.preferred_locale() {
+                set_current_locale(locale);
+                req.extensions_mut().insert::<Locale>(locale);
+            }
+            req.extensions_mut().insert(settings);
+        }
+        Err(_) => tracing::warn!("Gagal memuat pengaturan user {}, memakai default", claims.sub),
+    }
+
+    // 6. Simpan data token di request 'extensions' agar bisa
     //    diambil oleh handler (seperti handler /logout)
     req.extensions_mut().insert(claims);
-    req.extensions_mut().insert(token_str); // Simpan juga raw token-nya
+    req.extensions_mut().insert(TokenHash(token_hash)); // Dipakai handler logout
 
     // Lanjutkan ke handler
     next.run(req).await
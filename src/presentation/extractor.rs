@@ -1,10 +1,11 @@
 use axum::{
-    extract::FromRequest,
-    http::Request,
+    extract::{FromRequest, FromRequestParts, Multipart, Query},
+    http::{header, request::Parts, Request},
     Json,
 };
 use serde::de::DeserializeOwned;
-use crate::utils::error::AppError;
+use crate::domain::models::{user::TokenClaims, user_settings::UserSettings};
+use crate::utils::{error::AppError, i18n::{Locale, Msg}};
 use async_trait::async_trait;
 use axum::body::Body;
 // Buat struct "newtype" yang membungkus Axum::Json
@@ -27,4 +28,89 @@ where
         let Json(value) = Json::<T>::from_request(req, state).await?;
         Ok(Self(Json(value)))
     }
-}
\ No newline at end of file
+}
+
+// Multipart dengan error memakai format body error yang biasa
+// (misal Content-Type bukan multipart/form-data)
+pub struct ApiMultipart(pub Multipart);
+
+#[async_trait]
+impl<S> FromRequest<S, Body> for ApiMultipart
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request<Body>, state: &S) -> Result<Self, Self::Rejection> {
+        let multipart = Multipart::from_request(req, state)
+            .await
+            .map_err(|rejection| AppError::BadRequest(Msg::InvalidMultipart(rejection.body_text())))?;
+        Ok(Self(multipart))
+    }
+}
+
+// Sama seperti ApiJson tapi untuk query string (`?page=1`),
+// supaya error parsing memakai format body error yang biasa
+pub struct ApiQuery<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+        Ok(Self(value))
+    }
+}
+
+// Ekstraktor bahasa respons.
+// Biasanya locale sudah diisi oleh `locale_middleware`, tapi jika
+// middleware tidak terpasang kita parse header `Accept-Language` di sini.
+pub struct Lang(pub Locale);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Lang
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(locale) = parts.extensions.get::<Locale>() {
+            return Ok(Self(*locale));
+        }
+
+        let locale = parts
+            .headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Locale::from_accept_language)
+            .unwrap_or_default();
+        Ok(Self(locale))
+    }
+}
+
+// Ekstraktor preferensi user (zona waktu & format tanggal).
+// Diisi oleh `auth_middleware`; untuk rute tanpa login dipakai pengaturan default.
+pub struct UserPrefs(pub UserSettings);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for UserPrefs
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(settings) = parts.extensions.get::<UserSettings>() {
+            return Ok(Self(settings.clone()));
+        }
+
+        let user_id = parts.extensions.get::<TokenClaims>().map(|claims| claims.sub).unwrap_or_default();
+        Ok(Self(UserSettings::default_for(user_id)))
+    }
+}
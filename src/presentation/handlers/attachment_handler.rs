@@ -0,0 +1,241 @@
+use axum::{
+    body::Body,
+    extract::{Extension, Path, State},
+    http::{header, StatusCode},
+    response::{Json, Response},
+};
+use futures_util::TryStreamExt;
+use std::{io, sync::Arc};
+
+use crate::{
+    application::attachment_service::{AttachmentSettings, AttachmentService},
+    domain::models::{
+        api_response::{ApiResponse, ErrorResponse},
+        attachment::{Attachment, ThumbnailQuery},
+        user::TokenClaims,
+    },
+    presentation::{
+        extractor::{ApiMultipart, ApiQuery, Lang},
+        openapi::{AttachmentUpload, MessageResponse},
+    },
+    utils::{
+        error::{AppError, AppResult},
+        i18n::Msg,
+    },
+    AppState,
+};
+
+fn attachment_service(state: &AppState) -> AttachmentService {
+    AttachmentService::new(
+        state.attachment_repo.clone(),
+        state.note_repo.clone(),
+        state.blobs.clone(),
+        AttachmentSettings::from(state.config.as_ref()),
+        state.background.clone(),
+    )
+}
+
+// === UPLOAD ===
+#[utoipa::path(
+    post,
+    path = "/notes/{id}/attachments",
+    tag = "attachments",
+    summary = "Unggah lampiran ke catatan",
+    description = "Body multipart/form-data dengan file di field `file`. Isi file dialirkan langsung ke storage; \
+                   content type ditentukan dari isi file, bukan dari header klien.",
+    params(("id" = u32, Path, description = "Id catatan")),
+    request_body(content = AttachmentUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Lampiran tersimpan", body = ApiResponse<Attachment>),
+        (status = 400, description = "Body multipart tidak valid atau field `file` tidak ada", body = ErrorResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Catatan tidak ditemukan", body = ErrorResponse),
+        (status = 413, description = "File melewati batas ukuran atau kuota user", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upload_attachment(
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    Path(note_id): Path<u32>,
+    Extension(claims): Extension<TokenClaims>,
+    ApiMultipart(mut multipart): ApiMultipart,
+) -> AppResult<(StatusCode, Json<ApiResponse<Attachment>>)> {
+    let service = attachment_service(&state);
+
+    // Field selain `file` dilewati; hanya file pertama yang disimpan
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(Msg::InvalidMultipart(e.body_text())))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let file_name = field.file_name().unwrap_or_default().to_string();
+        let data = field.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.body_text()));
+        let attachment = service.upload(note_id, claims.sub, &file_name, Box::pin(data)).await?;
+
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: Msg::AttachmentUploaded.text(lang),
+            data: attachment,
+        };
+        return Ok((StatusCode::CREATED, Json(response)));
+    }
+    Err(AppError::BadRequest(Msg::AttachmentFileRequired))
+}
+
+// === LIST ===
+#[utoipa::path(
+    get,
+    path = "/notes/{id}/attachments",
+    tag = "attachments",
+    summary = "Daftar lampiran catatan",
+    params(("id" = u32, Path, description = "Id catatan")),
+    responses(
+        (status = 200, description = "Daftar lampiran, yang paling lama dulu", body = ApiResponse<Vec<Attachment>>),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Catatan tidak ditemukan", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_attachments(
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    Path(note_id): Path<u32>,
+    Extension(claims): Extension<TokenClaims>,
+) -> AppResult<Json<ApiResponse<Vec<Attachment>>>> {
+    let attachments = attachment_service(&state).list(note_id, claims.sub).await?;
+
+    let response = ApiResponse {
+        status: "success".to_string(),
+        message: Msg::AttachmentsFetched.text(lang),
+        data: attachments,
+    };
+    Ok(Json(response))
+}
+
+// === DOWNLOAD ===
+#[utoipa::path(
+    get,
+    path = "/notes/{id}/attachments/{attachment_id}",
+    tag = "attachments",
+    summary = "Unduh isi lampiran",
+    params(
+        ("id" = u32, Path, description = "Id catatan"),
+        ("attachment_id" = u32, Path, description = "Id lampiran"),
+    ),
+    responses(
+        (status = 200, description = "Isi file apa adanya, dengan Content-Type hasil sniffing", content_type = "application/octet-stream"),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Lampiran tidak ditemukan", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn download_attachment(
+    State(state): State<Arc<AppState>>,
+    Path((note_id, id)): Path<(u32, u32)>,
+    Extension(claims): Extension<TokenClaims>,
+) -> AppResult<Response> {
+    let (attachment, data) = attachment_service(&state).open(id, note_id, claims.sub).await?;
+
+    // Gambar boleh tampil langsung di browser; tipe lain selalu diunduh
+    let response = Response::builder()
+        .header(header::CONTENT_TYPE, &attachment.content_type)
+        .header(header::CONTENT_LENGTH, attachment.size)
+        .header(header::CONTENT_DISPOSITION, content_disposition(&attachment.file_name, attachment.is_image()))
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .body(Body::from_stream(data))
+        .expect("header lampiran selalu valid");
+    Ok(response)
+}
+
+// === THUMBNAIL ===
+#[utoipa::path(
+    get,
+    path = "/notes/{id}/attachments/{attachment_id}/thumbnail",
+    tag = "attachments",
+    summary = "Thumbnail lampiran gambar",
+    description = "Thumbnail dibuat di background setelah upload; ukuran yang tersedia ada di field `thumbnails` \
+                   lampiran. Thumbnail tidak membawa EXIF (termasuk lokasi GPS).",
+    params(
+        ("id" = u32, Path, description = "Id catatan"),
+        ("attachment_id" = u32, Path, description = "Id lampiran"),
+        ThumbnailQuery,
+    ),
+    responses(
+        (status = 200, description = "Thumbnail JPEG (foto) atau PNG (format lain)", content_type = "image/*"),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Lampiran tidak ditemukan, bukan gambar, atau thumbnail belum siap", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_thumbnail(
+    State(state): State<Arc<AppState>>,
+    Path((note_id, id)): Path<(u32, u32)>,
+    Extension(claims): Extension<TokenClaims>,
+    ApiQuery(query): ApiQuery<ThumbnailQuery>,
+) -> AppResult<Response> {
+    let (attachment, data) = attachment_service(&state).thumbnail(id, note_id, claims.sub, query.size).await?;
+
+    // Key thumbnail tidak pernah berubah isinya, jadi aman di-cache selamanya
+    let response = Response::builder()
+        .header(header::CONTENT_TYPE, attachment.thumbnail_content_type())
+        .header(header::CACHE_CONTROL, "private, max-age=31536000, immutable")
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .body(Body::from_stream(data))
+        .expect("header thumbnail selalu valid");
+    Ok(response)
+}
+
+// === DELETE ===
+#[utoipa::path(
+    delete,
+    path = "/notes/{id}/attachments/{attachment_id}",
+    tag = "attachments",
+    summary = "Hapus lampiran",
+    params(
+        ("id" = u32, Path, description = "Id catatan"),
+        ("attachment_id" = u32, Path, description = "Id lampiran"),
+    ),
+    responses(
+        (status = 200, description = "Lampiran dihapus", body = MessageResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Lampiran tidak ditemukan", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_attachment(
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    Path((note_id, id)): Path<(u32, u32)>,
+    Extension(claims): Extension<TokenClaims>,
+) -> AppResult<Json<ApiResponse<()>>> {
+    attachment_service(&state).delete(id, note_id, claims.sub).await?;
+
+    let response = ApiResponse {
+        status: "success".to_string(),
+        message: Msg::AttachmentDeleted.text(lang),
+        data: (),
+    };
+    Ok(Json(response))
+}
+
+// `attachment; filename="laporan.pdf"; filename*=UTF-8''laporan.pdf`.
+// filename* (RFC 5987) menyimpan nama asli, filename berisi versi ASCII-nya.
+fn content_disposition(file_name: &str, inline: bool) -> String {
+    let ascii: String = file_name
+        .chars()
+        .map(|c| if c.is_ascii_graphic() && c != '"' && c != '\\' || c == ' ' { c } else { '_' })
+        .collect();
+    let encoded: String = file_name
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+    let kind = if inline { "inline" } else { "attachment" };
+    format!("{}; filename=\"{}\"; filename*=UTF-8''{}", kind, ascii, encoded)
+}
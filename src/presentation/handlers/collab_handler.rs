@@ -0,0 +1,142 @@
+use axum::{
+    extract::{
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Extension, Path, State,
+    },
+    response::Response,
+};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    application::{
+        collab_service::{CollabSession, RoomEvent},
+        note_service::NoteService,
+    },
+    domain::models::{
+        api_response::ErrorResponse,
+        collab::{CollabClientMessage, CollabServerMessage, MAX_COLLAB_MESSAGE_BYTES, MAX_PRESENCE_BYTES},
+        user::TokenClaims,
+    },
+    utils::error::AppResult,
+    AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/notes/{id}/collab",
+    tag = "notes",
+    summary = "Edit `content` catatan bersama lewat WebSocket (CRDT automerge)",
+    description = "Frame biner berisi pesan sync automerge. Dokumennya punya satu objek teks `content` di root; \
+                   mulai dari dokumen kosong setiap kali tersambung dan tunggu pesan sync pertama dari server. \
+                   Frame teks berisi JSON: klien mengirim `{\"type\":\"presence\",\"state\":{...}}` (kursor, \
+                   seleksi, dll., maksimal 4 KiB), server mengirim CollabServerMessage (`welcome`, `joined`, \
+                   `presence`, `left`). Isi dokumen disimpan berkala ke catatan dan saat peserta terakhir keluar. \
+                   Hanya pemilik catatan yang bisa bergabung; browser boleh memakai `?access_token=`.",
+    params(
+        ("id" = u32, Path, description = "Id catatan"),
+        ("access_token" = Option<String>, Query, description = "Pengganti header Authorization untuk browser"),
+    ),
+    responses(
+        (status = 101, description = "Koneksi di-upgrade ke WebSocket", body = CollabServerMessage),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Catatan tidak ditemukan", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn collab(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<TokenClaims>,
+    Path(id): Path<u32>,
+    ws: WebSocketUpgrade,
+) -> AppResult<Response> {
+    // Cek kepemilikan sebelum upgrade; catatan milik user lain dijawab 404
+    let note_service = NoteService::new(state.note_repo.clone(), state.events.clone());
+    let note = note_service.get_note_by_id(id, claims.sub).await?;
+    let shutdown = state.background.cancellation_token();
+
+    Ok(ws.max_message_size(MAX_COLLAB_MESSAGE_BYTES).on_upgrade(move |socket| async move {
+        let session = state.collab.join(&note, claims.sub, &state.background);
+        tokio::select! {
+            _ = shutdown.cancelled() => {}
+            _ = run_session(socket, session) => {}
+        }
+    }))
+}
+
+async fn run_session(mut socket: WebSocket, mut session: CollabSession) {
+    let mut events = session.events();
+
+    let welcome = CollabServerMessage::Welcome { peer_id: session.peer_id.clone(), peers: session.peers() };
+    if send_json(&mut socket, &welcome).await.is_err() || send_sync(&mut socket, &session).await.is_err() {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let result = match event {
+                    // Event yang terlewat cukup diganti satu putaran sync
+                    Ok(RoomEvent::Changed) | Err(RecvError::Lagged(_)) => send_sync(&mut socket, &session).await,
+                    Ok(RoomEvent::Joined { peer_id, user_id }) if peer_id != session.peer_id => {
+                        send_json(&mut socket, &CollabServerMessage::Joined { peer_id, user_id }).await
+                    }
+                    Ok(RoomEvent::Presence { peer_id, user_id, state }) if peer_id != session.peer_id => {
+                        send_json(&mut socket, &CollabServerMessage::Presence { peer_id, user_id, state }).await
+                    }
+                    Ok(RoomEvent::Left { peer_id }) if peer_id != session.peer_id => {
+                        send_json(&mut socket, &CollabServerMessage::Left { peer_id }).await
+                    }
+                    Ok(RoomEvent::Closed) | Err(RecvError::Closed) => {
+                        let _ = close(&mut socket, close_code::NORMAL, "catatan dihapus").await;
+                        break;
+                    }
+                    Ok(_) => Ok(()),
+                };
+                if result.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Binary(data))) => {
+                    if session.receive(&data).is_err() {
+                        let _ = close(&mut socket, close_code::INVALID, "pesan sync tidak valid").await;
+                        break;
+                    }
+                    // Balas supaya pertukaran sync dengan peserta ini berlanjut
+                    if send_sync(&mut socket, &session).await.is_err() {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Text(text))) => {
+                    if text.len() > MAX_PRESENCE_BYTES {
+                        tracing::debug!("Presence dari peer {} terlalu besar, diabaikan", session.peer_id);
+                        continue;
+                    }
+                    match serde_json::from_str::<CollabClientMessage>(&text) {
+                        Ok(CollabClientMessage::Presence { state }) => session.set_presence(state),
+                        Err(e) => tracing::debug!("Pesan teks kolaborasi tidak dikenal: {}", e),
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+async fn send_sync(socket: &mut WebSocket, session: &CollabSession) -> Result<(), axum::Error> {
+    match session.sync_message() {
+        Some(message) => socket.send(Message::Binary(message)).await,
+        None => Ok(()),
+    }
+}
+
+async fn send_json(socket: &mut WebSocket, message: &CollabServerMessage) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(message).expect("pesan selalu bisa diserialisasi");
+    socket.send(Message::Text(text)).await
+}
+
+async fn close(socket: &mut WebSocket, code: u16, reason: &'static str) -> Result<(), axum::Error> {
+    socket.send(Message::Close(Some(CloseFrame { code, reason: reason.into() }))).await
+}
@@ -0,0 +1,204 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Extension, State,
+    },
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Response,
+    },
+};
+use futures_util::{stream, Stream, StreamExt};
+use std::collections::VecDeque;
+use std::{convert::Infallible, sync::Arc};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::{
+    domain::{
+        models::{
+            api_response::ErrorResponse,
+            event::{EventsQuery, NoteEvent},
+            user::TokenClaims,
+        },
+        repositories::event_bus::DynEventBus,
+    },
+    presentation::extractor::ApiQuery,
+    utils::error::AppResult,
+    AppState,
+};
+
+// Header standar SSE yang dikirim EventSource saat tersambung ulang
+const LAST_EVENT_ID: &str = "last-event-id";
+
+// === SSE ===
+#[utoipa::path(
+    get,
+    path = "/events",
+    tag = "events",
+    summary = "Stream SSE perubahan catatan milik user",
+    description = "Mengirim event `note.created`, `note.updated`, dan `note.deleted`. Field `id` tiap event \
+                   dipakai untuk melanjutkan stream lewat header `Last-Event-ID` (otomatis oleh EventSource) \
+                   atau `?last_event_id=`. Browser yang tidak bisa mengirim header Authorization boleh memakai \
+                   `?access_token=`.",
+    params(
+        EventsQuery,
+        ("Last-Event-ID" = Option<u64>, Header, description = "Id event terakhir yang sudah diterima"),
+        ("access_token" = Option<String>, Query, description = "Pengganti header Authorization untuk browser"),
+    ),
+    responses(
+        (status = 200, description = "Stream event (`text/event-stream`), data tiap event berbentuk NoteEvent",
+            body = NoteEvent, content_type = "text/event-stream"),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn events_sse(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<TokenClaims>,
+    headers: HeaderMap,
+    ApiQuery(query): ApiQuery<EventsQuery>,
+) -> AppResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    let last_id = last_event_id(&headers, query);
+    let events = note_events(state.events.clone(), claims.sub, last_id).await?;
+
+    // Stream berakhir saat server shutdown supaya koneksi tidak menahan drain
+    let shutdown = state.background.cancellation_token();
+    let stream = events
+        .take_until(async move { shutdown.cancelled().await })
+        .map(|event| {
+            let data = serde_json::to_string(&event).expect("event selalu bisa diserialisasi");
+            Ok(Event::default().id(event.id.to_string()).event(event.kind.as_str()).data(data))
+        });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+// === WEBSOCKET ===
+#[utoipa::path(
+    get,
+    path = "/events/ws",
+    tag = "events",
+    summary = "Alternatif WebSocket untuk /events",
+    description = "Setiap event dikirim sebagai pesan teks JSON berbentuk NoteEvent. Resume dengan \
+                   `?last_event_id=`; pesan dari klien diabaikan.",
+    params(
+        EventsQuery,
+        ("access_token" = Option<String>, Query, description = "Pengganti header Authorization untuk browser"),
+    ),
+    responses(
+        (status = 101, description = "Koneksi di-upgrade ke WebSocket"),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn events_ws(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<TokenClaims>,
+    headers: HeaderMap,
+    ApiQuery(query): ApiQuery<EventsQuery>,
+    ws: WebSocketUpgrade,
+) -> AppResult<Response> {
+    let last_id = last_event_id(&headers, query);
+    // Subscribe sebelum upgrade supaya error backend masih bisa dijawab dengan status HTTP
+    let events = note_events(state.events.clone(), claims.sub, last_id).await?;
+    let shutdown = state.background.cancellation_token();
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        tokio::select! {
+            _ = shutdown.cancelled() => {}
+            _ = forward_to_socket(socket, events) => {}
+        }
+    }))
+}
+
+async fn forward_to_socket(mut socket: WebSocket, events: impl Stream<Item = NoteEvent>) {
+    let mut events = std::pin::pin!(events);
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else { break };
+                let text = serde_json::to_string(&event).expect("event selalu bisa diserialisasi");
+                if socket.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+            // Dibaca hanya untuk mendeteksi klien menutup koneksi (ping dijawab otomatis)
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+// Header Last-Event-ID diutamakan, lalu `?last_event_id=`
+fn last_event_id(headers: &HeaderMap, query: EventsQuery) -> Option<u64> {
+    headers
+        .get(LAST_EVENT_ID)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .or(query.last_event_id)
+}
+
+// Stream event milik satu user: event yang terlewat sejak `last_id` dulu,
+// lalu event baru. Subscribe dilakukan sebelum replay supaya tidak ada event
+// yang jatuh di antara keduanya; event yang muncul di keduanya dikirim sekali.
+async fn note_events(events: DynEventBus, user_id: u32, last_id: Option<u64>) -> AppResult<impl Stream<Item = NoteEvent>> {
+    let receiver = events.subscribe();
+    let missed = match last_id {
+        Some(last_id) => events.replay(user_id, last_id).await?,
+        None => Vec::new(),
+    };
+
+    let cursor = Cursor {
+        last_sent: last_id.unwrap_or_default(),
+        pending: missed.into(),
+        receiver,
+        events,
+        user_id,
+    };
+    Ok(stream::unfold(cursor, |mut cursor| async move {
+        let event = cursor.next().await?;
+        Some((event, cursor))
+    }))
+}
+
+struct Cursor {
+    receiver: broadcast::Receiver<NoteEvent>,
+    events: DynEventBus,
+    user_id: u32,
+    // Event hasil replay yang belum dikirim
+    pending: VecDeque<NoteEvent>,
+    // Id tertinggi yang sudah dikirim. Event bus mengirim event dengan id naik,
+    // jadi event live dengan id <= ini sudah terkirim lewat replay.
+    last_sent: u64,
+}
+
+impl Cursor {
+    async fn next(&mut self) -> Option<NoteEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                self.last_sent = self.last_sent.max(event.id);
+                return Some(event);
+            }
+            match self.receiver.recv().await {
+                Ok(event) if event.user_id == self.user_id && event.id > self.last_sent => {
+                    self.last_sent = event.id;
+                    return Some(event);
+                }
+                Ok(_) => {}
+                // Subscriber terlalu lambat dan sebagian event terbuang dari channel:
+                // ambil lagi dari riwayat
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Stream event tertinggal {} event, mengambil ulang dari riwayat", skipped);
+                    match self.events.replay(self.user_id, self.last_sent).await {
+                        Ok(missed) => self.pending.extend(missed),
+                        Err(e) => tracing::warn!("Gagal mengambil riwayat event: {}", e),
+                    }
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
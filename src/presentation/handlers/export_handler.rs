@@ -0,0 +1,98 @@
+use axum::{
+    body::Body,
+    extract::{Extension, Path, State},
+    http::header,
+    response::Response,
+};
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::{
+    application::export_service::ExportService,
+    domain::models::{
+        api_response::ErrorResponse,
+        export::{ExportQuery, NoteExportQuery},
+        user::TokenClaims,
+    },
+    presentation::extractor::{ApiQuery, UserPrefs},
+    utils::{
+        error::{AppError, AppResult},
+        i18n::Msg,
+    },
+    AppState,
+};
+
+fn export_service(state: &AppState) -> ExportService {
+    ExportService::new(state.note_repo.clone(), state.renderer.clone())
+}
+
+// === EKSPOR SEMUA CATATAN ===
+#[utoipa::path(
+    get,
+    path = "/notes/export",
+    tag = "notes",
+    summary = "Ekspor catatan ke ZIP, JSON, HTML, atau PDF",
+    description = "Body dikirim sebagai stream sambil catatan dibaca dari database. ZIP berisi satu file `.md` \
+                   per catatan dengan front-matter YAML (`id`, `title`, `created_at`, `updated_at`, \
+                   `content_format`, `tags`). Tanggal `from`/`to` inklusif, di zona waktu pengaturan user.",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "File ekspor", content(("application/zip"), ("application/json"), ("text/html"), ("application/pdf"))),
+        (status = 400, description = "Query tidak valid atau `from` setelah `to`", body = ErrorResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_notes(
+    State(state): State<Arc<AppState>>,
+    UserPrefs(prefs): UserPrefs,
+    Extension(claims): Extension<TokenClaims>,
+    ApiQuery(query): ApiQuery<ExportQuery>,
+) -> AppResult<Response> {
+    let filter = query.filter(&prefs).ok_or(AppError::BadRequest(Msg::InvalidDateRange))?;
+    let file_name = format!("catatan-{}.{}", Utc::now().with_timezone(&prefs.tz()).format("%Y%m%d"), query.format.extension());
+    let data = export_service(&state).export(claims.sub, filter, query.format, prefs);
+
+    let response = Response::builder()
+        .header(header::CONTENT_TYPE, query.format.content_type())
+        .header(header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name))
+        .header(header::CACHE_CONTROL, "no-store")
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .body(Body::from_stream(data))
+        .expect("header ekspor selalu valid");
+    Ok(response)
+}
+
+// === EKSPOR SATU CATATAN ===
+#[utoipa::path(
+    get,
+    path = "/notes/{id}/export",
+    tag = "notes",
+    summary = "Ekspor satu catatan ke Markdown, JSON, HTML, atau PDF",
+    params(("id" = u32, Path, description = "Id catatan"), NoteExportQuery),
+    responses(
+        (status = 200, description = "File ekspor", content(("text/markdown"), ("application/json"), ("text/html"), ("application/pdf"))),
+        (status = 400, description = "Format tidak dikenal", body = ErrorResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Catatan tidak ditemukan", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_note(
+    State(state): State<Arc<AppState>>,
+    UserPrefs(prefs): UserPrefs,
+    Path(id): Path<u32>,
+    Extension(claims): Extension<TokenClaims>,
+    ApiQuery(query): ApiQuery<NoteExportQuery>,
+) -> AppResult<Response> {
+    let file = export_service(&state).export_note(id, claims.sub, query.format, &prefs).await?;
+
+    let response = Response::builder()
+        .header(header::CONTENT_TYPE, file.content_type)
+        .header(header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file.file_name))
+        .header(header::CACHE_CONTROL, "no-store")
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .body(Body::from(file.data))
+        .expect("header ekspor selalu valid");
+    Ok(response)
+}
@@ -0,0 +1,58 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::{
+    application::health_service::HealthService,
+    domain::models::health::{ReadinessReport, VersionInfo},
+    AppState,
+};
+
+// === LIVENESS ===
+// Hanya menandakan proses hidup, tidak menyentuh dependensi apa pun
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    summary = "Liveness probe",
+    responses((status = 200, description = "Proses hidup", body = Value, example = json!({ "status": "ok" })))
+)]
+pub async fn live() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+// === READINESS ===
+// 503 jika ada dependensi kritis (database, Redis) yang tidak bisa dijangkau
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    summary = "Readiness probe beserta status tiap dependensi",
+    responses(
+        (status = 200, description = "Siap menerima trafik (ok atau degraded)", body = ReadinessReport),
+        (status = 503, description = "Ada dependensi kritis yang down", body = ReadinessReport),
+    )
+)]
+pub async fn ready(State(state): State<Arc<AppState>>) -> (StatusCode, Json<ReadinessReport>) {
+    let health_service = HealthService::new(state.health_checks.clone(), state.config.health_check_timeout);
+
+    let report = health_service.readiness().await;
+    let status = if report.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+// === VERSION ===
+#[utoipa::path(
+    get,
+    path = "/version",
+    tag = "health",
+    summary = "Versi dan info build",
+    responses((status = 200, description = "Info build", body = VersionInfo))
+)]
+pub async fn version() -> Json<VersionInfo> {
+    Json(HealthService::version())
+}
@@ -0,0 +1,114 @@
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::sync::Arc;
+
+use crate::{
+    application::import_service::ImportService,
+    domain::models::{
+        api_response::{ApiResponse, ErrorResponse},
+        import::{ImportJob, ImportQuery},
+        user::TokenClaims,
+    },
+    presentation::{
+        extractor::{ApiMultipart, ApiQuery, Lang, UserPrefs},
+        openapi::ImportUpload,
+    },
+    utils::{
+        error::{AppError, AppResult},
+        i18n::Msg,
+    },
+    AppState,
+};
+
+fn import_service(state: &AppState) -> ImportService {
+    ImportService::new(state.note_repo.clone(), state.events.clone(), state.imports.clone(), state.background.clone())
+}
+
+// === MULAI IMPOR ===
+#[utoipa::path(
+    post,
+    path = "/notes/import",
+    tag = "notes",
+    summary = "Impor catatan dari ZIP Markdown, Evernote (.enex), atau Google Keep Takeout",
+    description = "Body multipart/form-data dengan file di field `file`. File diproses di background; \
+                   pantau progres dan error per item lewat `GET /notes/import/{job_id}`. \
+                   Waktu dibuat/diubah dari file asal ikut disimpan. Front-matter Markdown yang dikenali: \
+                   `title`, `created_at`/`created`/`date`, `updated_at`/`updated`, `content_format`, `tags`.",
+    params(ImportQuery),
+    request_body(content = ImportUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 202, description = "Job impor dibuat", body = ApiResponse<ImportJob>),
+        (status = 400, description = "Body multipart tidak valid, field `file` tidak ada, atau format tidak dikenali", body = ErrorResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 413, description = "File melewati IMPORT_MAX_BYTES", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn start_import(
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    UserPrefs(prefs): UserPrefs,
+    Extension(claims): Extension<TokenClaims>,
+    ApiQuery(query): ApiQuery<ImportQuery>,
+    ApiMultipart(mut multipart): ApiMultipart,
+) -> AppResult<(StatusCode, Json<ApiResponse<ImportJob>>)> {
+    let max_bytes = state.config.import_max_bytes;
+    let invalid = |e: axum::extract::multipart::MultipartError| AppError::BadRequest(Msg::InvalidMultipart(e.body_text()));
+
+    // Parser butuh seluruh isi file (direktori ZIP ada di akhir file), jadi ditampung di memori dengan batas
+    while let Some(mut field) = multipart.next_field().await.map_err(invalid)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let file_name = field.file_name().unwrap_or_default().to_string();
+        let mut data = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(invalid)? {
+            if (data.len() + chunk.len()) as u64 > max_bytes {
+                return Err(AppError::PayloadTooLarge(Msg::FileTooLarge(max_bytes)));
+            }
+            data.extend_from_slice(&chunk);
+        }
+        let job = import_service(&state).start(claims.sub, &file_name, data, query.source, prefs.tz(), lang)?;
+
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: Msg::ImportStarted.text(lang),
+            data: job,
+        };
+        return Ok((StatusCode::ACCEPTED, Json(response)));
+    }
+    Err(AppError::BadRequest(Msg::AttachmentFileRequired))
+}
+
+// === STATUS IMPOR ===
+#[utoipa::path(
+    get,
+    path = "/notes/import/{job_id}",
+    tag = "notes",
+    summary = "Progres, status, dan laporan error job impor",
+    params(("job_id" = String, Path, description = "Id job dari POST /notes/import")),
+    responses(
+        (status = 200, description = "Status job", body = ApiResponse<ImportJob>),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Job tidak ditemukan, milik user lain, atau lebih dari 24 jam", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_import(
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    Path(job_id): Path<String>,
+    Extension(claims): Extension<TokenClaims>,
+) -> AppResult<Json<ApiResponse<ImportJob>>> {
+    let job = import_service(&state).status(&job_id, claims.sub)?;
+
+    let response = ApiResponse {
+        status: "success".to_string(),
+        message: Msg::ImportJobFetched.text(lang),
+        data: job,
+    };
+    Ok(Json(response))
+}
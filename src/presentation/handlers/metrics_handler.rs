@@ -0,0 +1,20 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+use std::sync::Arc;
+
+use crate::AppState;
+
+// === METRICS ===
+// Format teks Prometheus untuk di-scrape
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "health",
+    summary = "Metrik Prometheus",
+    responses((status = 200, description = "Format teks Prometheus", body = String, content_type = "text/plain"))
+)]
+pub async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        state.metrics.render(),
+    )
+}
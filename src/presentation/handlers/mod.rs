@@ -1,3 +1,11 @@
 // Hanya mendeklarasikan handler yang ada di dalam folder handlers
+pub mod attachment_handler;
+pub mod collab_handler;
+pub mod event_handler;
+pub mod export_handler;
+pub mod health_handler;
+pub mod import_handler;
+pub mod metrics_handler;
 pub mod note_handler;
-pub mod user_handler;
\ No newline at end of file
+pub mod sync_handler;
+pub mod user_handler;
@@ -1,103 +1,269 @@
-use axum::{ extract::{ State, Path, Extension }, http::StatusCode, response::Json };
+use axum::{ extract::{ State, Path, Extension }, http::{header, StatusCode}, response::{IntoResponse, Json} };
 use std::sync::Arc;
 
 use crate::{
-    application::note_service::NoteService,
+    application::{note_renderer, note_service::NoteService},
     domain::models::{
-        api_response::ApiResponse,
-        note::{CreateNotePayload, Note, UpdateNotePayload},
+        api_response::{ApiResponse, ErrorResponse},
+        bulk::{BulkItemStatus, BulkOperationKind, BulkPayload, BulkResult},
+        note::{CreateNotePayload, Note, NoteQuery, RenderMode, UpdateNotePayload},
+        pagination::PageQuery,
         user::TokenClaims,
+        user_settings::Localize,
     },
-    presentation::extractor::ApiJson,
-    utils::error::AppResult,
+    presentation::{
+        extractor::{ApiJson, ApiQuery, Lang, UserPrefs},
+        openapi::MessageResponse,
+    },
+    utils::{error::AppResult, i18n::Msg},
     AppState,
 };
 
 // === CREATE ===
+#[utoipa::path(
+    post,
+    path = "/notes",
+    tag = "notes",
+    summary = "Buat catatan baru",
+    request_body = CreateNotePayload,
+    responses(
+        (status = 201, description = "Catatan dibuat", body = ApiResponse<Note>),
+        (status = 400, description = "Payload tidak valid", body = ErrorResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn create_note(
     State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    UserPrefs(prefs): UserPrefs,
     Extension(claims): Extension<TokenClaims>, // Ambil user ID dari token
     ApiJson(payload): ApiJson<CreateNotePayload>
 ) -> AppResult<(StatusCode, Json<ApiResponse<Note>>)> {
     let user_id = claims.sub; // ID user yang membuat catatan
-    let note_service = NoteService::new(state.note_repo.clone());
-    let new_note = note_service.create_note(payload.0, user_id).await?;
+    let note_service = NoteService::new(state.note_repo.clone(), state.events.clone());
+    let mut new_note = note_service.create_note(payload.0, user_id).await?;
+    new_note.localize(&prefs);
     
     let response = ApiResponse {
         status: "success".to_string(),
-        message: "Catatan berhasil dibuat.".to_string(),
+        message: Msg::NoteCreated.text(lang),
         data: new_note,
     };
     Ok((StatusCode::CREATED, Json(response)))
 }
 
 // === READ (Get All) ===
-pub async fn get_all_notes(State(
-    state,
-): State<Arc<AppState>>, Extension(claims): Extension<TokenClaims>) -> AppResult<Json<ApiResponse<Vec<Note>>>> {
+#[utoipa::path(
+    get,
+    path = "/notes",
+    tag = "notes",
+    summary = "Daftar catatan milik user, terbaru dulu",
+    params(PageQuery),
+    responses(
+        (status = 200, description = "Daftar catatan", body = ApiResponse<Vec<Note>>),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_all_notes(
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    UserPrefs(prefs): UserPrefs,
+    Extension(claims): Extension<TokenClaims>,
+    ApiQuery(page): ApiQuery<PageQuery>
+) -> AppResult<Json<ApiResponse<Vec<Note>>>> {
     let user_id = claims.sub;
-    let note_service = NoteService::new(state.note_repo.clone());
-    let notes = note_service.get_all_notes(user_id).await?;
+    let note_service = NoteService::new(state.note_repo.clone(), state.events.clone());
+    let mut notes = note_service.get_all_notes(user_id, page).await?;
+    notes.localize(&prefs);
     
     let response = ApiResponse {
         status: "success".to_string(),
-        message: "Data catatan berhasil diambil.".to_string(),
+        message: Msg::NotesFetched.text(lang),
         data: notes,
     };
     Ok(Json(response))
 }
 
 // === READ (Get One by ID) ===
+#[utoipa::path(
+    get,
+    path = "/notes/{id}",
+    tag = "notes",
+    summary = "Detail satu catatan",
+    description = "Dengan `?render=html`, respons menyertakan `content_html`: isi catatan (Markdown GFM atau teks biasa) \
+                   yang sudah dirender dan disanitasi di server. Blok kode diberi class `hl-*`, \
+                   stylesheet-nya ada di `/render/highlight.css`.",
+    params(("id" = u32, Path, description = "Id catatan"), NoteQuery),
+    responses(
+        (status = 200, description = "Catatan ditemukan", body = ApiResponse<Note>),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Catatan tidak ditemukan", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_note_by_id(
     State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    UserPrefs(prefs): UserPrefs,
     Path(id): Path<u32>,
-    Extension(claims): Extension<TokenClaims>
+    Extension(claims): Extension<TokenClaims>,
+    ApiQuery(query): ApiQuery<NoteQuery>
 ) -> AppResult<Json<ApiResponse<Note>>> {
     let user_id = claims.sub;
-    let note_service = NoteService::new(state.note_repo.clone());
-    let note = note_service.get_note_by_id(id, user_id).await?;
+    let note_service = NoteService::new(state.note_repo.clone(), state.events.clone());
+    let mut note = note_service.get_note_by_id(id, user_id).await?;
+    note.localize(&prefs);
+    if query.render == Some(RenderMode::Html) {
+        note.content_html = Some(state.renderer.render(&note).await.to_string());
+    }
     
     let response = ApiResponse {
         status: "success".to_string(),
-        message: "Catatan berhasil ditemukan.".to_string(),
+        message: Msg::NoteFound.text(lang),
         data: note,
     };
     Ok(Json(response))
 }
 
 // === UPDATE ===
+#[utoipa::path(
+    put,
+    path = "/notes/{id}",
+    tag = "notes",
+    summary = "Ubah judul dan/atau isi catatan",
+    params(("id" = u32, Path, description = "Id catatan")),
+    request_body = UpdateNotePayload,
+    responses(
+        (status = 200, description = "Catatan diperbarui", body = ApiResponse<Note>),
+        (status = 400, description = "Payload tidak valid", body = ErrorResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Catatan tidak ditemukan", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn update_note(
     State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    UserPrefs(prefs): UserPrefs,
     Path(id): Path<u32>,
     Extension(claims): Extension<TokenClaims>,
     ApiJson(payload): ApiJson<UpdateNotePayload>
 ) -> AppResult<Json<ApiResponse<Note>>> {
     let user_id = claims.sub;
-    let note_service = NoteService::new(state.note_repo.clone());
-    let note = note_service.update_note(id, user_id, payload.0).await?;
+    let note_service = NoteService::new(state.note_repo.clone(), state.events.clone());
+    let mut note = note_service.update_note(id, user_id, payload.0).await?;
+    note.localize(&prefs);
     
     let response = ApiResponse {
         status: "success".to_string(),
-        message: "Catatan berhasil diperbarui.".to_string(),
+        message: Msg::NoteUpdated.text(lang),
         data: note,
     };
     Ok(Json(response))
 }
 
 // === DELETE ===
+#[utoipa::path(
+    delete,
+    path = "/notes/{id}",
+    tag = "notes",
+    summary = "Hapus catatan",
+    params(("id" = u32, Path, description = "Id catatan")),
+    responses(
+        (status = 200, description = "Catatan dihapus", body = MessageResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 404, description = "Catatan tidak ditemukan", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn delete_note(
     State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
     Path(id): Path<u32>,
     Extension(claims): Extension<TokenClaims>
 ) -> AppResult<Json<ApiResponse<()>>> {
     let user_id = claims.sub;
-    let note_service = NoteService::new(state.note_repo.clone());
+    let note_service = NoteService::new(state.note_repo.clone(), state.events.clone());
     note_service.delete_note(id, user_id).await?;
     
     let response = ApiResponse {
         status: "success".to_string(),
-        message: "Catatan berhasil dihapus.".to_string(),
+        message: Msg::NoteDeleted.text(lang),
         data: (), // Unit type untuk data kosong
     };
     Ok(Json(response))
 }
+
+// === BULK ===
+#[utoipa::path(
+    post,
+    path = "/notes/bulk",
+    tag = "notes",
+    summary = "Hapus, ubah, arsipkan, atau beri tag banyak catatan sekaligus",
+    description = "Operasi di `operations` dijalankan berurutan, masing-masing dalam satu transaksi. \
+                   Dengan `atomic: true` (default) satu id yang gagal membatalkan seluruh operasinya \
+                   (id lain berstatus `skipped`); dengan `atomic: false` id yang valid tetap diterapkan. \
+                   Hasil per id ada di `results[].items`.",
+    request_body = BulkPayload,
+    responses(
+        (status = 200, description = "Hasil per operasi dan per id", body = ApiResponse<BulkResult>),
+        (status = 400, description = "Payload tidak valid atau lebih dari 500 id", body = ErrorResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn bulk_notes(
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    UserPrefs(prefs): UserPrefs,
+    Extension(claims): Extension<TokenClaims>,
+    ApiJson(payload): ApiJson<BulkPayload>
+) -> AppResult<Json<ApiResponse<BulkResult>>> {
+    let note_service = NoteService::new(state.note_repo.clone(), state.events.clone());
+    let mut result = note_service.bulk(claims.sub, payload.0).await?;
+
+    for operation in &mut result.results {
+        for item in &mut operation.items {
+            if let Some(note) = &mut item.note {
+                note.localize(&prefs);
+            }
+            item.message = match item.status {
+                BulkItemStatus::Applied => None,
+                BulkItemStatus::NotFound => Some(Msg::NoteNotFound(item.id).text(lang)),
+                BulkItemStatus::Conflict => Some(Msg::SyncConflict(item.id).text(lang)),
+                BulkItemStatus::Invalid if operation.op == BulkOperationKind::Tag => Some(Msg::InvalidTags.text(lang)),
+                BulkItemStatus::Invalid => Some(Msg::NoteTitleRequired.text(lang)),
+                BulkItemStatus::Skipped => Some(Msg::BulkSkipped.text(lang)),
+            };
+        }
+    }
+
+    let response = ApiResponse {
+        status: "success".to_string(),
+        message: Msg::BulkApplied.text(lang),
+        data: result,
+    };
+    Ok(Json(response))
+}
+
+// === STYLESHEET HIGHLIGHT KODE ===
+#[utoipa::path(
+    get,
+    path = "/render/highlight.css",
+    tag = "notes",
+    summary = "CSS untuk blok kode di content_html",
+    responses(
+        (status = 200, description = "Stylesheet untuk class `hl-*`", content_type = "text/css"),
+    )
+)]
+pub async fn highlight_css() -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "text/css; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=86400"),
+        ],
+        note_renderer::highlight_css(),
+    )
+}
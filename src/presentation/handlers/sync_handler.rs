@@ -0,0 +1,99 @@
+use axum::{ extract::{ State, Extension }, response::Json };
+use std::sync::Arc;
+
+use crate::{
+    application::note_service::NoteService,
+    domain::models::{
+        api_response::{ApiResponse, ErrorResponse},
+        sync::{SyncPull, SyncPushPayload, SyncPushResult, SyncQuery, SyncStatus},
+        user::TokenClaims,
+        user_settings::Localize,
+    },
+    presentation::extractor::{ApiJson, ApiQuery, Lang, UserPrefs},
+    utils::{error::AppResult, i18n::Msg},
+    AppState,
+};
+
+// === PULL ===
+#[utoipa::path(
+    get,
+    path = "/sync",
+    tag = "sync",
+    summary = "Ambil perubahan catatan sejak token sinkronisasi terakhir",
+    description = "Mengembalikan catatan yang dibuat/diubah dan catatan yang dihapus (`deleted`) sejak `since`, \
+                   urut sesuai perubahan. Simpan `token` untuk sinkronisasi berikutnya; jika `has_more` bernilai \
+                   true, panggil lagi dengan token baru.",
+    params(SyncQuery),
+    responses(
+        (status = 200, description = "Daftar perubahan", body = ApiResponse<SyncPull>),
+        (status = 400, description = "Token sinkronisasi tidak valid", body = ErrorResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn pull(
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    UserPrefs(prefs): UserPrefs,
+    Extension(claims): Extension<TokenClaims>,
+    ApiQuery(query): ApiQuery<SyncQuery>
+) -> AppResult<Json<ApiResponse<SyncPull>>> {
+    let note_service = NoteService::new(state.note_repo.clone(), state.events.clone());
+    let mut changes = note_service.pull_changes(claims.sub, query).await?;
+    changes.notes.localize(&prefs);
+
+    let response = ApiResponse {
+        status: "success".to_string(),
+        message: Msg::SyncFetched.text(lang),
+        data: changes,
+    };
+    Ok(Json(response))
+}
+
+// === PUSH ===
+#[utoipa::path(
+    post,
+    path = "/sync",
+    tag = "sync",
+    summary = "Terapkan perubahan dari klien offline",
+    description = "Setiap perubahan diproses sendiri-sendiri dan mendapat hasil di `results` dengan urutan yang sama. \
+                   Update/delete dengan `base_seq` yang sudah usang ditolak dengan status `conflict` beserta versi \
+                   catatan di server.",
+    request_body = SyncPushPayload,
+    responses(
+        (status = 200, description = "Hasil per perubahan", body = ApiResponse<SyncPushResult>),
+        (status = 400, description = "Payload tidak valid atau terlalu banyak perubahan", body = ErrorResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn push(
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    UserPrefs(prefs): UserPrefs,
+    Extension(claims): Extension<TokenClaims>,
+    ApiJson(payload): ApiJson<SyncPushPayload>
+) -> AppResult<Json<ApiResponse<SyncPushResult>>> {
+    let note_service = NoteService::new(state.note_repo.clone(), state.events.clone());
+    let mut results = note_service.push_changes(claims.sub, payload.0.changes).await?;
+
+    for result in &mut results {
+        if let Some(note) = &mut result.note {
+            note.localize(&prefs);
+        }
+        let id = result.id.unwrap_or_default();
+        result.message = match result.status {
+            SyncStatus::Applied => None,
+            SyncStatus::Conflict => Some(Msg::SyncConflict(id).text(lang)),
+            SyncStatus::NotFound => Some(Msg::NoteNotFound(id).text(lang)),
+            SyncStatus::Invalid => Some(Msg::NoteTitleRequired.text(lang)),
+        };
+    }
+
+    let response = ApiResponse {
+        status: "success".to_string(),
+        message: Msg::SyncApplied.text(lang),
+        data: SyncPushResult { results },
+    };
+    Ok(Json(response))
+}
@@ -1,60 +1,86 @@
 use axum::{ extract::{ State, Extension }, http::StatusCode, response::Json };
 
 use std::sync::Arc;
-use std::time::Duration;
-use chrono::{ FixedOffset, Utc };
+use chrono::Utc;
 use jsonwebtoken::{ encode, Header, EncodingKey };
-use humantime;
-use redis::AsyncCommands;
 use crate::{
     application::user_service::UserService,
     domain::models::{
-        api_response::ApiResponse,
-        user::{LoginPayload, RegisterPayload, TokenClaims, TokenResponse, User, UserProfile},
+        api_response::{ApiResponse, ErrorResponse},
+        pagination::PageQuery,
+        user::{LoginPayload, RegisterPayload, TokenClaims, TokenHash, TokenResponse, User, UserProfile},
+        user_settings::{Localize, UpdateSettingsPayload, UserSettings},
+    },
+    presentation::{
+        extractor::{ApiJson, ApiQuery, Lang, UserPrefs},
+        openapi::MessageResponse,
+    },
+    utils::{
+        error::{AppError, AppResult},
+        i18n::Msg,
     },
-    presentation::extractor::ApiJson,
-    utils::error::{AppError, AppResult},
     AppState,
 };
 
 // === REGISTER ===
+#[utoipa::path(
+    post,
+    path = "/auth/register",
+    tag = "auth",
+    summary = "Daftar akun baru",
+    request_body = RegisterPayload,
+    responses(
+        (status = 201, description = "Akun dibuat", body = ApiResponse<User>),
+        (status = 400, description = "Payload tidak valid", body = ErrorResponse),
+        (status = 409, description = "Email atau username sudah dipakai", body = ErrorResponse),
+    )
+)]
 pub async fn register(
     State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
     ApiJson(payload): ApiJson<RegisterPayload>
 ) -> AppResult<(StatusCode, Json<ApiResponse<User>>)> {
     // Handler menjadi "tipis", hanya mendelegasikan ke service
     let user_service = UserService::new(state.user_repo.clone());
-    let new_user = user_service.register_user(payload.0).await?;
+    let mut new_user = user_service.register_user(payload.0).await?;
+    new_user.localize(&UserSettings::default_for(new_user.id));
 
     let response = ApiResponse {
         status: "success".to_string(),
-        message: "Registrasi berhasil.".to_string(),
+        message: Msg::RegisterSuccess.text(lang),
         data: new_user,
     };
     Ok((StatusCode::CREATED, Json(response)))
 }
 
 // === LOGIN ===
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    summary = "Login dan dapatkan access token (JWT)",
+    request_body = LoginPayload,
+    responses(
+        (status = 200, description = "Login berhasil", body = ApiResponse<TokenResponse>),
+        (status = 400, description = "Payload tidak valid", body = ErrorResponse),
+        (status = 401, description = "Email atau password salah", body = ErrorResponse),
+    )
+)]
 pub async fn login(
     State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
     ApiJson(payload): ApiJson<LoginPayload>,
 ) -> AppResult<Json<ApiResponse<TokenResponse>>> {
     // Delegasikan logika login ke service
     let user_service = UserService::new(state.user_repo.clone());
     let user = user_service.login_user(payload.0).await?;
+    let settings = user_service.get_settings(user.id).await?;
 
     // Buat token JWT
     let now = Utc::now();
-    let expires_in = humantime
-        ::parse_duration(state.config.jwt_expires_in.trim())
-        .unwrap_or(Duration::from_secs(7 * 24 * 60 * 60));
-    let expires_at_datetime = now + expires_in;
+    let expires_at_datetime = now + state.config.jwt_expires_in;
     let exp_timestamp = expires_at_datetime.timestamp();
 
-    let wib = FixedOffset::east_opt(7 * 3600).unwrap();
-    let expires_at_wib = expires_at_datetime.with_timezone(&wib);
-    let formatted_expires_at = expires_at_wib.format("%Y-%m-%d %H:%M:%S WIB").to_string();
-
     // Buat claims token (HARUS PAKAI i64)
     let claims = TokenClaims {
         sub: user.id,
@@ -68,32 +94,54 @@ pub async fn login(
         &EncodingKey::from_secret(state.config.jwt_secret_key.as_ref())
     ).map_err(|_| AppError::TokenCreationError)?;
 
+    // Waktu kedaluwarsa dikirim dalam UTC + bentuk lokal sesuai pengaturan user
+    let mut token_response = TokenResponse {
+        access_token: token,
+        expires_at: expires_at_datetime,
+        expires_at_local: None,
+    };
+    token_response.localize(&settings);
+
+    // Bahasa pilihan user (jika ada) menang atas Accept-Language
+    let lang = settings.preferred_locale().unwrap_or(lang);
+
     // Kirim respons
     let response = ApiResponse {
         status: "success".to_string(),
-        message: "Login berhasil.".to_string(),
-        data: TokenResponse {
-            access_token: token,
-            expires_at: formatted_expires_at,
-        },
+        message: Msg::LoginSuccess.text(lang),
+        data: token_response,
     };
 
     Ok(Json(response))
 }
 
 // === GET PROFILE ===
+#[utoipa::path(
+    get,
+    path = "/auth/profile",
+    tag = "auth",
+    summary = "Profil user yang sedang login",
+    responses(
+        (status = 200, description = "Profil user", body = ApiResponse<UserProfile>),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_profile(
     State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    UserPrefs(prefs): UserPrefs,
     Extension(claims): Extension<TokenClaims> // Ambil user ID dari token
 ) -> AppResult<Json<ApiResponse<UserProfile>>> {
     let user_id = claims.sub;
-    let user_profile = state.user_repo
+    let mut user_profile = state.user_repo
         .find_profile_by_id(user_id).await?
-        .ok_or_else(|| AppError::NotFound(format!("Profil user dengan id {} tidak ditemukan", user_id)))?;
+        .ok_or(AppError::NotFound(Msg::UserProfileNotFound(user_id)))?;
+    user_profile.localize(&prefs);
 
     let response = ApiResponse {
         status: "success".to_string(),
-        message: "Profil user berhasil diambil.".to_string(),
+        message: Msg::ProfileFetched.text(lang),
         data: user_profile,
     };
 
@@ -101,12 +149,23 @@ pub async fn get_profile(
 }
 
 // === LOGOUT ===
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    tag = "auth",
+    summary = "Logout (token saat ini tidak bisa dipakai lagi)",
+    responses(
+        (status = 200, description = "Logout berhasil", body = MessageResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn logout(
     State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
     Extension(claims): Extension<TokenClaims>, // 1. Ambil claims dari middleware
-    Extension(token_str): Extension<String> // 2. Ambil raw token string dari middleware
+    Extension(TokenHash(token_hash)): Extension<TokenHash> // 2. Ambil hash token dari middleware
 ) -> AppResult<Json<ApiResponse<()>>> {
-    let mut redis_conn = state.redis_client.get_multiplexed_async_connection().await?;
     let now = Utc::now().timestamp();
     let ttl = claims.exp - now;
 
@@ -114,16 +173,13 @@ pub async fn logout(
         return Err(AppError::TokenExpired);
     }
 
-    let _: () = redis_conn
-        .set_ex(
-            token_str,
-            1,
-            ttl as u64
-        ).await?;
+    // Tandai token sebagai revoked sampai token itu kedaluwarsa
+    state.token_revocation.revoke(&token_hash, ttl as u64).await?;
+
     // 6. Kirim respons sukses
     let response = ApiResponse {
         status: "success".to_string(),
-        message: "Logout berhasil.".to_string(),
+        message: Msg::LogoutSuccess.text(lang),
         data: (), // data: null (akan di-skip oleh serde)
     };
 
@@ -131,16 +187,99 @@ pub async fn logout(
 }
 
 // === GET ALL USERS ===
+#[utoipa::path(
+    get,
+    path = "/users",
+    tag = "admin",
+    summary = "Daftar semua user (khusus admin)",
+    params(PageQuery),
+    responses(
+        (status = 200, description = "Daftar user", body = ApiResponse<Vec<UserProfile>>),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+        (status = 403, description = "Bukan admin", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = ["admin"])),
+    extensions(("x-admin-only" = json!(true)))
+)]
 pub async fn get_all_users(
-    State(state): State<Arc<AppState>>
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    UserPrefs(prefs): UserPrefs,
+    ApiQuery(page): ApiQuery<PageQuery>
 ) -> AppResult<Json<ApiResponse<Vec<UserProfile>>>> {
-    let users = state.user_repo.get_all_profiles().await?;
+    let mut users = match page.limit_offset() {
+        Some((limit, offset)) => state.user_repo.get_profiles_page(limit, offset).await?,
+        None => state.user_repo.get_all_profiles().await?,
+    };
+    users.localize(&prefs);
 
     let response = ApiResponse {
         status: "success".to_string(),
-        message: "Data semua user berhasil diambil.".to_string(),
+        message: Msg::UsersFetched.text(lang),
         data: users,
     };
 
     Ok(Json(response))
 }
+
+// === GET SETTINGS ===
+#[utoipa::path(
+    get,
+    path = "/auth/settings",
+    tag = "auth",
+    summary = "Pengaturan zona waktu, bahasa, dan format tanggal",
+    responses(
+        (status = 200, description = "Pengaturan user", body = ApiResponse<UserSettings>),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_settings(
+    Lang(lang): Lang,
+    UserPrefs(settings): UserPrefs // Sudah dimuat oleh auth_middleware
+) -> AppResult<Json<ApiResponse<UserSettings>>> {
+    let response = ApiResponse {
+        status: "success".to_string(),
+        message: Msg::SettingsFetched.text(lang),
+        data: settings,
+    };
+
+    Ok(Json(response))
+}
+
+// === UPDATE SETTINGS ===
+#[utoipa::path(
+    patch,
+    path = "/auth/settings",
+    tag = "auth",
+    summary = "Ubah sebagian pengaturan user",
+    request_body = UpdateSettingsPayload,
+    responses(
+        (status = 200, description = "Pengaturan diperbarui", body = ApiResponse<UserSettings>),
+        (status = 400, description = "Zona waktu, bahasa, atau format tidak valid", body = ErrorResponse),
+        (status = 401, description = "Token tidak ada, tidak valid, atau sudah logout", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_settings(
+    State(state): State<Arc<AppState>>,
+    Lang(lang): Lang,
+    Extension(claims): Extension<TokenClaims>,
+    ApiJson(payload): ApiJson<UpdateSettingsPayload>
+) -> AppResult<Json<ApiResponse<UserSettings>>> {
+    let user_service = UserService::new(state.user_repo.clone());
+    let settings = user_service.update_settings(claims.sub, payload.0).await?;
+    // Request berikutnya langsung memakai pengaturan baru
+    state.settings.set(claims.sub, settings.clone());
+
+    // Pakai bahasa yang baru dipilih untuk pesan respons ini
+    let lang = settings.preferred_locale().unwrap_or(lang);
+
+    let response = ApiResponse {
+        status: "success".to_string(),
+        message: Msg::SettingsUpdated.text(lang),
+        data: settings,
+    };
+
+    Ok(Json(response))
+}
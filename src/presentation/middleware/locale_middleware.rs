@@ -0,0 +1,33 @@
+use axum::{
+    body::Body,
+    extract::Request,
+    http::header,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::utils::i18n::{with_locale, Locale};
+
+/// Middleware global untuk menentukan bahasa respons.
+/// Locale diambil dari header `Accept-Language` (fallback Bahasa Indonesia),
+/// disimpan di request extensions untuk ekstraktor `Lang`, lalu handler
+/// dijalankan di dalam scope locale agar pesan `AppError` ikut diterjemahkan.
+pub async fn locale_middleware(mut req: Request<Body>, next: Next) -> Response {
+    let locale = req
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Locale::from_accept_language)
+        .unwrap_or_default();
+
+    req.extensions_mut().insert(locale);
+
+    let mut response = with_locale(locale, next.run(req)).await;
+
+    // Beri tahu klien bahasa yang dipakai di respons
+    response.headers_mut().insert(
+        header::CONTENT_LANGUAGE,
+        header::HeaderValue::from_static(locale.as_str()),
+    );
+    response
+}
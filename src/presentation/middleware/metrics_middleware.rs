@@ -0,0 +1,43 @@
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::AppState;
+
+/// Middleware global untuk metrik HTTP.
+/// Label `route` memakai template route (`/notes/:id`), bukan path asli,
+/// supaya jumlah label tidak meledak karena id yang berbeda-beda.
+pub async fn metrics_middleware(State(state): State<Arc<AppState>>, req: Request<Body>, next: Next) -> Response {
+    let started = Instant::now();
+    let method = req.method().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(req).await;
+
+    let status = response.status();
+    let status_label = status.as_u16().to_string();
+    let labels = [method.as_str(), route.as_str(), status_label.as_str()];
+    let metrics = &state.metrics;
+    metrics.http_requests_total.with_label_values(&labels).inc();
+    metrics
+        .http_request_duration_seconds
+        .with_label_values(&labels)
+        .observe(started.elapsed().as_secs_f64());
+
+    // Login sukses = 2xx, selain itu (salah password, payload invalid, dll) = gagal
+    if route == "/auth/login" {
+        let result = if status.is_success() { "success" } else { "failure" };
+        metrics.login_attempts_total.with_label_values(&[result]).inc();
+    }
+
+    response
+}
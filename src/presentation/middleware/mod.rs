@@ -0,0 +1,4 @@
+pub mod locale_middleware;
+pub mod metrics_middleware;
+pub mod request_id_middleware;
+pub mod trace_middleware;
@@ -0,0 +1,31 @@
+use axum::{
+    body::Body,
+    extract::Request,
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::utils::request_id::{with_request_id, RequestId, X_REQUEST_ID};
+
+/// Middleware global untuk `X-Request-Id`.
+/// Id dari klien/proxy dipakai ulang jika valid, kalau tidak dibuatkan UUID baru.
+/// Id disimpan di extensions (dibaca trace_middleware untuk log), di task-local
+/// (dibaca `AppError`), dan dikirim balik di header respons.
+pub async fn request_id_middleware(mut req: Request<Body>, next: Next) -> Response {
+    let request_id = req
+        .headers()
+        .get(X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .and_then(RequestId::from_header)
+        .unwrap_or_else(RequestId::generate);
+
+    req.extensions_mut().insert(request_id.clone());
+
+    let mut response = with_request_id(request_id.0.clone(), next.run(req)).await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id.0) {
+        response.headers_mut().insert(X_REQUEST_ID, value);
+    }
+    response
+}
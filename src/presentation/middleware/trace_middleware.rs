@@ -0,0 +1,61 @@
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use opentelemetry::global;
+use opentelemetry_http::HeaderExtractor;
+use std::time::Instant;
+use tracing::{field::Empty, Instrument};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::utils::{redact::redact_headers, request_id::RequestId};
+
+// Satu span per request; kalau ada header `traceparent`, span jadi anak trace klien.
+// `user.id` diisi belakangan oleh auth_middleware, `request_id` untuk korelasi log.
+pub async fn trace_middleware(req: Request<Body>, next: Next) -> Response {
+    let method = req.method().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let request_id = req
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.clone())
+        .unwrap_or_default();
+
+    let span = tracing::info_span!(
+        "http_request",
+        otel.name = format!("{} {}", method, route),
+        otel.kind = "server",
+        http.request.method = %method,
+        http.route = %route,
+        url.path = %req.uri().path(),
+        http.response.status_code = Empty,
+        user.id = Empty,
+        request_id = %request_id,
+    );
+
+    let parent_cx = global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(req.headers())));
+    let _ = span.set_parent(parent_cx);
+
+    let started = Instant::now();
+    let response = async move {
+        // Header Authorization/Cookie disamarkan sebelum masuk log
+        tracing::debug!(headers = ?redact_headers(req.headers()), "Request diterima");
+        let response = next.run(req).await;
+        tracing::info!(
+            status = response.status().as_u16(),
+            latency_ms = started.elapsed().as_millis() as u64,
+            "Request selesai"
+        );
+        response
+    }
+    .instrument(span.clone())
+    .await;
+    span.record("http.response.status_code", response.status().as_u16() as i64);
+    response
+}
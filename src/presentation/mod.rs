@@ -1,4 +1,6 @@
 pub mod extractor;
 pub mod handlers;
 pub mod auth;
+pub mod middleware;
+pub mod openapi;
 pub mod routes;
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi, ToSchema,
+};
+
+use crate::domain::models::api_response::ErrorResponse;
+use crate::presentation::handlers::{attachment_handler, collab_handler, event_handler, export_handler, health_handler, import_handler, metrics_handler, note_handler, sync_handler, user_handler};
+
+// Spesifikasi OpenAPI 3 dibangun dari anotasi `#[utoipa::path]` di handler.
+// Setiap rute baru di routes.rs WAJIB ditambahkan juga ke `paths` di bawah
+// (dijaga oleh test tests/openapi_flow.rs).
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "API Catatan",
+        description = "REST API untuk menyimpan catatan pribadi. Rute bertanda `x-admin-only` hanya bisa diakses admin."
+    ),
+    paths(
+        user_handler::register,
+        user_handler::login,
+        user_handler::get_profile,
+        user_handler::logout,
+        user_handler::get_settings,
+        user_handler::update_settings,
+        user_handler::get_all_users,
+        note_handler::create_note,
+        note_handler::get_all_notes,
+        note_handler::get_note_by_id,
+        note_handler::update_note,
+        note_handler::delete_note,
+        note_handler::bulk_notes,
+        note_handler::highlight_css,
+        export_handler::export_notes,
+        export_handler::export_note,
+        import_handler::start_import,
+        import_handler::get_import,
+        attachment_handler::upload_attachment,
+        attachment_handler::list_attachments,
+        attachment_handler::download_attachment,
+        attachment_handler::get_thumbnail,
+        attachment_handler::delete_attachment,
+        collab_handler::collab,
+        sync_handler::pull,
+        sync_handler::push,
+        event_handler::events_sse,
+        event_handler::events_ws,
+        health_handler::live,
+        health_handler::ready,
+        health_handler::version,
+        metrics_handler::metrics,
+    ),
+    components(schemas(ErrorResponse)),
+    modifiers(&BearerSecurity),
+    tags(
+        (name = "auth", description = "Registrasi, login, dan akun user"),
+        (name = "notes", description = "CRUD catatan milik user yang login"),
+        (name = "attachments", description = "File lampiran pada catatan"),
+        (name = "sync", description = "Sinkronisasi delta untuk klien offline-first"),
+        (name = "events", description = "Notifikasi real-time perubahan catatan (SSE & WebSocket)"),
+        (name = "admin", description = "Khusus user dengan role admin"),
+        (name = "health", description = "Probe, versi, dan metrik"),
+    )
+)]
+pub struct ApiDoc;
+
+// Bentuk `ApiResponse<()>` di dokumentasi: field `data` tidak ikut dikirim
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MessageResponse {
+    pub status: String,
+    pub message: String,
+}
+
+// Body multipart POST /notes/{id}/attachments
+#[derive(Debug, ToSchema)]
+pub struct AttachmentUpload {
+    // Isi file; nama file diambil dari `filename` di header Content-Disposition part ini
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+// Body multipart POST /notes/import
+#[derive(Debug, ToSchema)]
+pub struct ImportUpload {
+    // ZIP (Markdown atau Google Keep Takeout) atau file .enex
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+// Skema keamanan `bearer_auth`: header `Authorization: Bearer <JWT dari /auth/login>`
+struct BearerSecurity;
+
+impl Modify for BearerSecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
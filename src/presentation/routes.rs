@@ -1,4 +1,5 @@
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{
         get,
         post,
@@ -8,6 +9,8 @@ use axum::{
     middleware,
 };
 use std::sync::Arc;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 use crate::AppState;
 use crate::presentation::{
     // Akses presentation/auth/mod.rs
@@ -17,14 +20,28 @@ use crate::presentation::{
         auth_middleware::auth_middleware,
     },
     // middleware::auth_middleware,
-    handlers::{note_handler, user_handler},
+    handlers::{attachment_handler, collab_handler, event_handler, export_handler, health_handler, import_handler, metrics_handler, note_handler, sync_handler, user_handler},
+    middleware::{
+        locale_middleware::locale_middleware, metrics_middleware::metrics_middleware,
+        request_id_middleware::request_id_middleware, trace_middleware::trace_middleware,
+    },
+    openapi::ApiDoc,
 };
 
 pub fn create_router(state: Arc<AppState>) -> Router {
     // 1. Definisikan rute publik (tidak perlu login)
     let public_routes = Router::new()
         .route("/auth/register", post(user_handler::register))
-        .route("/auth/login", post(user_handler::login));
+        .route("/auth/login", post(user_handler::login))
+        // --- Probe Kubernetes & info build ---
+        .route("/health/live", get(health_handler::live))
+        .route("/health/ready", get(health_handler::ready))
+        .route("/version", get(health_handler::version))
+        .route("/metrics", get(metrics_handler::metrics))
+        // --- Stylesheet highlight kode untuk content_html ---
+        .route("/render/highlight.css", get(note_handler::highlight_css))
+        // --- Dokumentasi: spesifikasi di /openapi.json, Swagger UI di /docs ---
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", ApiDoc::openapi()));
 
     // 2. Definisikan rute admin (perlu login + peran admin)
     let admin_routes = Router::new()
@@ -36,13 +53,41 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .merge(admin_routes) // Gabungkan rute admin di sini
         // --- Endpoint Notes ---
         .route("/notes", post(note_handler::create_note).get(note_handler::get_all_notes))
+        // --- Operasi banyak catatan sekaligus ---
+        .route("/notes/bulk", post(note_handler::bulk_notes))
+        // --- Ekspor (body dialirkan sambil catatan dibaca) ---
+        .route("/notes/export", get(export_handler::export_notes))
+        .route("/notes/:id/export", get(export_handler::export_note))
+        // --- Impor (diproses di background, batas ukuran file dicek di handler) ---
+        .route("/notes/import", post(import_handler::start_import).layer(DefaultBodyLimit::disable()))
+        .route("/notes/import/:job_id", get(import_handler::get_import))
         .route(
             "/notes/:id",
             get(note_handler::get_note_by_id).put(note_handler::update_note).delete(note_handler::delete_note)
         )
+        // --- Lampiran catatan; upload dialirkan ke storage, batas ukurannya dicek di service ---
+        .route(
+            "/notes/:id/attachments",
+            post(attachment_handler::upload_attachment)
+                .layer(DefaultBodyLimit::disable())
+                .get(attachment_handler::list_attachments)
+        )
+        .route(
+            "/notes/:id/attachments/:attachment_id",
+            get(attachment_handler::download_attachment).delete(attachment_handler::delete_attachment)
+        )
+        .route("/notes/:id/attachments/:attachment_id/thumbnail", get(attachment_handler::get_thumbnail))
+        // --- Edit kolaboratif (WebSocket + CRDT) ---
+        .route("/notes/:id/collab", get(collab_handler::collab))
+        // --- Sinkronisasi delta untuk klien offline ---
+        .route("/sync", get(sync_handler::pull).post(sync_handler::push))
+        // --- Event real-time (SSE, atau WebSocket sebagai alternatif) ---
+        .route("/events", get(event_handler::events_sse))
+        .route("/events/ws", get(event_handler::events_ws))
         // --- Endpoint Users ---
         .route("/auth/profile", get(user_handler::get_profile))
         .route("/auth/logout", post(user_handler::logout))
+        .route("/auth/settings", get(user_handler::get_settings).patch(user_handler::update_settings))
         // --- Terapkan Middleware ---
         .route_layer(
             middleware::from_fn_with_state(
@@ -52,6 +97,17 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         );
 
     // 4. Gabungkan semua router
-    Router::new().merge(public_routes).merge(protected_routes).with_state(state)
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
+        // Locale dipasang paling luar agar error dari middleware auth ikut diterjemahkan
+        .layer(middleware::from_fn(locale_middleware))
+        // Metrik HTTP mencatat semua request, termasuk yang ditolak middleware lain
+        .layer(middleware::from_fn_with_state(state.clone(), metrics_middleware))
+        // Span tracing per request, semua log request ada di dalamnya
+        .layer(middleware::from_fn(trace_middleware))
+        // X-Request-Id paling luar supaya id-nya sudah ada untuk span & log di atas
+        .layer(middleware::from_fn(request_id_middleware))
+        .with_state(state)
 
 }
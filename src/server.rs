@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use axum::Router;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+// Jalankan server HTTP sampai `shutdown` dibatalkan.
+// Setelah itu server berhenti menerima koneksi baru dan menunggu request
+// yang sedang berjalan selesai, maksimal selama `drain_timeout`.
+// Mengembalikan false jika masih ada request yang terpaksa diputus.
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    shutdown: CancellationToken,
+    drain_timeout: Duration,
+) -> std::io::Result<bool> {
+    let server = axum::serve(listener, app).with_graceful_shutdown(shutdown.clone().cancelled_owned());
+
+    let drain_deadline = async {
+        shutdown.cancelled().await;
+        tokio::time::sleep(drain_timeout).await;
+    };
+
+    tokio::select! {
+        result = server => result.map(|_| true),
+        _ = drain_deadline => {
+            tracing::warn!("Batas waktu drain {:?} habis, request yang tersisa diputus", drain_timeout);
+            Ok(false)
+        }
+    }
+}
+
+// Tunggu SIGINT (Ctrl+C) atau SIGTERM (dikirim Kubernetes/systemd saat deploy)
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Gagal memasang handler Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Gagal memasang handler SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("SIGINT diterima, memulai shutdown"),
+        _ = terminate => tracing::info!("SIGTERM diterima, memulai shutdown"),
+    }
+}
@@ -0,0 +1,40 @@
+use std::future::Future;
+use std::time::Duration;
+
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+
+// Pengelola background task (stream SSE, job impor, pembersihan berkala, ...).
+// Setiap task menerima CancellationToken yang dibatalkan saat server berhenti,
+// lalu `shutdown` menunggu semua task selesai dengan batas waktu.
+#[derive(Clone, Default)]
+pub struct BackgroundTasks {
+    token: CancellationToken,
+    tracker: TaskTracker,
+}
+
+impl BackgroundTasks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Token yang dibatalkan saat server mulai shutdown
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    pub fn spawn<F, Fut>(&self, task: F)
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.tracker.spawn(task(self.token.child_token()));
+    }
+
+    // Batalkan semua task lalu tunggu selesai. Mengembalikan false jika
+    // masih ada task yang belum selesai setelah `timeout`.
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.token.cancel();
+        self.tracker.close();
+        tokio::time::timeout(timeout, self.tracker.wait()).await.is_ok()
+    }
+}
@@ -1,22 +1,185 @@
+use redis::{aio::ConnectionManager, Client as RedisClient};
+use std::time::Duration;
+#[cfg(feature = "mysql")]
 use sqlx::mysql::{MySqlPool, MySqlPoolOptions};
-use crate::utils::config::Config; // Impor struct Config kita
-
-// fungsi ini menerima 'config'
-pub async fn create_pool(config: &Config) -> MySqlPool {
-    // Bangun connection string secara manual
-    let db_url = format!(
-        "mysql://{}:{}@{}:{}/{}",
-        config.db_username,
-        config.db_password,
-        config.db_host,
-        config.db_port,
-        config.db_name
+#[cfg(feature = "postgres")]
+use sqlx::postgres::{PgPool, PgPoolOptions};
+#[cfg(feature = "sqlite")]
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use crate::utils::{config::Config, migrate}; // Impor struct Config kita
+
+#[cfg(not(any(feature = "mysql", feature = "postgres", feature = "sqlite")))]
+compile_error!("Aktifkan minimal satu backend database: feature `mysql`, `postgres`, atau `sqlite`.");
+
+// Pool koneksi untuk backend yang dipilih lewat skema DATABASE_URL.
+// Hanya dipakai di lapisan infrastruktur (repository, migrasi),
+// handler tetap bergantung pada trait repository.
+#[derive(Clone)]
+pub enum DbPool {
+    #[cfg(feature = "mysql")]
+    MySql(MySqlPool),
+    #[cfg(feature = "postgres")]
+    Postgres(PgPool),
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqlitePool),
+}
+
+impl DbPool {
+    pub fn backend_name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(_) => "mysql",
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(_) => "postgres",
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(_) => "sqlite",
+        }
+    }
+
+    // Jumlah koneksi saat ini, untuk metrik /metrics
+    pub fn stats(&self) -> PoolStats {
+        match self {
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => PoolStats::of(pool),
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => PoolStats::of(pool),
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => PoolStats::of(pool),
+        }
+    }
+
+    // Query ringan untuk memastikan database bisa dijangkau (readiness probe)
+    pub async fn ping(&self) -> Result<(), sqlx::Error> {
+        match self {
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => sqlx::query("SELECT 1").execute(pool).await.map(|_| ()),
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => sqlx::query("SELECT 1").execute(pool).await.map(|_| ()),
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => sqlx::query("SELECT 1").execute(pool).await.map(|_| ()),
+        }
+    }
+
+    pub async fn close(&self) {
+        match self {
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => pool.close().await,
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => pool.close().await,
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => pool.close().await,
+        }
+    }
+}
+
+pub struct PoolStats {
+    pub size: u32,
+    pub idle: u32,
+    pub max: u32,
+}
+
+impl PoolStats {
+    fn of<DB: sqlx::Database>(pool: &sqlx::Pool<DB>) -> Self {
+        Self {
+            size: pool.size(),
+            idle: pool.num_idle() as u32,
+            max: pool.options().get_max_connections(),
+        }
+    }
+}
+
+// Buat pool untuk server. Jika DB_AUTO_MIGRATE aktif,
+// semua migrasi yang tertunda dijalankan sebelum pool dipakai.
+pub async fn create_pool(config: &Config) -> Result<DbPool, sqlx::Error> {
+    let pool = connect(config).await?;
+
+    if config.db_auto_migrate {
+        tracing::info!("Menjalankan migrasi database ({})...", pool.backend_name());
+        if let Err(e) = migrate::up(&pool).await {
+            pool.close().await;
+            return Err(sqlx::Error::Migrate(Box::new(e)));
+        }
+    }
+
+    Ok(pool)
+}
+
+// Koneksi mentah tanpa migrasi otomatis (dipakai subcommand `migrate`)
+pub async fn connect(config: &Config) -> Result<DbPool, sqlx::Error> {
+    let db_url = config.database_url();
+    let scheme = db_url.split(':').next().unwrap_or_default();
+
+    let pool = match scheme {
+        #[cfg(feature = "mysql")]
+        "mysql" | "mariadb" => DbPool::MySql(
+            MySqlPoolOptions::new()
+                .max_connections(config.db_max_connections)
+                .min_connections(config.db_min_connections)
+                .acquire_timeout(config.db_acquire_timeout)
+                .connect(&db_url)
+                .await?,
+        ),
+        #[cfg(feature = "postgres")]
+        "postgres" | "postgresql" => DbPool::Postgres(
+            PgPoolOptions::new()
+                .max_connections(config.db_max_connections)
+                .min_connections(config.db_min_connections)
+                .acquire_timeout(config.db_acquire_timeout)
+                .connect(&db_url)
+                .await?,
+        ),
+        #[cfg(feature = "sqlite")]
+        "sqlite" => {
+            use std::str::FromStr;
+
+            // Buat file database jika belum ada, dan pastikan foreign key aktif
+            let options = SqliteConnectOptions::from_str(&db_url)?
+                .create_if_missing(true)
+                .foreign_keys(true);
+
+            // Database in-memory hanya hidup selama koneksinya ada,
+            // jadi pool dibatasi satu koneksi yang tidak pernah ditutup.
+            let pool_options = if db_url.contains(":memory:") || db_url.contains("mode=memory") {
+                SqlitePoolOptions::new().max_connections(1).min_connections(1).idle_timeout(None).max_lifetime(None)
+            } else {
+                SqlitePoolOptions::new()
+                    .max_connections(config.db_max_connections)
+                    .min_connections(config.db_min_connections)
+            };
+
+            DbPool::Sqlite(pool_options.acquire_timeout(config.db_acquire_timeout).connect_with(options).await?)
+        }
+        other => {
+            return Err(sqlx::Error::Configuration(
+                format!("Backend database '{other}' tidak didukung atau feature-nya tidak aktif").into(),
+            ))
+        }
+    };
+
+    Ok(pool)
+}
+
+pub fn create_redis_client(config: &Config) -> redis::RedisResult<RedisClient> {
+    // Bangun Redis connection string
+    // Format: redis://[username:password@]host[:port][/database]
+    let redis_url = format!(
+        "redis://:{}@{}:{}/{}",
+        config.redis_password, // Asumsi password ada, walau kosong
+        config.redis_host,
+        config.redis_port,
+        config.redis_db
     );
 
-    // Buat pool koneksi menggunakan string yang baru kita buat
-    MySqlPoolOptions::new()
-        .max_connections(10)
-        .connect(&db_url) // Gunakan db_url hasil build
+    // Belum ada koneksi di sini; URL hanya divalidasi
+    RedisClient::open(redis_url)
+}
+
+// Batas waktu membuka ConnectionManager. `ConnectionManager::new` mencoba ulang dengan
+// jeda yang makin lama, jadi tanpa batas ini request bisa tertahan beberapa detik saat Redis mati.
+const REDIS_CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+pub async fn connect_redis(client: &RedisClient) -> redis::RedisResult<ConnectionManager> {
+    tokio::time::timeout(REDIS_CONNECT_TIMEOUT, ConnectionManager::new(client.clone()))
         .await
-        .expect("Gagal membuat database pool")
-}
\ No newline at end of file
+        .map_err(|_| redis::RedisError::from((redis::ErrorKind::IoError, "timeout saat menyambung ke Redis")))?
+}
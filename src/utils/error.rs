@@ -1,10 +1,14 @@
 use axum::{
-    extract::rejection::JsonRejection,
+    extract::rejection::{JsonRejection, QueryRejection},
     http::StatusCode,
     response::{ IntoResponse, Response },
     Json,
 };
-use serde_json::json;
+use crate::domain::models::api_response::ErrorResponse;
+use crate::utils::{
+    i18n::{current_locale, Msg},
+    request_id::current_request_id,
+};
 
 // --- Struct wrapper baru (Newtype Pattern) ---
 // bungkus error Axum dengan struct milik kita.
@@ -12,10 +16,12 @@ use serde_json::json;
 // pub struct ApiJsonRejection(JsonRejection);
 
 // --- Ubah AppError ---
+#[derive(Debug)]
 pub enum AppError {
     SqlxError(sqlx::Error),
     RedisError(redis::RedisError), // <-- TAMBAHKAN INI
-    NotFound(String),
+    NotFound(Msg),
+    BadRequest(Msg),
     UserAlreadyExists,
     WrongCredentials,
     HashingError,
@@ -24,46 +30,57 @@ pub enum AppError {
     InvalidToken,
     TokenExpired,
     Forbidden, // <-- TAMBAHKAN INI
+    PayloadTooLarge(Msg),
+    // Gagal membaca/menulis blob store (disk lokal atau S3) atau penyimpanan revokasi token
+    StorageError(Box<dyn std::error::Error + Send + Sync>),
     UsernameTaken,
     // JsonRejection(ApiJsonRejection),
     JsonRejection(JsonRejection), // <-- Gunakan JsonRejection secara langsung
+    QueryRejection(QueryRejection),
 }
 
 // --- Implementasi 'IntoResponse' untuk AppError ---
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let locale = current_locale();
         let (status_code, error_message) = match self {
             AppError::SqlxError(e) => {
                 tracing::error!("SQLx Error: {}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Terjadi kesalahan pada server.".to_string())
+                (StatusCode::INTERNAL_SERVER_ERROR, Msg::ServerError.text(locale))
             }
             AppError::RedisError(e) => { // <-- TAMBAHKAN INI
                 tracing::error!("Redis Error: {}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Terjadi kesalahan pada server.".to_string())
+                (StatusCode::INTERNAL_SERVER_ERROR, Msg::ServerError.text(locale))
+            }
+            AppError::StorageError(e) => {
+                tracing::error!("Storage Error: {}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, Msg::ServerError.text(locale))
             }
             AppError::HashingError => {
                 tracing::error!("Hashing Error: Gagal memproses password.");
-                (StatusCode::INTERNAL_SERVER_ERROR, "Gagal memproses password.".to_string())
+                (StatusCode::INTERNAL_SERVER_ERROR, Msg::PasswordProcessingFailed.text(locale))
             }
             AppError::TokenCreationError => {
                 tracing::error!("JWT Error: Gagal membuat token.");
-                (StatusCode::INTERNAL_SERVER_ERROR, "Gagal membuat token.".to_string())
+                (StatusCode::INTERNAL_SERVER_ERROR, Msg::TokenCreationFailed.text(locale))
             }
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.text(locale)),
+            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.text(locale)),
+            AppError::PayloadTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg.text(locale)),
             AppError::UserAlreadyExists =>
-                (StatusCode::CONFLICT, "User dengan email ini sudah terdaftar.".to_string()),
+                (StatusCode::CONFLICT, Msg::UserAlreadyExists.text(locale)),
             AppError::WrongCredentials =>
-                (StatusCode::UNAUTHORIZED, "Email atau password salah.".to_string()),
+                (StatusCode::UNAUTHORIZED, Msg::WrongCredentials.text(locale)),
             AppError::MissingToken =>
-                (StatusCode::UNAUTHORIZED, "Token autentikasi tidak ditemukan.".to_string()),
+                (StatusCode::UNAUTHORIZED, Msg::MissingToken.text(locale)),
             AppError::InvalidToken =>
-                (StatusCode::UNAUTHORIZED, "Token autentikasi tidak valid.".to_string()),
+                (StatusCode::UNAUTHORIZED, Msg::InvalidToken.text(locale)),
             AppError::TokenExpired =>
-                (StatusCode::UNAUTHORIZED, "Token autentikasi telah kedaluwarsa.".to_string()),
+                (StatusCode::UNAUTHORIZED, Msg::TokenExpired.text(locale)),
             AppError::Forbidden =>
-                (StatusCode::FORBIDDEN, "Anda tidak memiliki hak akses untuk sumber daya ini.".to_string()),
+                (StatusCode::FORBIDDEN, Msg::Forbidden.text(locale)),
             AppError::UsernameTaken =>
-                (StatusCode::CONFLICT, "Username ini sudah digunakan.".to_string()),
+                (StatusCode::CONFLICT, Msg::UsernameTaken.text(locale)),
 
             // --- TAMBAHKAN LOGIKA UNTUK MENANGANI JSON REJECTION ---
             AppError::JsonRejection(rejection) => {
@@ -77,24 +94,53 @@ impl IntoResponse for AppError {
                     JsonRejection::MissingJsonContentType(_) =>
                         (
                             StatusCode::BAD_REQUEST,
-                            "Header Content-Type tidak ada atau salah.".to_string(),
+                            Msg::InvalidContentType.text(locale),
                         ),
                     _ => (StatusCode::BAD_REQUEST, rejection.to_string()),
                 }
             }
+            AppError::QueryRejection(rejection) => (StatusCode::BAD_REQUEST, rejection.body_text()),
         };
 
-        let body = Json(
-            json!({
-            "status": "error",
-            "message": error_message,
-        })
-        );
+        let body = Json(ErrorResponse {
+            status: "error".to_string(),
+            message: error_message,
+            // Sertakan id request supaya klien bisa melaporkannya ke kami
+            request_id: current_request_id(),
+        });
 
         (status_code, body).into_response()
     }
 }
 
+// Display dipakai di luar HTTP (misal CLI admin) untuk mencetak error
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let locale = current_locale();
+        match self {
+            AppError::SqlxError(e) => write!(f, "SQLx Error: {}", e),
+            AppError::RedisError(e) => write!(f, "Redis Error: {}", e),
+            AppError::JsonRejection(rejection) => write!(f, "{}", rejection),
+            AppError::QueryRejection(rejection) => write!(f, "{}", rejection),
+            AppError::StorageError(e) => write!(f, "Storage Error: {}", e),
+            AppError::NotFound(msg) | AppError::BadRequest(msg) | AppError::PayloadTooLarge(msg) => {
+                write!(f, "{}", msg.text(locale))
+            }
+            AppError::UserAlreadyExists => write!(f, "{}", Msg::UserAlreadyExists.text(locale)),
+            AppError::WrongCredentials => write!(f, "{}", Msg::WrongCredentials.text(locale)),
+            AppError::HashingError => write!(f, "{}", Msg::PasswordProcessingFailed.text(locale)),
+            AppError::TokenCreationError => write!(f, "{}", Msg::TokenCreationFailed.text(locale)),
+            AppError::MissingToken => write!(f, "{}", Msg::MissingToken.text(locale)),
+            AppError::InvalidToken => write!(f, "{}", Msg::InvalidToken.text(locale)),
+            AppError::TokenExpired => write!(f, "{}", Msg::TokenExpired.text(locale)),
+            AppError::Forbidden => write!(f, "{}", Msg::Forbidden.text(locale)),
+            AppError::UsernameTaken => write!(f, "{}", Msg::UsernameTaken.text(locale)),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
 impl From<sqlx::Error> for AppError {
     fn from(e: sqlx::Error) -> Self {
         AppError::SqlxError(e)
@@ -108,6 +154,18 @@ impl From<redis::RedisError> for AppError {
     }
 }
 
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::StorageError(Box::new(e))
+    }
+}
+
+impl From<object_store::Error> for AppError {
+    fn from(e: object_store::Error) -> Self {
+        AppError::StorageError(Box::new(e))
+    }
+}
+
 // Tipe alias publik untuk Result<T, AppError>
 pub type AppResult<T> = Result<T, AppError>;
 
@@ -120,3 +178,9 @@ impl From<JsonRejection> for AppError {
         AppError::JsonRejection(rejection) // <-- Langsung bungkus tanpa struct tambahan
     }
 }
+
+impl From<QueryRejection> for AppError {
+    fn from(rejection: QueryRejection) -> Self {
+        AppError::QueryRejection(rejection)
+    }
+}
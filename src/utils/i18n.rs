@@ -0,0 +1,399 @@
+use std::cell::Cell;
+use std::future::Future;
+
+use crate::domain::models::note::{MAX_TAGS_PER_NOTE, MAX_TAG_LEN};
+
+// Bahasa yang didukung API.
+// Bahasa Indonesia adalah default (fallback) jika locale tidak dikenali.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    Id,
+    En,
+}
+
+impl Locale {
+    // Cocokkan language tag (misal "en", "en-US", "id-ID") ke locale yang didukung.
+    // Hanya bagian bahasa utama yang diperhatikan, region diabaikan.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "id" | "in" => Some(Locale::Id),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    // Parse header Accept-Language (misal "en-US,en;q=0.9,id;q=0.8")
+    // dan ambil locale yang didukung dengan nilai q tertinggi.
+    pub fn from_accept_language(header: &str) -> Option<Locale> {
+        let mut best: Option<(Locale, f32)> = None;
+
+        for entry in header.split(',') {
+            let mut parts = entry.split(';');
+            let tag = parts.next().unwrap_or_default();
+            let quality = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            if quality <= 0.0 {
+                continue;
+            }
+
+            if let Some(locale) = Locale::from_tag(tag) {
+                // Gunakan '>' agar urutan di header menang jika nilai q sama
+                if best.is_none_or(|(_, q)| quality > q) {
+                    best = Some((locale, quality));
+                }
+            }
+        }
+
+        best.map(|(locale, _)| locale)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::Id => "id",
+            Locale::En => "en",
+        }
+    }
+}
+
+// Locale untuk request yang sedang diproses.
+// Diisi oleh `locale_middleware` supaya `AppError::into_response`
+// bisa memakai bahasa yang sama dengan handler tanpa perlu akses ke request.
+tokio::task_local! {
+    static CURRENT_LOCALE: Cell<Locale>;
+}
+
+// Jalankan future di dalam scope locale tertentu
+pub async fn with_locale<F: Future>(locale: Locale, fut: F) -> F::Output {
+    CURRENT_LOCALE.scope(Cell::new(locale), fut).await
+}
+
+// Ambil locale request saat ini, fallback ke Bahasa Indonesia
+// jika dipanggil di luar scope (misal dari background task).
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE.try_with(Cell::get).unwrap_or_default()
+}
+
+// Ganti locale request saat ini (misal dari preferensi user di `auth_middleware`).
+// Tidak melakukan apa-apa jika dipanggil di luar scope.
+pub fn set_current_locale(locale: Locale) {
+    let _ = CURRENT_LOCALE.try_with(|current| current.set(locale));
+}
+
+// Katalog pesan API.
+// Setiap varian adalah "key", terjemahannya ada di `Msg::text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Msg {
+    // --- Error ---
+    ServerError,
+    PasswordProcessingFailed,
+    TokenCreationFailed,
+    UserAlreadyExists,
+    WrongCredentials,
+    MissingToken,
+    InvalidToken,
+    TokenExpired,
+    Forbidden,
+    UsernameTaken,
+    InvalidContentType,
+    NoteNotFound(u32),
+    UserProfileNotFound(u32),
+    UserNotFoundAfterCreate,
+    InvalidTimezone(String),
+    InvalidLocale(String),
+    InvalidDateFormat,
+    InvalidSyncToken,
+    TooManySyncChanges(usize),
+    SyncConflict(u32),
+    NoteTitleRequired,
+    InvalidTags,
+    InvalidDateRange,
+    InvalidCollabMessage,
+    AttachmentNotFound(u32),
+    ThumbnailNotAvailable(u32),
+    AttachmentFileRequired,
+    InvalidMultipart(String),
+    FileTooLarge(u64),
+    StorageQuotaExceeded(u64),
+    UnsupportedImportFormat,
+    InvalidImportFile(String),
+    InvalidImportItem(String),
+    ImportItemTooLarge(u64),
+    ImportArchiveTooLarge(u64),
+    ImportSaveFailed,
+    ImportInterrupted,
+    ImportJobNotFound(String),
+    TooManyBulkItems(usize),
+    BulkSkipped,
+
+    // --- Sukses ---
+    RegisterSuccess,
+    LoginSuccess,
+    LogoutSuccess,
+    ProfileFetched,
+    UsersFetched,
+    NoteCreated,
+    NotesFetched,
+    NoteFound,
+    NoteUpdated,
+    NoteDeleted,
+    SettingsFetched,
+    SettingsUpdated,
+    SyncFetched,
+    SyncApplied,
+    AttachmentUploaded,
+    AttachmentsFetched,
+    AttachmentDeleted,
+    ImportStarted,
+    ImportJobFetched,
+    BulkApplied,
+}
+
+impl Msg {
+    pub fn text(&self, locale: Locale) -> String {
+        use Locale::{En, Id};
+
+        match (self, locale) {
+            (Msg::ServerError, Id) => "Terjadi kesalahan pada server.".to_string(),
+            (Msg::ServerError, En) => "An internal server error occurred.".to_string(),
+
+            (Msg::PasswordProcessingFailed, Id) => "Gagal memproses password.".to_string(),
+            (Msg::PasswordProcessingFailed, En) => "Failed to process the password.".to_string(),
+
+            (Msg::TokenCreationFailed, Id) => "Gagal membuat token.".to_string(),
+            (Msg::TokenCreationFailed, En) => "Failed to create the token.".to_string(),
+
+            (Msg::UserAlreadyExists, Id) => "User dengan email ini sudah terdaftar.".to_string(),
+            (Msg::UserAlreadyExists, En) => "A user with this email is already registered.".to_string(),
+
+            (Msg::WrongCredentials, Id) => "Email atau password salah.".to_string(),
+            (Msg::WrongCredentials, En) => "Incorrect email or password.".to_string(),
+
+            (Msg::MissingToken, Id) => "Token autentikasi tidak ditemukan.".to_string(),
+            (Msg::MissingToken, En) => "Authentication token is missing.".to_string(),
+
+            (Msg::InvalidToken, Id) => "Token autentikasi tidak valid.".to_string(),
+            (Msg::InvalidToken, En) => "Authentication token is invalid.".to_string(),
+
+            (Msg::TokenExpired, Id) => "Token autentikasi telah kedaluwarsa.".to_string(),
+            (Msg::TokenExpired, En) => "Authentication token has expired.".to_string(),
+
+            (Msg::Forbidden, Id) => "Anda tidak memiliki hak akses untuk sumber daya ini.".to_string(),
+            (Msg::Forbidden, En) => "You do not have permission to access this resource.".to_string(),
+
+            (Msg::UsernameTaken, Id) => "Username ini sudah digunakan.".to_string(),
+            (Msg::UsernameTaken, En) => "This username is already taken.".to_string(),
+
+            (Msg::InvalidContentType, Id) => "Header Content-Type tidak ada atau salah.".to_string(),
+            (Msg::InvalidContentType, En) => "The Content-Type header is missing or incorrect.".to_string(),
+
+            (Msg::NoteNotFound(id), Id) => {
+                format!("Catatan dengan id {} tidak ditemukan atau bukan milik anda", id)
+            }
+            (Msg::NoteNotFound(id), En) => {
+                format!("Note with id {} was not found or does not belong to you", id)
+            }
+
+            (Msg::UserProfileNotFound(id), Id) => format!("Profil user dengan id {} tidak ditemukan", id),
+            (Msg::UserProfileNotFound(id), En) => format!("User profile with id {} was not found", id),
+
+            (Msg::UserNotFoundAfterCreate, Id) => "Gagal mengambil user setelah dibuat".to_string(),
+            (Msg::UserNotFoundAfterCreate, En) => "Failed to fetch the user after creation".to_string(),
+
+            (Msg::InvalidTimezone(tz), Id) => format!("Zona waktu '{}' tidak dikenal.", tz),
+            (Msg::InvalidTimezone(tz), En) => format!("Unknown timezone '{}'.", tz),
+
+            (Msg::InvalidLocale(locale), Id) => {
+                format!("Bahasa '{}' tidak didukung. Pilihan: id, en.", locale)
+            }
+            (Msg::InvalidLocale(locale), En) => {
+                format!("Language '{}' is not supported. Options: id, en.", locale)
+            }
+
+            (Msg::InvalidDateFormat, Id) => "Format tanggal tidak valid.".to_string(),
+            (Msg::InvalidDateFormat, En) => "The date format is invalid.".to_string(),
+
+            (Msg::InvalidSyncToken, Id) => {
+                "Token sinkronisasi tidak valid, lakukan sinkronisasi penuh tanpa `since`.".to_string()
+            }
+            (Msg::InvalidSyncToken, En) => {
+                "The sync token is invalid, run a full sync without `since`.".to_string()
+            }
+
+            (Msg::InvalidTags, Id) => format!(
+                "Tag tidak valid: maksimal {} tag per catatan, masing-masing 1-{} karakter.",
+                MAX_TAGS_PER_NOTE, MAX_TAG_LEN
+            ),
+            (Msg::InvalidTags, En) => format!(
+                "Invalid tags: at most {} tags per note, each 1-{} characters long.",
+                MAX_TAGS_PER_NOTE, MAX_TAG_LEN
+            ),
+
+            (Msg::InvalidDateRange, Id) => "Tanggal `from` tidak boleh setelah `to`.".to_string(),
+            (Msg::InvalidDateRange, En) => "The `from` date must not be after `to`.".to_string(),
+
+            (Msg::InvalidCollabMessage, Id) => "Pesan sinkronisasi kolaborasi tidak valid.".to_string(),
+            (Msg::InvalidCollabMessage, En) => "The collaboration sync message is invalid.".to_string(),
+
+            (Msg::AttachmentNotFound(id), Id) => format!("Lampiran dengan id {} tidak ditemukan", id),
+            (Msg::AttachmentNotFound(id), En) => format!("Attachment with id {} was not found", id),
+
+            (Msg::ThumbnailNotAvailable(id), Id) => {
+                format!("Thumbnail lampiran {} belum tersedia atau lampiran bukan gambar", id)
+            }
+            (Msg::ThumbnailNotAvailable(id), En) => {
+                format!("Thumbnail for attachment {} is not available yet or the attachment is not an image", id)
+            }
+
+            (Msg::AttachmentFileRequired, Id) => "Kirim file di field multipart `file`.".to_string(),
+            (Msg::AttachmentFileRequired, En) => "Send the file in the multipart field `file`.".to_string(),
+
+            (Msg::InvalidMultipart(e), Id) => format!("Body multipart tidak valid: {}", e),
+            (Msg::InvalidMultipart(e), En) => format!("Invalid multipart body: {}", e),
+
+            (Msg::FileTooLarge(max), Id) => format!("Ukuran file maksimal {}.", human_bytes(*max)),
+            (Msg::FileTooLarge(max), En) => format!("Files may be at most {}.", human_bytes(*max)),
+
+            (Msg::StorageQuotaExceeded(quota), Id) => {
+                format!("Kuota penyimpanan lampiran ({}) sudah penuh.", human_bytes(*quota))
+            }
+            (Msg::StorageQuotaExceeded(quota), En) => {
+                format!("The attachment storage quota ({}) is full.", human_bytes(*quota))
+            }
+
+            (Msg::UnsupportedImportFormat, Id) => {
+                "Format file impor tidak dikenali. Kirim ZIP berisi file Markdown, ekspor Evernote (.enex), \
+                 atau arsip Google Keep dari Takeout."
+                    .to_string()
+            }
+            (Msg::UnsupportedImportFormat, En) => {
+                "Unrecognised import file. Send a ZIP of Markdown files, an Evernote export (.enex), \
+                 or a Google Keep Takeout archive."
+                    .to_string()
+            }
+
+            (Msg::InvalidImportFile(e), Id) => format!("File impor tidak bisa dibaca: {}", e),
+            (Msg::InvalidImportFile(e), En) => format!("The import file could not be read: {}", e),
+
+            (Msg::InvalidImportItem(e), Id) => format!("Isi tidak valid: {}", e),
+            (Msg::InvalidImportItem(e), En) => format!("Invalid content: {}", e),
+
+            (Msg::ImportItemTooLarge(max), Id) => format!("Ukuran satu catatan maksimal {}.", human_bytes(*max)),
+            (Msg::ImportItemTooLarge(max), En) => format!("A single note may be at most {}.", human_bytes(*max)),
+
+            (Msg::ImportArchiveTooLarge(max), Id) => {
+                format!("Isi arsip setelah diekstrak melebihi {}.", human_bytes(*max))
+            }
+            (Msg::ImportArchiveTooLarge(max), En) => {
+                format!("The extracted archive is larger than {}.", human_bytes(*max))
+            }
+
+            (Msg::ImportSaveFailed, Id) => "Gagal menyimpan catatan ke database.".to_string(),
+            (Msg::ImportSaveFailed, En) => "Failed to save the note to the database.".to_string(),
+
+            (Msg::ImportInterrupted, Id) => "Impor terhenti karena server dimatikan.".to_string(),
+            (Msg::ImportInterrupted, En) => "The import stopped because the server shut down.".to_string(),
+
+            (Msg::ImportJobNotFound(id), Id) => format!("Job impor '{}' tidak ditemukan atau sudah kedaluwarsa", id),
+            (Msg::ImportJobNotFound(id), En) => format!("Import job '{}' was not found or has expired", id),
+
+            (Msg::TooManyBulkItems(max), Id) => format!("Maksimal {} id catatan per permintaan bulk.", max),
+            (Msg::TooManyBulkItems(max), En) => format!("At most {} note ids are allowed per bulk request.", max),
+
+            (Msg::BulkSkipped, Id) => {
+                "Tidak diterapkan karena catatan lain di operasi yang sama gagal.".to_string()
+            }
+            (Msg::BulkSkipped, En) => "Not applied because another note in the same operation failed.".to_string(),
+
+            (Msg::TooManySyncChanges(max), Id) => format!("Maksimal {} perubahan per permintaan sinkronisasi.", max),
+            (Msg::TooManySyncChanges(max), En) => format!("At most {} changes are allowed per sync request.", max),
+
+            (Msg::SyncConflict(id), Id) => {
+                format!("Catatan dengan id {} sudah diubah di tempat lain; versi server disertakan.", id)
+            }
+            (Msg::SyncConflict(id), En) => {
+                format!("Note with id {} was changed elsewhere; the server version is included.", id)
+            }
+
+            (Msg::NoteTitleRequired, Id) => "Judul catatan tidak boleh kosong.".to_string(),
+            (Msg::NoteTitleRequired, En) => "The note title must not be empty.".to_string(),
+
+            (Msg::RegisterSuccess, Id) => "Registrasi berhasil.".to_string(),
+            (Msg::RegisterSuccess, En) => "Registration successful.".to_string(),
+
+            (Msg::LoginSuccess, Id) => "Login berhasil.".to_string(),
+            (Msg::LoginSuccess, En) => "Login successful.".to_string(),
+
+            (Msg::LogoutSuccess, Id) => "Logout berhasil.".to_string(),
+            (Msg::LogoutSuccess, En) => "Logout successful.".to_string(),
+
+            (Msg::ProfileFetched, Id) => "Profil user berhasil diambil.".to_string(),
+            (Msg::ProfileFetched, En) => "User profile retrieved successfully.".to_string(),
+
+            (Msg::UsersFetched, Id) => "Data semua user berhasil diambil.".to_string(),
+            (Msg::UsersFetched, En) => "All users retrieved successfully.".to_string(),
+
+            (Msg::NoteCreated, Id) => "Catatan berhasil dibuat.".to_string(),
+            (Msg::NoteCreated, En) => "Note created successfully.".to_string(),
+
+            (Msg::NotesFetched, Id) => "Data catatan berhasil diambil.".to_string(),
+            (Msg::NotesFetched, En) => "Notes retrieved successfully.".to_string(),
+
+            (Msg::NoteFound, Id) => "Catatan berhasil ditemukan.".to_string(),
+            (Msg::NoteFound, En) => "Note found.".to_string(),
+
+            (Msg::NoteUpdated, Id) => "Catatan berhasil diperbarui.".to_string(),
+            (Msg::NoteUpdated, En) => "Note updated successfully.".to_string(),
+
+            (Msg::NoteDeleted, Id) => "Catatan berhasil dihapus.".to_string(),
+            (Msg::NoteDeleted, En) => "Note deleted successfully.".to_string(),
+
+            (Msg::SettingsFetched, Id) => "Pengaturan user berhasil diambil.".to_string(),
+            (Msg::SettingsFetched, En) => "User settings retrieved successfully.".to_string(),
+
+            (Msg::SettingsUpdated, Id) => "Pengaturan user berhasil diperbarui.".to_string(),
+            (Msg::SettingsUpdated, En) => "User settings updated successfully.".to_string(),
+
+            (Msg::SyncFetched, Id) => "Perubahan catatan berhasil diambil.".to_string(),
+            (Msg::SyncFetched, En) => "Note changes retrieved successfully.".to_string(),
+
+            (Msg::SyncApplied, Id) => "Perubahan dari klien selesai diproses.".to_string(),
+            (Msg::SyncApplied, En) => "Client changes processed.".to_string(),
+
+            (Msg::AttachmentUploaded, Id) => "Lampiran berhasil diunggah.".to_string(),
+            (Msg::AttachmentUploaded, En) => "Attachment uploaded successfully.".to_string(),
+
+            (Msg::AttachmentsFetched, Id) => "Data lampiran berhasil diambil.".to_string(),
+            (Msg::AttachmentsFetched, En) => "Attachments retrieved successfully.".to_string(),
+
+            (Msg::AttachmentDeleted, Id) => "Lampiran berhasil dihapus.".to_string(),
+            (Msg::AttachmentDeleted, En) => "Attachment deleted successfully.".to_string(),
+
+            (Msg::ImportStarted, Id) => "Impor dimulai, pantau progresnya lewat status job.".to_string(),
+            (Msg::ImportStarted, En) => "Import started, follow its progress via the job status.".to_string(),
+
+            (Msg::ImportJobFetched, Id) => "Status impor berhasil diambil.".to_string(),
+            (Msg::ImportJobFetched, En) => "Import status retrieved successfully.".to_string(),
+
+            (Msg::BulkApplied, Id) => "Operasi bulk selesai diproses.".to_string(),
+            (Msg::BulkApplied, En) => "Bulk operations processed.".to_string(),
+        }
+    }
+}
+
+// 26214400 -> "25 MiB", 1536 -> "1.5 KiB"
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["byte", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    let number = format!("{:.1}", value);
+    format!("{} {}", number.trim_end_matches(".0"), UNITS[unit])
+}
@@ -0,0 +1,142 @@
+use prometheus::{
+    core::{Collector, Desc},
+    proto::MetricFamily,
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+use crate::utils::db::DbPool;
+
+// Semua metrik Prometheus aplikasi. Registry-nya milik sendiri (bukan global)
+// supaya tiap instance AppState, termasuk di test, punya angka yang terpisah.
+pub struct Metrics {
+    registry: Registry,
+    // HTTP, diisi oleh metrics_middleware
+    pub http_requests_total: IntCounterVec,
+    pub http_request_duration_seconds: HistogramVec,
+    pub login_attempts_total: IntCounterVec,
+    // Repository, diisi oleh decorator Metered*Repository
+    pub repository_query_duration_seconds: HistogramVec,
+    pub notes_changes_total: IntCounterVec,
+    // Penyimpanan revokasi token (Redis/memori/database)
+    pub token_revocation_duration_seconds: HistogramVec,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new_custom(Some("api_catatan".to_string()), None)
+            .expect("prefix metrik tidak valid");
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "Jumlah request HTTP per route dan status"),
+            &["method", "route", "status"],
+        )
+        .unwrap();
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "Latensi request HTTP"),
+            &["method", "route", "status"],
+        )
+        .unwrap();
+        let login_attempts_total = IntCounterVec::new(
+            Opts::new("login_attempts_total", "Jumlah percobaan login (success/failure)"),
+            &["result"],
+        )
+        .unwrap();
+        let repository_query_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("repository_query_duration_seconds", "Latensi pemanggilan repository")
+                .buckets(vec![0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]),
+            &["repository", "method", "outcome"],
+        )
+        .unwrap();
+        let notes_changes_total = IntCounterVec::new(
+            Opts::new("notes_changes_total", "Jumlah catatan yang dibuat, diubah, dan dihapus"),
+            &["operation"],
+        )
+        .unwrap();
+        let token_revocation_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "token_revocation_duration_seconds",
+                "Latensi penyimpanan revokasi token (Redis, memori, atau database)",
+            )
+            .buckets(vec![0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]),
+            &["backend", "operation", "outcome"],
+        )
+        .unwrap();
+
+        for collector in [
+            Box::new(http_requests_total.clone()) as Box<dyn Collector>,
+            Box::new(http_request_duration_seconds.clone()),
+            Box::new(login_attempts_total.clone()),
+            Box::new(repository_query_duration_seconds.clone()),
+            Box::new(notes_changes_total.clone()),
+            Box::new(token_revocation_duration_seconds.clone()),
+        ] {
+            registry.register(collector).expect("metrik terdaftar dua kali");
+        }
+
+        Self {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            login_attempts_total,
+            repository_query_duration_seconds,
+            notes_changes_total,
+            token_revocation_duration_seconds,
+        }
+    }
+
+    // Statistik koneksi pool dibaca saat scrape, bukan disimpan terus-menerus
+    pub fn register_db_pool(&self, db_pool: DbPool) {
+        self.registry
+            .register(Box::new(DbPoolCollector::new(db_pool)))
+            .expect("metrik pool database terdaftar dua kali");
+    }
+
+    // Format teks Prometheus untuk GET /metrics
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("gagal encode metrik");
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Collector yang membaca jumlah koneksi idle/aktif dari pool sqlx
+struct DbPoolCollector {
+    db_pool: DbPool,
+    connections: IntGaugeVec,
+}
+
+impl DbPoolCollector {
+    fn new(db_pool: DbPool) -> Self {
+        let connections = IntGaugeVec::new(
+            Opts::new("db_pool_connections", "Koneksi pool database per state (idle/active/max)"),
+            &["backend", "state"],
+        )
+        .unwrap();
+        Self { db_pool, connections }
+    }
+}
+
+impl Collector for DbPoolCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        self.connections.desc()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        let stats = self.db_pool.stats();
+        let backend = self.db_pool.backend_name();
+        let active = stats.size.saturating_sub(stats.idle);
+
+        self.connections.with_label_values(&[backend, "idle"]).set(stats.idle as i64);
+        self.connections.with_label_values(&[backend, "active"]).set(active as i64);
+        self.connections.with_label_values(&[backend, "max"]).set(stats.max as i64);
+        self.connections.collect()
+    }
+}
@@ -0,0 +1,97 @@
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+use sqlx::{Database, Pool};
+
+use crate::utils::db::DbPool;
+
+// Setiap backend punya folder migrasi sendiri karena dialek SQL-nya berbeda.
+// Semua file di-embed ke binary saat kompilasi.
+#[cfg(feature = "mysql")]
+pub static MYSQL_MIGRATOR: Migrator = sqlx::migrate!("./migrations/mysql");
+#[cfg(feature = "postgres")]
+pub static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
+#[cfg(feature = "sqlite")]
+pub static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
+
+// Jalankan `$body` dengan pool konkret (`$pool`) dan migrator yang sesuai (`$migrator`)
+macro_rules! with_backend {
+    ($db:expr, |$pool:ident, $migrator:ident| $body:expr) => {
+        match $db {
+            #[cfg(feature = "mysql")]
+            DbPool::MySql($pool) => {
+                let $migrator = &MYSQL_MIGRATOR;
+                $body
+            }
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres($pool) => {
+                let $migrator = &POSTGRES_MIGRATOR;
+                $body
+            }
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite($pool) => {
+                let $migrator = &SQLITE_MIGRATOR;
+                $body
+            }
+        }
+    };
+}
+
+// Status satu migrasi untuk subcommand `migrate status`
+#[derive(Debug)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+}
+
+// Jalankan semua migrasi yang belum diterapkan
+pub async fn up(db: &DbPool) -> Result<(), MigrateError> {
+    with_backend!(db, |pool, migrator| migrator.run(pool).await)
+}
+
+// Batalkan `steps` migrasi terakhir yang sudah diterapkan.
+// Mengembalikan daftar versi yang di-revert.
+pub async fn down(db: &DbPool, steps: usize) -> Result<Vec<i64>, MigrateError> {
+    with_backend!(db, |pool, migrator| {
+        let mut applied = applied_versions(pool).await?;
+        applied.sort_unstable();
+
+        if steps == 0 || applied.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // `undo` me-revert semua versi yang LEBIH BESAR dari target
+        let keep = applied.len().saturating_sub(steps);
+        let target = if keep == 0 { 0 } else { applied[keep - 1] };
+        migrator.undo(pool, target).await?;
+
+        Ok(applied.split_off(keep).into_iter().rev().collect())
+    })
+}
+
+pub async fn status(db: &DbPool) -> Result<Vec<MigrationStatus>, MigrateError> {
+    with_backend!(db, |pool, migrator| {
+        let applied = applied_versions(pool).await?;
+
+        Ok(migrator
+            .iter()
+            // File `.down.sql` juga muncul di iterator, cukup tampilkan yang `up`
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| MigrationStatus {
+                version: migration.version,
+                description: migration.description.to_string(),
+                applied: applied.contains(&migration.version),
+            })
+            .collect())
+    })
+}
+
+async fn applied_versions<DB>(pool: &Pool<DB>) -> Result<Vec<i64>, MigrateError>
+where
+    DB: Database,
+    DB::Connection: Migrate,
+{
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+    let applied = conn.list_applied_migrations().await?;
+    Ok(applied.into_iter().map(|migration| migration.version).collect())
+}
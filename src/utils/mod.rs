@@ -1,3 +1,11 @@
+pub mod background;
 pub mod config;
 pub mod db;
-pub mod error;
\ No newline at end of file
+pub mod error;
+pub mod i18n;
+pub mod metrics;
+pub mod migrate;
+pub mod pdf;
+pub mod redact;
+pub mod request_id;
+pub mod telemetry;
\ No newline at end of file
@@ -0,0 +1,202 @@
+// Penulis PDF minimal tanpa dependency & tanpa akses jaringan: hanya teks,
+// memakai font bawaan PDF (Courier) sehingga tidak perlu menyematkan file font.
+// Dokumen ditulis berurutan dan bisa diambil sepotong-sepotong lewat `take`,
+// jadi ekspor besar tidak perlu disimpan utuh di memori.
+
+// A4 dalam satuan point
+const PAGE_WIDTH: u32 = 595;
+const PAGE_HEIGHT: u32 = 842;
+const MARGIN: u32 = 50;
+const FONT_SIZE: u32 = 10;
+const LEADING: u32 = 13;
+// Courier: lebar tiap karakter 0.6 em -> 6pt pada ukuran 10
+const CHARS_PER_LINE: usize = ((PAGE_WIDTH - 2 * MARGIN) * 10 / (FONT_SIZE * 6)) as usize;
+const LINES_PER_PAGE: usize = ((PAGE_HEIGHT - 2 * MARGIN) / LEADING) as usize;
+
+// Nomor objek yang sudah dipesan sejak awal; isinya ditulis di `finish`
+const CATALOG: usize = 1;
+const PAGES: usize = 2;
+const FONT_REGULAR: usize = 3;
+const FONT_BOLD: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PdfFont {
+    Regular,
+    Bold,
+}
+
+pub struct PdfWriter {
+    buf: Vec<u8>,
+    // Jumlah byte yang sudah diambil lewat `take`
+    flushed: usize,
+    // Posisi byte tiap objek (indeks 0 = objek 1), untuk tabel xref
+    offsets: Vec<usize>,
+    pages: Vec<usize>,
+}
+
+impl PdfWriter {
+    pub fn new() -> Self {
+        let mut writer = Self { buf: Vec::new(), flushed: 0, offsets: vec![0; FONT_BOLD], pages: Vec::new() };
+        // Baris kedua berisi byte > 127 supaya file dikenali sebagai biner
+        writer.buf.extend_from_slice(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n");
+        for (id, name) in [(FONT_REGULAR, "Courier"), (FONT_BOLD, "Courier-Bold")] {
+            writer.write_object(
+                id,
+                format!("<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>", name).as_bytes(),
+            );
+        }
+        writer
+    }
+
+    // Tulis satu dokumen (misal satu catatan) mulai dari halaman baru.
+    // Baris yang terlalu panjang dipotong per kata; halaman ditambah sesuai kebutuhan.
+    pub fn add_section(&mut self, lines: &[(PdfFont, &str)]) {
+        let mut wrapped = Vec::new();
+        for (font, text) in lines {
+            for line in text.split('\n') {
+                wrap_line(&line.replace('\t', "    "), &mut |part| wrapped.push((*font, part)));
+            }
+        }
+        if wrapped.is_empty() {
+            wrapped.push((PdfFont::Regular, String::new()));
+        }
+        for page in wrapped.chunks(LINES_PER_PAGE) {
+            self.add_page(page);
+        }
+    }
+
+    // Ambil byte yang sudah ditulis sejauh ini
+    pub fn take(&mut self) -> Vec<u8> {
+        self.flushed += self.buf.len();
+        std::mem::take(&mut self.buf)
+    }
+
+    // Tutup dokumen: daftar halaman, katalog, tabel xref, dan trailer
+    pub fn finish(mut self) -> Vec<u8> {
+        if self.pages.is_empty() {
+            self.add_page(&[]);
+        }
+        let kids: Vec<String> = self.pages.iter().map(|id| format!("{} 0 R", id)).collect();
+        let pages = format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), self.pages.len());
+        self.write_object(PAGES, pages.as_bytes());
+        self.write_object(CATALOG, format!("<< /Type /Catalog /Pages {} 0 R >>", PAGES).as_bytes());
+
+        let xref_offset = self.position();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            xref.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        xref.push_str(&format!(
+            "trailer\n<< /Size {} /Root {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            self.offsets.len() + 1,
+            CATALOG,
+            xref_offset
+        ));
+        self.buf.extend_from_slice(xref.as_bytes());
+        self.take()
+    }
+
+    fn position(&self) -> usize {
+        self.flushed + self.buf.len()
+    }
+
+    fn allocate(&mut self) -> usize {
+        self.offsets.push(0);
+        self.offsets.len()
+    }
+
+    fn write_object(&mut self, id: usize, body: &[u8]) {
+        self.offsets[id - 1] = self.position();
+        self.buf.extend_from_slice(format!("{} 0 obj\n", id).as_bytes());
+        self.buf.extend_from_slice(body);
+        self.buf.extend_from_slice(b"\nendobj\n");
+    }
+
+    fn add_page(&mut self, lines: &[(PdfFont, String)]) {
+        let mut content = format!("BT\n{} TL\n{} {} Td\n", LEADING, MARGIN, PAGE_HEIGHT - MARGIN - FONT_SIZE).into_bytes();
+        let mut current = None;
+        for (font, line) in lines {
+            if current != Some(*font) {
+                let name = if *font == PdfFont::Bold { "F2" } else { "F1" };
+                content.extend_from_slice(format!("/{} {} Tf\n", name, FONT_SIZE).as_bytes());
+                current = Some(*font);
+            }
+            content.push(b'(');
+            content.extend_from_slice(&encode_text(line));
+            content.extend_from_slice(b") Tj T*\n");
+        }
+        content.extend_from_slice(b"ET");
+
+        let contents = self.allocate();
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend_from_slice(&content);
+        stream.extend_from_slice(b"\nendstream");
+        self.write_object(contents, &stream);
+
+        let page = self.allocate();
+        let body = format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 {} 0 R /F2 {} 0 R >> >> /Contents {} 0 R >>",
+            PAGES, PAGE_WIDTH, PAGE_HEIGHT, FONT_REGULAR, FONT_BOLD, contents
+        );
+        self.write_object(page, body.as_bytes());
+        self.pages.push(page);
+    }
+}
+
+impl Default for PdfWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Potong satu baris jadi beberapa baris selebar CHARS_PER_LINE, sebisa mungkin di spasi
+fn wrap_line(line: &str, push: &mut impl FnMut(String)) {
+    let mut chars: Vec<char> = line.trim_end().chars().collect();
+    while chars.len() > CHARS_PER_LINE {
+        let split = chars[..=CHARS_PER_LINE]
+            .iter()
+            .rposition(|c| *c == ' ')
+            .filter(|i| *i > 0)
+            .unwrap_or(CHARS_PER_LINE);
+        push(chars[..split].iter().collect::<String>().trim_end().to_string());
+        let rest = if chars.get(split) == Some(&' ') { split + 1 } else { split };
+        chars.drain(..rest);
+    }
+    push(chars.into_iter().collect());
+}
+
+// String PDF dalam WinAnsiEncoding: karakter di luar tabel jadi '?',
+// tanda kurung & backslash di-escape, byte non-ASCII ditulis oktal
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match win_ansi(c) {
+            b @ (b'(' | b')' | b'\\') => out.extend_from_slice(&[b'\\', b]),
+            b @ 0x20..=0x7E => out.push(b),
+            b => out.extend_from_slice(format!("\\{:03o}", b).as_bytes()),
+        }
+    }
+    out
+}
+
+fn win_ansi(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        // Latin-1 (0xA0-0xFF) sama persis di WinAnsi
+        '\u{A0}'..='\u{FF}' => c as u32 as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        '„' => 0x84,
+        '…' => 0x85,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '™' => 0x99,
+        _ => b'?',
+    }
+}
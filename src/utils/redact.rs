@@ -0,0 +1,21 @@
+use axum::http::HeaderMap;
+
+// Pengganti nilai sensitif di log dan output Debug
+pub const REDACTED: &str = "[REDACTED]";
+
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie", "set-cookie"];
+
+// Header request dalam bentuk yang aman untuk di-log
+pub fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                value.to_str().unwrap_or("<binary>").to_string()
+            };
+            (name.as_str().to_string(), value)
+        })
+        .collect()
+}
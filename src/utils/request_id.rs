@@ -0,0 +1,41 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+pub const X_REQUEST_ID: &str = "x-request-id";
+
+// Id request yang sedang diproses, diisi oleh `request_id_middleware`.
+// Sama seperti locale, disimpan di task-local supaya `AppError` bisa
+// menyertakannya di body error tanpa akses ke request.
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+// Id request yang disimpan di request extensions
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    pub fn generate() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    // Terima id dari klien/proxy hanya jika wajar (tidak kosong, maks 128
+    // karakter, tanpa spasi/karakter kontrol) supaya log tidak bisa disusupi.
+    pub fn from_header(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let valid = !value.is_empty()
+            && value.len() <= 128
+            && value.chars().all(|c| c.is_ascii_graphic());
+        valid.then(|| Self(value.to_string()))
+    }
+}
+
+pub async fn with_request_id<F: Future>(request_id: String, fut: F) -> F::Output {
+    CURRENT_REQUEST_ID.scope(request_id, fut).await
+}
+
+// None jika dipanggil di luar request (misal dari CLI atau background task)
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+}
@@ -0,0 +1,87 @@
+use opentelemetry::{global, trace::TracerProvider as _};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::SdkTracerProvider, Resource};
+use tracing::Subscriber;
+use tracing_subscriber::{
+    fmt::MakeWriter, layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt, EnvFilter, Layer,
+};
+
+use crate::utils::config::{Config, LogFormat};
+
+// Filter log gaya RUST_LOG (misal `info,api_catatan=debug,sqlx=warn`),
+// default `info` jika RUST_LOG kosong atau tidak valid.
+pub fn env_filter(default_directive: &str) -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_directive))
+}
+
+// Layer output log: teks biasa untuk development, JSON (satu objek per baris)
+// untuk log aggregator. Field span (termasuk `request_id`) ikut di setiap baris.
+pub fn fmt_layer<S, W>(format: LogFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => tracing_subscriber::fmt::layer().with_writer(writer).boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(false)
+            .with_span_list(true)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+// Provider OTLP yang mengirim span ke collector (Jaeger, Tempo, otel-collector, ...).
+// `endpoint` adalah base URL OTLP/HTTP, misal http://localhost:4318.
+pub fn otlp_tracer_provider(endpoint: &str, service_name: &str) -> Result<SdkTracerProvider, String> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()
+        .map_err(|e| format!("Gagal membuat exporter OTLP: {e}"))?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name.to_string()).build())
+        .build())
+}
+
+// Pasang subscriber tracing global: log ke stdout (filter RUST_LOG, format
+// LOG_FORMAT=text|json), plus export span lewat
+// OTLP jika OTEL_EXPORTER_OTLP_ENDPOINT diisi. Header `traceparent` (W3C)
+// dari request masuk dipakai sebagai parent span.
+//
+// Provider yang dikembalikan perlu di-`shutdown()` saat server berhenti
+// supaya span yang masih di-buffer ikut terkirim.
+pub fn init_tracing(config: &Config) -> Option<SdkTracerProvider> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let provider = config.otel_exporter_otlp_endpoint.as_deref().and_then(|endpoint| {
+        match otlp_tracer_provider(endpoint, &config.otel_service_name) {
+            Ok(provider) => Some(provider),
+            Err(e) => {
+                eprintln!("{e}, tracing OTLP dinonaktifkan");
+                None
+            }
+        }
+    });
+
+    let otel_layer = provider
+        .as_ref()
+        .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer("api_catatan")));
+
+    tracing_subscriber::registry()
+        .with(env_filter("info"))
+        .with(fmt_layer(config.log_format, std::io::stdout))
+        .with(otel_layer)
+        .init();
+
+    if let Some(endpoint) = &config.otel_exporter_otlp_endpoint {
+        if provider.is_some() {
+            tracing::info!("Span OpenTelemetry dikirim ke {}", endpoint);
+        }
+    }
+
+    provider
+}
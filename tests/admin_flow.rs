@@ -0,0 +1,23 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+#[tokio::test]
+async fn admin_can_list_users() {
+    let app = common::test_app();
+    app.user_token("budi").await;
+    let admin = app.admin_token("admin").await;
+
+    let (status, body) = app.request(Method::GET, "/users", Some(&admin), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn regular_user_cannot_list_users() {
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+
+    let (status, _) = app.request(Method::GET, "/users", Some(&token), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
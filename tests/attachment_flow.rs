@@ -0,0 +1,332 @@
+mod common;
+
+use std::{io::Cursor, path::PathBuf, sync::Arc, time::Duration};
+
+use api_catatan::infrastructure::storage::local_store::LocalBlobStore;
+use axum::http::{header, Method, StatusCode};
+use image::{DynamicImage, ImageFormat, RgbImage, RgbaImage};
+use serde_json::{json, Value};
+
+use common::TestApp;
+
+const BOUNDARY: &str = "batas-multipart-test";
+const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+fn multipart(file_name: &str, content_type: &str, data: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    // Field lain sebelum `file` dilewati server
+    body.extend_from_slice(format!("--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"keterangan\"\r\n\r\nabaikan\r\n").as_bytes());
+    body.extend_from_slice(
+        format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+             Content-Type: {content_type}\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+async fn upload(app: &TestApp, token: &str, note_id: u64, file_name: &str, data: &[u8]) -> (StatusCode, Value) {
+    let (status, _, bytes) = app
+        .request_raw(
+            Method::POST,
+            &format!("/notes/{note_id}/attachments"),
+            Some(token),
+            Some(&format!("multipart/form-data; boundary={BOUNDARY}")),
+            // Header Content-Type dari klien sengaja salah; server menebak dari isi file
+            multipart(file_name, "image/png", data),
+        )
+        .await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+fn app_with_limits(max_file_bytes: u64, quota_bytes: u64) -> TestApp {
+    let mut config = common::test_config();
+    config.attachment_max_file_bytes = max_file_bytes;
+    config.attachment_quota_bytes = quota_bytes;
+    let mut state = common::test_state();
+    state.config = Arc::new(config);
+    TestApp::from_state(state)
+}
+
+#[tokio::test]
+async fn upload_daftar_unduh_dan_hapus_lampiran() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("nina").await;
+        let note_id = app.create_note(&token, json!({ "title": "Laporan" })).await["id"].as_u64().unwrap();
+
+        let (status, body) = upload(&app, &token, note_id, "catatan rapat.txt", "Isi rapat: anggaran ✓".as_bytes()).await;
+        assert_eq!(status, StatusCode::CREATED, "{backend}: {body}");
+        let attachment = body["data"].clone();
+        assert_eq!(attachment["file_name"], "catatan rapat.txt", "{backend}");
+        assert_eq!(attachment["content_type"], "text/plain; charset=utf-8", "{backend}");
+        assert_eq!(attachment["size"], "Isi rapat: anggaran ✓".len(), "{backend}");
+        assert!(attachment.get("storage_key").is_none(), "{backend}");
+        let id = attachment["id"].as_u64().unwrap();
+
+        let (status, body) = app.request(Method::GET, &format!("/notes/{note_id}/attachments"), Some(&token), None).await;
+        assert_eq!(status, StatusCode::OK, "{backend}");
+        assert_eq!(body["data"].as_array().unwrap().len(), 1, "{backend}");
+
+        let uri = format!("/notes/{note_id}/attachments/{id}");
+        let (status, headers, bytes) = app.request_raw(Method::GET, &uri, Some(&token), None, Vec::new()).await;
+        assert_eq!(status, StatusCode::OK, "{backend}");
+        assert_eq!(&bytes[..], "Isi rapat: anggaran ✓".as_bytes(), "{backend}");
+        assert_eq!(headers[header::CONTENT_TYPE], "text/plain; charset=utf-8");
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(
+            headers[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"catatan rapat.txt\"; filename*=UTF-8''catatan%20rapat.txt"
+        );
+
+        let (status, _) = app.request(Method::DELETE, &uri, Some(&token), None).await;
+        assert_eq!(status, StatusCode::OK, "{backend}");
+        let (status, _) = app.request(Method::GET, &uri, Some(&token), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{backend}");
+    }
+}
+
+#[tokio::test]
+async fn content_type_ditebak_dari_isi_file() {
+    let app = common::test_app();
+    let token = app.user_token("oki").await;
+    let note_id = app.create_note(&token, json!({ "title": "Laporan" })).await["id"].as_u64().unwrap();
+
+    let (status, body) = upload(&app, &token, note_id, "../../foto.png", PNG_HEADER).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(body["data"]["content_type"], "image/png");
+    // Path dari klien dibuang, hanya nama filenya yang disimpan
+    assert_eq!(body["data"]["file_name"], "foto.png");
+
+    let uri = format!("/notes/{note_id}/attachments/{}", body["data"]["id"]);
+    let (_, headers, _) = app.request_raw(Method::GET, &uri, Some(&token), None, Vec::new()).await;
+    assert!(headers[header::CONTENT_DISPOSITION].to_str().unwrap().starts_with("inline;"));
+
+    let (status, body) = upload(&app, &token, note_id, "data.bin", &[0, 159, 146, 150]).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(body["data"]["content_type"], "application/octet-stream");
+}
+
+#[tokio::test]
+async fn batas_ukuran_file_dan_kuota_user() {
+    let app = app_with_limits(1024, 1500);
+    let token = app.user_token("putu").await;
+    let note_id = app.create_note(&token, json!({ "title": "Laporan" })).await["id"].as_u64().unwrap();
+
+    let (status, body) = upload(&app, &token, note_id, "besar.txt", &[b'a'; 2048]).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE, "{body}");
+    assert!(body["message"].as_str().unwrap().contains("1 KiB"), "{body}");
+
+    let (status, _) = upload(&app, &token, note_id, "pertama.txt", &[b'a'; 1000]).await;
+    assert_eq!(status, StatusCode::CREATED);
+    // Sisa kuota 500 byte
+    let (status, body) = upload(&app, &token, note_id, "kedua.txt", &[b'a'; 1000]).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE, "{body}");
+    assert!(body["message"].as_str().unwrap().contains("1.5 KiB"), "{body}");
+
+    let (_, body) = app.request(Method::GET, &format!("/notes/{note_id}/attachments"), Some(&token), None).await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn lampiran_hanya_untuk_pemilik_catatan() {
+    let app = common::test_app();
+    let owner = app.user_token("rani").await;
+    let other = app.user_token("sari").await;
+    let note_id = app.create_note(&owner, json!({ "title": "Laporan" })).await["id"].as_u64().unwrap();
+    let (_, body) = upload(&app, &owner, note_id, "rahasia.txt", b"rahasia").await;
+    let uri = format!("/notes/{note_id}/attachments/{}", body["data"]["id"]);
+
+    let (status, _) = upload(&app, &other, note_id, "titip.txt", b"halo").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    for method in [Method::GET, Method::DELETE] {
+        let (status, _) = app.request(method, &uri, Some(&other), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+    let (status, _) = app.request(Method::GET, &uri, None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Bukan multipart, atau tanpa field `file`
+    let (status, _) = app
+        .request(Method::POST, &format!("/notes/{note_id}/attachments"), Some(&owner), Some(json!({})))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _, _) = app
+        .request_raw(
+            Method::POST,
+            &format!("/notes/{note_id}/attachments"),
+            Some(&owner),
+            Some(&format!("multipart/form-data; boundary={BOUNDARY}")),
+            format!("--{BOUNDARY}--\r\n").into_bytes(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn lampiran_ikut_terhapus_bersama_catatan() {
+    let dir: PathBuf = std::env::temp_dir().join(format!("api_catatan-lampiran-{}", uuid::Uuid::new_v4()));
+    let mut state = common::test_state();
+    state.blobs = Arc::new(LocalBlobStore::new(&dir));
+    let app = TestApp::from_state(state);
+
+    let token = app.user_token("tono").await;
+    let note_id = app.create_note(&token, json!({ "title": "Laporan" })).await["id"].as_u64().unwrap();
+    let (status, body) = upload(&app, &token, note_id, "a.txt", b"isi lampiran").await;
+    assert_eq!(status, StatusCode::CREATED);
+    let user_id = body["data"]["user_id"].as_u64().unwrap() as u32;
+    let note_dir = dir.join(format!("notes/{user_id}/{note_id}"));
+    assert_eq!(std::fs::read_dir(&note_dir).unwrap().count(), 1);
+
+    let (status, _) = app.request(Method::DELETE, &format!("/notes/{note_id}"), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Dibersihkan di background setelah event note.deleted
+    let cleaned = tokio::time::timeout(Duration::from_secs(5), async {
+        while note_dir.exists() || app.state.attachment_repo.total_size(user_id).await.unwrap() != 0 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await;
+    assert!(cleaned.is_ok(), "lampiran catatan yang dihapus masih tersisa");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// JPEG 300x200 dengan EXIF: Make, Orientation = 6 (putar 90°) dan GPS IFD
+fn jpeg_with_exif() -> Vec<u8> {
+    let mut jpeg = Cursor::new(Vec::new());
+    DynamicImage::ImageRgb8(RgbImage::from_pixel(300, 200, image::Rgb([200, 40, 40])))
+        .write_to(&mut jpeg, ImageFormat::Jpeg)
+        .unwrap();
+    let jpeg = jpeg.into_inner();
+
+    let entry = |tag: u16, kind: u16, count: u32, value: [u8; 4]| {
+        [&tag.to_le_bytes()[..], &kind.to_le_bytes(), &count.to_le_bytes(), &value].concat()
+    };
+    let mut tiff = b"II*\0".to_vec();
+    tiff.extend_from_slice(&8u32.to_le_bytes());
+    // IFD0 (offset 8): 3 entry, selesai di offset 8 + 2 + 36 + 4 = 50
+    tiff.extend_from_slice(&3u16.to_le_bytes());
+    tiff.extend(entry(0x010F, 2, 4, *b"Uji\0"));
+    tiff.extend(entry(0x0112, 3, 1, [6, 0, 0, 0]));
+    tiff.extend(entry(0x8825, 4, 1, 50u32.to_le_bytes()));
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    // GPS IFD: GPSLatitudeRef = "S"
+    tiff.extend_from_slice(&1u16.to_le_bytes());
+    tiff.extend(entry(0x0001, 2, 2, *b"S\0\0\0"));
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+
+    // Segmen APP1 disisipkan tepat setelah SOI
+    let mut out = jpeg[..2].to_vec();
+    out.extend_from_slice(&[0xFF, 0xE1]);
+    out.extend_from_slice(&((2 + 6 + tiff.len()) as u16).to_be_bytes());
+    out.extend_from_slice(b"Exif\0\0");
+    out.extend_from_slice(&tiff);
+    out.extend_from_slice(&jpeg[2..]);
+    out
+}
+
+// Tunggu sampai pemrosesan gambar di background selesai
+async fn processed_attachment(app: &TestApp, token: &str, note_id: u64, id: u64) -> Value {
+    let found = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let (_, body) = app.request(Method::GET, &format!("/notes/{note_id}/attachments"), Some(token), None).await;
+            let attachment = body["data"].as_array().unwrap().iter().find(|a| a["id"] == id).cloned().unwrap();
+            if !attachment["thumbnails"].is_null() {
+                return attachment;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await;
+    found.expect("thumbnail tidak kunjung dibuat")
+}
+
+#[tokio::test]
+async fn thumbnail_dan_exif_gambar_dibuat_di_background() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("umar").await;
+        let note_id = app.create_note(&token, json!({ "title": "Laporan" })).await["id"].as_u64().unwrap();
+        let (status, body) = upload(&app, &token, note_id, "foto.jpg", &jpeg_with_exif()).await;
+        assert_eq!(status, StatusCode::CREATED, "{backend}: {body}");
+        assert_eq!(body["data"]["content_type"], "image/jpeg", "{backend}");
+        let id = body["data"]["id"].as_u64().unwrap();
+
+        let attachment = processed_attachment(&app, &token, note_id, id).await;
+        // Orientation 6 sudah diterapkan: 300x200 jadi 200x300
+        assert_eq!((attachment["width"].clone(), attachment["height"].clone()), (json!(200), json!(300)), "{backend}");
+        assert_eq!(attachment["thumbnails"], json!([128, 512]), "{backend}");
+        let exif = attachment["exif"].as_object().unwrap();
+        assert!(exif.contains_key("Make"), "{backend}: {exif:?}");
+        assert!(exif.keys().all(|key| !key.starts_with("GPS")), "{backend}: lokasi GPS ikut tersimpan {exif:?}");
+
+        let uri = format!("/notes/{note_id}/attachments/{id}/thumbnail");
+        for (query, expected) in [("", (85, 128)), ("?size=100", (85, 128)), ("?size=200", (200, 300)), ("?size=4000", (200, 300))] {
+            let (status, headers, bytes) =
+                app.request_raw(Method::GET, &format!("{uri}{query}"), Some(&token), None, Vec::new()).await;
+            assert_eq!(status, StatusCode::OK, "{backend} {query}");
+            assert_eq!(headers[header::CONTENT_TYPE], "image/jpeg");
+            assert_eq!(headers[header::CACHE_CONTROL], "private, max-age=31536000, immutable");
+            let thumbnail = image::load_from_memory_with_format(&bytes, ImageFormat::Jpeg).unwrap();
+            assert_eq!((thumbnail.width(), thumbnail.height()), expected, "{backend} {query}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn thumbnail_png_dan_gambar_rusak() {
+    let dir: PathBuf = std::env::temp_dir().join(format!("api_catatan-thumbnail-{}", uuid::Uuid::new_v4()));
+    let mut state = common::test_state();
+    state.blobs = Arc::new(LocalBlobStore::new(&dir));
+    let app = TestApp::from_state(state);
+    let token = app.user_token("vivi").await;
+    let note_id = app.create_note(&token, json!({ "title": "Laporan" })).await["id"].as_u64().unwrap();
+
+    let mut png = Cursor::new(Vec::new());
+    DynamicImage::ImageRgba8(RgbaImage::from_pixel(40, 30, image::Rgba([0, 0, 255, 128])))
+        .write_to(&mut png, ImageFormat::Png)
+        .unwrap();
+    let (_, body) = upload(&app, &token, note_id, "ikon.png", &png.into_inner()).await;
+    let id = body["data"]["id"].as_u64().unwrap();
+    let attachment = processed_attachment(&app, &token, note_id, id).await;
+    assert_eq!((attachment["width"].clone(), attachment["height"].clone()), (json!(40), json!(30)));
+    assert!(attachment["exif"].is_null());
+
+    let uri = format!("/notes/{note_id}/attachments/{id}");
+    let (status, headers, bytes) = app.request_raw(Method::GET, &format!("{uri}/thumbnail"), Some(&token), None, Vec::new()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_TYPE], "image/png");
+    // Transparansi tetap ada dan gambar kecil tidak diperbesar
+    let thumbnail = image::load_from_memory_with_format(&bytes, ImageFormat::Png).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (40, 30));
+    assert!(thumbnail.color().has_alpha());
+
+    // Thumbnail ikut terhapus bersama lampirannya
+    let user_id = attachment["user_id"].as_u64().unwrap();
+    let note_dir = dir.join(format!("notes/{user_id}/{note_id}"));
+    assert_eq!(std::fs::read_dir(&note_dir).unwrap().count(), 3);
+    let (status, _) = app.request(Method::DELETE, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(std::fs::read_dir(&note_dir).unwrap().count(), 0);
+
+    // Header PNG tanpa isi: ditandai sudah diproses, tapi tanpa thumbnail
+    let (_, body) = upload(&app, &token, note_id, "rusak.png", PNG_HEADER).await;
+    let id = body["data"]["id"].as_u64().unwrap();
+    let attachment = processed_attachment(&app, &token, note_id, id).await;
+    assert_eq!(attachment["thumbnails"], json!([]));
+    assert!(attachment["width"].is_null());
+    let (status, body) = app
+        .request(Method::GET, &format!("/notes/{note_id}/attachments/{id}/thumbnail"), Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+
+    // Bukan gambar: tidak pernah diproses
+    let (_, body) = upload(&app, &token, note_id, "a.txt", b"teks").await;
+    let (status, _) = app
+        .request(Method::GET, &format!("/notes/{note_id}/attachments/{}/thumbnail", body["data"]["id"]), Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let _ = std::fs::remove_dir_all(&dir);
+}
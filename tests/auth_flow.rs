@@ -0,0 +1,245 @@
+mod common;
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use api_catatan::{
+    domain::repositories::token_revocation_store::{hash_token, TokenRevocationStore},
+    infrastructure::token_revocation::in_memory_store::InMemoryTokenRevocationStore,
+    utils::error::{AppError, AppResult},
+};
+use async_trait::async_trait;
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+#[tokio::test]
+async fn register_then_login_returns_token() {
+    let app = common::test_app();
+
+    let (status, body) = app.register("budi@example.com", "budi", "rahasia123").await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["status"], "success");
+    assert_eq!(body["data"]["username"], "budi");
+    assert_eq!(body["data"]["role"], "user");
+    // password_hash tidak boleh bocor ke respons
+    assert!(body["data"].get("password_hash").is_none());
+
+    let token = app.login("budi@example.com", "rahasia123").await;
+    assert!(!token.is_empty());
+}
+
+#[tokio::test]
+async fn register_rejects_duplicate_email_and_username() {
+    let app = common::test_app();
+    app.register("budi@example.com", "budi", "rahasia123").await;
+
+    let (status, _) = app.register("budi@example.com", "budi2", "rahasia123").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = app.register("lain@example.com", "budi", "rahasia123").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn login_with_wrong_password_is_unauthorized() {
+    let app = common::test_app();
+    app.register("budi@example.com", "budi", "rahasia123").await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "email": "budi@example.com", "password": "salah" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["message"], "Email atau password salah.");
+}
+
+#[tokio::test]
+async fn profile_requires_token() {
+    let app = common::test_app();
+
+    let (status, _) = app.request(Method::GET, "/auth/profile", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let token = app.user_token("budi").await;
+    let (status, body) = app.request(Method::GET, "/auth/profile", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["email"], "budi@example.com");
+}
+
+#[tokio::test]
+async fn logout_revokes_token() {
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+
+    let (status, _) = app.request(Method::POST, "/auth/logout", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app.request(Method::GET, "/auth/profile", Some(&token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Yang disimpan hanya hash-nya, bukan token mentah
+    let revocation = &app.state.token_revocation;
+    assert!(revocation.is_revoked(&hash_token(&token)).await.unwrap());
+    assert!(!revocation.is_revoked(&token).await.unwrap());
+}
+
+#[tokio::test]
+async fn full_memory_revocation_store_keeps_old_tokens_revoked() {
+    let app = common::test_app_with(common::test_config(), Arc::new(InMemoryTokenRevocationStore::new(2)));
+    let mut tokens = Vec::new();
+    for username in ["budi", "sari", "tono"] {
+        tokens.push(app.user_token(username).await);
+    }
+
+    for token in &tokens[..2] {
+        let (status, _) = app.request(Method::POST, "/auth/logout", Some(token), None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    // Store penuh: logout ke-3 gagal, bukan membuang token yang sudah logout
+    let (status, _) = app.request(Method::POST, "/auth/logout", Some(&tokens[2]), None).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+
+    let (status, _) = app.request(Method::GET, "/auth/profile", Some(&tokens[0]), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = app.request(Method::GET, "/auth/profile", Some(&tokens[1]), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+fn redis_down() -> AppError {
+    redis::RedisError::from((redis::ErrorKind::IoError, "koneksi ditolak")).into()
+}
+
+// Penyimpanan revokasi yang selalu gagal, untuk menguji kebijakan fail-open/closed
+struct UnavailableStore;
+
+#[async_trait]
+impl TokenRevocationStore for UnavailableStore {
+    async fn revoke(&self, _token_hash: &str, _ttl_secs: u64) -> AppResult<()> {
+        Err(redis_down())
+    }
+
+    async fn is_revoked(&self, _token_hash: &str) -> AppResult<bool> {
+        Err(redis_down())
+    }
+
+    async fn purge(&self) -> AppResult<usize> {
+        Ok(0)
+    }
+}
+
+#[tokio::test]
+async fn revocation_store_failure_respects_policy() {
+    // Default fail-closed: token ditolak
+    let app = common::test_app_with(common::test_config(), Arc::new(UnavailableStore));
+    let token = app.user_token("budi").await;
+    let (status, _) = app.request(Method::GET, "/auth/profile", Some(&token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Fail-open: request tetap diizinkan
+    let mut config = common::test_config();
+    config.token_revocation_fail_open = true;
+    let app = common::test_app_with(config, Arc::new(UnavailableStore));
+    let token = app.user_token("budi").await;
+    let (status, _) = app.request(Method::GET, "/auth/profile", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn messages_follow_accept_language() {
+    let app = common::test_app();
+
+    let (status, body) = app
+        .request_with_headers(Method::GET, "/auth/profile", None, None, &[("accept-language", "en-US,en;q=0.9")])
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["message"], "Authentication token is missing.");
+}
+
+#[tokio::test]
+async fn settings_locale_overrides_accept_language() {
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+    // Pengaturan default sudah tersimpan di cache sebelum diubah
+    let (_, body) = app.request(Method::GET, "/auth/profile", Some(&token), None).await;
+    assert_eq!(body["message"], "Profil user berhasil diambil.");
+
+    let (status, body) = app
+        .request(
+            Method::PATCH,
+            "/auth/settings",
+            Some(&token),
+            Some(json!({ "locale": "en", "timezone": "Asia/Makassar" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["timezone"], "Asia/Makassar");
+
+    let (_, body) = app
+        .request_with_headers(Method::GET, "/auth/profile", Some(&token), None, &[("accept-language", "id")])
+        .await;
+    assert_eq!(body["message"], "User profile retrieved successfully.");
+    assert!(body["data"]["created_at_local"].as_str().unwrap().ends_with("WITA"));
+}
+
+#[tokio::test]
+async fn settings_are_cached_between_requests() {
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+    let loads = || {
+        app.state
+            .metrics
+            .repository_query_duration_seconds
+            .with_label_values(&["user", "find_settings", "ok"])
+            .get_sample_count()
+    };
+    let before = loads();
+
+    for _ in 0..3 {
+        let (status, _) = app.request(Method::GET, "/notes", Some(&token), None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    assert_eq!(loads() - before, 1);
+}
+
+// Menghitung pemanggilan `is_revoked`
+#[derive(Default)]
+struct CountingStore {
+    checks: AtomicUsize,
+}
+
+#[async_trait]
+impl TokenRevocationStore for CountingStore {
+    async fn revoke(&self, _token_hash: &str, _ttl_secs: u64) -> AppResult<()> {
+        Ok(())
+    }
+
+    async fn is_revoked(&self, _token_hash: &str) -> AppResult<bool> {
+        self.checks.fetch_add(1, Ordering::SeqCst);
+        Ok(false)
+    }
+
+    async fn purge(&self) -> AppResult<usize> {
+        Ok(0)
+    }
+}
+
+#[tokio::test]
+async fn invalid_token_is_rejected_before_revocation_lookup() {
+    let store = Arc::new(CountingStore::default());
+    let app = common::test_app_with(common::test_config(), store.clone());
+
+    let (status, _) = app.request(Method::GET, "/auth/profile", Some("bukan-jwt"), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(store.checks.load(Ordering::SeqCst), 0);
+
+    let token = app.user_token("budi").await;
+    let (status, _) = app.request(Method::GET, "/auth/profile", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(store.checks.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,193 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+
+use common::TestApp;
+
+async fn bulk(app: &TestApp, token: &str, body: Value) -> Value {
+    let (status, body) = app.request(Method::POST, "/notes/bulk", Some(token), Some(body)).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    body["data"]["results"].clone()
+}
+
+async fn get_note(app: &TestApp, token: &str, id: u64) -> (StatusCode, Value) {
+    let (status, body) = app.request(Method::GET, &format!("/notes/{id}"), Some(token), None).await;
+    (status, body["data"].clone())
+}
+
+fn statuses(result: &Value) -> Vec<&str> {
+    result["items"].as_array().unwrap().iter().map(|item| item["status"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn bulk_update_arsip_tag_dan_hapus() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("dina").await;
+        let a = app
+            .create_note(&token, json!({ "title": "A", "content": "satu", "tags": ["kerja", "lama"] }))
+            .await["id"]
+            .as_u64()
+            .unwrap();
+        let b = app.create_note(&token, json!({ "title": "B", "tags": ["lama"] })).await["id"].as_u64().unwrap();
+        let c = app.create_note(&token, json!({ "title": "C" })).await["id"].as_u64().unwrap();
+
+        let results = bulk(
+            &app,
+            &token,
+            json!({ "operations": [
+                { "op": "update", "ids": [a, b, a], "content": "baru" },
+                { "op": "archive", "ids": [a, b] },
+                { "op": "tag", "ids": [a, b], "add": [" Penting "], "remove": ["LAMA"] },
+                { "op": "delete", "ids": [c] },
+            ] }),
+        )
+        .await;
+
+        assert_eq!(results[0]["op"], "update", "{backend}");
+        // Id duplikat hanya diproses sekali
+        assert_eq!(statuses(&results[0]), ["applied", "applied"], "{backend}");
+        assert_eq!(results[0]["applied"], 2, "{backend}");
+        assert_eq!(results[0]["items"][0]["note"]["content"], "baru", "{backend}");
+        assert_eq!(results[0]["items"][0]["note"]["title"], "A", "{backend}");
+        assert_eq!(statuses(&results[1]), ["applied", "applied"], "{backend}");
+        assert_eq!(statuses(&results[2]), ["applied", "applied"], "{backend}");
+        assert_eq!(statuses(&results[3]), ["applied"], "{backend}");
+        assert!(results[3]["items"][0].get("note").is_none(), "{backend}");
+
+        let (_, note_a) = get_note(&app, &token, a).await;
+        assert_eq!(note_a["content"], "baru", "{backend}");
+        assert_eq!(note_a["archived"], true, "{backend}");
+        assert_eq!(note_a["tags"], json!(["kerja", "penting"]), "{backend}");
+        let (_, note_b) = get_note(&app, &token, b).await;
+        assert_eq!(note_b["tags"], json!(["penting"]), "{backend}");
+        let (status, _) = get_note(&app, &token, c).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{backend}");
+
+        // Perubahan bulk ikut terbaca lewat /sync seperti perubahan biasa
+        let (_, sync) = app.request(Method::GET, "/sync", Some(&token), None).await;
+        assert_eq!(sync["data"]["deleted"][0]["note_id"], c, "{backend}");
+        assert_eq!(sync["data"]["notes"].as_array().unwrap().len(), 2, "{backend}");
+
+        let (_, list) = app.request(Method::GET, "/notes", Some(&token), None).await;
+        assert_eq!(list["data"].as_array().unwrap().len(), 2, "{backend}");
+    }
+}
+
+#[tokio::test]
+async fn bulk_atomik_dibatalkan_jika_satu_id_gagal() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("dina").await;
+        let lain = app.user_token("edo").await;
+        let a = app.create_note(&token, json!({ "title": "A" })).await["id"].as_u64().unwrap();
+        let b = app.create_note(&token, json!({ "title": "B" })).await["id"].as_u64().unwrap();
+        let punya_edo = app.create_note(&lain, json!({ "title": "Punya Edo" })).await["id"].as_u64().unwrap();
+
+        let results = bulk(
+            &app,
+            &token,
+            json!({ "operations": [
+                { "op": "delete", "ids": [a, punya_edo] },
+                { "op": "update", "ids": [a, b], "title": "Judul baru" },
+                { "op": "update", "ids": [b], "title": "  " },
+            ] }),
+        )
+        .await;
+
+        // Catatan milik user lain dianggap tidak ada; `a` ikut batal dihapus
+        assert_eq!(statuses(&results[0]), ["skipped", "not_found"], "{backend}");
+        assert_eq!(results[0]["applied"], 0, "{backend}");
+        assert_eq!(results[0]["failed"], 2, "{backend}");
+        assert!(results[0]["items"][0]["message"].is_string(), "{backend}");
+        // Operasi lain tetap berjalan sendiri-sendiri
+        assert_eq!(statuses(&results[1]), ["applied", "applied"], "{backend}");
+        assert_eq!(statuses(&results[2]), ["invalid"], "{backend}");
+
+        let (status, note_a) = get_note(&app, &token, a).await;
+        assert_eq!(status, StatusCode::OK, "{backend}");
+        assert_eq!(note_a["title"], "Judul baru", "{backend}");
+        let (_, note_b) = get_note(&app, &token, b).await;
+        assert_eq!(note_b["title"], "Judul baru", "{backend}");
+        let (status, _) = get_note(&app, &lain, punya_edo).await;
+        assert_eq!(status, StatusCode::OK, "{backend}");
+    }
+}
+
+#[tokio::test]
+async fn bulk_non_atomik_menerapkan_id_yang_valid() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("dina").await;
+        let a = app.create_note(&token, json!({ "title": "A" })).await["id"].as_u64().unwrap();
+        let b = app.create_note(&token, json!({ "title": "B", "tags": ["x"] })).await["id"].as_u64().unwrap();
+        let penuh: Vec<String> = (0..20).map(|i| format!("t{i}")).collect();
+        let c = app.create_note(&token, json!({ "title": "C", "tags": penuh })).await["id"].as_u64().unwrap();
+
+        let results = bulk(
+            &app,
+            &token,
+            json!({ "atomic": false, "operations": [
+                { "op": "tag", "ids": [a, c, 9999], "add": ["baru"] },
+                { "op": "delete", "ids": [b, 9999] },
+            ] }),
+        )
+        .await;
+
+        // Tag ke-21 melewati batas per catatan
+        assert_eq!(statuses(&results[0]), ["applied", "invalid", "not_found"], "{backend}");
+        assert_eq!(results[0]["applied"], 1, "{backend}");
+        assert_eq!(statuses(&results[1]), ["applied", "not_found"], "{backend}");
+
+        let (_, note_a) = get_note(&app, &token, a).await;
+        assert_eq!(note_a["tags"], json!(["baru"]), "{backend}");
+        let (status, _) = get_note(&app, &token, b).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{backend}");
+    }
+}
+
+#[tokio::test]
+async fn bulk_menolak_payload_tidak_valid() {
+    let app = common::test_app();
+    let token = app.user_token("dina").await;
+
+    let ids: Vec<u32> = (1..=501).collect();
+    let (status, body) = app
+        .request(Method::POST, "/notes/bulk", Some(&token), Some(json!({ "operations": [{ "op": "delete", "ids": ids }] })))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+
+    let (status, _) = app
+        .request(Method::POST, "/notes/bulk", Some(&token), Some(json!({ "operations": [{ "op": "pindah", "ids": [1] }] })))
+        .await;
+    assert!(status.is_client_error());
+
+    let (status, _) = app.request(Method::POST, "/notes/bulk", None, Some(json!({ "operations": [] }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn delete_many_tidak_menghapus_catatan_yang_sudah_berubah() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("dina").await;
+        let (_, profile) = app.request(Method::GET, "/auth/profile", Some(&token), None).await;
+        let user_id = profile["data"]["id"].as_u64().unwrap() as u32;
+        let note_a = app.create_note(&token, json!({ "title": "A" })).await;
+        let note_b = app.create_note(&token, json!({ "title": "B" })).await;
+        let (a, b) = (note_a["id"].as_u64().unwrap(), note_b["id"].as_u64().unwrap());
+        let seq_a = note_a["change_seq"].as_i64().unwrap();
+        let seq_b = note_b["change_seq"].as_i64().unwrap();
+
+        // `a` diubah setelah dibaca; versi lamanya tidak boleh terhapus
+        let (status, _) = app.request(Method::PUT, &format!("/notes/{a}"), Some(&token), Some(json!({ "title": "A2" }))).await;
+        assert_eq!(status, StatusCode::OK, "{backend}");
+
+        let repo = &app.state.note_repo;
+        let expected = [(a as u32, seq_a), (b as u32, seq_b)];
+        assert!(repo.delete_many(user_id, &expected, true).await.unwrap().is_empty(), "{backend}");
+        assert_eq!(get_note(&app, &token, b).await.0, StatusCode::OK, "{backend}: rollback");
+
+        assert_eq!(repo.delete_many(user_id, &expected, false).await.unwrap(), [b as u32], "{backend}");
+        let (status, note_a) = get_note(&app, &token, a).await;
+        assert_eq!(status, StatusCode::OK, "{backend}");
+        assert_eq!(note_a["title"], "A2", "{backend}");
+    }
+}
@@ -0,0 +1,312 @@
+mod common;
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use api_catatan::application::collab_service::CollabHub;
+use automerge::{
+    sync::{self, SyncDoc},
+    transaction::Transactable,
+    AutoCommit, ObjId, ObjType, ReadDoc, Value as AmValue, ROOT,
+};
+use axum::http::{Method, StatusCode};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    tungstenite::{self, client::IntoClientRequest, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+
+use common::TestApp;
+
+// Batas tunggu pesan dari server
+const WAIT: Duration = Duration::from_secs(5);
+
+// Klien kolaborasi di test: dokumen automerge lokal + status sync dengan server
+struct Client {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    doc: AutoCommit,
+    sync: sync::State,
+    peer_id: String,
+    welcome: Value,
+}
+
+impl Client {
+    async fn connect(addr: SocketAddr, token: &str, note_id: u64) -> Client {
+        let request = format!("ws://{addr}/notes/{note_id}/collab?access_token={token}")
+            .into_client_request()
+            .unwrap();
+        let (socket, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+        let mut client = Client {
+            socket,
+            doc: AutoCommit::new(),
+            sync: sync::State::new(),
+            peer_id: String::new(),
+            welcome: Value::Null,
+        };
+
+        client.welcome = client.next_text().await;
+        assert_eq!(client.welcome["type"], "welcome");
+        client.peer_id = client.welcome["peer_id"].as_str().unwrap().to_string();
+        // Tunggu isi dokumen dari server
+        client.pump_until(|doc| content_obj(doc).is_some()).await;
+        client
+    }
+
+    // Proses satu pesan dari server; pesan teks JSON dikembalikan
+    async fn step(&mut self) -> Option<Value> {
+        let message = self.socket.next().await.expect("koneksi tertutup").unwrap();
+        match message {
+            Message::Binary(data) => {
+                let message = sync::Message::decode(&data).unwrap();
+                self.doc.sync().receive_sync_message(&mut self.sync, message).unwrap();
+                self.send_sync().await;
+                None
+            }
+            Message::Text(text) => Some(serde_json::from_str(&text).unwrap()),
+            Message::Close(frame) => Some(json!({ "type": "closed", "frame": frame.map(|f| f.reason.to_string()) })),
+            _ => None,
+        }
+    }
+
+    async fn next_text(&mut self) -> Value {
+        tokio::time::timeout(WAIT, async {
+            loop {
+                if let Some(value) = self.step().await {
+                    return value;
+                }
+            }
+        })
+        .await
+        .expect("tidak ada pesan teks dalam batas waktu")
+    }
+
+    // Pesan teks berikutnya dengan `type` tertentu, pesan lain dilewati
+    async fn next_of_type(&mut self, kind: &str) -> Value {
+        loop {
+            let value = self.next_text().await;
+            if value["type"] == kind {
+                return value;
+            }
+        }
+    }
+
+    async fn pump_until(&mut self, done: impl Fn(&AutoCommit) -> bool) {
+        tokio::time::timeout(WAIT, async {
+            while !done(&self.doc) {
+                self.step().await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("dokumen tidak sesuai dalam batas waktu, isi: {:?}", self.content()));
+    }
+
+    // Tunggu sampai server punya semua perubahan lokal. Server bisa meminta
+    // ulang perubahan (bloom filter di protokol sync), jadi klien harus tetap menjawab.
+    async fn wait_synced(&mut self) {
+        tokio::time::timeout(WAIT, async {
+            loop {
+                let mut heads = self.doc.get_heads();
+                heads.sort();
+                let mut theirs = self.sync.their_heads.clone().unwrap_or_default();
+                theirs.sort();
+                if heads == theirs {
+                    break;
+                }
+                self.step().await;
+            }
+        })
+        .await
+        .expect("server tidak menerima perubahan dalam batas waktu");
+    }
+
+    async fn send_sync(&mut self) {
+        if let Some(message) = self.doc.sync().generate_sync_message(&mut self.sync) {
+            self.socket.send(Message::Binary(message.encode())).await.unwrap();
+        }
+    }
+
+    async fn splice(&mut self, pos: usize, text: &str) {
+        let content = content_obj(&self.doc).unwrap();
+        self.doc.splice_text(&content, pos, 0, text).unwrap();
+        self.send_sync().await;
+    }
+
+    async fn send_presence(&mut self, state: Value) {
+        let text = json!({ "type": "presence", "state": state }).to_string();
+        self.socket.send(Message::Text(text)).await.unwrap();
+    }
+
+    fn content(&self) -> Option<String> {
+        content_obj(&self.doc).map(|content| self.doc.text(&content).unwrap())
+    }
+}
+
+fn content_obj(doc: &AutoCommit) -> Option<ObjId> {
+    match doc.get(ROOT, "content").unwrap() {
+        Some((AmValue::Object(ObjType::Text), id)) => Some(id),
+        _ => None,
+    }
+}
+
+async fn serve(app: &TestApp) -> SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = app.router.clone();
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    addr
+}
+
+// App dengan interval snapshot singkat
+fn app_with_snapshot_interval(interval: Duration) -> TestApp {
+    let mut state = common::test_state();
+    state.collab = Arc::new(CollabHub::new(state.note_repo.clone(), state.events.clone(), interval));
+    TestApp::from_state(state)
+}
+
+async fn note_content(app: &TestApp, token: &str, id: u64) -> Value {
+    let (status, body) = app.request(Method::GET, &format!("/notes/{id}"), Some(token), None).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    body["data"]["content"].clone()
+}
+
+// Tunggu sampai isi catatan di database sama dengan `expected`
+async fn wait_for_content(app: &TestApp, token: &str, id: u64, expected: &str) {
+    let result = tokio::time::timeout(WAIT, async {
+        while note_content(app, token, id).await != expected {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await;
+    assert!(result.is_ok(), "isi catatan tetap {:?}", note_content(app, token, id).await);
+}
+
+#[tokio::test]
+async fn dua_klien_konvergen_saat_edit_bersamaan() {
+    let app = common::test_app();
+    let addr = serve(&app).await;
+    let token = app.user_token("gita").await;
+    let id = app.create_note(&token, json!({ "title": "Rapat", "content": "Agenda" })).await["id"].as_u64().unwrap();
+
+    let mut a = Client::connect(addr, &token, id).await;
+    let mut b = Client::connect(addr, &token, id).await;
+    assert_eq!(a.content().as_deref(), Some("Agenda"));
+    assert_eq!(b.content().as_deref(), Some("Agenda"));
+
+    // Kedua klien mengedit sebelum melihat perubahan satu sama lain
+    a.splice(0, "[A] ").await;
+    b.splice(6, " [B]").await;
+
+    let both = |doc: &AutoCommit| {
+        let text = doc.text(content_obj(doc).unwrap()).unwrap();
+        text.contains("[A]") && text.contains("[B]")
+    };
+    // Dipompa bersamaan: server bisa meminta ulang perubahan ke klien mana pun
+    tokio::join!(a.pump_until(both), b.pump_until(both));
+    assert_eq!(a.content().as_deref(), Some("[A] Agenda [B]"));
+    assert_eq!(a.content(), b.content());
+}
+
+#[tokio::test]
+async fn presence_diteruskan_ke_peserta_lain() {
+    let app = common::test_app();
+    let addr = serve(&app).await;
+    let token = app.user_token("hadi").await;
+    let id = app.create_note(&token, json!({ "title": "Rapat", "content": "Agenda" })).await["id"].as_u64().unwrap();
+
+    let mut a = Client::connect(addr, &token, id).await;
+    a.send_presence(json!({ "cursor": 3, "name": "Hadi" })).await;
+    // Tunggu presence sampai ke server sebelum B bergabung
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut b = Client::connect(addr, &token, id).await;
+    let peers = b.welcome["peers"].as_array().unwrap();
+    assert_eq!(peers.len(), 1);
+    assert_eq!(peers[0]["peer_id"], a.peer_id);
+    assert_eq!(peers[0]["presence"]["cursor"], 3);
+
+    let joined = a.next_of_type("joined").await;
+    assert_eq!(joined["peer_id"], b.peer_id);
+
+    b.send_presence(json!({ "cursor": 7 })).await;
+    let presence = a.next_of_type("presence").await;
+    assert_eq!(presence["peer_id"], b.peer_id);
+    assert_eq!(presence["state"]["cursor"], 7);
+
+    b.socket.close(None).await.unwrap();
+    let left = a.next_of_type("left").await;
+    assert_eq!(left["peer_id"], b.peer_id);
+}
+
+#[tokio::test]
+async fn isi_disimpan_saat_peserta_terakhir_keluar() {
+    let app = common::test_app();
+    let addr = serve(&app).await;
+    let token = app.user_token("indah").await;
+    let id = app.create_note(&token, json!({ "title": "Rapat", "content": "Agenda" })).await["id"].as_u64().unwrap();
+
+    let mut a = Client::connect(addr, &token, id).await;
+    a.splice(6, ": anggaran").await;
+    a.wait_synced().await;
+    a.socket.close(None).await.unwrap();
+
+    wait_for_content(&app, &token, id, "Agenda: anggaran").await;
+}
+
+#[tokio::test]
+async fn snapshot_berkala_dan_perubahan_rest_tidak_hilang() {
+    let app = app_with_snapshot_interval(Duration::from_millis(50));
+    let addr = serve(&app).await;
+    let token = app.user_token("joko").await;
+    let id = app.create_note(&token, json!({ "title": "Rapat", "content": "Agenda" })).await["id"].as_u64().unwrap();
+
+    // Snapshot berjalan walau klien masih tersambung
+    let mut a = Client::connect(addr, &token, id).await;
+    a.splice(0, "Rapat: ").await;
+    a.wait_synced().await;
+    wait_for_content(&app, &token, id, "Rapat: Agenda").await;
+
+    // Perubahan lewat REST ikut masuk ke dokumen yang sedang diedit
+    let (status, _) = app
+        .request(Method::PUT, &format!("/notes/{id}"), Some(&token), Some(json!({ "content": "Rapat: Agenda baru" })))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    a.pump_until(|doc| doc.text(content_obj(doc).unwrap()).unwrap() == "Rapat: Agenda baru").await;
+
+    a.splice(0, "[x] ").await;
+    a.wait_synced().await;
+    wait_for_content(&app, &token, id, "[x] Rapat: Agenda baru").await;
+}
+
+#[tokio::test]
+async fn koneksi_ditutup_saat_catatan_dihapus() {
+    let app = common::test_app();
+    let addr = serve(&app).await;
+    let token = app.user_token("kiki").await;
+    let id = app.create_note(&token, json!({ "title": "Rapat", "content": "Agenda" })).await["id"].as_u64().unwrap();
+
+    let mut a = Client::connect(addr, &token, id).await;
+    let (status, _) = app.request(Method::DELETE, &format!("/notes/{id}"), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let closed = a.next_of_type("closed").await;
+    assert_eq!(closed["frame"], "catatan dihapus");
+}
+
+#[tokio::test]
+async fn collab_hanya_untuk_pemilik_catatan() {
+    let app = common::test_app();
+    let addr = serve(&app).await;
+    let owner = app.user_token("lina").await;
+    let other = app.user_token("mira").await;
+    let id = app.create_note(&owner, json!({ "title": "Rapat", "content": "Rahasia" })).await["id"].as_u64().unwrap();
+
+    let (status, _) = app.request(Method::GET, &format!("/notes/{id}/collab"), None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let request = format!("ws://{addr}/notes/{id}/collab?access_token={other}").into_client_request().unwrap();
+    match tokio_tungstenite::connect_async(request).await {
+        Err(tungstenite::Error::Http(response)) => assert_eq!(response.status(), StatusCode::NOT_FOUND),
+        other => panic!("user lain seharusnya ditolak: {:?}", other.map(|(_, response)| response.status())),
+    }
+}
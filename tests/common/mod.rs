@@ -0,0 +1,279 @@
+// Helper untuk test HTTP end-to-end.
+// Router dibangun dengan repository & penyimpanan revokasi in-memory, jadi test
+// tidak butuh MySQL maupun Redis.
+#![allow(dead_code)] // Tidak semua file test memakai semua helper
+
+use std::sync::Arc;
+
+use api_catatan::{
+    application::{
+        attachment_service::{AttachmentSettings, AttachmentService},
+        collab_service::CollabHub,
+        import_service::ImportJobs,
+        note_renderer::NoteRenderer,
+        user_service::{SettingsCache, UserService},
+    },
+    domain::{
+        models::user::{RegisterPayload, Role},
+        repositories::{event_bus::DynEventBus, token_revocation_store::DynTokenRevocationStore},
+    },
+    infrastructure::{
+        events::in_memory_bus::InMemoryEventBus,
+        repositories::{
+            self, create_repositories,
+            in_memory::{
+                attachment_repository_impl::InMemoryAttachmentRepository,
+                note_repository_impl::InMemoryNoteRepository,
+                user_repository_impl::InMemoryUserRepository,
+            },
+        },
+        storage::s3_store::S3BlobStore,
+        token_revocation::in_memory_store::InMemoryTokenRevocationStore,
+    },
+    presentation::routes::create_router,
+    utils::{background::BackgroundTasks, config::Config, db, metrics::Metrics},
+    AppState,
+};
+use axum::{
+    body::{Body, Bytes},
+    http::{header, HeaderMap, Method, Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+pub struct TestApp {
+    pub router: Router,
+    pub state: Arc<AppState>,
+}
+
+pub fn test_config() -> Config {
+    serde_json::from_value(json!({
+        "database_url": "sqlite::memory:",
+        "redis_host": "localhost",
+        "redis_port": 6379,
+        "redis_password": "",
+        "redis_db": 0,
+        "jwt_secret_key": "test-secret-key-yang-cukup-panjang-123",
+        "jwt_expires_in": "1h",
+    }))
+    .expect("config test tidak valid")
+}
+
+pub fn test_app() -> TestApp {
+    TestApp::from_state(test_state())
+}
+
+// State default untuk test; field-nya bisa diganti sebelum dipakai
+pub fn test_state() -> AppState {
+    // Repository dibungkus decorator metrik seperti di main.rs
+    let metrics = Arc::new(Metrics::new());
+    let (user_repo, note_repo, attachment_repo) = repositories::with_metrics(
+        (
+            Arc::new(InMemoryUserRepository::new()),
+            Arc::new(InMemoryNoteRepository::new()),
+            Arc::new(InMemoryAttachmentRepository::new()),
+        ),
+        &metrics,
+    );
+
+    let events: DynEventBus = Arc::new(InMemoryEventBus::default());
+    let config = test_config();
+    let collab = Arc::new(CollabHub::new(note_repo.clone(), events.clone(), config.collab_snapshot_interval));
+
+    AppState {
+        config: Arc::new(config),
+        user_repo,
+        note_repo,
+        attachment_repo,
+        // S3 palsu di memori; test lampiran bisa menggantinya dengan folder lokal
+        blobs: Arc::new(S3BlobStore::in_memory()),
+        token_revocation: Arc::new(InMemoryTokenRevocationStore::default()),
+        health_checks: Vec::new(),
+        metrics,
+        background: BackgroundTasks::new(),
+        events,
+        collab,
+        renderer: Arc::new(NoteRenderer::default()),
+        imports: Arc::new(ImportJobs::new()),
+        settings: Arc::new(SettingsCache::new()),
+    }
+}
+
+// App in-memory dan SQLite (in-memory, sudah dimigrasi) untuk test yang harus lolos
+// di kedua backend, supaya query SQL-nya ikut teruji
+pub async fn apps() -> Vec<(&'static str, TestApp)> {
+    let mut config = test_config();
+    config.db_auto_migrate = true;
+    let pool = db::create_pool(&config).await.expect("sqlite in-memory gagal dibuat");
+
+    let mut state = test_state();
+    (state.user_repo, state.note_repo, state.attachment_repo) =
+        repositories::with_metrics(create_repositories(&pool), &state.metrics);
+    state.config = Arc::new(config);
+
+    vec![("in-memory", test_app()), ("sqlite", TestApp::from_state(state))]
+}
+
+// Sama seperti test_app, tapi config & penyimpanan revokasi bisa diganti
+pub fn test_app_with(config: Config, token_revocation: DynTokenRevocationStore) -> TestApp {
+    TestApp::from_state(AppState {
+        config: Arc::new(config),
+        token_revocation,
+        ..test_state()
+    })
+}
+
+impl TestApp {
+    pub fn from_state(state: AppState) -> Self {
+        // Pembersih lampiran dijalankan di sini (bukan di test_state) supaya
+        // memakai repository & blob store final setelah field state diganti
+        AttachmentService::new(
+            state.attachment_repo.clone(),
+            state.note_repo.clone(),
+            state.blobs.clone(),
+            AttachmentSettings::from(state.config.as_ref()),
+            state.background.clone(),
+        )
+        .spawn_cleanup(&state.events);
+
+        let state = Arc::new(state);
+        TestApp {
+            router: create_router(state.clone()),
+            state,
+        }
+    }
+
+    // Kirim satu request ke router dan kembalikan status + body JSON
+    pub async fn request(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        self.request_with_headers(method, uri, token, body, &[]).await
+    }
+
+    pub async fn request_with_headers(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+        headers: &[(&str, &str)],
+    ) -> (StatusCode, Value) {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+
+        let request = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .unwrap();
+
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let json = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into()))
+        };
+        (status, json)
+    }
+
+    // Request dengan body mentah (multipart, file, ...); respons juga dikembalikan mentah
+    pub async fn request_raw(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        content_type: Option<&str>,
+        body: Vec<u8>,
+    ) -> (StatusCode, HeaderMap, Bytes) {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        if let Some(content_type) = content_type {
+            builder = builder.header(header::CONTENT_TYPE, content_type);
+        }
+
+        let response = self.router.clone().oneshot(builder.body(Body::from(body)).unwrap()).await.unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, headers, bytes)
+    }
+
+    pub async fn register(&self, email: &str, username: &str, password: &str) -> (StatusCode, Value) {
+        self.request(
+            Method::POST,
+            "/auth/register",
+            None,
+            Some(json!({
+                "email": email,
+                "full_name": "Test User",
+                "username": username,
+                "password": password,
+            })),
+        )
+        .await
+    }
+
+    // Login dan kembalikan access token
+    pub async fn login(&self, email: &str, password: &str) -> String {
+        let (status, body) = self
+            .request(
+                Method::POST,
+                "/auth/login",
+                None,
+                Some(json!({ "email": email, "password": password })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "login gagal: {body}");
+        body["data"]["access_token"].as_str().unwrap().to_string()
+    }
+
+    // Register + login user biasa, kembalikan token
+    pub async fn user_token(&self, username: &str) -> String {
+        let email = format!("{username}@example.com");
+        let (status, body) = self.register(&email, username, "rahasia123").await;
+        assert_eq!(status, StatusCode::CREATED, "register gagal: {body}");
+        self.login(&email, "rahasia123").await
+    }
+
+    // Buat catatan lewat POST /notes, kembalikan `data` dari respons
+    pub async fn create_note(&self, token: &str, body: Value) -> Value {
+        let (status, body) = self.request(Method::POST, "/notes", Some(token), Some(body)).await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+        body["data"].clone()
+    }
+
+    // Admin tidak bisa dibuat lewat HTTP, jadi dibuat langsung lewat service
+    pub async fn admin_token(&self, username: &str) -> String {
+        let email = format!("{username}@example.com");
+        UserService::new(self.state.user_repo.clone())
+            .create_user(
+                RegisterPayload {
+                    email: email.clone(),
+                    full_name: "Admin".to_string(),
+                    username: username.to_string(),
+                    password: "rahasia123".to_string(),
+                },
+                Role::Admin,
+            )
+            .await
+            .unwrap();
+        self.login(&email, "rahasia123").await
+    }
+}
@@ -0,0 +1,265 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use api_catatan::{
+    domain::{
+        models::event::{NoteEvent, NoteEventKind},
+        repositories::event_bus::DynEventBus,
+    },
+    infrastructure::events::{in_memory_bus::InMemoryEventBus, redis_bus::RedisEventBus},
+    utils::background::BackgroundTasks,
+};
+use axum::{
+    body::{Body, BodyDataStream},
+    http::{header, Method, Request, StatusCode},
+};
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+use tower::ServiceExt;
+
+use common::TestApp;
+
+// Satu event SSE yang sudah di-parse
+#[derive(Debug)]
+struct SseEvent {
+    id: u64,
+    event: String,
+    data: Value,
+}
+
+// Pembaca stream SSE sederhana di atas body response
+struct SseReader {
+    body: BodyDataStream,
+    buffer: String,
+}
+
+impl SseReader {
+    // Event berikutnya (komentar keep-alive dilewati), gagal jika tidak ada dalam 5 detik
+    async fn next(&mut self) -> SseEvent {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(end) = self.buffer.find("\n\n") {
+                    let block: String = self.buffer.drain(..end + 2).collect();
+                    let (mut id, mut event, mut data) = (None, None, None);
+                    for line in block.lines() {
+                        match line.split_once(':') {
+                            Some(("id", value)) => id = value.trim().parse().ok(),
+                            Some(("event", value)) => event = Some(value.trim().to_string()),
+                            Some(("data", value)) => data = serde_json::from_str(value.trim()).ok(),
+                            _ => {}
+                        }
+                    }
+                    if let (Some(id), Some(event), Some(data)) = (id, event, data) {
+                        return SseEvent { id, event, data };
+                    }
+                    continue;
+                }
+                let chunk = self.body.next().await.expect("stream SSE berakhir").unwrap();
+                self.buffer.push_str(&String::from_utf8_lossy(&chunk));
+            }
+        })
+        .await
+        .expect("tidak ada event SSE dalam 5 detik")
+    }
+
+    // Pastikan tidak ada event lain yang datang dalam waktu singkat
+    async fn assert_idle(&mut self) {
+        let result = tokio::time::timeout(Duration::from_millis(200), self.next()).await;
+        assert!(result.is_err(), "ada event yang tidak diharapkan: {result:?}");
+    }
+}
+
+async fn open_sse(app: &TestApp, token: &str, last_event_id: Option<u64>) -> SseReader {
+    let mut builder = Request::builder()
+        .uri("/events")
+        .header(header::AUTHORIZATION, format!("Bearer {token}"));
+    if let Some(id) = last_event_id {
+        builder = builder.header("Last-Event-ID", id.to_string());
+    }
+
+    let response = app.router.clone().oneshot(builder.body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+    SseReader { body: response.into_body().into_data_stream(), buffer: String::new() }
+}
+
+// Publish dari banyak task sekaligus harus sampai ke subscriber dengan id naik,
+// supaya resume lewat Last-Event-ID tidak melewatkan event
+async fn assert_publish_berurutan(bus: DynEventBus, user_id: u32) {
+    const JUMLAH: usize = 50;
+    let mut receiver = bus.subscribe();
+
+    let tasks: Vec<_> = (0..JUMLAH)
+        .map(|note_id| {
+            let bus = bus.clone();
+            tokio::spawn(async move {
+                bus.publish(NoteEvent::new(NoteEventKind::Created, user_id, note_id as u32, None)).await.unwrap()
+            })
+        })
+        .collect();
+    let mut published = Vec::new();
+    for task in tasks {
+        published.push(task.await.unwrap());
+    }
+
+    let mut received = Vec::new();
+    while received.len() < JUMLAH {
+        let event = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .expect("event tidak sampai dalam 5 detik")
+            .unwrap();
+        if event.user_id == user_id {
+            received.push(event.id);
+        }
+    }
+    assert!(received.windows(2).all(|pair| pair[0] < pair[1]), "urutan id: {received:?}");
+    published.sort_unstable();
+    assert_eq!(received, published);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn publish_bersamaan_tetap_urut() {
+    for _ in 0..20 {
+        assert_publish_berurutan(Arc::new(InMemoryEventBus::default()), 1).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "butuh Redis di localhost:6379"]
+async fn redis_publish_bersamaan_tetap_urut() {
+    let client = redis::Client::open("redis://127.0.0.1:6379").unwrap();
+    let bus = RedisEventBus::new(client, 100, Duration::from_secs(60));
+    let background = BackgroundTasks::new();
+    bus.start_listener(&background);
+    // Beri waktu listener untuk SUBSCRIBE sebelum event pertama terbit
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let user_id = unique_user_id();
+    assert_publish_berurutan(Arc::new(bus), user_id).await;
+    background.shutdown(Duration::from_secs(1)).await;
+}
+
+// User id acak supaya riwayat dari run sebelumnya di Redis yang sama tidak tercampur
+fn unique_user_id() -> u32 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().subsec_nanos() | 1 << 31
+}
+
+#[tokio::test]
+async fn sse_mengirim_event_create_update_delete() {
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+    let mut events = open_sse(&app, &token, None).await;
+
+    let note_id = app.create_note(&token, json!({ "title": "Belanja" })).await["id"].as_u64().unwrap();
+    let created = events.next().await;
+    assert_eq!(created.event, "note.created");
+    assert_eq!(created.data["type"], "note.created");
+    assert_eq!(created.data["note_id"], note_id);
+    assert_eq!(created.data["note"]["title"], "Belanja");
+    assert_eq!(created.data["id"], created.id);
+
+    let (status, _) = app
+        .request(Method::PUT, &format!("/notes/{note_id}"), Some(&token), Some(json!({ "title": "Belanja bulanan" })))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let updated = events.next().await;
+    assert_eq!(updated.event, "note.updated");
+    assert_eq!(updated.data["note"]["title"], "Belanja bulanan");
+    assert!(updated.id > created.id);
+
+    let (status, _) = app.request(Method::DELETE, &format!("/notes/{note_id}"), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let deleted = events.next().await;
+    assert_eq!(deleted.event, "note.deleted");
+    assert_eq!(deleted.data["note_id"], note_id);
+    assert!(deleted.data.get("note").is_none());
+}
+
+#[tokio::test]
+async fn event_user_lain_tidak_terkirim() {
+    let app = common::test_app();
+    let budi = app.user_token("budi").await;
+    let citra = app.user_token("citra").await;
+    let mut events = open_sse(&app, &budi, None).await;
+
+    app.create_note(&citra, json!({ "title": "Rahasia Citra" })).await;
+    app.create_note(&budi, json!({ "title": "Punya Budi" })).await;
+
+    let event = events.next().await;
+    assert_eq!(event.data["note"]["title"], "Punya Budi");
+    events.assert_idle().await;
+}
+
+#[tokio::test]
+async fn resume_dengan_last_event_id() {
+    let app = common::test_app();
+    let token = app.user_token("dodi").await;
+
+    let mut events = open_sse(&app, &token, None).await;
+    app.create_note(&token, json!({ "title": "Satu" })).await;
+    let first = events.next().await;
+    drop(events);
+
+    // Event saat klien terputus
+    app.create_note(&token, json!({ "title": "Dua" })).await;
+    app.create_note(&token, json!({ "title": "Tiga" })).await;
+
+    let mut events = open_sse(&app, &token, Some(first.id)).await;
+    assert_eq!(events.next().await.data["note"]["title"], "Dua");
+    assert_eq!(events.next().await.data["note"]["title"], "Tiga");
+
+    // Setelah riwayat, event baru tetap datang tanpa duplikat
+    app.create_note(&token, json!({ "title": "Empat" })).await;
+    assert_eq!(events.next().await.data["note"]["title"], "Empat");
+    events.assert_idle().await;
+
+    // ?last_event_id= harus berupa angka
+    let (status, _) = app
+        .request(Method::GET, "/events?last_event_id=abc", Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn events_butuh_login() {
+    let app = common::test_app();
+    let (status, _) = app.request(Method::GET, "/events", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = app.request(Method::GET, "/events/ws", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // access_token di query hanya berlaku untuk /events
+    let token = app.user_token("eka").await;
+    let (status, _) = app.request(Method::GET, &format!("/notes?access_token={token}"), None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn websocket_menerima_event() {
+    let app = common::test_app();
+    let token = app.user_token("fajar").await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = app.router.clone();
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    // Token lewat query seperti WebSocket di browser
+    let request = format!("ws://{addr}/events/ws?access_token={token}").into_client_request().unwrap();
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+
+    let note_id = app.create_note(&token, json!({ "title": "Lewat WebSocket" })).await["id"].as_u64().unwrap();
+    let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+        .await
+        .expect("tidak ada pesan WebSocket dalam 5 detik")
+        .unwrap()
+        .unwrap();
+    let Message::Text(text) = message else { panic!("pesan bukan teks: {message:?}") };
+    let event: Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(event["type"], "note.created");
+    assert_eq!(event["note_id"], note_id);
+
+    socket.close(None).await.unwrap();
+}
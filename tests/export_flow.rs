@@ -0,0 +1,189 @@
+mod common;
+
+use std::io::Read;
+
+use api_catatan::domain::models::note::NewNote;
+use axum::http::{header, Method, StatusCode};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+use common::TestApp;
+
+async fn export(app: &TestApp, token: &str, uri: &str) -> (StatusCode, axum::http::HeaderMap, Vec<u8>) {
+    let (status, headers, body) = app.request_raw(Method::GET, uri, Some(token), None, Vec::new()).await;
+    (status, headers, body.to_vec())
+}
+
+async fn user_id(app: &TestApp, token: &str) -> u32 {
+    let (_, body) = app.request(Method::GET, "/auth/profile", Some(token), None).await;
+    body["data"]["id"].as_u64().unwrap() as u32
+}
+
+#[tokio::test]
+async fn ekspor_zip_berisi_markdown_dengan_front_matter() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("dina").await;
+        let kerja = app.create_note(
+            &token,
+            json!({ "title": "Rapat \"Q3\": rencana", "content": "# Agenda\n- satu", "content_format": "markdown", "tags": [" Kerja ", "kerja", "Penting"] }),
+        )
+        .await;
+        // Tag dirapikan: trim, huruf kecil, tanpa duplikat
+        assert_eq!(kerja["tags"], json!(["kerja", "penting"]), "{backend}");
+        app.create_note(&token, json!({ "title": "Belanja", "content": "kopi", "tags": ["rumah"] })).await;
+        let lain = app.user_token("edo").await;
+        app.create_note(&lain, json!({ "title": "Punya Edo", "tags": ["kerja"] })).await;
+
+        let (status, headers, body) = export(&app, &token, "/notes/export?format=zip&tag=KERJA").await;
+        assert_eq!(status, StatusCode::OK, "{backend}");
+        assert_eq!(headers[header::CONTENT_TYPE], "application/zip");
+        assert!(headers[header::CONTENT_DISPOSITION].to_str().unwrap().starts_with("attachment; filename=\"catatan-"));
+
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(body)).expect("ZIP tidak valid");
+        assert_eq!(zip.len(), 1, "{backend}: hanya catatan milik dina dengan tag kerja");
+        let mut file = zip.by_index(0).unwrap();
+        assert_eq!(file.name(), format!("{}-rapat-q3-rencana.md", kerja["id"]), "{backend}");
+        let mut markdown = String::new();
+        file.read_to_string(&mut markdown).unwrap();
+        let expected_head = format!(
+            "---\nid: {}\ntitle: \"Rapat \\\"Q3\\\": rencana\"\ncreated_at: \"{}\"\ncontent_format: markdown\ntags: [\"kerja\", \"penting\"]\n---\n\n# Agenda\n- satu\n",
+            kerja["id"],
+            kerja["created_at"].as_str().unwrap().parse::<DateTime<Utc>>().unwrap().to_rfc3339()
+        );
+        assert_eq!(markdown, expected_head, "{backend}");
+
+        // Tanpa filter semua catatan milik user ikut
+        let (_, _, body) = export(&app, &token, "/notes/export").await;
+        assert_eq!(zip::ZipArchive::new(std::io::Cursor::new(body)).unwrap().len(), 2, "{backend}");
+
+        let (status, _) = app
+            .request(Method::PUT, &format!("/notes/{}", kerja["id"]), Some(&token), Some(json!({ "tags": [""] })))
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{backend}");
+        let (status, body) = app
+            .request(Method::PUT, &format!("/notes/{}", kerja["id"]), Some(&token), Some(json!({ "tags": [] })))
+            .await;
+        assert_eq!(status, StatusCode::OK, "{backend}");
+        assert_eq!(body["data"]["tags"], json!([]), "{backend}");
+    }
+}
+
+#[tokio::test]
+async fn ekspor_json_dibaca_per_batch_dan_difilter_tanggal_lokal() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("fani").await;
+        let user_id = user_id(&app, &token).await;
+
+        // Zona waktu default Asia/Jakarta (UTC+7): 17:30 UTC sudah tanggal 2 Maret
+        for (i, created_at) in ["2024-03-01T16:30:00Z", "2024-03-01T17:30:00Z", "2024-03-02T16:59:59Z", "2024-03-02T17:00:00Z"]
+            .into_iter()
+            .enumerate()
+        {
+            let new_note = NewNote {
+                user_id,
+                title: format!("Lama {i}"),
+                content: None,
+                content_format: Default::default(),
+                tags: Vec::new(),
+                created_at: Some(created_at.parse().unwrap()),
+                updated_at: None,
+            };
+            app.state.note_repo.create(&new_note).await.unwrap();
+        }
+
+        let (status, headers, body) = export(&app, &token, "/notes/export?format=json&from=2024-03-02&to=2024-03-02").await;
+        assert_eq!(status, StatusCode::OK, "{backend}");
+        assert_eq!(headers[header::CONTENT_TYPE], "application/json");
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        let titles: Vec<&str> = body["notes"].as_array().unwrap().iter().map(|n| n["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, ["Lama 1", "Lama 2"], "{backend}");
+
+        // Lebih dari satu batch (200 catatan per query): tidak ada yang hilang atau dobel
+        for i in 0..205 {
+            app.create_note(&token, json!({ "title": format!("Baru {i}") })).await;
+        }
+        let (_, _, body) = export(&app, &token, "/notes/export?format=json").await;
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["exported_at"].is_string());
+        let ids: Vec<u64> = body["notes"].as_array().unwrap().iter().map(|n| n["id"].as_u64().unwrap()).collect();
+        assert_eq!(ids.len(), 209, "{backend}");
+        assert!(ids.windows(2).all(|w| w[0] < w[1]), "{backend}: urut id naik tanpa duplikat");
+
+        let (status, _, _) = export(&app, &token, "/notes/export?from=2024-03-03&to=2024-03-02").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{backend}");
+        let (status, _, _) = export(&app, &token, "/notes/export?format=docx").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{backend}");
+    }
+}
+
+#[tokio::test]
+async fn ekspor_html_dan_pdf() {
+    let app = common::test_app();
+    let token = app.user_token("gita").await;
+    app.create_note(
+        &token,
+        json!({ "title": "<b>Resep</b>", "content": "**Gula** (2 sdm)\n\n<script>alert(1)</script>", "content_format": "markdown", "tags": ["dapur"] }),
+    )
+    .await;
+    app.create_note(&token, json!({ "title": "Kanji 漢字 & café", "content": "x".repeat(200) })).await;
+
+    let (status, headers, body) = export(&app, &token, "/notes/export?format=html").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_TYPE], "text/html; charset=utf-8");
+    let html = String::from_utf8(body).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.ends_with("</html>\n"));
+    assert!(html.contains("<h1>&lt;b&gt;Resep&lt;/b&gt;</h1>"), "{html}");
+    assert!(html.contains("<strong>Gula</strong>"));
+    assert!(html.contains("#dapur"));
+    assert!(html.contains(".hl-"), "stylesheet highlight ikut disematkan");
+    assert!(!html.contains("<script>"));
+
+    let (status, headers, pdf) = export(&app, &token, "/notes/export?format=pdf").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_TYPE], "application/pdf");
+    assert!(pdf.starts_with(b"%PDF-1.4\n"));
+    assert!(pdf.ends_with(b"%%EOF\n"));
+    let text = String::from_utf8_lossy(&pdf);
+    // Satu catatan per halaman, baris panjang dipotong
+    assert!(text.contains("/Count 2"), "{text}");
+    assert!(text.contains("(**Gula** \\(2 sdm\\)) Tj"));
+    assert!(text.contains("(Kanji ?? & caf\\351) Tj"));
+    assert!(text.contains(&format!("({}) Tj", "x".repeat(82))));
+    // startxref menunjuk ke tabel xref yang benar
+    let startxref: usize = text.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+    assert!(pdf[startxref..].starts_with(b"xref\n0 "));
+}
+
+#[tokio::test]
+async fn ekspor_satu_catatan() {
+    let app = common::test_app();
+    let token = app.user_token("hadi").await;
+    let note = app.create_note(&token, json!({ "title": "Ide Besar", "content": "isi ide" })).await;
+    let id = note["id"].as_u64().unwrap();
+
+    let (status, headers, body) = export(&app, &token, &format!("/notes/{id}/export")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_TYPE], "text/markdown; charset=utf-8");
+    assert_eq!(headers[header::CONTENT_DISPOSITION], format!("attachment; filename=\"{id}-ide-besar.md\""));
+    let markdown = String::from_utf8(body).unwrap();
+    assert!(markdown.starts_with(&format!("---\nid: {id}\ntitle: \"Ide Besar\"\n")));
+    assert!(markdown.ends_with("---\n\nisi ide\n"));
+
+    let (_, _, body) = export(&app, &token, &format!("/notes/{id}/export?format=json")).await;
+    let exported: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(exported["title"], "Ide Besar");
+    assert!(exported["created_at_local"].is_string());
+
+    let (status, headers, body) = export(&app, &token, &format!("/notes/{id}/export?format=pdf")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_DISPOSITION], format!("attachment; filename=\"{id}-ide-besar.pdf\""));
+    assert!(body.starts_with(b"%PDF-"));
+
+    // Catatan milik user lain tidak bisa diekspor
+    let lain = app.user_token("indah").await;
+    let (status, _, _) = export(&app, &lain, &format!("/notes/{id}/export")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _, _) = export(&app, &token, &format!("/notes/{id}/export?format=zip")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
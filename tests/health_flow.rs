@@ -0,0 +1,131 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use api_catatan::{
+    domain::repositories::health_check::HealthCheck,
+    utils::error::AppResult,
+};
+use async_trait::async_trait;
+use axum::http::{Method, StatusCode};
+
+// Dependensi palsu dengan perilaku yang bisa diatur
+struct FakeDependency {
+    name: &'static str,
+    critical: bool,
+    up: bool,
+    delay: Duration,
+}
+
+impl FakeDependency {
+    fn new(name: &'static str, critical: bool, up: bool) -> Arc<Self> {
+        Arc::new(Self { name, critical, up, delay: Duration::ZERO })
+    }
+}
+
+#[async_trait]
+impl HealthCheck for FakeDependency {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn is_critical(&self) -> bool {
+        self.critical
+    }
+
+    async fn ping(&self) -> AppResult<()> {
+        tokio::time::sleep(self.delay).await;
+        if self.up {
+            Ok(())
+        } else {
+            Err(redis::RedisError::from((redis::ErrorKind::IoError, "koneksi ditolak")).into())
+        }
+    }
+}
+
+fn app_with_checks(checks: Vec<Arc<FakeDependency>>) -> common::TestApp {
+    let mut state = common::test_state();
+    state.health_checks = checks.into_iter().map(|c| c as _).collect();
+    common::TestApp::from_state(state)
+}
+
+#[tokio::test]
+async fn live_always_ok() {
+    let app = app_with_checks(vec![FakeDependency::new("database", true, false)]);
+
+    let (status, body) = app.request(Method::GET, "/health/live", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+}
+
+#[tokio::test]
+async fn ready_reports_each_dependency() {
+    let app = app_with_checks(vec![
+        FakeDependency::new("database", true, true),
+        FakeDependency::new("redis", true, true),
+    ]);
+
+    let (status, body) = app.request(Method::GET, "/health/ready", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["checks"]["database"]["status"], "up");
+    assert_eq!(body["checks"]["redis"]["status"], "up");
+    assert!(body["checks"]["redis"]["latency_ms"].is_u64());
+}
+
+#[tokio::test]
+async fn ready_is_503_when_critical_dependency_down() {
+    let app = app_with_checks(vec![
+        FakeDependency::new("database", true, false),
+        FakeDependency::new("redis", false, true),
+    ]);
+
+    let (status, body) = app.request(Method::GET, "/health/ready", None, None).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "unavailable");
+    assert_eq!(body["checks"]["database"]["status"], "down");
+    assert!(body["checks"]["database"]["error"].is_string());
+}
+
+#[tokio::test]
+async fn non_critical_failure_only_degrades() {
+    let app = app_with_checks(vec![
+        FakeDependency::new("database", true, true),
+        FakeDependency::new("redis", false, false),
+    ]);
+
+    let (status, body) = app.request(Method::GET, "/health/ready", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "degraded");
+}
+
+#[tokio::test]
+async fn slow_dependency_times_out() {
+    let mut state = common::test_state();
+    let mut config = common::test_config();
+    config.health_check_timeout = Duration::from_millis(50);
+    state.config = Arc::new(config);
+    state.health_checks = vec![Arc::new(FakeDependency {
+        name: "database",
+        critical: true,
+        up: true,
+        delay: Duration::from_secs(5),
+    })];
+    let app = common::TestApp::from_state(state);
+
+    let (status, body) = app.request(Method::GET, "/health/ready", None, None).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(body["checks"]["database"]["error"].as_str().unwrap().contains("timeout"));
+}
+
+#[tokio::test]
+async fn version_exposes_build_info() {
+    let app = common::test_app();
+
+    let (status, body) = app.request(Method::GET, "/version", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "api_catatan");
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert!(body["git_commit"].is_string());
+}
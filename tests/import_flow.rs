@@ -0,0 +1,270 @@
+mod common;
+
+use std::{io::Write, sync::Arc, time::Duration};
+
+use axum::http::{Method, StatusCode};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use zip::write::SimpleFileOptions;
+
+use common::TestApp;
+
+const BOUNDARY: &str = "batas-multipart-impor";
+
+fn zip_file(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    // Waktu lokal user (default Asia/Jakarta): 2023-05-06 10:00 WIB = 03:00 UTC
+    let modified = zip::DateTime::from_date_and_time(2023, 5, 6, 10, 0, 0).unwrap();
+    for (name, data) in files {
+        zip.start_file(*name, SimpleFileOptions::default().last_modified_time(modified)).unwrap();
+        zip.write_all(data).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+async fn import(app: &TestApp, token: &str, query: &str, file_name: &str, data: &[u8]) -> (StatusCode, Value) {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    let (status, _, bytes) = app
+        .request_raw(
+            Method::POST,
+            &format!("/notes/import{query}"),
+            Some(token),
+            Some(&format!("multipart/form-data; boundary={BOUNDARY}")),
+            body,
+        )
+        .await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+// Mulai impor lalu tunggu sampai job selesai
+async fn import_and_wait(app: &TestApp, token: &str, file_name: &str, data: &[u8]) -> Value {
+    let (status, body) = import(app, token, "", file_name, data).await;
+    assert_eq!(status, StatusCode::ACCEPTED, "{body}");
+    let job_id = body["data"]["id"].as_str().unwrap().to_string();
+    for _ in 0..500 {
+        let (status, body) = app.request(Method::GET, &format!("/notes/import/{job_id}"), Some(token), None).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        if !matches!(body["data"]["status"].as_str(), Some("pending" | "running")) {
+            return body["data"].clone();
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("job impor {job_id} tidak selesai");
+}
+
+// Catatan milik user diurutkan menurut judul
+async fn notes(app: &TestApp, token: &str) -> Vec<Value> {
+    let (_, body) = app.request(Method::GET, "/notes", Some(token), None).await;
+    let mut notes = body["data"].as_array().unwrap().clone();
+    notes.sort_by(|a, b| a["title"].as_str().cmp(&b["title"].as_str()));
+    notes
+}
+
+fn time(value: &Value) -> DateTime<Utc> {
+    value.as_str().unwrap().parse().unwrap()
+}
+
+#[tokio::test]
+async fn impor_zip_markdown_dengan_front_matter() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("joko").await;
+        let data = zip_file(&[
+            (
+                "catatan/rapat.md",
+                b"---\ntitle: Rapat Mingguan\ncreated: 2024-01-31T08:30:00Z\nupdated: 2024-02-01 09:00\ntags: [Kerja, kerja, \"\"]\n---\n\n# Agenda\n- satu\n",
+            ),
+            ("catatan/Ide Liburan.md", b"# Ke Bali\n\nbawa kamera"),
+            ("belanja.txt", b"\xEF\xBB\xBFkopi\r\ngula"),
+            ("rusak.md", b"---\ntitle: [tidak ditutup\n---\nisi"),
+            ("biner.md", b"\xFF\xFE\x00"),
+            ("__MACOSX/catatan/._rapat.md", b"\x00\x05"),
+            (".obsidian/workspace.md", b"abaikan"),
+            ("gambar.png", b"\x89PNG"),
+        ]);
+
+        let job = import_and_wait(&app, &token, "ekspor-obsidian.zip", &data).await;
+        assert_eq!(job["status"], "completed", "{backend}: {job}");
+        assert_eq!(job["source"], "markdown");
+        assert_eq!((job["total"].as_u64(), job["processed"].as_u64()), (Some(5), Some(5)), "{backend}");
+        assert_eq!((job["imported"].as_u64(), job["failed"].as_u64()), (Some(3), Some(2)), "{backend}");
+        let failed: Vec<&str> = job["errors"].as_array().unwrap().iter().map(|e| e["item"].as_str().unwrap()).collect();
+        assert_eq!(failed, ["rusak.md", "biner.md"], "{backend}");
+
+        let notes = notes(&app, &token).await;
+        let titles: Vec<&str> = notes.iter().map(|n| n["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, ["Ke Bali", "Rapat Mingguan", "belanja"], "{backend}");
+
+        let rapat = &notes[1];
+        assert_eq!(rapat["content"], "# Agenda\n- satu\n", "{backend}");
+        assert_eq!(rapat["content_format"], "markdown");
+        assert_eq!(rapat["tags"], json!(["kerja"]), "{backend}");
+        assert_eq!(time(&rapat["created_at"]), "2024-01-31T08:30:00Z".parse::<DateTime<Utc>>().unwrap(), "{backend}");
+        // Tanpa zona waktu = waktu lokal user (Asia/Jakarta)
+        assert_eq!(time(&rapat["updated_at"]), "2024-02-01T02:00:00Z".parse::<DateTime<Utc>>().unwrap(), "{backend}");
+
+        // Tanpa front-matter: waktu dari ZIP, judul dari heading pertama atau nama file
+        assert_eq!(time(&notes[0]["created_at"]), "2023-05-06T03:00:00Z".parse::<DateTime<Utc>>().unwrap(), "{backend}");
+        assert_eq!(notes[2]["content"], "kopi\ngula", "{backend}");
+        assert_eq!(notes[2]["content_format"], "plain");
+    }
+}
+
+#[tokio::test]
+async fn impor_evernote_enex() {
+    let app = common::test_app();
+    let token = app.user_token("kiki").await;
+    let enex = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE en-export SYSTEM "http://xml.evernote.com/pub/evernote-export4.dtd">
+<en-export export-date="20240301T000000Z" application="Evernote">
+  <note>
+    <title>Resep &amp; Catatan</title>
+    <content><![CDATA[<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<!DOCTYPE en-note SYSTEM "http://xml.evernote.com/pub/enml2.dtd">
+<en-note><div><b>Bahan</b>:&nbsp;gula</div><ul><li>telur</li><li><i>tepung</i></li></ul><div><en-todo checked="true"/>beli susu</div><div><en-todo/>panggang</div><div><br/></div><div>lihat <a href="https://contoh.id/kue">resep asli</a></div><en-media type="image/png" hash="abc"/></en-note>]]></content>
+    <created>20240131T083000Z</created>
+    <updated>20240202T101500Z</updated>
+    <tag>Dapur</tag>
+    <tag>resep</tag>
+    <note-attributes><author>kiki</author></note-attributes>
+    <resource><data encoding="base64">iVBORw0KGgo=</data><mime>image/png</mime></resource>
+  </note>
+  <note>
+    <title>Rusak</title>
+    <content><![CDATA[<en-note><div>tidak ditutup</en-note>]]></content>
+  </note>
+  <note>
+    <title></title>
+    <content><![CDATA[<en-note>isi saja</en-note>]]></content>
+  </note>
+</en-export>"#;
+
+    let job = import_and_wait(&app, &token, "Evernote.enex", enex.as_bytes()).await;
+    assert_eq!(job["status"], "completed", "{job}");
+    assert_eq!(job["source"], "enex");
+    assert_eq!((job["imported"].as_u64(), job["failed"].as_u64()), (Some(2), Some(1)));
+    assert_eq!(job["errors"][0]["item"], "Rusak");
+
+    let notes = notes(&app, &token).await;
+    assert_eq!(notes[0]["title"], "Resep & Catatan");
+    assert_eq!(
+        notes[0]["content"],
+        "**Bahan**:\u{a0}gula\n- telur\n- *tepung*\n- [x] beli susu\n- [ ] panggang\n\nlihat [resep asli](https://contoh.id/kue)"
+    );
+    assert_eq!(notes[0]["content_format"], "markdown");
+    assert_eq!(notes[0]["tags"], json!(["dapur", "resep"]));
+    assert_eq!(time(&notes[0]["created_at"]), "2024-01-31T08:30:00Z".parse::<DateTime<Utc>>().unwrap());
+    assert_eq!(time(&notes[0]["updated_at"]), "2024-02-02T10:15:00Z".parse::<DateTime<Utc>>().unwrap());
+    assert_eq!(notes[1]["title"], "Tanpa judul");
+    assert_eq!(notes[1]["content"], "isi saja");
+}
+
+#[tokio::test]
+async fn impor_google_keep_takeout() {
+    let app = common::test_app();
+    let token = app.user_token("lala").await;
+    let data = zip_file(&[
+        (
+            "Takeout/Keep/Belanja.json",
+            br#"{"color":"DEFAULT","isTrashed":false,"isPinned":true,"isArchived":false,"title":"",
+                "listContent":[{"text":"Telur","isChecked":true},{"text":"Roti","isChecked":false}],
+                "labels":[{"name":"Rumah"}],"userEditedTimestampUsec":1706689800000000,"createdTimestampUsec":1706603400000000}"#,
+        ),
+        (
+            "Takeout/Keep/Ide.json",
+            br#"{"isTrashed":false,"title":"Ide","textContent":"tulis blog","createdTimestampUsec":1706603400000000,"userEditedTimestampUsec":1706603400000000}"#,
+        ),
+        ("Takeout/Keep/Lama.json", br#"{"isTrashed":true,"title":"Sampah","textContent":"x"}"#),
+        ("Takeout/Keep/Rusak.json", b"{bukan json"),
+        ("Takeout/Keep/Belanja.html", b"<html></html>"),
+        ("Takeout/Keep/Labels.txt", b"Rumah"),
+    ]);
+
+    let job = import_and_wait(&app, &token, "takeout-20240301.zip", &data).await;
+    assert_eq!(job["status"], "completed", "{job}");
+    assert_eq!(job["source"], "keep");
+    assert_eq!((job["imported"].as_u64(), job["failed"].as_u64()), (Some(2), Some(1)));
+    assert_eq!(job["errors"][0]["item"], "Takeout/Keep/Rusak.json");
+
+    let notes = notes(&app, &token).await;
+    assert_eq!(notes.len(), 2, "catatan di tempat sampah tidak ikut");
+    assert_eq!(notes[0]["title"], "Ide");
+    assert_eq!(notes[0]["content_format"], "plain");
+    assert!(notes[0]["updated_at"].is_null());
+    assert_eq!(notes[1]["title"], "Telur");
+    // Judul kosong diganti item pertama, checklist jadi task list Markdown
+    assert_eq!(notes[1]["content"], "- [x] Telur\n- [ ] Roti");
+    assert_eq!(notes[1]["content_format"], "markdown");
+    assert_eq!(notes[1]["tags"], json!(["rumah"]));
+    assert_eq!(time(&notes[1]["created_at"]), "2024-01-30T08:30:00Z".parse::<DateTime<Utc>>().unwrap());
+    assert_eq!(time(&notes[1]["updated_at"]), "2024-01-31T08:30:00Z".parse::<DateTime<Utc>>().unwrap());
+}
+
+#[tokio::test]
+async fn impor_banyak_catatan_per_batch_dan_validasi() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("mira").await;
+        let files: Vec<(String, Vec<u8>)> =
+            (0..250).map(|i| (format!("catatan-{i:03}.md"), format!("isi {i}").into_bytes())).collect();
+        let files: Vec<(&str, &[u8])> = files.iter().map(|(name, data)| (name.as_str(), data.as_slice())).collect();
+
+        let job = import_and_wait(&app, &token, "banyak.zip", &zip_file(&files)).await;
+        assert_eq!((job["status"].as_str(), job["imported"].as_u64()), (Some("completed"), Some(250)), "{backend}");
+        let notes = notes(&app, &token).await;
+        assert_eq!(notes.len(), 250, "{backend}");
+        assert_eq!(notes[249]["title"], "catatan-249");
+        assert_eq!(notes[249]["content"], "isi 249");
+
+        // Catatan hasil impor ikut tercatat untuk sinkronisasi
+        let (_, body) = app.request(Method::GET, "/sync", Some(&token), None).await;
+        assert_eq!(body["data"]["notes"].as_array().unwrap().len(), 250, "{backend}");
+
+        // Job milik user lain tidak terlihat
+        let lain = app.user_token("nanda").await;
+        let (_, body) = import(&app, &token, "", "satu.zip", &zip_file(&[("a.md", b"a")])).await;
+        let job_id = body["data"]["id"].as_str().unwrap();
+        let (status, _) = app.request(Method::GET, &format!("/notes/import/{job_id}"), Some(&lain), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{backend}");
+    }
+
+    let app = common::test_app();
+    let token = app.user_token("nina").await;
+    let (status, _) = import(&app, &token, "", "foto.png", b"\x89PNG\r\n").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "format tidak dikenali");
+    let (status, _) = import(&app, &token, "", "kosong.zip", &zip_file(&[("foto.png", b"x")])).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = import(&app, &token, "?source=notion", "a.zip", b"x").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Sumber dipaksa lewat query: file yang bukan ZIP membuat job gagal total
+    let (status, body) = import(&app, &token, "?source=markdown", "a.zip", b"bukan zip").await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job_id = body["data"]["id"].as_str().unwrap();
+    let job = loop {
+        let (_, body) = app.request(Method::GET, &format!("/notes/import/{job_id}"), Some(&token), None).await;
+        if body["data"]["status"] != "pending" && body["data"]["status"] != "running" {
+            break body["data"].clone();
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    assert_eq!(job["status"], "failed");
+    assert!(job["error"].is_string());
+
+    let (status, _) = app.request(Method::GET, "/notes/import/tidak-ada", Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Melewati IMPORT_MAX_BYTES
+    let mut state = common::test_state();
+    let mut config = common::test_config();
+    config.import_max_bytes = 64;
+    state.config = Arc::new(config);
+    let app = TestApp::from_state(state);
+    let token = app.user_token("oki").await;
+    let (status, _) = import(&app, &token, "", "besar.enex", &[b'a'; 100]).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+}
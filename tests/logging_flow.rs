@@ -0,0 +1,135 @@
+mod common;
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use api_catatan::{
+    domain::models::user::LoginPayload,
+    utils::{config::LogFormat, telemetry},
+};
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use tower::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+
+// Writer log yang menampung output di memori
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for CapturedLogs {
+    type Writer = CapturedLogs;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+async fn send(app: &common::TestApp, request: Request<Body>) -> (StatusCode, Option<String>, Value) {
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let request_id = response
+        .headers()
+        .get("x-request-id")
+        .map(|value| value.to_str().unwrap().to_string());
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, request_id, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn request_id_is_generated_and_included_in_errors() {
+    let app = common::test_app();
+
+    let request = Request::get("/auth/profile").body(Body::empty()).unwrap();
+    let (status, request_id, body) = send(&app, request).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let request_id = request_id.expect("header X-Request-Id tidak ada");
+    assert_eq!(request_id.len(), 36); // UUID v4
+    assert_eq!(body["request_id"], request_id);
+}
+
+#[tokio::test]
+async fn incoming_request_id_is_propagated() {
+    let app = common::test_app();
+
+    let request = Request::get("/notes/1")
+        .header("x-request-id", "req-abc-123")
+        .body(Body::empty())
+        .unwrap();
+    let (_, request_id, body) = send(&app, request).await;
+    assert_eq!(request_id.as_deref(), Some("req-abc-123"));
+    assert_eq!(body["request_id"], "req-abc-123");
+
+    // Id yang tidak wajar diganti dengan id baru
+    let request = Request::get("/health/live")
+        .header("x-request-id", "a".repeat(200))
+        .body(Body::empty())
+        .unwrap();
+    let (_, request_id, _) = send(&app, request).await;
+    assert_ne!(request_id.unwrap(), "a".repeat(200));
+}
+
+#[tokio::test]
+async fn json_logs_carry_request_id_and_redact_secrets() {
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::registry().with(telemetry::fmt_layer(LogFormat::Json, logs.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri("/auth/profile")
+        .header("x-request-id", "req-log-42")
+        .header(header::AUTHORIZATION, format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let (status, _, _) = send(&app, request).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let output = logs.contents();
+    let lines: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    let finished = lines
+        .iter()
+        .find(|line| line["fields"]["message"] == "Request selesai" && line.to_string().contains("req-log-42"))
+        .expect("log 'Request selesai' dengan request id tidak ditemukan");
+    assert_eq!(finished["fields"]["status"], 200);
+
+    // Token dan password tidak boleh muncul di log
+    assert!(output.contains("[REDACTED]"));
+    assert!(!output.contains(&token));
+    assert!(!output.contains("rahasia123"));
+}
+
+#[test]
+fn payload_debug_hides_password() {
+    let payload = LoginPayload {
+        email: "budi@example.com".to_string(),
+        password: "rahasia123".to_string(),
+    };
+    let debug = format!("{payload:?}");
+    assert!(debug.contains("budi@example.com"));
+    assert!(!debug.contains("rahasia123"));
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+// Ambil nilai satu baris metrik, misal `api_catatan_notes_changes_total{operation="created"}`
+fn metric_value(metrics: &str, series: &str) -> Option<f64> {
+    metrics
+        .lines()
+        .find(|line| line.starts_with(series))
+        .and_then(|line| line.rsplit(' ').next())
+        .and_then(|value| value.parse().ok())
+}
+
+async fn scrape(app: &common::TestApp) -> String {
+    let (status, body) = app.request(Method::GET, "/metrics", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    body.as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn http_metrics_use_route_template() {
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+
+    app.request(Method::GET, "/notes/41", Some(&token), None).await;
+    app.request(Method::GET, "/notes/42", Some(&token), None).await;
+
+    let metrics = scrape(&app).await;
+    assert_eq!(
+        metric_value(&metrics, r#"api_catatan_http_requests_total{method="GET",route="/notes/:id",status="404"}"#),
+        Some(2.0)
+    );
+    assert!(metrics.contains("api_catatan_http_request_duration_seconds_bucket"));
+}
+
+#[tokio::test]
+async fn login_attempts_are_counted() {
+    let app = common::test_app();
+    app.register("budi@example.com", "budi", "rahasia123").await;
+
+    app.login("budi@example.com", "rahasia123").await;
+    app.request(
+        Method::POST,
+        "/auth/login",
+        None,
+        Some(json!({ "email": "budi@example.com", "password": "salah" })),
+    )
+    .await;
+
+    let metrics = scrape(&app).await;
+    assert_eq!(metric_value(&metrics, r#"api_catatan_login_attempts_total{result="success"}"#), Some(1.0));
+    assert_eq!(metric_value(&metrics, r#"api_catatan_login_attempts_total{result="failure"}"#), Some(1.0));
+}
+
+#[tokio::test]
+async fn note_changes_are_counted_by_repository_decorator() {
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+
+    let (_, body) = app
+        .request(Method::POST, "/notes", Some(&token), Some(json!({ "title": "Belanja" })))
+        .await;
+    let id = body["data"]["id"].as_u64().unwrap();
+    app.request(Method::PUT, &format!("/notes/{id}"), Some(&token), Some(json!({ "title": "Belanja bulanan" })))
+        .await;
+    app.request(Method::DELETE, &format!("/notes/{id}"), Some(&token), None).await;
+    // Menghapus catatan yang sudah tidak ada tidak ikut dihitung
+    app.request(Method::DELETE, &format!("/notes/{id}"), Some(&token), None).await;
+
+    let metrics = scrape(&app).await;
+    for operation in ["created", "updated", "deleted"] {
+        let series = format!(r#"api_catatan_notes_changes_total{{operation="{operation}"}}"#);
+        assert_eq!(metric_value(&metrics, &series), Some(1.0), "{operation}");
+    }
+    assert!(metrics.contains(r#"api_catatan_repository_query_duration_seconds_count{method="create",outcome="ok",repository="note"}"#));
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+#[tokio::test]
+async fn note_crud_flow() {
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+
+    // CREATE
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/notes",
+            Some(&token),
+            Some(json!({ "title": "Belanja", "content": "Beli susu" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let id = body["data"]["id"].as_u64().unwrap();
+
+    // READ (list + detail)
+    let (status, body) = app.request(Method::GET, "/notes", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    let (status, body) = app.request(Method::GET, &format!("/notes/{id}"), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["title"], "Belanja");
+
+    // UPDATE (hanya judul, konten tetap)
+    let (status, body) = app
+        .request(Method::PUT, &format!("/notes/{id}"), Some(&token), Some(json!({ "title": "Belanja bulanan" })))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["title"], "Belanja bulanan");
+    assert_eq!(body["data"]["content"], "Beli susu");
+
+    // DELETE
+    let (status, _) = app.request(Method::DELETE, &format!("/notes/{id}"), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app.request(Method::GET, &format!("/notes/{id}"), Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn notes_are_isolated_per_user() {
+    let app = common::test_app();
+    let budi = app.user_token("budi").await;
+    let siti = app.user_token("siti").await;
+
+    let (_, body) = app
+        .request(Method::POST, "/notes", Some(&budi), Some(json!({ "title": "Rahasia" })))
+        .await;
+    let id = body["data"]["id"].as_u64().unwrap();
+
+    let (status, _) = app.request(Method::GET, &format!("/notes/{id}"), Some(&siti), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = app.request(Method::DELETE, &format!("/notes/{id}"), Some(&siti), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, body) = app.request(Method::GET, "/notes", Some(&siti), None).await;
+    assert!(body["data"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn create_note_rejects_invalid_json() {
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+
+    let (status, _) = app
+        .request(Method::POST, "/notes", Some(&token), Some(json!({ "content": "tanpa judul" })))
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn list_notes_supports_pagination() {
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+
+    for i in 0..3 {
+        app.request(Method::POST, "/notes", Some(&token), Some(json!({ "title": format!("Catatan {i}") })))
+            .await;
+    }
+
+    let (status, body) = app.request(Method::GET, "/notes?page=1&per_page=2", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let titles: Vec<&str> = body["data"].as_array().unwrap().iter().map(|n| n["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, vec!["Catatan 2", "Catatan 1"]);
+
+    let (_, body) = app.request(Method::GET, "/notes?page=2&per_page=2", Some(&token), None).await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    // Tanpa query: semua catatan seperti sebelumnya
+    let (_, body) = app.request(Method::GET, "/notes", Some(&token), None).await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 3);
+
+    // Query tidak valid memakai body error standar
+    let (status, body) = app.request(Method::GET, "/notes?page=satu", Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["status"], "error");
+}
@@ -0,0 +1,162 @@
+mod common;
+
+use std::collections::BTreeSet;
+
+use axum::http::{Method, StatusCode};
+use serde_json::Value;
+
+use common::test_app;
+
+// Rute yang sengaja tidak didokumentasikan (halaman dokumentasi itu sendiri)
+const UNDOCUMENTED: &[&str] = &["/openapi.json", "/docs"];
+
+const METHODS: &[&str] = &["get", "post", "put", "patch", "delete"];
+
+// Ambil semua pasangan "METHOD /path" dari routes.rs.
+// Parameter gaya axum (`:id`) diubah ke gaya OpenAPI (`{id}`).
+fn routes_from_source() -> BTreeSet<String> {
+    let source = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/src/presentation/routes.rs")).unwrap();
+    let mut routes = BTreeSet::new();
+
+    for (start, _) in source.match_indices(".route(") {
+        // Ambil isi argumen `.route(...)` dengan menghitung kurung
+        let args_start = start + ".route(".len();
+        let mut depth = 1;
+        let mut end = args_start;
+        for (i, c) in source[args_start..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                end = args_start + i;
+                break;
+            }
+        }
+        let args = &source[args_start..end];
+
+        let path = args.split('"').nth(1).expect("path rute harus string literal");
+        let path = path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(param) => format!("{{{param}}}"),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        for method in METHODS {
+            let call = format!("{method}(");
+            let found = args.match_indices(&call).any(|(i, _)| {
+                // `get(` harus berdiri sendiri, bukan bagian dari nama lain
+                i == 0 || !args.as_bytes()[i - 1].is_ascii_alphanumeric() && args.as_bytes()[i - 1] != b'_'
+            });
+            if found {
+                routes.insert(format!("{} {}", method.to_uppercase(), path));
+            }
+        }
+    }
+    routes
+}
+
+async fn fetch_spec() -> Value {
+    let app = test_app();
+    let (status, spec) = app.request(Method::GET, "/openapi.json", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    spec
+}
+
+fn operations(spec: &Value) -> Vec<(String, String, Value)> {
+    let mut result = Vec::new();
+    for (path, item) in spec["paths"].as_object().unwrap() {
+        for (method, operation) in item.as_object().unwrap() {
+            result.push((method.to_uppercase(), path.clone(), operation.clone()));
+        }
+    }
+    result
+}
+
+#[tokio::test]
+async fn rute_di_router_sama_dengan_spesifikasi() {
+    let spec = fetch_spec().await;
+
+    let documented: BTreeSet<String> = operations(&spec)
+        .into_iter()
+        .map(|(method, path, _)| format!("{method} {path}"))
+        .collect();
+    let routed: BTreeSet<String> = routes_from_source()
+        .into_iter()
+        .filter(|route| !UNDOCUMENTED.iter().any(|path| route.ends_with(&format!(" {path}"))))
+        .collect();
+
+    assert!(!routed.is_empty(), "rute di routes.rs tidak terbaca");
+    let missing_in_spec: Vec<_> = routed.difference(&documented).collect();
+    let missing_in_router: Vec<_> = documented.difference(&routed).collect();
+    assert!(
+        missing_in_spec.is_empty() && missing_in_router.is_empty(),
+        "routes.rs dan OpenAPI tidak sinkron.\nBelum didokumentasikan: {missing_in_spec:?}\nTidak ada di router: {missing_in_router:?}"
+    );
+}
+
+#[tokio::test]
+async fn setiap_operasi_bisa_dijangkau_dan_keamanannya_sesuai() {
+    let spec = fetch_spec().await;
+    let app = test_app();
+
+    for (method, path, operation) in operations(&spec) {
+        let uri = path.replace("{id}", "1");
+        let method = Method::from_bytes(method.as_bytes()).unwrap();
+        let (status, body) = app.request(method.clone(), &uri, None, None).await;
+
+        // 404 tanpa body = router tidak punya rute ini
+        assert_ne!(status, StatusCode::METHOD_NOT_ALLOWED, "{method} {path}");
+        assert!(!(status == StatusCode::NOT_FOUND && body.is_null()), "{method} {path} tidak ada di router");
+
+        // Rute yang menolak request tanpa token harus mendeklarasikan bearer_auth, dan sebaliknya
+        let secured = operation["security"]
+            .as_array()
+            .is_some_and(|schemes| schemes.iter().any(|scheme| scheme.get("bearer_auth").is_some()));
+        assert_eq!(secured, status == StatusCode::UNAUTHORIZED, "{method} {path}: security tidak sesuai");
+    }
+}
+
+#[tokio::test]
+async fn skema_bearer_dan_penanda_admin_ada() {
+    let spec = fetch_spec().await;
+
+    let scheme = &spec["components"]["securitySchemes"]["bearer_auth"];
+    assert_eq!(scheme["type"], "http");
+    assert_eq!(scheme["scheme"], "bearer");
+    assert_eq!(scheme["bearerFormat"], "JWT");
+
+    for (method, path, operation) in operations(&spec) {
+        let admin_only = operation["x-admin-only"] == Value::Bool(true);
+        assert_eq!(admin_only, path == "/users", "{method} {path}: penanda admin tidak sesuai");
+    }
+}
+
+#[tokio::test]
+async fn body_error_sesuai_skema() {
+    let spec = fetch_spec().await;
+    let app = test_app();
+
+    let schema = &spec["components"]["schemas"]["ErrorResponse"]["properties"];
+    let (status, body) = app.request(Method::GET, "/notes", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    for key in body.as_object().unwrap().keys() {
+        assert!(schema.get(key).is_some(), "field '{key}' tidak ada di skema ErrorResponse");
+    }
+
+    // Hash password tidak boleh muncul di skema user
+    let user = &spec["components"]["schemas"]["User"]["properties"];
+    assert!(user.get("password_hash").is_none());
+}
+
+#[tokio::test]
+async fn swagger_ui_disajikan() {
+    let app = test_app();
+    let (status, body) = app.request(Method::GET, "/docs/", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.as_str().unwrap_or_default().contains("swagger"), "{body}");
+}
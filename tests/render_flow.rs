@@ -0,0 +1,125 @@
+mod common;
+
+use axum::http::{header, Method, StatusCode};
+use serde_json::{json, Value};
+
+use common::TestApp;
+
+async fn rendered(app: &TestApp, token: &str, id: u64) -> String {
+    let (status, body) = app.request(Method::GET, &format!("/notes/{id}?render=html"), Some(token), None).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    body["data"]["content_html"].as_str().unwrap().to_string()
+}
+
+const MARKDOWN: &str = "# Rencana\n\n\
+- [x] beli **kopi**\n\
+- [ ] ~~tidur~~\n\n\
+| Item | Harga |\n|:-----|------:|\n| Kopi | 20000 |\n\n\
+```rust\nfn main() {}\n```\n\n\
+<script>alert('xss')</script>\n\n\
+<img src=\"x\" onerror=\"alert(1)\"> [klik](javascript:alert(1)) <input type=\"text\" value=\"isi\">\n";
+
+#[tokio::test]
+async fn markdown_dirender_dan_disanitasi() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("wati").await;
+        let note = app
+            .create_note(&token, json!({ "title": "Rencana", "content": MARKDOWN, "content_format": "markdown" }))
+            .await;
+        assert_eq!(note["content_format"], "markdown", "{backend}");
+        let id = note["id"].as_u64().unwrap();
+
+        // Tanpa ?render=html tidak ada content_html
+        let (_, body) = app.request(Method::GET, &format!("/notes/{id}"), Some(&token), None).await;
+        assert!(body["data"].get("content_html").is_none(), "{backend}");
+
+        let html = rendered(&app, &token, id).await;
+        for expected in [
+            "<h1>Rencana</h1>",
+            "<strong>kopi</strong>",
+            "<del>tidur</del>",
+            "<table>",
+            "style=\"text-align: right\"",
+            "<code class=\"language-rust\">",
+            "class=\"hl-",
+        ] {
+            assert!(html.contains(expected), "{backend}: `{expected}` tidak ada di {html}");
+        }
+        // Checkbox task list selalu disabled, input lain dipaksa jadi checkbox juga
+        assert_eq!(html.matches("type=\"checkbox\"").count(), 3, "{backend}: {html}");
+        assert_eq!(html.matches("disabled=\"\"").count(), 3, "{backend}: {html}");
+        assert_eq!(html.matches("checked=\"\"").count(), 1, "{backend}: {html}");
+        for forbidden in ["<script", "onerror", "javascript:", "type=\"text\""] {
+            assert!(!html.contains(forbidden), "{backend}: `{forbidden}` lolos sanitasi: {html}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn teks_biasa_di_escape_dan_cache_ikut_versi_catatan() {
+    let app = common::test_app();
+    let token = app.user_token("yani").await;
+    let note = app.create_note(&token, json!({ "title": "Biasa", "content": "a < b\nbaris 2\n\n**bukan tebal**" })).await;
+    // Default content_format = plain
+    assert_eq!(note["content_format"], "plain");
+    let id = note["id"].as_u64().unwrap();
+
+    assert_eq!(rendered(&app, &token, id).await, "<p>a &lt; b<br>\nbaris 2</p>\n<p>**bukan tebal**</p>\n");
+
+    // Ganti format saja: change_seq naik, jadi hasil render lama tidak dipakai
+    let (status, body) = app
+        .request(Method::PUT, &format!("/notes/{id}"), Some(&token), Some(json!({ "content_format": "markdown" })))
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["content"], "a < b\nbaris 2\n\n**bukan tebal**");
+    assert!(rendered(&app, &token, id).await.contains("<strong>bukan tebal</strong>"));
+
+    let (status, _) = app
+        .request(Method::PUT, &format!("/notes/{id}"), Some(&token), Some(json!({ "content": "_baru_" })))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(rendered(&app, &token, id).await, "<p><em>baru</em></p>\n");
+}
+
+#[tokio::test]
+async fn format_dan_mode_render_tidak_dikenal_ditolak() {
+    let app = common::test_app();
+    let token = app.user_token("zaki").await;
+
+    let (status, _) = app
+        .request(Method::POST, "/notes", Some(&token), Some(json!({ "title": "X", "content_format": "html" })))
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let note = app.create_note(&token, json!({ "title": "X" })).await;
+    let (status, _) = app
+        .request(Method::GET, &format!("/notes/{}?render=pdf", note["id"]), Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Stylesheet highlight bisa diambil tanpa login
+    let (status, headers, css) = app.request_raw(Method::GET, "/render/highlight.css", None, None, Vec::new()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_TYPE], "text/css; charset=utf-8");
+    assert!(String::from_utf8_lossy(&css).contains(".hl-"));
+}
+
+#[tokio::test]
+async fn sync_membawa_content_format() {
+    let app = common::test_app();
+    let token = app.user_token("ayu").await;
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/sync",
+            Some(&token),
+            Some(json!({ "changes": [
+                { "op": "create", "client_id": "c1", "title": "Dari HP", "content": "# Judul", "content_format": "markdown" },
+            ] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let note: &Value = &body["data"]["results"][0]["note"];
+    assert_eq!(note["content_format"], "markdown");
+    assert_eq!(rendered(&app, &token, note["id"].as_u64().unwrap()).await, "<h1>Judul</h1>\n");
+}
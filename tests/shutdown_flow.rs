@@ -0,0 +1,103 @@
+mod common;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use api_catatan::{
+    domain::repositories::health_check::HealthCheck,
+    presentation::routes::create_router,
+    server,
+    utils::{background::BackgroundTasks, error::AppResult},
+};
+use async_trait::async_trait;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+// Dependensi lambat supaya /health/ready menjadi request yang "sedang berjalan"
+struct SlowDependency(Duration);
+
+#[async_trait]
+impl HealthCheck for SlowDependency {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    async fn ping(&self) -> AppResult<()> {
+        tokio::time::sleep(self.0).await;
+        Ok(())
+    }
+}
+
+async fn start_server(
+    delay: Duration,
+    drain_timeout: Duration,
+) -> (String, CancellationToken, tokio::task::JoinHandle<std::io::Result<bool>>) {
+    let mut state = common::test_state();
+    state.health_checks = vec![Arc::new(SlowDependency(delay))];
+    let mut config = common::test_config();
+    config.health_check_timeout = Duration::from_secs(60);
+    state.config = Arc::new(config);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let shutdown = CancellationToken::new();
+    let app = create_router(Arc::new(state));
+    let handle = tokio::spawn(server::serve(listener, app, shutdown.clone(), drain_timeout));
+    (addr, shutdown, handle)
+}
+
+async fn get(addr: &str, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn in_flight_requests_finish_before_shutdown() {
+    let (addr, shutdown, server) = start_server(Duration::from_millis(300), Duration::from_secs(5)).await;
+
+    let request = tokio::spawn(async move { get(&addr, "/health/ready").await });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    shutdown.cancel();
+
+    let response = request.await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(server.await.unwrap().unwrap(), "server seharusnya selesai tanpa memutus request");
+}
+
+#[tokio::test]
+async fn drain_timeout_bounds_shutdown() {
+    let (addr, shutdown, server) = start_server(Duration::from_secs(30), Duration::from_millis(100)).await;
+
+    let _request = tokio::spawn(async move { get(&addr, "/health/ready").await });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let started = Instant::now();
+    shutdown.cancel();
+    let drained = server.await.unwrap().unwrap();
+
+    assert!(!drained);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn background_tasks_are_cancelled_on_shutdown() {
+    let background = BackgroundTasks::new();
+    let finished = Arc::new(AtomicBool::new(false));
+
+    background.spawn({
+        let finished = finished.clone();
+        |token| async move {
+            token.cancelled().await;
+            finished.store(true, Ordering::SeqCst);
+        }
+    });
+
+    assert!(background.shutdown(Duration::from_secs(1)).await);
+    assert!(finished.load(Ordering::SeqCst));
+}
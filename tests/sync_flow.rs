@@ -0,0 +1,163 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+
+use common::TestApp;
+
+async fn pull(app: &TestApp, token: &str, since: Option<&str>) -> Value {
+    let uri = match since {
+        Some(since) => format!("/sync?since={since}"),
+        None => "/sync".to_string(),
+    };
+    let (status, body) = app.request(Method::GET, &uri, Some(token), None).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    body["data"].clone()
+}
+
+async fn push(app: &TestApp, token: &str, changes: Value) -> Vec<Value> {
+    let (status, body) = app
+        .request(Method::POST, "/sync", Some(token), Some(json!({ "changes": changes })))
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    body["data"]["results"].as_array().unwrap().clone()
+}
+
+fn titles(changes: &Value) -> Vec<&str> {
+    changes["notes"].as_array().unwrap().iter().map(|note| note["title"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn pull_mengembalikan_delta_dan_tombstone() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("budi").await;
+        let satu = app.create_note(&token, json!({ "title": "Satu" })).await;
+        let dua = app.create_note(&token, json!({ "title": "Dua" })).await;
+
+        let full = pull(&app, &token, None).await;
+        assert_eq!(titles(&full), vec!["Satu", "Dua"], "{backend}");
+        assert_eq!(full["deleted"], json!([]), "{backend}");
+        let since = full["token"].as_str().unwrap().to_string();
+
+        // Tidak ada perubahan: token tetap, daftar kosong
+        let empty = pull(&app, &token, Some(&since)).await;
+        assert_eq!(empty["notes"], json!([]), "{backend}");
+        assert_eq!(empty["token"], since.as_str(), "{backend}");
+
+        app.request(Method::PUT, &format!("/notes/{}", satu["id"]), Some(&token), Some(json!({ "title": "Satu baru" })))
+            .await;
+        app.request(Method::DELETE, &format!("/notes/{}", dua["id"]), Some(&token), None).await;
+
+        let delta = pull(&app, &token, Some(&since)).await;
+        assert_eq!(titles(&delta), vec!["Satu baru"], "{backend}");
+        assert!(delta["notes"][0]["updated_at"].is_string(), "{backend}");
+        assert_eq!(delta["deleted"][0]["note_id"], dua["id"], "{backend}");
+        assert_eq!(delta["has_more"], false, "{backend}");
+        assert_ne!(delta["token"], since.as_str(), "{backend}");
+
+        // Perubahan user lain tidak ikut
+        let other = app.user_token("citra").await;
+        assert_eq!(pull(&app, &other, None).await["notes"], json!([]), "{backend}");
+    }
+}
+
+#[tokio::test]
+async fn pull_bertahap_dengan_limit() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("dodi").await;
+        for i in 0..5 {
+            app.create_note(&token, json!({ "title": format!("Catatan {i}") })).await;
+        }
+
+        let mut since: Option<String> = None;
+        let mut seen = Vec::new();
+        loop {
+            let uri = match &since {
+                Some(since) => format!("/sync?since={since}&limit=2"),
+                None => "/sync?limit=2".to_string(),
+            };
+            let (_, body) = app.request(Method::GET, &uri, Some(&token), None).await;
+            let page = &body["data"];
+            seen.extend(titles(page).into_iter().map(String::from));
+            since = Some(page["token"].as_str().unwrap().to_string());
+            if page["has_more"] == false {
+                break;
+            }
+        }
+        assert_eq!(seen.len(), 5, "{backend}: {seen:?}");
+        assert_eq!(seen.first().map(String::as_str), Some("Catatan 0"), "{backend}");
+    }
+}
+
+#[tokio::test]
+async fn push_menerapkan_perubahan_dan_melaporkan_konflik() {
+    for (backend, app) in common::apps().await {
+        let token = app.user_token("eka").await;
+        let note = app.create_note(&token, json!({ "title": "Rapat" })).await;
+        let base_seq = note["change_seq"].as_i64().unwrap();
+
+        // Perangkat lain mengubah catatan lebih dulu
+        app.request(Method::PUT, &format!("/notes/{}", note["id"]), Some(&token), Some(json!({ "content": "dari web" })))
+            .await;
+
+        let results = push(
+            &app,
+            &token,
+            json!([
+                { "op": "create", "client_id": "tmp-1", "title": "Dari HP", "content": "offline" },
+                { "op": "update", "id": note["id"], "base_seq": base_seq, "content": "dari HP" },
+                { "op": "delete", "id": 9999 },
+                { "op": "create", "client_id": "tmp-2", "title": " " },
+            ]),
+        )
+        .await;
+
+        assert_eq!(results[0]["status"], "applied", "{backend}");
+        assert_eq!(results[0]["client_id"], "tmp-1", "{backend}");
+        assert_eq!(results[0]["note"]["title"], "Dari HP", "{backend}");
+
+        assert_eq!(results[1]["status"], "conflict", "{backend}");
+        assert_eq!(results[1]["note"]["content"], "dari web", "{backend}");
+        assert!(results[1]["message"].is_string(), "{backend}");
+
+        assert_eq!(results[2]["status"], "not_found", "{backend}");
+        assert_eq!(results[3]["status"], "invalid", "{backend}");
+
+        // Dengan change_seq terbaru, update & delete berhasil
+        let current_seq = &results[1]["note"]["change_seq"];
+        let results = push(
+            &app,
+            &token,
+            json!([{ "op": "update", "id": note["id"], "base_seq": current_seq, "content": "gabungan" }]),
+        )
+        .await;
+        assert_eq!(results[0]["status"], "applied", "{backend}");
+        assert_eq!(results[0]["note"]["content"], "gabungan", "{backend}");
+
+        let seq = &results[0]["note"]["change_seq"];
+        let results = push(&app, &token, json!([{ "op": "delete", "id": note["id"], "base_seq": seq }])).await;
+        assert_eq!(results[0]["status"], "applied", "{backend}");
+
+        let full = pull(&app, &token, None).await;
+        assert_eq!(titles(&full), vec!["Dari HP"], "{backend}");
+        assert_eq!(full["deleted"][0]["note_id"], note["id"], "{backend}");
+    }
+}
+
+#[tokio::test]
+async fn token_dan_payload_tidak_valid_ditolak() {
+    let app = common::test_app();
+    let token = app.user_token("fajar").await;
+
+    let (status, _) = app.request(Method::GET, "/sync?since=bukan-token", Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let changes: Vec<Value> = (0..501).map(|i| json!({ "op": "delete", "id": i })).collect();
+    let (status, _) = app
+        .request(Method::POST, "/sync", Some(&token), Some(json!({ "changes": changes })))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app.request(Method::GET, "/sync", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use std::time::Duration;
+
+use api_catatan::utils::telemetry;
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, Method, StatusCode},
+    routing::post,
+    Router,
+};
+use opentelemetry::{global, trace::TracerProvider as _, Value};
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    trace::{InMemorySpanExporter, SdkTracerProvider, SpanData},
+};
+use serde_json::json;
+use tokio::sync::mpsc;
+use tracing_subscriber::layer::SubscriberExt;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+fn find_span<'a>(spans: &'a [SpanData], name: &str) -> &'a SpanData {
+    spans
+        .iter()
+        .find(|span| span.name == name)
+        .unwrap_or_else(|| panic!("span '{name}' tidak ditemukan"))
+}
+
+fn attribute(span: &SpanData, key: &str) -> Option<Value> {
+    span.attributes.iter().find(|kv| kv.key.as_str() == key).map(|kv| kv.value.clone())
+}
+
+#[tokio::test]
+async fn request_spans_continue_incoming_traceparent() {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder().with_simple_exporter(exporter.clone()).build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let app = common::test_app();
+    let token = app.user_token("budi").await;
+    let traceparent = format!("00-{TRACE_ID}-{PARENT_SPAN_ID}-01");
+    let (status, _) = app
+        .request_with_headers(
+            Method::POST,
+            "/notes",
+            Some(&token),
+            Some(json!({ "title": "Belanja" })),
+            &[("traceparent", traceparent.as_str())],
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    provider.force_flush().unwrap();
+    let spans = exporter.get_finished_spans().unwrap();
+
+    // Span request melanjutkan trace dari header traceparent
+    let http_span = find_span(&spans, "POST /notes");
+    assert_eq!(http_span.span_context.trace_id().to_string(), TRACE_ID);
+    assert_eq!(http_span.parent_span_id.to_string(), PARENT_SPAN_ID);
+    assert_eq!(attribute(http_span, "http.route"), Some(Value::from("/notes")));
+    assert!(attribute(http_span, "user.id").is_some());
+    assert_eq!(attribute(http_span, "http.response.status_code"), Some(Value::I64(201)));
+
+    // Service dan query repository menjadi anak dari span request
+    let service_span = find_span(&spans, "NoteService.create_note");
+    assert_eq!(service_span.parent_span_id, http_span.span_context.span_id());
+    let db_span = find_span(&spans, "note.create");
+    assert_eq!(db_span.parent_span_id, service_span.span_context.span_id());
+    assert_eq!(db_span.span_context.trace_id().to_string(), TRACE_ID);
+}
+
+// Collector pengganti: menerima OTLP/HTTP dan meneruskan body-nya ke channel
+async fn spawn_stand_in_collector() -> (String, mpsc::UnboundedReceiver<(HeaderMap, Bytes)>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let router = Router::new().route(
+        "/v1/traces",
+        post(move |headers: HeaderMap, body: Bytes| {
+            let tx = tx.clone();
+            async move {
+                let _ = tx.send((headers, body));
+                StatusCode::OK
+            }
+        }),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    (endpoint, rx)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn spans_are_exported_over_otlp() {
+    let (endpoint, mut received) = spawn_stand_in_collector().await;
+    let provider = telemetry::otlp_tracer_provider(&endpoint, "api_catatan-test").unwrap();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let app = common::test_app();
+    let (status, _) = app.request(Method::GET, "/health/live", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Exporter OTLP memakai HTTP client blocking, jadi flush di thread terpisah
+    tokio::task::spawn_blocking(move || provider.force_flush()).await.unwrap().unwrap();
+
+    let (headers, body) = tokio::time::timeout(Duration::from_secs(10), received.recv())
+        .await
+        .expect("collector tidak menerima span")
+        .unwrap();
+    assert_eq!(headers["content-type"], "application/x-protobuf");
+    assert!(!body.is_empty());
+    // Nama span dan service ikut terkirim di payload protobuf
+    let payload = String::from_utf8_lossy(&body);
+    assert!(payload.contains("GET /health/live"));
+    assert!(payload.contains("api_catatan-test"));
+}